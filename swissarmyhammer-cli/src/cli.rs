@@ -15,6 +15,7 @@ pub use swissarmyhammer::PromptSource;
 #[derive(ValueEnum, Clone, Debug, PartialEq)]
 pub enum PromptSourceArg {
     Builtin,
+    Package,
     User,
     Local,
     Dynamic,
@@ -24,6 +25,8 @@ impl From<PromptSourceArg> for PromptSource {
     fn from(arg: PromptSourceArg) -> Self {
         match arg {
             PromptSourceArg::Builtin => PromptSource::Builtin,
+            // An unnamed package source matches every installed package
+            PromptSourceArg::Package => PromptSource::Package(String::new()),
             PromptSourceArg::User => PromptSource::User,
             PromptSourceArg::Local => PromptSource::Local,
             PromptSourceArg::Dynamic => PromptSource::Dynamic,
@@ -35,6 +38,7 @@ impl From<PromptSource> for PromptSourceArg {
     fn from(source: PromptSource) -> Self {
         match source {
             PromptSource::Builtin => PromptSourceArg::Builtin,
+            PromptSource::Package(_) => PromptSourceArg::Package,
             PromptSource::User => PromptSourceArg::User,
             PromptSource::Local => PromptSourceArg::Local,
             PromptSource::Dynamic => PromptSourceArg::Dynamic,
@@ -123,6 +127,18 @@ Example:
         #[command(subcommand)]
         subcommand: FlowSubcommand,
     },
+    /// Manage local GGUF models for the LlamaAgent executor
+    #[command(long_about = commands::model::DESCRIPTION)]
+    Model {
+        #[command(subcommand)]
+        subcommand: ModelSubcommand,
+    },
+    /// Install and manage workflow packages
+    #[command(long_about = commands::package::DESCRIPTION)]
+    Package {
+        #[command(subcommand)]
+        subcommand: PackageSubcommand,
+    },
     /// Generate shell completion scripts
     #[command(long_about = "
 Generates shell completion scripts for various shells. Supports:
@@ -416,6 +432,14 @@ pub enum FlowSubcommand {
         /// Quiet mode - only show errors
         #[arg(short, long)]
         quiet: bool,
+
+        /// Replay cached agent responses for unchanged prompts
+        #[arg(long)]
+        cache: bool,
+
+        /// Bypass the agent response cache, even when SWISSARMYHAMMER_AGENT_CACHE enables it
+        #[arg(long, conflicts_with = "cache")]
+        no_cache: bool,
     },
     /// Resume a paused workflow run
     Resume {
@@ -433,6 +457,35 @@ pub enum FlowSubcommand {
         /// Quiet mode - only show errors
         #[arg(short, long)]
         quiet: bool,
+
+        /// Replay cached agent responses for unchanged prompts
+        #[arg(long)]
+        cache: bool,
+
+        /// Bypass the agent response cache, even when SWISSARMYHAMMER_AGENT_CACHE enables it
+        #[arg(long, conflicts_with = "cache")]
+        no_cache: bool,
+    },
+    /// Approve or reject a workflow run waiting at an approval gate and resume it
+    Approve {
+        /// Run ID waiting for approval
+        run_id: String,
+
+        /// Reject instead of approving
+        #[arg(long)]
+        reject: bool,
+
+        /// Reason recorded with the decision
+        #[arg(long)]
+        reason: Option<String>,
+
+        /// Execution timeout for the resumed run (e.g., 30s, 5m, 1h)
+        #[arg(long)]
+        timeout: Option<String>,
+
+        /// Quiet mode - only show errors
+        #[arg(short, long)]
+        quiet: bool,
     },
     /// List available workflows
     List {
@@ -474,9 +527,17 @@ pub enum FlowSubcommand {
         #[arg(short = 'n', long)]
         tail: Option<usize>,
 
-        /// Filter logs by level (info, warn, error)
+        /// Show only events at or above this level (debug, info, warn, error)
         #[arg(long)]
         level: Option<String>,
+
+        /// Show only events for this state
+        #[arg(long)]
+        state: Option<String>,
+
+        /// Print events as JSON lines
+        #[arg(long)]
+        json: bool,
     },
     /// View metrics for workflow runs
     Metrics {
@@ -520,6 +581,37 @@ pub enum FlowSubcommand {
         #[arg(long)]
         path_only: bool,
     },
+    /// Export a workflow run as OpenTelemetry (OTLP) spans
+    Trace {
+        /// Run ID to export
+        run_id: String,
+
+        /// Write the trace as an OTLP JSON file instead of sending it
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// OTLP/HTTP collector endpoint (defaults to telemetry.otlp_endpoint in sah.toml)
+        #[arg(long)]
+        endpoint: Option<String>,
+    },
+    /// Compare two workflow runs
+    Diff {
+        /// Baseline run ID
+        run_a: String,
+
+        /// Run ID to compare against the baseline
+        run_b: String,
+
+        /// Print the comparison as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove and archive old workflow runs according to the retention policy
+    Prune {
+        /// Show what would be removed or archived without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Test a workflow without executing actions (simulates dry run)
     #[command(long_about = "
 Test workflows in simulation mode without actually executing actions.
@@ -568,6 +660,85 @@ for better discoverability and clearer intent.
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ModelSubcommand {
+    /// List local GGUF models with size and quantization
+    List {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Verify a model's header, checksum, memory fit and configuration
+    Verify {
+        /// Model path or name (defaults to the configured model)
+        model: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Show GGUF metadata for a model
+    Info {
+        /// Model path or name (defaults to the configured model)
+        model: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Select a model for the LlamaAgent executor in sah.toml
+    Use {
+        /// Model path, listed model name, or HuggingFace repository when --file is given
+        model: String,
+
+        /// Model file within the HuggingFace repository
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Write to the global ~/.swissarmyhammer/sah.toml instead of the project config
+        #[arg(long)]
+        global: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PackageSubcommand {
+    /// Install a package from a git URL, a .tar.gz archive or a directory
+    Install {
+        /// Package source, e.g. https://github.com/org/kit.git#v1.0 or kit-1.0.tar.gz
+        source: String,
+
+        /// Install into ~/.swissarmyhammer instead of the project
+        #[arg(long)]
+        global: bool,
+    },
+    /// List installed packages and the files they provide or that override them
+    List {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+
+        /// List packages in ~/.swissarmyhammer instead of the project
+        #[arg(long)]
+        global: bool,
+    },
+    /// Remove an installed package
+    Remove {
+        /// Package name
+        name: String,
+
+        /// Remove from ~/.swissarmyhammer instead of the project
+        #[arg(long)]
+        global: bool,
+    },
+    /// Install exactly the packages pinned in sah.lock
+    Sync {
+        /// Sync ~/.swissarmyhammer instead of the project
+        #[arg(long)]
+        global: bool,
+    },
+}
+
 impl Cli {
     #[allow(dead_code)]
     pub fn try_parse_from_args<I, T>(args: I) -> Result<Self, clap::Error>
@@ -1156,6 +1327,159 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_flow_approve_subcommand() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "approve",
+            "01K0000000000000000000000",
+            "--reject",
+            "--reason",
+            "tests are red",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Flow { subcommand }) = cli.command {
+            if let FlowSubcommand::Approve {
+                run_id,
+                reject,
+                reason,
+                timeout,
+                quiet,
+            } = subcommand
+            {
+                assert_eq!(run_id, "01K0000000000000000000000");
+                assert!(reject);
+                assert_eq!(reason, Some("tests are red".to_string()));
+                assert_eq!(timeout, None);
+                assert!(!quiet);
+            } else {
+                unreachable!("Expected Approve subcommand");
+            }
+        } else {
+            unreachable!("Expected Flow command");
+        }
+    }
+
+    #[test]
+    fn test_cli_flow_logs_filters() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "logs",
+            "01K0000000000000000000000",
+            "--follow",
+            "--level",
+            "warn",
+            "--state",
+            "review",
+            "--json",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Flow { subcommand }) = cli.command {
+            if let FlowSubcommand::Logs {
+                run_id,
+                follow,
+                tail,
+                level,
+                state,
+                json,
+            } = subcommand
+            {
+                assert_eq!(run_id, "01K0000000000000000000000");
+                assert!(follow);
+                assert_eq!(tail, None);
+                assert_eq!(level, Some("warn".to_string()));
+                assert_eq!(state, Some("review".to_string()));
+                assert!(json);
+            } else {
+                unreachable!("Expected Logs subcommand");
+            }
+        } else {
+            unreachable!("Expected Flow command");
+        }
+    }
+
+    #[test]
+    fn test_flow_trace_subcommand() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "trace",
+            "01K0000000000000000000000",
+            "--output",
+            "run.otlp.json",
+            "--endpoint",
+            "http://localhost:4318",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Flow { subcommand }) = cli.command {
+            if let FlowSubcommand::Trace {
+                run_id,
+                output,
+                endpoint,
+            } = subcommand
+            {
+                assert_eq!(run_id, "01K0000000000000000000000");
+                assert_eq!(output, Some("run.otlp.json".to_string()));
+                assert_eq!(endpoint, Some("http://localhost:4318".to_string()));
+            } else {
+                unreachable!("Expected Trace subcommand");
+            }
+        } else {
+            unreachable!("Expected Flow command");
+        }
+    }
+
+    #[test]
+    fn test_flow_diff_subcommand() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "diff",
+            "01K0000000000000000000000",
+            "01K0000000000000000000001",
+            "--json",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Flow { subcommand }) = cli.command {
+            if let FlowSubcommand::Diff { run_a, run_b, json } = subcommand {
+                assert_eq!(run_a, "01K0000000000000000000000");
+                assert_eq!(run_b, "01K0000000000000000000001");
+                assert!(json);
+            } else {
+                unreachable!("Expected Diff subcommand");
+            }
+        } else {
+            unreachable!("Expected Flow command");
+        }
+    }
+
+    #[test]
+    fn test_flow_prune_subcommand() {
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "flow", "prune", "--dry-run"]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Flow { subcommand }) = cli.command {
+            if let FlowSubcommand::Prune { dry_run } = subcommand {
+                assert!(dry_run);
+            } else {
+                unreachable!("Expected Prune subcommand");
+            }
+        } else {
+            unreachable!("Expected Flow command");
+        }
+    }
+
     #[test]
     fn test_parse_args_panics_on_error() {
         // This test verifies that parse_args would panic on invalid input
@@ -1202,6 +1526,10 @@ mod tests {
             PromptSourceArg::from(PromptSource::Dynamic),
             PromptSourceArg::Dynamic
         ));
+        assert!(matches!(
+            PromptSourceArg::from(PromptSource::Package("review-kit".to_string())),
+            PromptSourceArg::Package
+        ));
     }
 
     #[test]
@@ -1531,4 +1859,90 @@ mod tests {
         assert!(cli.debug);
         assert!(matches!(cli.command, Some(Commands::Implement)));
     }
+
+    #[test]
+    fn test_cli_model_use_command() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "model",
+            "use",
+            "unsloth/Phi-4-mini-instruct-GGUF",
+            "--file",
+            "Phi-4-mini-instruct-Q4_K_M.gguf",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Model {
+            subcommand:
+                ModelSubcommand::Use {
+                    model,
+                    file,
+                    global,
+                },
+        }) = cli.command
+        {
+            assert_eq!(model, "unsloth/Phi-4-mini-instruct-GGUF");
+            assert_eq!(file.as_deref(), Some("Phi-4-mini-instruct-Q4_K_M.gguf"));
+            assert!(!global);
+        } else {
+            unreachable!("Expected Model Use command");
+        }
+    }
+
+    #[test]
+    fn test_cli_model_verify_defaults_to_configured_model() {
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "model", "verify"]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Model {
+                subcommand: ModelSubcommand::Verify { model: None, .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_package_install_command() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "package",
+            "install",
+            "https://github.com/acme/review-kit.git#v1.2.0",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Package {
+            subcommand: PackageSubcommand::Install { source, global },
+        }) = cli.command
+        {
+            assert_eq!(source, "https://github.com/acme/review-kit.git#v1.2.0");
+            assert!(!global);
+        } else {
+            unreachable!("Expected Package Install command");
+        }
+    }
+
+    #[test]
+    fn test_cli_package_remove_global() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "package",
+            "remove",
+            "review-kit",
+            "--global",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Package {
+                subcommand: PackageSubcommand::Remove { global: true, .. }
+            })
+        ));
+    }
 }
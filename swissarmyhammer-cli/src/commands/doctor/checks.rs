@@ -34,6 +34,7 @@ pub mod check_names {
     pub const WORKFLOW_PARSING: &str = "Workflow parsing";
     pub const WORKFLOW_RUN_STORAGE_ACCESS: &str = "Workflow run storage accessibility";
     pub const WORKFLOW_RUN_STORAGE_SPACE: &str = "Workflow run storage space";
+    pub const WORKFLOW_RUN_STORAGE_SIZE: &str = "Workflow run storage size";
     pub const WORKFLOW_NAME_CONFLICTS: &str = "Workflow name conflicts";
     pub const WORKFLOW_CIRCULAR_DEPS: &str = "Workflow circular dependencies";
 }
@@ -761,6 +762,49 @@ fn check_run_storage_disk_space(checks: &mut Vec<Check>, run_storage: &Path) ->
     Ok(())
 }
 
+/// Check how much space stored workflow runs take up
+///
+/// Warns when the project's or the user's run storage is larger than
+/// `warning_mb`, pointing at `sah flow prune` and the retention policy.
+pub fn check_workflow_run_storage_size(checks: &mut Vec<Check>, warning_mb: u64) -> Result<()> {
+    let mut locations = vec![PathBuf::from(SWISSARMYHAMMER_DIR).join("workflow-runs")];
+    if let Some(home) = dirs::home_dir() {
+        locations.push(home.join(SWISSARMYHAMMER_DIR));
+    }
+
+    for base_path in locations {
+        if !base_path.join("runs").exists() {
+            continue;
+        }
+        let size = swissarmyhammer::workflow::run_storage_size(&base_path);
+        let size_text = swissarmyhammer::workflow::model_catalog::format_size(size);
+        let runs_dir = base_path.join("runs");
+
+        if size / (1024 * 1024) > warning_mb {
+            checks.push(Check {
+                name: check_names::WORKFLOW_RUN_STORAGE_SIZE.to_string(),
+                status: CheckStatus::Warning,
+                message: format!(
+                    "{} uses {size_text}, above the {warning_mb} MB threshold",
+                    runs_dir.display()
+                ),
+                fix: Some(
+                    "Run 'sah flow prune' or tighten [run_retention] in sah.toml".to_string(),
+                ),
+            });
+        } else {
+            checks.push(Check {
+                name: check_names::WORKFLOW_RUN_STORAGE_SIZE.to_string(),
+                status: CheckStatus::Ok,
+                message: format!("{} uses {size_text}", runs_dir.display()),
+                fix: None,
+            });
+        }
+    }
+
+    Ok(())
+}
+
 /// Check for workflow circular dependencies and conflicts
 ///
 /// Detects potential issues in the workflow system:
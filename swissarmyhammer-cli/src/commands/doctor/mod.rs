@@ -18,6 +18,7 @@
 use crate::exit_codes::EXIT_ERROR;
 use anyhow::Result;
 use colored::*;
+use swissarmyhammer::workflow::{RunRetentionPolicy, RUN_RETENTION_CONFIG_KEY};
 
 // Re-export types from submodules
 pub use types::*;
@@ -54,12 +55,22 @@ struct CheckGroups<'a> {
 /// of the system's configuration and any potential issues.
 pub struct Doctor {
     checks: Vec<Check>,
+    run_storage_warning_mb: u64,
 }
 
 impl Doctor {
     /// Create a new Doctor instance for running diagnostics
     pub fn new() -> Self {
-        Self { checks: Vec::new() }
+        Self {
+            checks: Vec::new(),
+            run_storage_warning_mb: RunRetentionPolicy::default().size_warning_mb,
+        }
+    }
+
+    /// Warn when workflow run storage grows beyond `megabytes`
+    pub fn with_run_storage_warning_mb(mut self, megabytes: u64) -> Self {
+        self.run_storage_warning_mb = megabytes;
+        self
     }
 
     /// Run diagnostic checks with specific options
@@ -138,6 +149,7 @@ impl Doctor {
         checks::check_workflow_permissions(&mut self.checks)?;
         checks::check_workflow_parsing(&mut self.checks)?;
         checks::check_workflow_run_storage(&mut self.checks)?;
+        checks::check_workflow_run_storage_size(&mut self.checks, self.run_storage_warning_mb)?;
         checks::check_workflow_dependencies(&mut self.checks)?;
         Ok(())
     }
@@ -443,7 +455,14 @@ fn print_check(check: &Check, use_color: bool) {
 
 /// Handle the doctor command
 pub async fn handle_command(_template_context: &swissarmyhammer_config::TemplateContext) -> i32 {
-    let mut doctor = Doctor::new();
+    let retention = match _template_context.get(RUN_RETENTION_CONFIG_KEY) {
+        Some(value) => RunRetentionPolicy::from_config_value(value).unwrap_or_else(|e| {
+            eprintln!("Warning: {e}");
+            RunRetentionPolicy::default()
+        }),
+        None => RunRetentionPolicy::default(),
+    };
+    let mut doctor = Doctor::new().with_run_storage_warning_mb(retention.size_warning_mb);
 
     match doctor.run_diagnostics_with_options() {
         Ok(exit_code) => exit_code,
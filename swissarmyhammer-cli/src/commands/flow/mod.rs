@@ -14,10 +14,14 @@ use std::future;
 use std::io::{self, Write};
 use std::time::Duration;
 use swissarmyhammer::common::mcp_errors::ToSwissArmyHammerError;
+use swissarmyhammer::workflow::model_catalog::format_size;
 use swissarmyhammer::workflow::{
-    ExecutionVisualizer, ExecutorError, MemoryWorkflowStorage, Workflow, WorkflowExecutor,
-    WorkflowName, WorkflowResolver, WorkflowRunId, WorkflowRunStatus, WorkflowRunStorageBackend,
-    WorkflowStorage, WorkflowStorageBackend,
+    prune_run_storage, record_approval_decision, ApprovalDecision, EventLevel, ExecutionVisualizer,
+    ExecutorError, FileSystemWorkflowRunStorage, JsonlEventObserver, MemoryWorkflowStorage,
+    OtlpTrace, PendingApproval, PruneAction, RunDiff, RunRetentionPolicy, RunSnapshot, Workflow,
+    WorkflowEvent, WorkflowEventReader, WorkflowEventRecord, WorkflowExecutor, WorkflowName,
+    WorkflowResolver, WorkflowRun, WorkflowRunId, WorkflowRunStatus, WorkflowRunStorageBackend,
+    WorkflowStorage, WorkflowStorageBackend, LOCAL_RUN_STORAGE_DIR, RUN_RETENTION_CONFIG_KEY,
 };
 use swissarmyhammer::{Result, SwissArmyHammerError};
 use tokio::signal;
@@ -53,6 +57,8 @@ pub async fn run_flow_command(
             dry_run,
             timeout: timeout_str,
             quiet,
+            cache,
+            no_cache,
         } => {
             let all_vars = vars;
 
@@ -65,6 +71,8 @@ pub async fn run_flow_command(
 
                     timeout_str,
                     quiet,
+                    cache,
+                    no_cache,
                 },
                 _template_context,
             )
@@ -75,7 +83,18 @@ pub async fn run_flow_command(
             interactive,
             timeout: timeout_str,
             quiet,
-        } => resume_workflow_command(run_id, interactive, timeout_str, quiet).await,
+            cache,
+            no_cache,
+        } => {
+            resume_workflow_command(run_id, interactive, timeout_str, quiet, cache, no_cache).await
+        }
+        FlowSubcommand::Approve {
+            run_id,
+            reject,
+            reason,
+            timeout: timeout_str,
+            quiet,
+        } => approve_workflow_command(run_id, reject, reason, timeout_str, quiet).await,
         FlowSubcommand::List {
             format,
             verbose,
@@ -91,7 +110,9 @@ pub async fn run_flow_command(
             follow,
             tail,
             level,
-        } => logs_workflow_command(run_id, follow, tail, level).await,
+            state,
+            json,
+        } => logs_workflow_command(run_id, follow, tail, level, state, json).await,
         FlowSubcommand::Metrics {
             run_id,
             workflow,
@@ -106,6 +127,15 @@ pub async fn run_flow_command(
             counts,
             path_only,
         } => visualize_workflow_command(run_id, format, output, timing, counts, path_only).await,
+        FlowSubcommand::Trace {
+            run_id,
+            output,
+            endpoint,
+        } => trace_workflow_command(run_id, output, endpoint, _template_context).await,
+        FlowSubcommand::Diff { run_a, run_b, json } => {
+            diff_workflow_command(run_a, run_b, json).await
+        }
+        FlowSubcommand::Prune { dry_run } => prune_workflow_command(dry_run, _template_context),
         FlowSubcommand::Test {
             workflow,
             vars,
@@ -124,6 +154,8 @@ pub async fn run_flow_command(
                     dry_run: false,
                     timeout_str,
                     quiet,
+                    cache: false,
+                    no_cache: false,
                 },
                 _template_context,
             )
@@ -141,6 +173,8 @@ pub struct WorkflowCommandConfig {
 
     pub timeout_str: Option<String>,
     pub quiet: bool,
+    pub cache: bool,
+    pub no_cache: bool,
 }
 
 /// Execute a workflow
@@ -230,7 +264,7 @@ pub async fn run_workflow_command(
     }
 
     // Create executor
-    let mut executor = WorkflowExecutor::new();
+    let mut executor = create_observed_executor();
 
     // Create workflow run
     let mut run = executor.start_workflow(workflow.clone()).map_err(|e| {
@@ -256,6 +290,14 @@ pub async fn run_workflow_command(
             .insert("_quiet".to_string(), serde_json::Value::Bool(true));
     }
 
+    // `--cache` and `--no-cache` override SWISSARMYHAMMER_AGENT_CACHE for this run
+    if config.cache || config.no_cache {
+        run.context.insert(
+            "_agent_cache".to_string(),
+            serde_json::Value::Bool(config.cache),
+        );
+    }
+
     // Set timeout in context for actions to use
     if let Some(timeout_duration) = timeout_duration {
         run.context.insert(
@@ -299,6 +341,9 @@ pub async fn run_workflow_command(
         }
     };
 
+    export_run_trace_if_configured(&run, _template_context).await;
+    apply_configured_retention(_template_context);
+
     // Create local workflow run storage (only store failed runs for debugging)
     let mut run_storage = create_local_workflow_run_storage()?;
 
@@ -332,6 +377,12 @@ pub async fn run_workflow_command(
             _ => {
                 tracing::info!("⏸️  Workflow paused");
                 tracing::info!("🆔 Run ID: {}", workflow_run_id_to_string(&run.id));
+
+                // Keep paused runs so they can be approved or resumed later
+                if let Err(storage_err) = run_storage.store_run(&run) {
+                    tracing::warn!("Failed to store paused run: {}", storage_err);
+                }
+                print_pending_approval(&run);
             }
         },
         Err(e) => {
@@ -357,14 +408,30 @@ async fn resume_workflow_command(
     interactive: bool,
     timeout_str: Option<String>,
     quiet: bool,
+    cache: bool,
+    no_cache: bool,
 ) -> Result<()> {
     let mut storage = WorkflowStorage::file_system()?;
 
     // Parse run ID
     let run_id_typed = parse_workflow_run_id(&run_id)?;
 
+    // Runs paused at an approval gate are kept in the project's run storage
+    let mut local_storage = if std::path::Path::new(LOCAL_RUN_STORAGE_DIR).exists() {
+        Some(create_local_workflow_run_storage()?)
+    } else {
+        None
+    };
+    let local_run = local_storage
+        .as_ref()
+        .and_then(|local_storage| local_storage.get_run(&run_id_typed).ok());
+    let from_local = local_run.is_some();
+
     // Get the run
-    let mut run = storage.get_run(&run_id_typed)?;
+    let mut run = match local_run {
+        Some(run) => run,
+        None => storage.get_run(&run_id_typed)?,
+    };
 
     // Check if run can be resumed
     if run.status == WorkflowRunStatus::Completed {
@@ -393,8 +460,16 @@ async fn resume_workflow_command(
             .insert("_quiet".to_string(), serde_json::Value::Bool(true));
     }
 
+    // The choice of the original run does not carry over, so the resumed run asks again
+    if cache || no_cache {
+        run.context
+            .insert("_agent_cache".to_string(), serde_json::Value::Bool(cache));
+    } else {
+        run.context.remove("_agent_cache");
+    }
+
     // Create executor
-    let mut executor = WorkflowExecutor::new();
+    let mut executor = create_observed_executor();
 
     // Setup signal handling for graceful shutdown
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::mpsc::channel(1);
@@ -431,8 +506,11 @@ async fn resume_workflow_command(
         }
     };
 
-    // Store the updated run
-    storage.store_run(&run)?;
+    // Store the updated run where it was loaded from
+    match local_storage.as_mut().filter(|_| from_local) {
+        Some(local_storage) => local_storage.store_run(&run)?,
+        None => storage.store_run(&run)?,
+    }
 
     match execution_result {
         Ok(_) => match run.status {
@@ -447,6 +525,7 @@ async fn resume_workflow_command(
             }
             _ => {
                 tracing::info!("⏸️  Workflow resumed and paused");
+                store_paused_run(&run);
             }
         },
         Err(e) => {
@@ -459,6 +538,121 @@ async fn resume_workflow_command(
     Ok(())
 }
 
+/// Record an approval decision for a paused run and resume it
+async fn approve_workflow_command(
+    run_id: String,
+    reject: bool,
+    reason: Option<String>,
+    timeout_str: Option<String>,
+    quiet: bool,
+) -> Result<()> {
+    let mut run_storage = create_local_workflow_run_storage()?;
+    let run_id_typed = parse_workflow_run_id(&run_id)?;
+    let mut run = run_storage.get_run(&run_id_typed)?;
+
+    let decision = if reject {
+        ApprovalDecision::reject(reason)
+    } else {
+        ApprovalDecision::approve(reason)
+    };
+    let decision = match std::env::var("USER").or_else(|_| std::env::var("USERNAME")) {
+        Ok(user) => decision.with_decided_by(user),
+        Err(_) => decision,
+    };
+
+    let pending = record_approval_decision(&mut run, decision)?;
+    println!(
+        "{} {} at state {}: {}",
+        if reject {
+            "❌ Rejected"
+        } else {
+            "✅ Approved"
+        },
+        run_id,
+        pending.state,
+        pending.message
+    );
+
+    // Persist the decision before resuming so it survives an interrupted resume
+    run_storage.store_run(&run)?;
+
+    let timeout_duration = if let Some(timeout_str) = timeout_str {
+        Some(parse_duration(&timeout_str)?)
+    } else {
+        None
+    };
+
+    if quiet {
+        run.context
+            .insert("_quiet".to_string(), serde_json::Value::Bool(true));
+    }
+
+    println!("🔄 Resuming workflow: {}", run.workflow.name);
+
+    let mut executor = create_observed_executor();
+    let execution_result = if let Some(timeout_duration) = timeout_duration {
+        tokio::select! {
+            result = execute_workflow_with_progress(&mut executor, &mut run, false) => result,
+            _ = timeout(timeout_duration, future::pending::<()>()) => {
+                tracing::warn!("Workflow execution timed out");
+                run.status = WorkflowRunStatus::Cancelled;
+                Ok(())
+            },
+        }
+    } else {
+        execute_workflow_with_progress(&mut executor, &mut run, false).await
+    };
+
+    if let Err(e) = execution_result {
+        tracing::error!("❌ Workflow resume failed: {}", e);
+        run.fail();
+        run_storage.store_run(&run)?;
+        return Err(e);
+    }
+
+    run_storage.store_run(&run)?;
+    match run.status {
+        WorkflowRunStatus::Completed => {
+            tracing::info!("✅ Workflow completed successfully");
+        }
+        WorkflowRunStatus::Failed => {
+            tracing::error!("❌ Workflow failed");
+        }
+        WorkflowRunStatus::Cancelled => {
+            tracing::warn!("🚫 Workflow cancelled");
+        }
+        _ => {
+            tracing::info!("⏸️  Workflow paused");
+            print_pending_approval(&run);
+        }
+    }
+
+    Ok(())
+}
+
+/// Store a paused run in the project run storage used by `flow approve`
+fn store_paused_run(run: &swissarmyhammer::workflow::WorkflowRun) {
+    match create_local_workflow_run_storage() {
+        Ok(mut run_storage) => {
+            if let Err(storage_err) = run_storage.store_run(run) {
+                tracing::warn!("Failed to store paused run: {}", storage_err);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to open run storage: {}", e),
+    }
+    print_pending_approval(run);
+}
+
+/// Tell the user how to continue a run waiting at an approval gate
+fn print_pending_approval(run: &swissarmyhammer::workflow::WorkflowRun) {
+    if let Some(pending) = PendingApproval::from_context(&run.context) {
+        let run_id = workflow_run_id_to_string(&run.id);
+        println!("⏸️  Waiting for approval: {}", pending.message);
+        println!("   Approve: sah flow approve {run_id}");
+        println!("   Reject:  sah flow approve {run_id} --reject --reason \"...\"");
+    }
+}
+
 /// List available workflows
 async fn list_workflows_command(
     format: OutputFormat,
@@ -480,6 +674,7 @@ async fn list_workflows_command(
         // Get the source from the resolver
         let workflow_source = match resolver.workflow_sources.get(&workflow.name) {
             Some(swissarmyhammer::FileSource::Builtin) => PromptSource::Builtin,
+            Some(swissarmyhammer::FileSource::Package(name)) => PromptSource::Package(name.clone()),
             Some(swissarmyhammer::FileSource::User) => PromptSource::User,
             Some(swissarmyhammer::FileSource::Local) => PromptSource::Local,
             Some(swissarmyhammer::FileSource::Dynamic) => PromptSource::Dynamic,
@@ -489,7 +684,9 @@ async fn list_workflows_command(
         // Apply source filter
         if let Some(ref filter) = source_filter {
             let filter_source: PromptSource = filter.clone().into();
-            if filter_source != workflow_source && filter_source != PromptSource::Dynamic {
+            if !workflow_source.matches_filter(&filter_source)
+                && filter_source != PromptSource::Dynamic
+            {
                 continue;
             }
         }
@@ -573,6 +770,9 @@ fn display_workflows_to_writer<W: Write>(
                 PromptSource::Builtin => {
                     (name.green().bold().to_string(), title.green().to_string())
                 }
+                PromptSource::Package(_) => {
+                    (name.cyan().bold().to_string(), title.cyan().to_string())
+                }
                 PromptSource::User => (name.blue().bold().to_string(), title.blue().to_string()),
                 PromptSource::Local => {
                     (name.yellow().bold().to_string(), title.yellow().to_string())
@@ -675,17 +875,48 @@ async fn status_workflow_command(run_id: String, format: OutputFormat, watch: bo
 }
 
 /// View workflow run logs
+///
+/// Runs started with `flow run` append structured events next to the local run
+/// storage, which can be filtered and followed while the run is still executing.
+/// Runs without an event log fall back to the transition history kept in storage.
 async fn logs_workflow_command(
     run_id: String,
     follow: bool,
     tail: Option<usize>,
     level: Option<String>,
+    state: Option<String>,
+    json: bool,
 ) -> Result<()> {
-    let storage = WorkflowStorage::file_system()?;
-
     // Parse run ID
     let run_id_typed = parse_workflow_run_id(&run_id)?;
 
+    let min_level = level
+        .as_deref()
+        .map(str::parse::<EventLevel>)
+        .transpose()
+        .map_err(SwissArmyHammerError::Other)?;
+
+    let events_path = FileSystemWorkflowRunStorage::run_events_path(
+        std::path::Path::new(LOCAL_RUN_STORAGE_DIR),
+        &run_id_typed,
+    );
+    if events_path.exists() {
+        let filter = EventFilter {
+            min_level,
+            state,
+            json,
+        };
+        return follow_run_events(
+            &run_id,
+            WorkflowEventReader::new(events_path),
+            follow,
+            tail,
+            &filter,
+        )
+        .await;
+    }
+
+    let storage = WorkflowStorage::file_system()?;
     let run = storage.get_run(&run_id_typed)?;
 
     if follow {
@@ -716,6 +947,101 @@ async fn logs_workflow_command(
     Ok(())
 }
 
+/// Filters and output format for `flow logs` event output
+struct EventFilter {
+    min_level: Option<EventLevel>,
+    state: Option<String>,
+    json: bool,
+}
+
+impl EventFilter {
+    fn matches(&self, record: &WorkflowEventRecord) -> bool {
+        self.min_level.map_or(true, |level| record.level >= level)
+            && self
+                .state
+                .as_deref()
+                .map_or(true, |state| record.event.involves_state(state))
+    }
+
+    fn print(&self, record: &WorkflowEventRecord) -> Result<()> {
+        if self.json {
+            println!("{}", serde_json::to_string(record)?);
+        } else {
+            let level = format!("{:<5}", record.level.to_string().to_uppercase());
+            let level = match record.level {
+                EventLevel::Error => level.as_str().red(),
+                EventLevel::Warn => level.as_str().yellow(),
+                EventLevel::Debug => level.as_str().dimmed(),
+                EventLevel::Info => level.as_str().normal(),
+            };
+            println!(
+                "{} {} {}",
+                record.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+                level,
+                record.event
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Whether the events so far show the run has reached a final status
+///
+/// A run that paused and was resumed starts a new segment, so only the most
+/// recent start or end event counts.
+fn run_finished(finished: bool, record: &WorkflowEventRecord) -> bool {
+    match &record.event {
+        WorkflowEvent::RunStart { .. } => false,
+        WorkflowEvent::RunEnd { status, .. } => matches!(
+            status,
+            WorkflowRunStatus::Completed | WorkflowRunStatus::Failed | WorkflowRunStatus::Cancelled
+        ),
+        _ => finished,
+    }
+}
+
+/// Print a run's recorded events and optionally keep streaming new ones
+async fn follow_run_events(
+    run_id: &str,
+    mut reader: WorkflowEventReader,
+    follow: bool,
+    tail: Option<usize>,
+    filter: &EventFilter,
+) -> Result<()> {
+    let records = reader.read_new()?;
+    let finished = records.iter().fold(false, run_finished);
+    let matching: Vec<&WorkflowEventRecord> =
+        records.iter().filter(|r| filter.matches(r)).collect();
+    let skip = tail.map_or(0, |n| matching.len().saturating_sub(n));
+
+    if follow && !filter.json {
+        println!("📄 Following logs for run {run_id} (Press Ctrl+C to stop)...");
+    }
+    for record in matching.into_iter().skip(skip) {
+        filter.print(record)?;
+    }
+
+    let mut finished = finished;
+    while follow && !finished {
+        // Check for Ctrl+C while waiting for new events
+        if (tokio::time::timeout(Duration::from_millis(500), signal::ctrl_c()).await).is_ok() {
+            if !filter.json {
+                println!("\n🛑 Stopped following logs");
+            }
+            break;
+        }
+
+        for record in reader.read_new()? {
+            finished = run_finished(finished, &record);
+            if filter.matches(&record) {
+                filter.print(&record)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Handle ExecutorError and check for abort condition
 fn handle_executor_error(executor_error: ExecutorError, _context: &str) -> SwissArmyHammerError {
     // Convert ExecutorError directly to SwissArmyHammerError using From trait
@@ -972,6 +1298,34 @@ async fn metrics_workflow_command(
                         println!("Duration: {:.2}s", duration.as_secs_f64());
                     }
                     println!("Transitions: {}", run_metrics.transition_count);
+                    println!(
+                        "Agent cache: {} hits, {} misses",
+                        run_metrics.agent_cache_hits, run_metrics.agent_cache_misses
+                    );
+                    if !run_metrics.prompt_token_usage.is_empty() {
+                        println!("Prompt tokens (estimated):");
+                        for usage in &run_metrics.prompt_token_usage {
+                            println!(
+                                "  {}: ~{} / {} tokens (~{} before packing, {:?} estimate)",
+                                usage.prompt,
+                                usage.total_tokens,
+                                usage.budget.available_tokens(),
+                                usage.system_tokens + usage.original_tokens,
+                                usage.model_family
+                            );
+                            for section in &usage.sections {
+                                let action = format!("{:?}", section.action).to_lowercase();
+                                println!(
+                                    "    {} (priority {}): ~{} -> ~{} tokens, {}",
+                                    section.name,
+                                    section.priority,
+                                    section.original_tokens,
+                                    section.tokens,
+                                    action
+                                );
+                            }
+                        }
+                    }
                     println!("State execution times:");
                     for (state_id, duration) in &run_metrics.state_durations {
                         println!("  {}: {:.2}s", state_id, duration.as_secs_f64());
@@ -1130,12 +1484,290 @@ async fn visualize_workflow_command(
     Ok(())
 }
 
+/// Compare two workflow runs
+async fn diff_workflow_command(run_a: String, run_b: String, json: bool) -> Result<()> {
+    let snapshot_a = load_run_snapshot(&run_a)?;
+    let snapshot_b = load_run_snapshot(&run_b)?;
+    let diff = RunDiff::between(&snapshot_a, &snapshot_b);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        print!("{}", diff.render_text());
+    }
+
+    Ok(())
+}
+
+/// Collect what a diff needs to know about a run
+///
+/// The event log provides timing, tokens and agent outputs; a stored run, when
+/// there is one, provides the final variable values and stands in for runs
+/// without an event log.
+fn load_run_snapshot(run_id: &str) -> Result<RunSnapshot> {
+    let run_id_typed = parse_workflow_run_id(run_id)?;
+    let visualizer = ExecutionVisualizer::new();
+    let events = read_run_events(&run_id_typed)?;
+
+    let (trace, stored_run) = match visualizer.generate_trace_from_events(&events) {
+        Some(trace) => (trace, load_stored_run(&run_id_typed).ok()),
+        None => {
+            let run = load_stored_run(&run_id_typed)?;
+            (visualizer.generate_trace(&run), Some(run))
+        }
+    };
+
+    let snapshot = RunSnapshot::new(trace, events);
+    Ok(match stored_run {
+        Some(run) => snapshot.with_variables(run.context.workflow_vars()),
+        None => snapshot,
+    })
+}
+
+/// Apply the run retention policy to run storage
+fn prune_workflow_command(
+    dry_run: bool,
+    template_context: &swissarmyhammer_config::TemplateContext,
+) -> Result<()> {
+    let policy = match template_context.get(RUN_RETENTION_CONFIG_KEY) {
+        Some(value) => RunRetentionPolicy::from_config_value(value)?,
+        None => RunRetentionPolicy::default(),
+    };
+
+    for base_path in run_storage_locations() {
+        let report = prune_run_storage(&base_path, &policy, dry_run)?;
+        println!(
+            "📁 {} ({} runs)",
+            base_path.join("runs").display(),
+            report.total_runs
+        );
+
+        for decision in &report.decisions {
+            let verb = match (decision.action, dry_run) {
+                (PruneAction::Remove, true) => "Would remove",
+                (PruneAction::Remove, false) => "Removed",
+                (PruneAction::Archive, true) => "Would archive",
+                (PruneAction::Archive, false) => "Archived",
+            };
+            println!(
+                "  {verb} {} {} ({}): {}",
+                decision.run.id,
+                decision.run.workflow,
+                format_size(decision.run.size_bytes),
+                decision.reason
+            );
+        }
+
+        if report.decisions.is_empty() {
+            println!("  Nothing to prune");
+        } else if dry_run {
+            println!(
+                "  Would free about {} (archived runs also shrink)",
+                format_size(report.bytes_freed)
+            );
+        } else {
+            println!("  Freed {}", format_size(report.bytes_freed));
+        }
+    }
+
+    Ok(())
+}
+
+/// Run storage directories retention applies to: the project's and the user's
+fn run_storage_locations() -> Vec<std::path::PathBuf> {
+    let mut locations = vec![std::path::PathBuf::from(LOCAL_RUN_STORAGE_DIR)];
+    if let Some(home) = dirs::home_dir() {
+        locations.push(home.join(".swissarmyhammer"));
+    }
+    locations
+        .into_iter()
+        .filter(|path| path.join("runs").exists())
+        .collect()
+}
+
+/// Prune run storage after a run when a retention policy is configured
+///
+/// Failures are logged rather than failing the run.
+fn apply_configured_retention(template_context: &swissarmyhammer_config::TemplateContext) {
+    let Some(value) = template_context.get(RUN_RETENTION_CONFIG_KEY) else {
+        return;
+    };
+    let policy = match RunRetentionPolicy::from_config_value(value) {
+        Ok(policy) => policy,
+        Err(e) => {
+            tracing::warn!("Not pruning workflow runs: {}", e);
+            return;
+        }
+    };
+
+    for base_path in run_storage_locations() {
+        match prune_run_storage(&base_path, &policy, false) {
+            Ok(report) if !report.decisions.is_empty() => tracing::debug!(
+                "Pruned {} workflow runs in {:?}, freeing {}",
+                report.decisions.len(),
+                base_path,
+                format_size(report.bytes_freed)
+            ),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to prune workflow runs in {:?}: {}", base_path, e),
+        }
+    }
+}
+
+/// Config key holding the OTLP/HTTP collector endpoint runs are exported to
+const OTLP_ENDPOINT_CONFIG_KEY: &str = "telemetry.otlp_endpoint";
+
+/// Config key holding the service name reported in exported traces
+const OTLP_SERVICE_NAME_CONFIG_KEY: &str = "telemetry.service_name";
+
+/// Export a workflow run as OpenTelemetry spans
+async fn trace_workflow_command(
+    run_id: String,
+    output: Option<String>,
+    endpoint: Option<String>,
+    template_context: &swissarmyhammer_config::TemplateContext,
+) -> Result<()> {
+    let run_id_typed = parse_workflow_run_id(&run_id)?;
+    let trace = match read_run_trace(&run_id_typed, None)? {
+        Some(trace) => trace,
+        None => {
+            let run = load_stored_run(&run_id_typed)?;
+            read_run_trace(&run_id_typed, Some(&run))?.ok_or_else(|| {
+                SwissArmyHammerError::Other(format!("No trace available for run {run_id}"))
+            })?
+        }
+    };
+    let trace = apply_service_name(trace, template_context);
+
+    if let Some(output_path) = &output {
+        let content = serde_json::to_string_pretty(&trace.to_request_json())?;
+        std::fs::write(output_path, content)?;
+        println!(
+            "Trace with {} spans saved to: {output_path}",
+            trace.spans.len()
+        );
+    }
+
+    let endpoint = endpoint.or_else(|| {
+        output
+            .is_none()
+            .then(|| config_string(template_context, OTLP_ENDPOINT_CONFIG_KEY))
+            .flatten()
+    });
+    match endpoint {
+        Some(endpoint) => {
+            trace.export_http(&endpoint).await?;
+            println!(
+                "Exported {} spans to {}",
+                trace.spans.len(),
+                swissarmyhammer::workflow::otlp_traces_url(&endpoint)
+            );
+        }
+        None if output.is_none() => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&trace.to_request_json())?
+            );
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
+/// Send a finished run's trace to the configured OTLP collector, if there is one
+///
+/// Export failures are logged rather than failing the run.
+async fn export_run_trace_if_configured(
+    run: &WorkflowRun,
+    template_context: &swissarmyhammer_config::TemplateContext,
+) {
+    let Some(endpoint) = config_string(template_context, OTLP_ENDPOINT_CONFIG_KEY) else {
+        return;
+    };
+
+    let trace = match read_run_trace(&run.id, Some(run)) {
+        Ok(Some(trace)) => apply_service_name(trace, template_context),
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!("Failed to build trace for run {}: {}", run.id, e);
+            return;
+        }
+    };
+    match trace.export_http(&endpoint).await {
+        Ok(()) => tracing::debug!("Exported trace for run {} to {}", run.id, endpoint),
+        Err(e) => tracing::warn!("Failed to export trace for run {}: {}", run.id, e),
+    }
+}
+
+/// Build the OTLP trace of a run from its event log, falling back to the stored run
+///
+/// The event log gives the full picture, including agent and tool call spans.
+/// Runs without one, such as runs recorded before event logging, only get run
+/// and state spans.
+fn read_run_trace(run_id: &WorkflowRunId, run: Option<&WorkflowRun>) -> Result<Option<OtlpTrace>> {
+    let visualizer = ExecutionVisualizer::new();
+    let events = read_run_events(run_id)?;
+    if let Some(trace) = visualizer.generate_trace_from_events(&events) {
+        return Ok(Some(OtlpTrace::from_trace(&trace, &events)));
+    }
+
+    Ok(run.map(|run| OtlpTrace::from_trace(&visualizer.generate_trace(run), &[])))
+}
+
+/// Read every event recorded for a run, empty when the run has no event log
+fn read_run_events(run_id: &WorkflowRunId) -> Result<Vec<WorkflowEventRecord>> {
+    swissarmyhammer::workflow::read_run_events(std::path::Path::new(LOCAL_RUN_STORAGE_DIR), run_id)
+}
+
+/// Load a run from the local run storage, falling back to the user's run storage
+fn load_stored_run(run_id: &WorkflowRunId) -> Result<WorkflowRun> {
+    let local_dir = std::path::Path::new(LOCAL_RUN_STORAGE_DIR);
+    if local_dir.exists() {
+        if let Ok(run) = FileSystemWorkflowRunStorage::new(local_dir)?.get_run(run_id) {
+            return Ok(run);
+        }
+    }
+    WorkflowStorage::file_system()?.get_run(run_id)
+}
+
+/// Use the configured service name for a trace, if one is set
+fn apply_service_name(
+    trace: OtlpTrace,
+    template_context: &swissarmyhammer_config::TemplateContext,
+) -> OtlpTrace {
+    match config_string(template_context, OTLP_SERVICE_NAME_CONFIG_KEY) {
+        Some(service_name) => trace.with_service_name(service_name),
+        None => trace,
+    }
+}
+
+/// Read a non-empty string value from sah.toml
+fn config_string(
+    template_context: &swissarmyhammer_config::TemplateContext,
+    key: &str,
+) -> Option<String> {
+    template_context
+        .get(key)
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Create an executor that writes each run's events next to the local run storage
+fn create_observed_executor() -> WorkflowExecutor {
+    let mut executor = WorkflowExecutor::new();
+    executor.add_observer(JsonlEventObserver::new(LOCAL_RUN_STORAGE_DIR));
+    executor
+}
+
 /// Create a local workflow run storage that stores runs in .swissarmyhammer/workflow-runs directory
 fn create_local_workflow_run_storage() -> Result<Box<dyn WorkflowRunStorageBackend>> {
     use std::fs;
 
     // Create local .swissarmyhammer/workflow-runs directory
-    let local_dir = std::path::PathBuf::from(".swissarmyhammer/workflow-runs");
+    let local_dir = std::path::PathBuf::from(LOCAL_RUN_STORAGE_DIR);
     fs::create_dir_all(&local_dir).map_err(|e| {
         SwissArmyHammerError::Other(format!(
             "Failed to create .swissarmyhammer/workflow-runs directory: {e}"
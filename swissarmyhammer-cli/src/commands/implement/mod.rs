@@ -17,6 +17,8 @@ pub async fn handle_command(template_context: &swissarmyhammer_config::TemplateC
         dry_run: false,
         timeout: None,
         quiet: false,
+        cache: false,
+        no_cache: false,
     };
 
     crate::commands::flow::handle_command(subcommand, template_context).await
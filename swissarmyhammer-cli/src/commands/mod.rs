@@ -8,6 +8,8 @@
 pub mod doctor;
 pub mod flow;
 pub mod implement;
+pub mod model;
+pub mod package;
 pub mod plan;
 pub mod prompt;
 pub mod serve;
@@ -0,0 +1,412 @@
+//! Model command implementation
+//!
+//! Lists, inspects, verifies and selects local GGUF models for the LlamaAgent executor
+
+use crate::cli::{ModelSubcommand, OutputFormat};
+use crate::exit_codes::{EXIT_ERROR, EXIT_SUCCESS};
+use colored::*;
+use is_terminal::IsTerminal;
+use std::path::PathBuf;
+use swissarmyhammer::workflow::model_catalog::{
+    format_size, memory_warning, verify_model, write_model_selection,
+};
+use swissarmyhammer::workflow::{ChecksumStatus, LlamaAgentExecutor, LocalModel, ModelCatalog};
+use swissarmyhammer::{Result, SwissArmyHammerError};
+use swissarmyhammer_config::{
+    AgentExecutorConfig, ConfigurationDiscovery, LlamaAgentConfig, ModelSource, TemplateContext,
+};
+
+/// Help text for the model command
+pub const DESCRIPTION: &str = include_str!("description.md");
+
+/// Handle the model command
+pub async fn handle_command(
+    subcommand: ModelSubcommand,
+    template_context: &TemplateContext,
+) -> i32 {
+    match run_model_command(subcommand, template_context).await {
+        Ok(_) => EXIT_SUCCESS,
+        Err(e) => {
+            eprintln!("Model command failed: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+/// Main entry point for model command
+pub async fn run_model_command(
+    subcommand: ModelSubcommand,
+    template_context: &TemplateContext,
+) -> Result<()> {
+    let configured = configured_llama_config(template_context);
+    let catalog = match &configured {
+        Some(config) => ModelCatalog::new().with_model_source(&config.model.source),
+        None => ModelCatalog::new(),
+    };
+
+    match subcommand {
+        ModelSubcommand::List { format } => list_models_command(&catalog, configured, format),
+        ModelSubcommand::Verify { model, format } => {
+            verify_model_command(&catalog, configured, model, format)
+        }
+        ModelSubcommand::Info { model, format } => {
+            info_model_command(&catalog, configured, model, format)
+        }
+        ModelSubcommand::Use {
+            model,
+            file,
+            global,
+        } => use_model_command(&catalog, configured, model, file, global),
+    }
+}
+
+/// LlamaAgent configuration currently in effect, if LlamaAgent is selected
+fn configured_llama_config(template_context: &TemplateContext) -> Option<LlamaAgentConfig> {
+    match template_context.get_agent_config(None).executor {
+        AgentExecutorConfig::LlamaAgent(config) => Some(config),
+        AgentExecutorConfig::ClaudeCode(_) => None,
+    }
+}
+
+fn list_models_command(
+    catalog: &ModelCatalog,
+    configured: Option<LlamaAgentConfig>,
+    format: OutputFormat,
+) -> Result<()> {
+    let models = catalog.list();
+    let configured_source = configured.map(|config| config.model.source);
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&models)?);
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&models)?);
+        }
+        OutputFormat::Table => {
+            if models.is_empty() {
+                println!("No GGUF models found.");
+                if let Some(dir) = catalog.hf_cache_dir() {
+                    println!("  Searched HuggingFace cache: {}", dir.display());
+                }
+                return Ok(());
+            }
+
+            let is_tty = std::io::stdout().is_terminal();
+            for model in &models {
+                let selected = configured_source
+                    .as_ref()
+                    .is_some_and(|source| model.matches_source(source));
+                let marker = if selected { "*" } else { " " };
+                let name = model.display_name();
+                if is_tty && selected {
+                    println!("{} {}", marker.green().bold(), name.green().bold());
+                } else if is_tty {
+                    println!("{} {}", marker, name.bold());
+                } else {
+                    println!("{} {}", marker, name);
+                }
+
+                println!("    {}", model_summary(model));
+                if let Some(error) = &model.header_error {
+                    println!("    ❌ {}", error);
+                }
+                if let Some(warning) = memory_warning(model.size_bytes) {
+                    println!("    ⚠️  {}", warning);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// One line summary of size, quantization, architecture and context length
+fn model_summary(model: &LocalModel) -> String {
+    let mut parts = vec![format_size(model.size_bytes)];
+    parts.push(
+        model
+            .quantization()
+            .unwrap_or_else(|| "unknown quant".to_string()),
+    );
+    if let Some(info) = &model.info {
+        if let Some(arch) = &info.architecture {
+            parts.push(arch.clone());
+        }
+        if let Some(context_length) = info.context_length {
+            parts.push(format!("ctx {}", context_length));
+        }
+    }
+    parts.join(" | ")
+}
+
+/// Find the model named by `query`, or the configured model when no query is given
+fn select_model(
+    catalog: &ModelCatalog,
+    configured: Option<&LlamaAgentConfig>,
+    query: Option<&str>,
+) -> Result<LocalModel> {
+    let Some(query) = query else {
+        let config = configured.ok_or_else(|| {
+            SwissArmyHammerError::Config(
+                "No LlamaAgent model is configured; pass a model or run 'sah model use'"
+                    .to_string(),
+            )
+        })?;
+        return catalog.resolve(&config.model.source).ok_or_else(|| {
+            SwissArmyHammerError::Other(format!(
+                "Configured model {} was not found locally; it has not been downloaded yet",
+                describe_source(&config.model.source)
+            ))
+        });
+    };
+
+    let mut matches = catalog.find(query);
+    match matches.len() {
+        0 => Err(SwissArmyHammerError::Other(format!(
+            "No local model matches '{}'. Run 'sah model list' to see available models",
+            query
+        ))),
+        1 => Ok(matches.remove(0)),
+        _ => {
+            let names: Vec<String> = matches.iter().map(LocalModel::display_name).collect();
+            Err(SwissArmyHammerError::Other(format!(
+                "'{}' matches several models:\n  {}",
+                query,
+                names.join("\n  ")
+            )))
+        }
+    }
+}
+
+fn describe_source(source: &ModelSource) -> String {
+    match source {
+        ModelSource::HuggingFace {
+            repo,
+            filename: Some(filename),
+            ..
+        } => format!("{}/{}", repo, filename),
+        ModelSource::HuggingFace { repo, .. } => repo.clone(),
+        ModelSource::Local { filename, .. } => filename.display().to_string(),
+    }
+}
+
+/// Validate a model source with the LlamaAgent executor's own configuration checks
+fn validate_source(configured: Option<&LlamaAgentConfig>, source: ModelSource) -> Result<()> {
+    let mut config = configured.cloned().unwrap_or_default();
+    config.model.source = source;
+    LlamaAgentExecutor::new(config)
+        .validate_config()
+        .map_err(|e| SwissArmyHammerError::Config(e.to_string()))
+}
+
+fn verify_model_command(
+    catalog: &ModelCatalog,
+    configured: Option<LlamaAgentConfig>,
+    query: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let model = select_model(catalog, configured.as_ref(), query.as_deref())?;
+    let verification = verify_model(&model.path)?;
+    let config_error = validate_source(configured.as_ref(), model.to_model_source())
+        .err()
+        .map(|e| e.to_string());
+
+    match format {
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let report = serde_json::json!({
+                "model": model.display_name(),
+                "verification": verification,
+                "config_error": config_error,
+            });
+            if matches!(format, OutputFormat::Json) {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!("{}", serde_yaml::to_string(&report)?);
+            }
+        }
+        OutputFormat::Table => {
+            println!("🔍 Verifying {}", model.display_name());
+            println!("   Path: {}", verification.path.display());
+            println!("   Size: {}", format_size(verification.size_bytes));
+
+            match (&verification.info, &verification.header_error) {
+                (Some(info), _) => println!(
+                    "✅ GGUF header: v{}, {} tensors, {}",
+                    info.version,
+                    info.tensor_count,
+                    info.architecture
+                        .as_deref()
+                        .unwrap_or("unknown architecture")
+                ),
+                (None, Some(error)) => println!("❌ GGUF header: {}", error),
+                (None, None) => {}
+            }
+
+            match &verification.checksum {
+                ChecksumStatus::Match { sha256 } => {
+                    println!("✅ Checksum: sha256 {} matches the hub cache", sha256)
+                }
+                ChecksumStatus::Mismatch { expected, actual } => println!(
+                    "❌ Checksum: expected sha256 {} but found {}",
+                    expected, actual
+                ),
+                ChecksumStatus::Unavailable => {
+                    println!("⚠️  Checksum: no reference checksum available")
+                }
+            }
+
+            match &verification.memory_warning {
+                Some(warning) => println!("⚠️  Memory: {}", warning),
+                None => println!("✅ Memory: fits in available memory"),
+            }
+
+            match &config_error {
+                Some(error) => println!("❌ Configuration: {}", error),
+                None => println!("✅ Configuration: valid for LlamaAgent"),
+            }
+        }
+    }
+
+    if verification.is_valid() && config_error.is_none() {
+        Ok(())
+    } else {
+        Err(SwissArmyHammerError::Other(format!(
+            "Verification failed for {}",
+            model.display_name()
+        )))
+    }
+}
+
+fn info_model_command(
+    catalog: &ModelCatalog,
+    configured: Option<LlamaAgentConfig>,
+    query: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let model = select_model(catalog, configured.as_ref(), query.as_deref())?;
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&model)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&model)?),
+        OutputFormat::Table => {
+            println!("📦 Model: {}", model.display_name());
+            println!("   Path: {}", model.path.display());
+            if let Some(repo) = &model.repo {
+                println!("   Repository: {}", repo);
+            }
+            println!("   Size: {}", format_size(model.size_bytes));
+            println!(
+                "   Quantization: {}",
+                model.quantization().as_deref().unwrap_or("unknown")
+            );
+
+            match (&model.info, &model.header_error) {
+                (Some(info), _) => {
+                    if let Some(name) = &info.name {
+                        println!("   Name: {}", name);
+                    }
+                    if let Some(arch) = &info.architecture {
+                        println!("   Architecture: {}", arch);
+                    }
+                    if let Some(context_length) = info.context_length {
+                        println!("   Context length: {}", context_length);
+                    }
+                    println!("   GGUF version: {}", info.version);
+                    println!("   Tensors: {}", info.tensor_count);
+
+                    let mut keys: Vec<&String> = info.metadata.keys().collect();
+                    keys.sort();
+                    println!("   Metadata:");
+                    for key in keys {
+                        println!("     {} = {}", key, info.metadata[key]);
+                    }
+                }
+                (None, Some(error)) => println!("❌ {}", error),
+                (None, None) => {}
+            }
+
+            if let Some(warning) = memory_warning(model.size_bytes) {
+                println!("⚠️  {}", warning);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn use_model_command(
+    catalog: &ModelCatalog,
+    configured: Option<LlamaAgentConfig>,
+    query: String,
+    file: Option<String>,
+    global: bool,
+) -> Result<()> {
+    let source = match (catalog.find(&query).as_slice(), file) {
+        // An explicit repository and file may be selected before it is downloaded
+        (_, Some(filename)) => ModelSource::HuggingFace {
+            repo: query.clone(),
+            filename: Some(filename),
+            folder: None,
+        },
+        ([model], None) => model.to_model_source(),
+        ([], None) => {
+            return Err(SwissArmyHammerError::Other(format!(
+                "No local model matches '{}'. Pass a .gguf path, or a repository with --file",
+                query
+            )))
+        }
+        (models, None) => {
+            let names: Vec<String> = models.iter().map(LocalModel::display_name).collect();
+            return Err(SwissArmyHammerError::Other(format!(
+                "'{}' matches several models:\n  {}",
+                query,
+                names.join("\n  ")
+            )));
+        }
+    };
+
+    validate_source(configured.as_ref(), source.clone())?;
+
+    let config_file = config_file_path(global)?;
+    write_model_selection(&config_file, &source)?;
+
+    println!(
+        "✅ Selected {} in {}",
+        describe_source(&source),
+        config_file.display()
+    );
+    if let Some(model) = catalog.resolve(&source) {
+        if let Some(warning) = memory_warning(model.size_bytes) {
+            println!("⚠️  {}", warning);
+        }
+    } else {
+        println!("   The model will be downloaded on first use.");
+    }
+
+    Ok(())
+}
+
+/// The `sah.toml` that `model use` writes to
+fn config_file_path(global: bool) -> Result<PathBuf> {
+    let discovery = ConfigurationDiscovery::for_cli()
+        .map_err(|e| SwissArmyHammerError::Config(e.to_string()))?;
+    let paths = discovery.paths();
+
+    let dir = if global {
+        paths
+            .global_dir
+            .clone()
+            .or_else(|| dirs::home_dir().map(|home| home.join(".swissarmyhammer")))
+    } else {
+        paths.project_dir.clone().or_else(|| {
+            std::env::current_dir()
+                .ok()
+                .map(|dir| dir.join(".swissarmyhammer"))
+        })
+    };
+
+    dir.map(|dir| dir.join("sah.toml")).ok_or_else(|| {
+        SwissArmyHammerError::Config("Could not determine configuration directory".to_string())
+    })
+}
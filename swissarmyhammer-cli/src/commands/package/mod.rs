@@ -0,0 +1,224 @@
+//! Package command implementation
+//!
+//! Installs, lists, removes and syncs workflow packages
+
+use crate::cli::{OutputFormat, PackageSubcommand};
+use crate::exit_codes::{EXIT_ERROR, EXIT_SUCCESS};
+use colored::*;
+use is_terminal::IsTerminal;
+use serde::Serialize;
+use std::path::PathBuf;
+use swissarmyhammer::directory_utils::{find_git_repository_root, find_swissarmyhammer_directory};
+use swissarmyhammer::file_loader::FileOverride;
+use swissarmyhammer::package::{InstallOutcome, InstallReport, PackageManager};
+use swissarmyhammer::workflow::{MemoryWorkflowStorage, WorkflowResolver};
+use swissarmyhammer::{FileSource, PromptLibrary, PromptResolver, Result, SwissArmyHammerError};
+
+/// Help text for the package command
+pub const DESCRIPTION: &str = include_str!("description.md");
+
+/// Handle the package command
+pub async fn handle_command(subcommand: PackageSubcommand) -> i32 {
+    match run_package_command(subcommand) {
+        Ok(_) => EXIT_SUCCESS,
+        Err(e) => {
+            eprintln!("Package command failed: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+/// Main entry point for package command
+pub fn run_package_command(subcommand: PackageSubcommand) -> Result<()> {
+    match subcommand {
+        PackageSubcommand::Install { source, global } => {
+            let manager = PackageManager::new(package_root(global)?);
+            let report = manager.install(&source)?;
+            print_report(&report);
+            println!("Lockfile: {}", manager.lockfile_path().display());
+            Ok(())
+        }
+        PackageSubcommand::List { format, global } => {
+            list_packages_command(&PackageManager::new(package_root(global)?), format)
+        }
+        PackageSubcommand::Remove { name, global } => {
+            let manager = PackageManager::new(package_root(global)?);
+            let removed = manager.remove(&name)?;
+            println!("Removed {} {}", removed.name, removed.version);
+            Ok(())
+        }
+        PackageSubcommand::Sync { global } => {
+            let manager = PackageManager::new(package_root(global)?);
+            let report = manager.sync()?;
+            if report.entries.is_empty() {
+                println!(
+                    "No packages pinned in {}",
+                    manager.lockfile_path().display()
+                );
+            }
+            print_report(&report);
+            Ok(())
+        }
+    }
+}
+
+/// The `.swissarmyhammer` directory packages are installed into
+fn package_root(global: bool) -> Result<PathBuf> {
+    let root = if global {
+        dirs::home_dir().map(|home| home.join(".swissarmyhammer"))
+    } else {
+        find_swissarmyhammer_directory()
+            .or_else(|| find_git_repository_root().map(|root| root.join(".swissarmyhammer")))
+            .or_else(|| {
+                std::env::current_dir()
+                    .ok()
+                    .map(|dir| dir.join(".swissarmyhammer"))
+            })
+    };
+
+    root.ok_or_else(|| {
+        SwissArmyHammerError::Config("Could not determine package directory".to_string())
+    })
+}
+
+fn print_report(report: &InstallReport) {
+    for entry in &report.entries {
+        let package = format!("{} {}", entry.name, entry.version);
+        let line = match &entry.outcome {
+            InstallOutcome::Installed => format!("Installed {package}"),
+            InstallOutcome::Upgraded { from } => format!("Upgraded {package} (was {from})"),
+            InstallOutcome::Unchanged => format!("Up to date {package}"),
+        };
+        if entry.dependency {
+            println!("{line} (dependency)");
+        } else {
+            println!("{line}");
+        }
+    }
+}
+
+/// A file override involving an installed package
+#[derive(Debug, Serialize)]
+struct PackageOverride {
+    kind: &'static str,
+    /// Whether the package file is the one being replaced
+    shadowed: bool,
+    name: String,
+    overridden: String,
+    by: String,
+}
+
+/// An installed package as shown by `package list`
+#[derive(Debug, Serialize)]
+struct PackageListing {
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved: Option<String>,
+    path: PathBuf,
+    overrides: Vec<PackageOverride>,
+}
+
+fn list_packages_command(manager: &PackageManager, format: OutputFormat) -> Result<()> {
+    let overrides = loaded_overrides()?;
+    let listings: Vec<PackageListing> = manager
+        .list()?
+        .into_iter()
+        .map(|package| {
+            let source = FileSource::Package(package.manifest.name.clone());
+            PackageListing {
+                overrides: overrides
+                    .iter()
+                    .filter(|(_, o)| o.overridden == source || o.by == source)
+                    .map(|(kind, o)| PackageOverride {
+                        kind: *kind,
+                        shadowed: o.overridden == source,
+                        name: o.name.clone(),
+                        overridden: o.overridden.to_string(),
+                        by: o.by.to_string(),
+                    })
+                    .collect(),
+                name: package.manifest.name,
+                version: package.manifest.version,
+                description: package.manifest.description,
+                source: package.locked.as_ref().map(|l| l.source.clone()),
+                resolved: package.locked.and_then(|l| l.resolved),
+                path: package.path,
+            }
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&listings)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&listings)?),
+        OutputFormat::Table => {
+            if listings.is_empty() {
+                println!(
+                    "No packages installed in {}",
+                    manager.packages_dir().display()
+                );
+                return Ok(());
+            }
+
+            let is_tty = std::io::stdout().is_terminal();
+            for listing in &listings {
+                let title = format!("{} {}", listing.name, listing.version);
+                if is_tty {
+                    println!("{}", title.cyan().bold());
+                } else {
+                    println!("{}", title);
+                }
+                if let Some(description) = &listing.description {
+                    println!("  {}", description);
+                }
+                if let Some(source) = &listing.source {
+                    match &listing.resolved {
+                        Some(commit) => println!("  source: {} ({})", source, commit),
+                        None => println!("  source: {}", source),
+                    }
+                }
+                for o in &listing.overrides {
+                    let line = if o.shadowed {
+                        format!("  {} '{}' is overridden by {}", o.kind, o.name, o.by)
+                    } else {
+                        format!("  overrides {} {} '{}'", o.overridden, o.kind, o.name)
+                    };
+                    if is_tty {
+                        println!("{}", line.yellow());
+                    } else {
+                        println!("{}", line);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Overrides recorded while loading prompts and workflows from every source
+fn loaded_overrides() -> Result<Vec<(&'static str, FileOverride)>> {
+    let mut library = PromptLibrary::new();
+    let mut prompt_resolver = PromptResolver::new();
+    prompt_resolver.load_all_prompts(&mut library)?;
+
+    let mut storage = MemoryWorkflowStorage::new();
+    let mut workflow_resolver = WorkflowResolver::new();
+    workflow_resolver.load_all_workflows(&mut storage)?;
+
+    Ok(prompt_resolver
+        .overrides()
+        .iter()
+        .map(|o| ("prompt", o.clone()))
+        .chain(
+            workflow_resolver
+                .overrides()
+                .iter()
+                .map(|o| ("workflow", o.clone())),
+        )
+        .collect())
+}
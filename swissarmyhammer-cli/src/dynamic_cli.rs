@@ -588,7 +588,7 @@ Example:
         arg
     }
 
-    /// Add static commands to the CLI (doctor, prompt, flow, validate, plan, implement)
+    /// Add static commands to the CLI (doctor, prompt, flow, model, package, validate, plan, implement)
     fn add_static_commands(mut cli: Command) -> Command {
         // Add doctor command
         cli = cli.subcommand(
@@ -623,6 +623,12 @@ Example:
         // Add flow command with subcommands
         cli = cli.subcommand(Self::build_flow_command());
 
+        // Add model command with subcommands
+        cli = cli.subcommand(Self::build_model_command());
+
+        // Add package command with subcommands
+        cli = cli.subcommand(Self::build_package_command());
+
         // Add validate command
         cli = cli.subcommand(
             Command::new("validate")
@@ -731,6 +737,121 @@ Examples:
         cli
     }
 
+    /// Build the model command with all its subcommands
+    fn build_model_command() -> Command {
+        let format_arg = || {
+            Arg::new("format")
+                .long("format")
+                .help("Output format")
+                .value_parser(["table", "json", "yaml"])
+                .default_value("table")
+        };
+
+        Command::new("model")
+            .about("Manage local GGUF models for the LlamaAgent executor")
+            .long_about(crate::commands::model::DESCRIPTION)
+            .subcommand(
+                Command::new("list")
+                    .about("List local GGUF models with size and quantization")
+                    .arg(format_arg()),
+            )
+            .subcommand(
+                Command::new("verify")
+                    .about("Verify a model's header, checksum, memory fit and configuration")
+                    .arg(
+                        Arg::new("model")
+                            .help("Model path or name (defaults to the configured model)")
+                            .value_name("MODEL"),
+                    )
+                    .arg(format_arg()),
+            )
+            .subcommand(
+                Command::new("info")
+                    .about("Show GGUF metadata for a model")
+                    .arg(
+                        Arg::new("model")
+                            .help("Model path or name (defaults to the configured model)")
+                            .value_name("MODEL"),
+                    )
+                    .arg(format_arg()),
+            )
+            .subcommand(
+                Command::new("use")
+                    .about("Select a model for the LlamaAgent executor in sah.toml")
+                    .arg(
+                        Arg::new("model")
+                            .help("Model path, listed model name, or HuggingFace repository when --file is given")
+                            .value_name("MODEL")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("file")
+                            .long("file")
+                            .help("Model file within the HuggingFace repository")
+                            .value_name("FILE"),
+                    )
+                    .arg(
+                        Arg::new("global")
+                            .long("global")
+                            .help("Write to the global ~/.swissarmyhammer/sah.toml instead of the project config")
+                            .action(ArgAction::SetTrue),
+                    ),
+            )
+    }
+
+    /// Build the package command with all its subcommands
+    fn build_package_command() -> Command {
+        let global_arg = |help: &'static str| {
+            Arg::new("global")
+                .long("global")
+                .help(help)
+                .action(ArgAction::SetTrue)
+        };
+
+        Command::new("package")
+            .about("Install and manage workflow packages")
+            .long_about(crate::commands::package::DESCRIPTION)
+            .subcommand(
+                Command::new("install")
+                    .about("Install a package from a git URL, a .tar.gz archive or a directory")
+                    .arg(
+                        Arg::new("source")
+                            .help("Package source, e.g. https://github.com/org/kit.git#v1.0 or kit-1.0.tar.gz")
+                            .value_name("SOURCE")
+                            .required(true),
+                    )
+                    .arg(global_arg("Install into ~/.swissarmyhammer instead of the project")),
+            )
+            .subcommand(
+                Command::new("list")
+                    .about("List installed packages and the files they provide or that override them")
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .help("Output format")
+                            .value_parser(["table", "json", "yaml"])
+                            .default_value("table"),
+                    )
+                    .arg(global_arg("List packages in ~/.swissarmyhammer instead of the project")),
+            )
+            .subcommand(
+                Command::new("remove")
+                    .about("Remove an installed package")
+                    .arg(
+                        Arg::new("name")
+                            .help("Package name")
+                            .value_name("NAME")
+                            .required(true),
+                    )
+                    .arg(global_arg("Remove from ~/.swissarmyhammer instead of the project")),
+            )
+            .subcommand(
+                Command::new("sync")
+                    .about("Install exactly the packages pinned in sah.lock")
+                    .arg(global_arg("Sync ~/.swissarmyhammer instead of the project")),
+            )
+    }
+
     /// Build the prompt command with all its subcommands
     fn build_prompt_command() -> Command {
         Command::new("prompt")
@@ -756,7 +877,7 @@ Examples:
                         Arg::new("source")
                             .long("source")
                             .help("Filter by source")
-                            .value_parser(["builtin", "user", "local", "dynamic"]),
+                            .value_parser(["builtin", "package", "user", "local", "dynamic"]),
                     )
                     .arg(
                         Arg::new("category")
@@ -855,7 +976,7 @@ Examples:
                         Arg::new("source")
                             .long("source")
                             .help("Filter by source")
-                            .value_parser(["builtin", "user", "local", "dynamic"]),
+                            .value_parser(["builtin", "package", "user", "local", "dynamic"]),
                     )
                     .arg(
                         Arg::new("has-arg")
@@ -948,6 +1069,19 @@ Examples:
                             .long("quiet")
                             .help("Quiet mode - only show errors")
                             .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("cache")
+                            .long("cache")
+                            .help("Replay cached agent responses for unchanged prompts")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("no-cache")
+                            .long("no-cache")
+                            .help("Bypass the agent response cache, even when SWISSARMYHAMMER_AGENT_CACHE enables it")
+                            .action(ArgAction::SetTrue)
+                            .conflicts_with("cache"),
                     ),
             )
             .subcommand(
@@ -972,6 +1106,54 @@ Examples:
                             .help("Execution timeout")
                             .value_name("DURATION"),
                     )
+                    .arg(
+                        Arg::new("quiet")
+                            .short('q')
+                            .long("quiet")
+                            .help("Quiet mode - only show errors")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("cache")
+                            .long("cache")
+                            .help("Replay cached agent responses for unchanged prompts")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("no-cache")
+                            .long("no-cache")
+                            .help("Bypass the agent response cache, even when SWISSARMYHAMMER_AGENT_CACHE enables it")
+                            .action(ArgAction::SetTrue)
+                            .conflicts_with("cache"),
+                    ),
+            )
+            .subcommand(
+                Command::new("approve")
+                    .about("Approve or reject a run waiting at an approval gate and resume it")
+                    .arg(
+                        Arg::new("run_id")
+                            .help("Run ID waiting for approval")
+                            .value_name("RUN_ID")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("reject")
+                            .long("reject")
+                            .help("Reject instead of approving")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("reason")
+                            .long("reason")
+                            .help("Reason recorded with the decision")
+                            .value_name("REASON"),
+                    )
+                    .arg(
+                        Arg::new("timeout")
+                            .long("timeout")
+                            .help("Execution timeout")
+                            .value_name("DURATION"),
+                    )
                     .arg(
                         Arg::new("quiet")
                             .short('q')
@@ -1001,7 +1183,7 @@ Examples:
                         Arg::new("source")
                             .long("source")
                             .help("Filter by source")
-                            .value_parser(["builtin", "user", "local", "dynamic"]),
+                            .value_parser(["builtin", "package", "user", "local", "dynamic"]),
                     ),
             )
             .subcommand(
@@ -1054,8 +1236,20 @@ Examples:
                     .arg(
                         Arg::new("level")
                             .long("level")
-                            .help("Filter logs by level")
+                            .help("Show only events at or above this level (debug, info, warn, error)")
                             .value_name("LEVEL"),
+                    )
+                    .arg(
+                        Arg::new("state")
+                            .long("state")
+                            .help("Show only events for this state")
+                            .value_name("STATE"),
+                    )
+                    .arg(
+                        Arg::new("json")
+                            .long("json")
+                            .help("Print events as JSON lines")
+                            .action(ArgAction::SetTrue),
                     ),
             )
             .subcommand(
@@ -1129,6 +1323,61 @@ Examples:
                             .action(ArgAction::SetTrue),
                     ),
             )
+            .subcommand(
+                Command::new("trace")
+                    .about("Export a workflow run as OpenTelemetry (OTLP) spans")
+                    .arg(
+                        Arg::new("run_id")
+                            .help("Run ID to export")
+                            .value_name("RUN_ID")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("output")
+                            .short('o')
+                            .long("output")
+                            .help("Write the trace as an OTLP JSON file instead of sending it")
+                            .value_name("FILE"),
+                    )
+                    .arg(
+                        Arg::new("endpoint")
+                            .long("endpoint")
+                            .help("OTLP/HTTP collector endpoint (defaults to telemetry.otlp_endpoint in sah.toml)")
+                            .value_name("URL"),
+                    ),
+            )
+            .subcommand(
+                Command::new("diff")
+                    .about("Compare two workflow runs")
+                    .arg(
+                        Arg::new("run_a")
+                            .help("Baseline run ID")
+                            .value_name("RUN_A")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("run_b")
+                            .help("Run ID to compare against the baseline")
+                            .value_name("RUN_B")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("json")
+                            .long("json")
+                            .help("Print the comparison as JSON")
+                            .action(ArgAction::SetTrue),
+                    ),
+            )
+            .subcommand(
+                Command::new("prune")
+                    .about("Remove and archive old workflow runs according to the retention policy")
+                    .arg(
+                        Arg::new("dry-run")
+                            .long("dry-run")
+                            .help("Show what would be removed or archived without changing anything")
+                            .action(ArgAction::SetTrue),
+                    ),
+            )
             .subcommand(
                 Command::new("test")
                     .about("Test a workflow without executing actions")
@@ -65,6 +65,9 @@ pub fn run_list_command(
         // Get the source from the resolver
         let prompt_source = match resolver.prompt_sources.get(&prompt.name) {
             Some(swissarmyhammer::PromptSource::Builtin) => PromptSource::Builtin,
+            Some(swissarmyhammer::PromptSource::Package(name)) => {
+                PromptSource::Package(name.clone())
+            }
             Some(swissarmyhammer::PromptSource::User) => PromptSource::User,
             Some(swissarmyhammer::PromptSource::Local) => PromptSource::Local,
             Some(swissarmyhammer::PromptSource::Dynamic) => PromptSource::Dynamic,
@@ -167,6 +170,10 @@ fn display_table_to_writer<W: Write>(
                     info.name.green().bold().to_string(),
                     title.green().to_string(),
                 ),
+                PromptSource::Package(_) => (
+                    info.name.cyan().bold().to_string(),
+                    title.cyan().to_string(),
+                ),
                 PromptSource::User => (
                     info.name.blue().bold().to_string(),
                     title.blue().to_string(),
@@ -225,6 +225,8 @@ async fn handle_dynamic_matches(
             handle_prompt_command(sub_matches, &template_context).await
         }
         Some(("flow", sub_matches)) => handle_flow_command(sub_matches, &template_context).await,
+        Some(("model", sub_matches)) => handle_model_command(sub_matches, &template_context).await,
+        Some(("package", sub_matches)) => handle_package_command(sub_matches).await,
         Some(("validate", sub_matches)) => {
             handle_validate_command(sub_matches, &template_context).await
         }
@@ -407,6 +409,7 @@ async fn handle_prompt_command(
                 .get_one::<String>("source")
                 .map(|s| match s.as_str() {
                     "builtin" => PromptSourceArg::Builtin,
+                    "package" => PromptSourceArg::Package,
                     "user" => PromptSourceArg::User,
                     "local" => PromptSourceArg::Local,
                     "dynamic" => PromptSourceArg::Dynamic,
@@ -457,6 +460,7 @@ async fn handle_prompt_command(
                 .get_one::<String>("source")
                 .map(|s| match s.as_str() {
                     "builtin" => PromptSourceArg::Builtin,
+                    "package" => PromptSourceArg::Package,
                     "user" => PromptSourceArg::User,
                     "local" => PromptSourceArg::Local,
                     "dynamic" => PromptSourceArg::Dynamic,
@@ -514,6 +518,8 @@ async fn handle_flow_command(
             let dry_run = sub_matches.get_flag("dry-run");
             let timeout = sub_matches.get_one::<String>("timeout").cloned();
             let quiet = sub_matches.get_flag("quiet");
+            let cache = sub_matches.get_flag("cache");
+            let no_cache = sub_matches.get_flag("no-cache");
 
             FlowSubcommand::Run {
                 workflow,
@@ -522,6 +528,8 @@ async fn handle_flow_command(
                 dry_run,
                 timeout,
                 quiet,
+                cache,
+                no_cache,
             }
         }
         Some(("resume", sub_matches)) => {
@@ -529,12 +537,31 @@ async fn handle_flow_command(
             let interactive = sub_matches.get_flag("interactive");
             let timeout = sub_matches.get_one::<String>("timeout").cloned();
             let quiet = sub_matches.get_flag("quiet");
+            let cache = sub_matches.get_flag("cache");
+            let no_cache = sub_matches.get_flag("no-cache");
 
             FlowSubcommand::Resume {
                 run_id,
                 interactive,
                 timeout,
                 quiet,
+                cache,
+                no_cache,
+            }
+        }
+        Some(("approve", sub_matches)) => {
+            let run_id = sub_matches.get_one::<String>("run_id").cloned().unwrap();
+            let reject = sub_matches.get_flag("reject");
+            let reason = sub_matches.get_one::<String>("reason").cloned();
+            let timeout = sub_matches.get_one::<String>("timeout").cloned();
+            let quiet = sub_matches.get_flag("quiet");
+
+            FlowSubcommand::Approve {
+                run_id,
+                reject,
+                reason,
+                timeout,
+                quiet,
             }
         }
         Some(("list", sub_matches)) => {
@@ -548,6 +575,7 @@ async fn handle_flow_command(
                 .get_one::<String>("source")
                 .map(|s| match s.as_str() {
                     "builtin" => PromptSourceArg::Builtin,
+                    "package" => PromptSourceArg::Package,
                     "user" => PromptSourceArg::User,
                     "local" => PromptSourceArg::Local,
                     "dynamic" => PromptSourceArg::Dynamic,
@@ -580,12 +608,16 @@ async fn handle_flow_command(
             let follow = sub_matches.get_flag("follow");
             let tail = sub_matches.get_one::<usize>("tail").copied();
             let level = sub_matches.get_one::<String>("level").cloned();
+            let state = sub_matches.get_one::<String>("state").cloned();
+            let json = sub_matches.get_flag("json");
 
             FlowSubcommand::Logs {
                 run_id,
                 follow,
                 tail,
                 level,
+                state,
+                json,
             }
         }
         Some(("metrics", sub_matches)) => {
@@ -627,6 +659,29 @@ async fn handle_flow_command(
                 path_only,
             }
         }
+        Some(("trace", sub_matches)) => {
+            let run_id = sub_matches.get_one::<String>("run_id").cloned().unwrap();
+            let output = sub_matches.get_one::<String>("output").cloned();
+            let endpoint = sub_matches.get_one::<String>("endpoint").cloned();
+
+            FlowSubcommand::Trace {
+                run_id,
+                output,
+                endpoint,
+            }
+        }
+        Some(("diff", sub_matches)) => {
+            let run_a = sub_matches.get_one::<String>("run_a").cloned().unwrap();
+            let run_b = sub_matches.get_one::<String>("run_b").cloned().unwrap();
+            let json = sub_matches.get_flag("json");
+
+            FlowSubcommand::Diff { run_a, run_b, json }
+        }
+        Some(("prune", sub_matches)) => {
+            let dry_run = sub_matches.get_flag("dry-run");
+
+            FlowSubcommand::Prune { dry_run }
+        }
         Some(("test", sub_matches)) => {
             let workflow = sub_matches.get_one::<String>("workflow").cloned().unwrap();
             let vars = sub_matches
@@ -654,6 +709,79 @@ async fn handle_flow_command(
     commands::flow::handle_command(subcommand, template_context).await
 }
 
+async fn handle_model_command(
+    matches: &clap::ArgMatches,
+    template_context: &TemplateContext,
+) -> i32 {
+    use crate::cli::{ModelSubcommand, OutputFormat};
+
+    let format_of = |sub_matches: &clap::ArgMatches| match sub_matches
+        .get_one::<String>("format")
+        .map(|s| s.as_str())
+    {
+        Some("json") => OutputFormat::Json,
+        Some("yaml") => OutputFormat::Yaml,
+        _ => OutputFormat::Table,
+    };
+
+    let subcommand = match matches.subcommand() {
+        Some(("list", sub_matches)) => ModelSubcommand::List {
+            format: format_of(sub_matches),
+        },
+        Some(("verify", sub_matches)) => ModelSubcommand::Verify {
+            model: sub_matches.get_one::<String>("model").cloned(),
+            format: format_of(sub_matches),
+        },
+        Some(("info", sub_matches)) => ModelSubcommand::Info {
+            model: sub_matches.get_one::<String>("model").cloned(),
+            format: format_of(sub_matches),
+        },
+        Some(("use", sub_matches)) => ModelSubcommand::Use {
+            model: sub_matches.get_one::<String>("model").cloned().unwrap(),
+            file: sub_matches.get_one::<String>("file").cloned(),
+            global: sub_matches.get_flag("global"),
+        },
+        _ => {
+            eprintln!("No model subcommand specified");
+            return EXIT_ERROR;
+        }
+    };
+
+    commands::model::handle_command(subcommand, template_context).await
+}
+
+async fn handle_package_command(matches: &clap::ArgMatches) -> i32 {
+    use crate::cli::{OutputFormat, PackageSubcommand};
+
+    let subcommand = match matches.subcommand() {
+        Some(("install", sub_matches)) => PackageSubcommand::Install {
+            source: sub_matches.get_one::<String>("source").cloned().unwrap(),
+            global: sub_matches.get_flag("global"),
+        },
+        Some(("list", sub_matches)) => PackageSubcommand::List {
+            format: match sub_matches.get_one::<String>("format").map(|s| s.as_str()) {
+                Some("json") => OutputFormat::Json,
+                Some("yaml") => OutputFormat::Yaml,
+                _ => OutputFormat::Table,
+            },
+            global: sub_matches.get_flag("global"),
+        },
+        Some(("remove", sub_matches)) => PackageSubcommand::Remove {
+            name: sub_matches.get_one::<String>("name").cloned().unwrap(),
+            global: sub_matches.get_flag("global"),
+        },
+        Some(("sync", sub_matches)) => PackageSubcommand::Sync {
+            global: sub_matches.get_flag("global"),
+        },
+        _ => {
+            eprintln!("No package subcommand specified");
+            return EXIT_ERROR;
+        }
+    };
+
+    commands::package::handle_command(subcommand).await
+}
+
 async fn handle_validate_command(
     matches: &clap::ArgMatches,
     template_context: &TemplateContext,
@@ -62,9 +62,12 @@ impl CliToolContext {
     ) -> Result<IssueStorageArc, Box<dyn std::error::Error>> {
         // Create storage with working directory - no global directory changes needed
         // This avoids race conditions in parallel test execution
-        let storage = swissarmyhammer::issues::FileSystemIssueStorage::new_default_in(working_dir)?;
+        let issues_dir =
+            swissarmyhammer::issues::FileSystemIssueStorage::default_directory_in(working_dir)?;
+        let backend = swissarmyhammer::issues::IssueStorageBackend::configured()?;
+        let storage = swissarmyhammer::issues::open_issue_storage(backend, issues_dir)?;
 
-        Ok(Arc::new(RwLock::new(Box::new(storage))))
+        Ok(Arc::new(RwLock::new(storage)))
     }
 
     /// Create git operations handler
@@ -235,6 +235,7 @@ impl Validator {
             // Get the source location for better error reporting
             let source_location = match resolver.workflow_sources.get(&workflow.name) {
                 Some(swissarmyhammer::FileSource::Builtin) => "builtin",
+                Some(swissarmyhammer::FileSource::Package(_)) => "package",
                 Some(swissarmyhammer::FileSource::User) => "user",
                 Some(swissarmyhammer::FileSource::Local) => "local",
                 Some(swissarmyhammer::FileSource::Dynamic) => "dynamic",
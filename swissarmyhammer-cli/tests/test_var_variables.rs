@@ -24,6 +24,8 @@ async fn run_workflow_with_vars_in_process(
         dry_run,
         timeout: Some("2s".to_string()), // Use 2 second timeout for fast tests
         quiet: true,
+        cache: false,
+        no_cache: false,
     };
 
     let test_context = swissarmyhammer_config::TemplateContext::new();
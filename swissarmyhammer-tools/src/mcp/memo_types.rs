@@ -11,6 +11,8 @@ use serde::{Deserialize, Serialize};
 /// CreateMemoRequest {
 ///     title: "Meeting Notes".to_string(),
 ///     content: "# Team Meeting\n\nDiscussed project roadmap...".to_string(),
+///     tags: vec!["meetings".to_string()],
+///     folder: Some("team".to_string()),
 /// }
 /// ```
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
@@ -19,6 +21,12 @@ pub struct CreateMemoRequest {
     pub title: String,
     /// Markdown content of the memo
     pub content: String,
+    /// Tags for grouping and filtering the memo
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Optional slash-separated folder path
+    #[serde(default)]
+    pub folder: Option<String>,
 }
 
 /// Request to get a memo by ID
@@ -37,7 +45,7 @@ pub struct GetMemoRequest {
     pub id: String,
 }
 
-/// Request to update a memo's content
+/// Request to update a memo's content, tags or folder
 ///
 /// # Examples
 ///
@@ -45,7 +53,9 @@ pub struct GetMemoRequest {
 /// ```ignore
 /// UpdateMemoRequest {
 ///     id: "01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string(),
-///     content: "# Updated Content\n\nNew information...".to_string(),
+///     content: Some("# Updated Content\n\nNew information...".to_string()),
+///     tags: None,
+///     folder: None,
 /// }
 /// ```
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
@@ -53,7 +63,14 @@ pub struct UpdateMemoRequest {
     /// ULID identifier of the memo to update
     pub id: String,
     /// New markdown content for the memo
-    pub content: String,
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Replacement tags
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// New folder, empty to move the memo out of its folder
+    #[serde(default)]
+    pub folder: Option<String>,
 }
 
 /// Request to delete a memo
@@ -80,42 +97,165 @@ pub struct DeleteMemoRequest {
 /// ```ignore
 /// SearchMemosRequest {
 ///     query: "meeting notes project".to_string(),
+///     tag: None,
+///     folder: None,
+///     mode: Some("hybrid".to_string()),
+///     limit: None,
 /// }
 /// ```
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct SearchMemosRequest {
     /// Search query string to match against memo titles and content
     pub query: String,
+    /// Only search memos with this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Only search memos in this folder or its subfolders
+    #[serde(default)]
+    pub folder: Option<String>,
+    /// Ranking mode: `keyword` (default), `semantic` or `hybrid`
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// Maximum number of results for semantic and hybrid search
+    #[serde(default)]
+    pub limit: Option<usize>,
 }
 
 /// Request to list all memos
 ///
 /// # Examples
 ///
-/// List all available memos:
+/// List the memos in a folder:
 /// ```ignore
 /// ListMemosRequest {
-///     // No parameters needed
+///     tag: None,
+///     folder: Some("projects".to_string()),
 /// }
 /// ```
-#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct ListMemosRequest {
-    // No parameters needed for listing all memos
+    /// Only list memos with this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Only list memos in this folder or its subfolders
+    #[serde(default)]
+    pub folder: Option<String>,
 }
 
 /// Request to get all memos as context
 ///
 /// # Examples
 ///
-/// Get all memo content for AI context:
+/// Get the content of every memo tagged `auth` for AI context:
 /// ```ignore
 /// GetAllContextRequest {
-///     // No parameters needed
+///     tag: Some("auth".to_string()),
+///     folder: None,
 /// }
 /// ```
-#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct GetAllContextRequest {
-    // No parameters needed - returns all memo content formatted for AI consumption
+    /// Only include memos with this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Only include memos in this folder or its subfolders
+    #[serde(default)]
+    pub folder: Option<String>,
+}
+
+/// Request to list memo tags with counts
+///
+/// # Examples
+///
+/// Count the tags used in a folder:
+/// ```ignore
+/// MemoTagsRequest {
+///     folder: Some("projects".to_string()),
+/// }
+/// ```
+#[derive(Debug, Default, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct MemoTagsRequest {
+    /// Only count tags of memos in this folder or its subfolders
+    #[serde(default)]
+    pub folder: Option<String>,
+}
+
+/// Request to list the previous revisions of a memo
+///
+/// # Examples
+///
+/// ```ignore
+/// MemoHistoryRequest {
+///     id: "Session_handling".to_string(),
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct MemoHistoryRequest {
+    /// Identifier of the memo
+    pub id: String,
+}
+
+/// Request to compare two versions of a memo
+///
+/// # Examples
+///
+/// Compare revision 2 with the current content:
+/// ```ignore
+/// MemoDiffRequest {
+///     id: "Session_handling".to_string(),
+///     from: Some(2),
+///     to: None,
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct MemoDiffRequest {
+    /// Identifier of the memo
+    pub id: String,
+    /// Older revision, the most recent revision when omitted
+    #[serde(default)]
+    pub from: Option<u32>,
+    /// Newer revision, the current content when omitted
+    #[serde(default)]
+    pub to: Option<u32>,
+}
+
+/// Request to bring back a previous revision of a memo
+///
+/// # Examples
+///
+/// ```ignore
+/// MemoRestoreRequest {
+///     id: "Session_handling".to_string(),
+///     revision: 2,
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct MemoRestoreRequest {
+    /// Identifier of the memo
+    pub id: String,
+    /// Revision to restore
+    pub revision: u32,
+}
+
+/// Request to export the links between memos, issues and files as a graph
+///
+/// # Examples
+///
+/// Graph the memos of a folder:
+/// ```ignore
+/// MemoGraphRequest {
+///     tag: None,
+///     folder: Some("projects".to_string()),
+/// }
+/// ```
+#[derive(Debug, Default, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct MemoGraphRequest {
+    /// Only graph the links of memos with this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Only graph the links of memos in this folder or its subfolders
+    #[serde(default)]
+    pub folder: Option<String>,
 }
 
 #[cfg(test)]
@@ -127,6 +267,8 @@ mod tests {
         let request = CreateMemoRequest {
             title: "Test Title".to_string(),
             content: "Test Content".to_string(),
+            tags: vec!["test".to_string()],
+            folder: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -152,7 +294,9 @@ mod tests {
     fn test_update_memo_request_serialization() {
         let request = UpdateMemoRequest {
             id: "01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string(),
-            content: "Updated content".to_string(),
+            content: Some("Updated content".to_string()),
+            tags: None,
+            folder: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -174,43 +318,69 @@ mod tests {
         assert_eq!(request.id, deserialized.id);
     }
 
+    #[test]
+    fn test_memo_history_requests_deserialization() {
+        let diff: MemoDiffRequest =
+            serde_json::from_value(serde_json::json!({"id": "Notes", "from": 2})).unwrap();
+        assert_eq!(diff.from, Some(2));
+        assert_eq!(diff.to, None);
+
+        let restore: MemoRestoreRequest =
+            serde_json::from_value(serde_json::json!({"id": "Notes", "revision": 3})).unwrap();
+        assert_eq!(restore.revision, 3);
+        assert!(
+            serde_json::from_value::<MemoRestoreRequest>(serde_json::json!({"id": "Notes"}))
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_search_memos_request_serialization() {
         let request = SearchMemosRequest {
             query: "test search".to_string(),
+            tag: None,
+            folder: None,
+            mode: Some("semantic".to_string()),
+            limit: Some(5),
         };
 
         let json = serde_json::to_string(&request).unwrap();
         let deserialized: SearchMemosRequest = serde_json::from_str(&json).unwrap();
 
         assert_eq!(request.query, deserialized.query);
+        assert_eq!(deserialized.mode.as_deref(), Some("semantic"));
+        assert_eq!(deserialized.limit, Some(5));
     }
 
     #[test]
     fn test_list_memos_request_serialization() {
-        let request = ListMemosRequest {};
+        let request = ListMemosRequest {
+            tag: Some("auth".to_string()),
+            folder: None,
+        };
 
         let json = serde_json::to_string(&request).unwrap();
         let deserialized: ListMemosRequest = serde_json::from_str(&json).unwrap();
 
-        // No fields to compare for empty struct
-        assert_eq!(
-            std::mem::size_of_val(&request),
-            std::mem::size_of_val(&deserialized)
-        );
+        assert_eq!(request.tag, deserialized.tag);
+        assert_eq!(request.folder, deserialized.folder);
+
+        // Filters are optional
+        let empty: ListMemosRequest = serde_json::from_str("{}").unwrap();
+        assert!(empty.tag.is_none() && empty.folder.is_none());
     }
 
     #[test]
     fn test_get_all_context_request_serialization() {
-        let request = GetAllContextRequest {};
+        let request = GetAllContextRequest {
+            tag: None,
+            folder: Some("projects".to_string()),
+        };
 
         let json = serde_json::to_string(&request).unwrap();
         let deserialized: GetAllContextRequest = serde_json::from_str(&json).unwrap();
 
-        // No fields to compare for empty struct
-        assert_eq!(
-            std::mem::size_of_val(&request),
-            std::mem::size_of_val(&deserialized)
-        );
+        assert_eq!(request.tag, deserialized.tag);
+        assert_eq!(request.folder, deserialized.folder);
     }
 }
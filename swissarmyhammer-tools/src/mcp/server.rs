@@ -10,7 +10,7 @@ use std::sync::Arc;
 use swissarmyhammer::common::rate_limiter::get_rate_limiter;
 use swissarmyhammer::file_watcher::{FileWatcher, FileWatcherCallback};
 use swissarmyhammer::git::GitOperations;
-use swissarmyhammer::issues::{FileSystemIssueStorage, IssueStorage};
+use swissarmyhammer::issues::{open_issue_storage, FileSystemIssueStorage, IssueStorageBackend};
 use swissarmyhammer::memoranda::{MarkdownMemoStorage, MemoStorage};
 use swissarmyhammer::workflow::{
     FileSystemWorkflowRunStorage, FileSystemWorkflowStorage, WorkflowRunStorageBackend,
@@ -22,10 +22,10 @@ use tokio::sync::{Mutex, RwLock};
 
 use super::tool_handlers::ToolHandlers;
 use super::tool_registry::{
-    register_abort_tools, register_file_tools, register_issue_tools, register_memo_tools,
-    register_notify_tools, register_outline_tools, register_search_tools, register_shell_tools,
-    register_todo_tools, register_web_fetch_tools, register_web_search_tools, ToolContext,
-    ToolRegistry,
+    register_abort_tools, register_file_tools, register_flow_tools, register_issue_tools,
+    register_memo_tools, register_notify_tools, register_outline_tools, register_search_tools,
+    register_shell_tools, register_todo_tools, register_web_fetch_tools, register_web_search_tools,
+    ToolContext, ToolRegistry,
 };
 
 /// MCP server for serving prompts and workflows
@@ -104,11 +104,15 @@ impl McpServer {
                 })?;
             }
 
-            // Create storage
-            let storage = FileSystemIssueStorage::new_default().map_err(|e| {
-                tracing::error!("Failed to create issue storage: {}", e);
-                SwissArmyHammerError::Other(format!("Failed to create issue storage: {e}"))
-            })?;
+            // Create storage with the configured backend
+            let storage = IssueStorageBackend::configured()
+                .and_then(|backend| {
+                    open_issue_storage(backend, FileSystemIssueStorage::default_directory()?)
+                })
+                .map_err(|e| {
+                    tracing::error!("Failed to create issue storage: {}", e);
+                    SwissArmyHammerError::Other(format!("Failed to create issue storage: {e}"))
+                })?;
 
             // Always restore original working directory if we changed it and it still exists
             if needs_dir_change {
@@ -119,7 +123,7 @@ impl McpServer {
                 }
             }
 
-            Box::new(storage) as Box<dyn IssueStorage>
+            storage
         };
 
         // Initialize memo storage with default location, fallback to temp dir for tests
@@ -168,6 +172,7 @@ impl McpServer {
         // Register all available tools
         register_abort_tools(&mut tool_registry);
         register_file_tools(&mut tool_registry);
+        register_flow_tools(&mut tool_registry);
         register_issue_tools(&mut tool_registry);
         register_memo_tools(&mut tool_registry);
         register_notify_tools(&mut tool_registry);
@@ -71,6 +71,17 @@ impl McpErrorHandler {
             SwissArmyHammerError::IssueAlreadyExists(num) => {
                 McpError::invalid_params(format!("Issue already exists: #{num:06}"), None)
             }
+            SwissArmyHammerError::IssueDependencyCycle(cycle) => {
+                McpError::invalid_params(format!("Issue dependency cycle: {cycle}"), None)
+            }
+            SwissArmyHammerError::IssueParentCycle(cycle) => {
+                McpError::invalid_params(format!("Issue parent cycle: {cycle}"), None)
+            }
+            SwissArmyHammerError::IssueBlocked(reason) => McpError::invalid_params(reason, None),
+            SwissArmyHammerError::IssueLeased(reason) => McpError::invalid_params(reason, None),
+            SwissArmyHammerError::IssueHasDependents(reason) => {
+                McpError::invalid_params(reason, None)
+            }
             SwissArmyHammerError::MemoNotFound(id) => {
                 McpError::invalid_params(format!("Memo not found: {id}"), None)
             }
@@ -83,6 +94,9 @@ impl McpErrorHandler {
             SwissArmyHammerError::MemoValidationFailed(reason) => {
                 McpError::invalid_params(format!("Memo validation failed: {reason}"), None)
             }
+            error @ SwissArmyHammerError::MemoRevisionNotFound { .. } => {
+                McpError::invalid_params(error.to_string(), None)
+            }
             SwissArmyHammerError::PromptNotFound(name) => {
                 McpError::invalid_params(format!("Prompt not found: {name}"), None)
             }
@@ -337,15 +351,31 @@ impl McpFormatter {
         memo: &swissarmyhammer::memoranda::Memo,
         preview_length: usize,
     ) -> String {
+        let metadata = Self::format_memo_metadata(memo)
+            .map(|line| format!("\n  {line}"))
+            .unwrap_or_default();
         format!(
-            "• {} ({})\n  Created: {}\n  Updated: {}\n  Preview: {}",
+            "• {} ({}){}\n  Created: {}\n  Updated: {}\n  Preview: {}",
             memo.title,
             memo.id,
+            metadata,
             Self::format_timestamp(memo.created_at),
             Self::format_timestamp(memo.updated_at),
             Self::format_preview(&memo.content, preview_length)
         )
     }
+
+    /// Format a memo's folder and tags as one line, `None` when it has neither
+    pub fn format_memo_metadata(memo: &swissarmyhammer::memoranda::Memo) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(folder) = &memo.folder {
+            parts.push(format!("Folder: {folder}"));
+        }
+        if !memo.tags.is_empty() {
+            parts.push(format!("Tags: {}", memo.tags.join(", ")));
+        }
+        (!parts.is_empty()).then(|| parts.join(" | "))
+    }
 }
 
 #[cfg(test)]
@@ -431,6 +461,8 @@ mod tests {
             content: "This is a long piece of content that should be truncated in the preview to show only the first part".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            tags: Vec::new(),
+            folder: None,
         };
 
         let preview = McpFormatter::format_memo_preview(&memo, 50);
@@ -439,5 +471,14 @@ mod tests {
         assert!(preview.contains("Updated:"));
         assert!(preview.contains("Preview:"));
         assert!(preview.contains("This is a long piece of content"));
+        assert!(McpFormatter::format_memo_metadata(&memo).is_none());
+
+        let tagged = Memo {
+            tags: vec!["auth".to_string(), "design".to_string()],
+            folder: Some("projects/login".to_string()),
+            ..memo
+        };
+        let preview = McpFormatter::format_memo_preview(&tagged, 50);
+        assert!(preview.contains("Folder: projects/login | Tags: auth, design"));
     }
 }
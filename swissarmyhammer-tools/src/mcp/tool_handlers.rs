@@ -169,7 +169,8 @@ impl ToolHandlers {
         tracing::debug!("Updating memo with ID: {}", request.id);
 
         // Validate memo content using shared validation
-        McpValidation::validate_not_empty(&request.content, "memo content")
+        let content = request.content.unwrap_or_default();
+        McpValidation::validate_not_empty(&content, "memo content")
             .map_err(|e| McpErrorHandler::handle_error(e, "validate memo content"))?;
 
         let memo_id = match MemoId::from_string(request.id.clone()) {
@@ -183,7 +184,7 @@ impl ToolHandlers {
         };
 
         let memo_storage = self.memo_storage.write().await;
-        match memo_storage.update_memo(&memo_id, request.content).await {
+        match memo_storage.update_memo(&memo_id, content).await {
             Ok(memo) => {
                 tracing::info!("Updated memo {}", memo.id);
                 Ok(create_success_response(format!(
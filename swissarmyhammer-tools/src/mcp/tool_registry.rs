@@ -1230,6 +1230,12 @@ pub fn register_abort_tools(registry: &mut ToolRegistry) {
     abort::register_abort_tools(registry);
 }
 
+/// Register all workflow run tools with the registry
+pub fn register_flow_tools(registry: &mut ToolRegistry) {
+    use super::tools::flow;
+    flow::register_flow_tools(registry);
+}
+
 /// Register all file-related tools with the registry
 pub fn register_file_tools(registry: &mut ToolRegistry) {
     use super::tools::files;
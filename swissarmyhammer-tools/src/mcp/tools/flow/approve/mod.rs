@@ -0,0 +1,213 @@
+//! Approval tool for MCP operations
+//!
+//! This module provides the FlowApproveTool for deciding on workflow runs that are
+//! paused at an `await approval` gate. The decision is recorded in the persisted run
+//! and the tool returns at once, so agents and editors can act as approvers without
+//! a terminal attached to the original `sah flow run`. The run continues from the
+//! gate with `sah flow resume`.
+
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::Deserialize;
+use swissarmyhammer::workflow::{
+    record_approval_decision, ApprovalDecision, FileSystemWorkflowRunStorage, WorkflowRunId,
+    WorkflowRunStorageBackend, LOCAL_RUN_STORAGE_DIR,
+};
+
+/// Request structure for deciding on a pending approval
+#[derive(Debug, Deserialize)]
+pub struct FlowApproveRequest {
+    /// ID of the paused workflow run
+    pub run_id: String,
+    /// Whether the gate is approved
+    pub approved: bool,
+    /// Optional reason for the decision
+    pub reason: Option<String>,
+}
+
+/// Tool for approving or rejecting workflow runs paused at an approval gate
+#[derive(Default)]
+pub struct FlowApproveTool;
+
+impl FlowApproveTool {
+    /// Creates a new instance of the FlowApproveTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for FlowApproveTool {
+    fn name(&self) -> &'static str {
+        "flow_approve"
+    }
+
+    fn description(&self) -> &'static str {
+        include_str!("description.md")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "run_id": {
+                    "type": "string",
+                    "description": "ID of the workflow run waiting for approval"
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "true to approve the gate, false to reject it"
+                },
+                "reason": {
+                    "type": "string",
+                    "description": "Optional reason for the decision"
+                }
+            },
+            "required": ["run_id", "approved"]
+        })
+    }
+
+    fn hidden_from_cli(&self) -> bool {
+        true
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: FlowApproveRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        context
+            .rate_limiter
+            .check_rate_limit("unknown", "flow_approve", 1)
+            .map_err(|e| {
+                tracing::warn!("Rate limit exceeded for flow approval: {}", e);
+                McpError::invalid_params(e.to_string(), None)
+            })?;
+
+        McpValidation::validate_not_empty(&request.run_id, "run ID")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate run ID"))?;
+
+        let run_id = WorkflowRunId::parse(&request.run_id)
+            .map_err(|e| McpError::invalid_params(format!("Invalid workflow run ID: {e}"), None))?;
+
+        let mut run_storage = FileSystemWorkflowRunStorage::new(LOCAL_RUN_STORAGE_DIR)
+            .map_err(|e| McpErrorHandler::handle_error(e, "open workflow run storage"))?;
+        let mut run = run_storage
+            .get_run(&run_id)
+            .map_err(|e| McpErrorHandler::handle_error(e, "load workflow run"))?;
+
+        let decision = if request.approved {
+            ApprovalDecision::approve(request.reason)
+        } else {
+            ApprovalDecision::reject(request.reason)
+        }
+        .with_decided_by("mcp");
+
+        let pending = record_approval_decision(&mut run, decision)
+            .map_err(|e| McpErrorHandler::handle_error(e, "record approval decision"))?;
+
+        run_storage
+            .store_run(&run)
+            .map_err(|e| McpErrorHandler::handle_error(e, "store workflow run"))?;
+
+        let verdict = if request.approved {
+            "Approved"
+        } else {
+            "Rejected"
+        };
+        tracing::info!(
+            "{} workflow run {} at state {}",
+            verdict,
+            request.run_id,
+            pending.state
+        );
+
+        // Running the rest of the workflow here would block the caller until it ends
+        let message = format!(
+            "{} '{}' at state {}. Continue run {} with `sah flow resume {}`.",
+            verdict, pending.message, pending.state, request.run_id, request.run_id
+        );
+
+        Ok(BaseToolImpl::create_success_response(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::tool_registry::{BaseToolImpl, ToolRegistry};
+
+    #[test]
+    fn test_flow_approve_tool_name() {
+        let tool = FlowApproveTool::new();
+        assert_eq!(tool.name(), "flow_approve");
+    }
+
+    #[test]
+    fn test_flow_approve_tool_description() {
+        let tool = FlowApproveTool::new();
+        let description = tool.description();
+        assert!(description.contains("approval gate"));
+        assert!(description.contains("run_id"));
+        assert!(description.contains("sah flow resume"));
+    }
+
+    #[test]
+    fn test_flow_approve_tool_schema() {
+        let tool = FlowApproveTool::new();
+        let schema = tool.schema();
+
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("run_id"));
+        assert!(properties.contains_key("approved"));
+        assert!(properties.contains_key("reason"));
+
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&serde_json::Value::String("run_id".to_string())));
+        assert!(required.contains(&serde_json::Value::String("approved".to_string())));
+    }
+
+    #[test]
+    fn test_parse_valid_arguments() {
+        let mut args = serde_json::Map::new();
+        args.insert(
+            "run_id".to_string(),
+            serde_json::Value::String("01K1KQM85501ECE8XJGNZKNJQW".to_string()),
+        );
+        args.insert("approved".to_string(), serde_json::Value::Bool(false));
+        args.insert(
+            "reason".to_string(),
+            serde_json::Value::String("tests are red".to_string()),
+        );
+
+        let request: FlowApproveRequest = BaseToolImpl::parse_arguments(args).unwrap();
+        assert_eq!(request.run_id, "01K1KQM85501ECE8XJGNZKNJQW");
+        assert!(!request.approved);
+        assert_eq!(request.reason.as_deref(), Some("tests are red"));
+    }
+
+    #[test]
+    fn test_parse_missing_decision() {
+        let mut args = serde_json::Map::new();
+        args.insert(
+            "run_id".to_string(),
+            serde_json::Value::String("01K1KQM85501ECE8XJGNZKNJQW".to_string()),
+        );
+        let result: Result<FlowApproveRequest, rmcp::ErrorData> =
+            BaseToolImpl::parse_arguments(args);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_tool_registration() {
+        let mut registry = ToolRegistry::new();
+        registry.register(FlowApproveTool::new());
+
+        assert!(registry.get_tool("flow_approve").is_some());
+    }
+}
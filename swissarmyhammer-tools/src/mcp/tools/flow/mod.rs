@@ -0,0 +1,19 @@
+//! Workflow run tools for MCP operations
+//!
+//! This module provides tools for interacting with workflow runs that were started
+//! outside the MCP session. Runs paused at an approval gate are persisted in the
+//! project's `.swissarmyhammer/workflow-runs` directory and can be continued from
+//! here without an interactive terminal.
+//!
+//! ## Available Tools
+//!
+//! - **approve**: Approve or reject a run waiting at an approval gate and resume it
+
+pub mod approve;
+
+use crate::mcp::tool_registry::ToolRegistry;
+
+/// Register all workflow run tools with the registry
+pub fn register_flow_tools(registry: &mut ToolRegistry) {
+    registry.register(approve::FlowApproveTool::new());
+}
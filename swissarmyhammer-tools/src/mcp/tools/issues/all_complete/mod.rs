@@ -9,6 +9,7 @@ use crate::mcp::types::AllCompleteRequest;
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use swissarmyhammer::issues::{describe_held_issues, IssueHierarchy, NextIssue};
 
 /// Tool for checking if all issues are complete
 #[derive(Default)]
@@ -39,7 +40,12 @@ impl McpTool for AllCompleteIssueTool {
     fn schema(&self) -> serde_json::Value {
         serde_json::json!({
             "type": "object",
-            "properties": {},
+            "properties": {
+                "epic": {
+                    "type": "string",
+                    "description": "Only check the sub-issues of this epic"
+                }
+            },
             "required": []
         })
     }
@@ -49,7 +55,7 @@ impl McpTool for AllCompleteIssueTool {
         arguments: serde_json::Map<String, serde_json::Value>,
         context: &ToolContext,
     ) -> std::result::Result<CallToolResult, McpError> {
-        let _request: AllCompleteRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let request: AllCompleteRequest = BaseToolImpl::parse_arguments(arguments)?;
 
         let issue_storage = context.issue_storage.read().await;
 
@@ -64,11 +70,42 @@ impl McpTool for AllCompleteIssueTool {
             }
         };
 
+        // Narrow the check to the sub-issues of an epic
+        let epic = request
+            .epic
+            .map(|epic| epic.trim().to_string())
+            .filter(|epic| !epic.is_empty());
+        let scope = match &epic {
+            Some(epic) => {
+                if !all_issue_infos.iter().any(|info| &info.issue.name == epic) {
+                    return Err(McpError::invalid_params(
+                        format!("Epic '{epic}' not found"),
+                        None,
+                    ));
+                }
+                Some(IssueHierarchy::from_issue_infos(&all_issue_infos).descendants(epic))
+            }
+            None => None,
+        };
+        let in_scope = |name: &str| {
+            scope
+                .as_ref()
+                .map_or(true, |scope| scope.iter().any(|n| n == name))
+        };
+        let scope_label = match &epic {
+            Some(epic) => format!("Epic {epic}"),
+            None => "Project".to_string(),
+        };
+
         // Separate active and completed issues
         let mut active_issues = Vec::new();
         let mut completed_issues = Vec::new();
 
-        for issue_info in all_issue_infos {
+        for issue_info in all_issue_infos
+            .iter()
+            .filter(|info| in_scope(&info.issue.name))
+            .cloned()
+        {
             if issue_info.completed {
                 completed_issues.push(issue_info.issue);
             } else {
@@ -88,12 +125,31 @@ impl McpTool for AllCompleteIssueTool {
             0
         };
 
+        // Active issues the work loop cannot pick, because each one is blocked, waiting
+        // on dependencies, in review, in progress or claimed by another agent
+        let held = if active_count == 0 {
+            Vec::new()
+        } else {
+            let leases = issue_storage
+                .issue_leases()
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "list issue leases"))?;
+            match NextIssue::pick_where(all_issue_infos, &leases, in_scope) {
+                NextIssue::Stalled(held) => held,
+                NextIssue::Ready(_) | NextIssue::AllDone => Vec::new(),
+            }
+        };
+
         // Generate comprehensive response text
         let response_text = if total_issues == 0 {
-            "📋 No issues found in the project\n\n✨ The project has no tracked issues. You can create issues using the `issue_create` tool.".to_string()
+            match &epic {
+                Some(epic) => format!("📋 Epic {epic} has no sub-issues\n\n✨ Create sub-issues with the `issue_create` tool and `parent: {epic}`."),
+                None => "📋 No issues found in the project\n\n✨ The project has no tracked issues. You can create issues using the `issue_create` tool.".to_string(),
+            }
         } else if all_complete {
             format!(
-                "🎉 All issues are complete!\n\n📊 Project Status:\n• Total Issues: {}\n• Completed: {} (100%)\n• Active: 0\n\n✅ Completed Issues:\n{}",
+                "🎉 All issues are complete!\n\n📊 {} Status:\n• Total Issues: {}\n• Completed: {} (100%)\n• Active: 0\n\n✅ Completed Issues:\n{}",
+                scope_label,
                 total_issues,
                 completed_count,
                 completed_issues.iter()
@@ -101,6 +157,11 @@ impl McpTool for AllCompleteIssueTool {
                     .collect::<Vec<_>>()
                     .join("\n")
             )
+        } else if !held.is_empty() {
+            format!(
+                "⏸️ No workable issue: {scope_label} has active issues, but none can be worked on now ({completion_percentage}% complete)\n\n📊 {scope_label} Status:\n• Total Issues: {total_issues}\n• Completed: {completed_count} ({completion_percentage}%)\n• Active: {active_count}\n\n🚧 Held Issues:\n{}\nUnblock, review or release one of them before working on issues again.",
+                describe_held_issues(&held)
+            )
         } else {
             let active_list = active_issues
                 .iter()
@@ -119,10 +180,84 @@ impl McpTool for AllCompleteIssueTool {
             };
 
             format!(
-                "⏳ Project has active issues ({completion_percentage}% complete)\n\n📊 Project Status:\n• Total Issues: {total_issues}\n• Completed: {completed_count} ({completion_percentage}%)\n• Active: {active_count}\n\n🔄 Active Issues:\n{active_list}\n\n✅ Completed Issues:\n{completed_list}"
+                "⏳ {scope_label} has active issues ({completion_percentage}% complete)\n\n📊 {scope_label} Status:\n• Total Issues: {total_issues}\n• Completed: {completed_count} ({completion_percentage}%)\n• Active: {active_count}\n\n🔄 Active Issues:\n{active_list}\n\n✅ Completed Issues:\n{completed_list}"
             )
         };
 
         Ok(create_success_response(response_text))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+    use swissarmyhammer::issues::{FileSystemIssueStorage, IssueStatus};
+    use swissarmyhammer::workflow::{
+        MemoryWorkflowStorage, StateId, WorkflowExecutor, WorkflowName, WorkflowResolver,
+        WorkflowRun, WorkflowStorageBackend,
+    };
+
+    #[tokio::test]
+    async fn test_implement_loop_stops_when_no_issue_is_workable() {
+        let context = create_test_context().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        *context.issue_storage.write().await =
+            Box::new(FileSystemIssueStorage::new(temp_dir.path().join("issues")).unwrap());
+
+        // Only a blocked issue and one leased by another agent are left
+        let issue_storage = context.issue_storage.write().await;
+        for name in ["blocked_work", "leased_work"] {
+            issue_storage
+                .create_issue(name.to_string(), format!("# {name}"))
+                .await
+                .unwrap();
+        }
+        issue_storage
+            .set_issue_status("blocked_work", IssueStatus::Blocked)
+            .await
+            .unwrap();
+        issue_storage
+            .claim_issue("leased_work", "agent-2", 600)
+            .await
+            .unwrap();
+        assert!(issue_storage.next_issue().await.unwrap().is_stalled());
+        drop(issue_storage);
+
+        let result = AllCompleteIssueTool::new()
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .unwrap();
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.starts_with("⏸️ No workable issue"), "{text}");
+        assert!(text.contains("- blocked_work is blocked\n"));
+        assert!(text.contains("- leased_work is claimed by agent-2 until "));
+
+        // The are_issues_complete prompt answers STALLED for this report
+        let mut storage = MemoryWorkflowStorage::new();
+        WorkflowResolver::new()
+            .load_all_workflows(&mut storage)
+            .unwrap();
+        let workflow = storage
+            .get_workflow(&WorkflowName::new("implement"))
+            .unwrap();
+        let mut run = WorkflowRun::new(workflow);
+        let mut executor = WorkflowExecutor::new();
+
+        run.current_state = StateId::new("loop");
+        run.context
+            .insert("result".to_string(), serde_json::json!({ "content": "NO" }));
+        assert_eq!(
+            executor.evaluate_transitions(&run).unwrap(),
+            Some(StateId::new("work"))
+        );
+
+        run.context.insert(
+            "result".to_string(),
+            serde_json::json!({ "content": "STALLED" }),
+        );
+        let next_state = executor.evaluate_transitions(&run).unwrap().unwrap();
+        assert_eq!(next_state, StateId::new("stalled"));
+        assert!(run.workflow.states[&next_state].is_terminal);
+    }
+}
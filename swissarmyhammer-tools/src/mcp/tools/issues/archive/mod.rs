@@ -0,0 +1,145 @@
+//! Issue archiving tool for MCP operations
+//!
+//! This module provides the ArchiveIssueTool for moving issues to the archive
+//! directory, where they no longer count towards listings or completion checks.
+
+use super::branch::IssueBranchState;
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::ArchiveIssueRequest;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+
+/// Tool for archiving issues
+#[derive(Default)]
+pub struct ArchiveIssueTool;
+
+impl ArchiveIssueTool {
+    /// Creates a new instance of the ArchiveIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for ArchiveIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_archive"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "archive")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Name of the issue to archive"
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: ArchiveIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(request.name.as_str(), "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+
+        // Archiving on the issue branch would break a later issue_merge
+        let branch_state = IssueBranchState::load(context, request.name.as_str()).await?;
+        if let Some(state) = &branch_state {
+            state.ensure_not_checked_out("archive")?;
+        }
+
+        let issue_storage = context.issue_storage.write().await;
+        let issue = issue_storage
+            .archive_issue(request.name.as_str())
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "archive issue"))?;
+
+        let branch_note = match branch_state {
+            Some(state) if state.unmerged => format!(
+                ", its branch '{}' still has unmerged work and was kept",
+                state.branch
+            ),
+            _ => String::new(),
+        };
+
+        tracing::info!("Archived issue '{}'", issue.name);
+        Ok(create_success_response(format!(
+            "Archived issue {}{}",
+            issue.name, branch_note
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{commit_test_file, create_test_git_context};
+
+    #[test]
+    fn test_archive_tool_schema() {
+        let tool = ArchiveIssueTool::new();
+        assert_eq!(tool.name(), "issue_archive");
+        assert_eq!(tool.cli_category(), Some("issue"));
+        assert_eq!(tool.cli_name(), "archive");
+        assert_eq!(tool.schema()["required"], serde_json::json!(["name"]));
+    }
+
+    #[tokio::test]
+    async fn test_archive_refused_on_checked_out_branch_and_keeps_unmerged_branch() {
+        let (temp_dir, context) = create_test_git_context().await;
+        context
+            .issue_storage
+            .write()
+            .await
+            .create_issue("fix_bug".to_string(), "Fix the bug".to_string())
+            .await
+            .unwrap();
+        let git_ops = context.git_ops.lock().await;
+        git_ops
+            .as_ref()
+            .unwrap()
+            .create_work_branch("fix_bug")
+            .unwrap();
+        drop(git_ops);
+
+        let tool = ArchiveIssueTool::new();
+        let args = serde_json::json!({ "name": "fix_bug" })
+            .as_object()
+            .unwrap()
+            .clone();
+        let error = tool.execute(args.clone(), &context).await.unwrap_err();
+        assert!(error.message.contains("is checked out"));
+
+        commit_test_file(temp_dir.path(), "fix.txt", "fixed");
+        let git_ops = context.git_ops.lock().await;
+        git_ops.as_ref().unwrap().checkout_branch("main").unwrap();
+        drop(git_ops);
+
+        let result = tool.execute(args, &context).await.unwrap();
+        assert_eq!(
+            result.content[0].as_text().unwrap().text,
+            "Archived issue fix_bug, its branch 'issue/fix_bug' still has unmerged work and was kept"
+        );
+        let git_ops = context.git_ops.lock().await;
+        assert!(git_ops
+            .as_ref()
+            .unwrap()
+            .branch_exists("issue/fix_bug")
+            .unwrap());
+    }
+}
@@ -0,0 +1,95 @@
+//! Git branch state of an issue
+//!
+//! Tools that move or remove issue files check the issue's work branch first so they
+//! don't pull the file out from under `issue_work` and `issue_merge`.
+//! Tools that accept `current` resolve it from the checked out branch.
+
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::ToolContext;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::config::Config;
+
+/// State of the work branch belonging to an issue
+#[derive(Debug, Clone)]
+pub(crate) struct IssueBranchState {
+    /// Name of the issue's work branch
+    pub branch: String,
+    /// Whether the branch exists locally
+    pub exists: bool,
+    /// Whether the branch is currently checked out
+    pub checked_out: bool,
+    /// Whether the branch has commits not merged into another branch
+    pub unmerged: bool,
+}
+
+impl IssueBranchState {
+    /// Inspect the work branch of an issue, `None` when git is not available
+    pub(crate) async fn load(
+        context: &ToolContext,
+        issue_name: &str,
+    ) -> Result<Option<Self>, McpError> {
+        let git_ops = context.git_ops.lock().await;
+        let Some(ops) = git_ops.as_ref() else {
+            return Ok(None);
+        };
+
+        let branch = format!("{}{}", Config::global().issue_branch_prefix, issue_name);
+        let exists = ops
+            .branch_exists(&branch)
+            .map_err(|e| McpErrorHandler::handle_error(e, "check issue branch"))?;
+        let checked_out = ops
+            .current_branch()
+            .map(|current| current == branch)
+            .unwrap_or(false);
+        let unmerged = exists
+            && ops
+                .list_unmerged_issue_branches()
+                .map_err(|e| McpErrorHandler::handle_error(e, "list unmerged branches"))?
+                .contains(&branch);
+
+        Ok(Some(Self {
+            branch,
+            exists,
+            checked_out,
+            unmerged,
+        }))
+    }
+
+    /// Reject operations that would remove the issue file from its own branch
+    pub(crate) fn ensure_not_checked_out(&self, action: &str) -> Result<(), McpError> {
+        if self.checked_out {
+            return Err(McpError::invalid_params(
+                format!(
+                    "Cannot {action} the issue while its branch '{}' is checked out. Switch to another branch first.",
+                    self.branch
+                ),
+                None,
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Name of the issue whose work branch is checked out, used to resolve `current`
+pub(crate) async fn current_issue_name(context: &ToolContext) -> Result<String, McpError> {
+    let git_ops = context.git_ops.lock().await;
+    let Some(ops) = git_ops.as_ref() else {
+        return Err(McpError::invalid_params(
+            "Git operations not available".to_string(),
+            None,
+        ));
+    };
+
+    let branch = ops
+        .current_branch()
+        .map_err(|e| McpErrorHandler::handle_error(e, "get current branch"))?;
+    branch
+        .strip_prefix(&Config::global().issue_branch_prefix)
+        .map(str::to_string)
+        .ok_or_else(|| {
+            McpError::invalid_params(
+                format!("Not on an issue branch. Current branch: {branch}"),
+                None,
+            )
+        })
+}
@@ -0,0 +1,193 @@
+//! Issue acceptance criteria tool for MCP operations
+//!
+//! This module provides the CheckIssueTool for ticking or unticking checklist items
+//! of an issue by index or text.
+
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::CheckIssueRequest;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::issues::{
+    find_criterion, parse_criteria, parse_issue_content, render_issue_content, set_criterion,
+    CriteriaProgress, CriterionSelector,
+};
+
+/// Tool for ticking acceptance criteria of an issue
+#[derive(Default)]
+pub struct CheckIssueTool;
+
+impl CheckIssueTool {
+    /// Creates a new instance of the CheckIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for CheckIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_check"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "check")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Issue name"
+                },
+                "index": {
+                    "type": "integer",
+                    "description": "Position of the criterion as shown by issue_show, starting at 1",
+                    "minimum": 1
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Text contained in exactly one criterion, used when no index is given"
+                },
+                "checked": {
+                    "type": "boolean",
+                    "description": "Tick the criterion, or untick it when false",
+                    "default": true
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: CheckIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(request.name.as_str(), "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+        let selector = match (request.index, &request.text) {
+            (Some(index), _) => CriterionSelector::Index(index),
+            (None, Some(text)) if !text.trim().is_empty() => CriterionSelector::Text(text.clone()),
+            _ => {
+                return Err(McpError::invalid_params(
+                    "Provide the index or text of the criterion to check".to_string(),
+                    None,
+                ))
+            }
+        };
+
+        let issue_storage = context.issue_storage.write().await;
+        let issue = issue_storage
+            .get_issue(request.name.as_str())
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "check issue criterion"))?;
+
+        let (metadata, body) = parse_issue_content(&issue.content);
+        let criteria = parse_criteria(body);
+        let criterion = find_criterion(&criteria, &selector)
+            .map_err(|e| McpError::invalid_params(format!("Issue '{}': {e}", issue.name), None))?;
+        let updated_body = set_criterion(body, criterion.index, request.checked)
+            .expect("criterion was found in this body");
+
+        let progress = CriteriaProgress::of(&parse_criteria(&updated_body));
+        issue_storage
+            .update_issue(&issue.name, render_issue_content(&metadata, &updated_body))
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "check issue criterion"))?;
+
+        let mut message = format!(
+            "{} criterion {} of issue {}: {} ({} met)",
+            if request.checked {
+                "Checked"
+            } else {
+                "Unchecked"
+            },
+            criterion.index,
+            issue.name,
+            criterion.text,
+            progress
+        );
+        if progress.is_complete() {
+            message.push_str("\nAll acceptance criteria are met.");
+        }
+
+        tracing::info!("{}", message);
+        Ok(create_success_response(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::tools::issues::mark_complete::MarkCompleteIssueTool;
+    use crate::test_utils::create_test_context;
+
+    fn arguments(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn test_check_criteria_gates_completion() {
+        let context = create_test_context().await;
+        let name = "check_tool_criteria";
+        context
+            .issue_storage
+            .write()
+            .await
+            .create_issue(
+                name.to_string(),
+                "# Login\n\n- [ ] Form validates input\n- [ ] Errors are shown\n".to_string(),
+            )
+            .await
+            .unwrap();
+
+        let mark_complete = MarkCompleteIssueTool::new();
+        let refused = mark_complete
+            .execute(arguments(serde_json::json!({"name": name})), &context)
+            .await;
+        assert!(refused.is_err());
+
+        let tool = CheckIssueTool::new();
+        tool.execute(
+            arguments(serde_json::json!({"name": name, "index": 1})),
+            &context,
+        )
+        .await
+        .unwrap();
+        tool.execute(
+            arguments(serde_json::json!({"name": name, "text": "errors"})),
+            &context,
+        )
+        .await
+        .unwrap();
+        assert!(tool
+            .execute(
+                arguments(serde_json::json!({"name": name, "index": 3})),
+                &context
+            )
+            .await
+            .is_err());
+
+        let issue = context
+            .issue_storage
+            .read()
+            .await
+            .get_issue(name)
+            .await
+            .unwrap();
+        assert_eq!(issue.criteria_progress().to_string(), "2/2");
+
+        mark_complete
+            .execute(arguments(serde_json::json!({"name": name})), &context)
+            .await
+            .unwrap();
+    }
+}
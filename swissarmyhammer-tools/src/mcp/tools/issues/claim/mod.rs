@@ -0,0 +1,245 @@
+//! Issue claim tool for MCP operations
+//!
+//! This module provides the ClaimIssueTool for taking, renewing and releasing the
+//! lease that keeps parallel agents from working on the same issue.
+
+use super::branch::current_issue_name;
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::ClaimIssueRequest;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::error::SwissArmyHammerError;
+use swissarmyhammer::issues::{
+    default_lease_owner, describe_held_issues, IssueLease, NextIssue, DEFAULT_LEASE_TTL_SECONDS,
+};
+
+/// How often `next` moves on when another agent claims the issue first
+const MAX_NEXT_ATTEMPTS: usize = 5;
+
+/// Tool for claiming issues
+#[derive(Default)]
+pub struct ClaimIssueTool;
+
+impl ClaimIssueTool {
+    /// Creates a new instance of the ClaimIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn format_lease(lease: &IssueLease) -> String {
+        format!(
+            "Claimed issue {} for {} until {}",
+            lease.issue,
+            lease.owner,
+            lease.expires_at().format("%Y-%m-%d %H:%M:%S UTC")
+        )
+    }
+}
+
+#[async_trait]
+impl McpTool for ClaimIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_claim"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "claim")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Issue name. Use 'next' to claim the next unclaimed issue that is ready, or 'current' for the issue of the current git branch."
+                },
+                "owner": {
+                    "type": "string",
+                    "description": "Who takes the lease. Defaults to SAH_AGENT_ID, or the host and process ID."
+                },
+                "ttl_seconds": {
+                    "type": "integer",
+                    "description": "How long the lease lasts without another claim",
+                    "default": DEFAULT_LEASE_TTL_SECONDS,
+                    "minimum": 1
+                },
+                "release": {
+                    "type": "boolean",
+                    "description": "Give up the lease instead of taking it",
+                    "default": false
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Release a lease held by another owner",
+                    "default": false
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: ClaimIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(request.name.as_str(), "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+        let owner = request
+            .owner
+            .map(|owner| owner.trim().to_string())
+            .filter(|owner| !owner.is_empty())
+            .unwrap_or_else(default_lease_owner);
+        let ttl_seconds = request.ttl_seconds.unwrap_or(DEFAULT_LEASE_TTL_SECONDS);
+        if ttl_seconds == 0 {
+            return Err(McpError::invalid_params(
+                "ttl_seconds must be at least 1".to_string(),
+                None,
+            ));
+        }
+
+        let is_next = request.name.as_str() == "next";
+        if is_next && request.release {
+            return Err(McpError::invalid_params(
+                "Name the issue to release, 'next' only works when claiming".to_string(),
+                None,
+            ));
+        }
+        let issue_name = if request.name.as_str() == "current" {
+            current_issue_name(context).await?
+        } else {
+            request.name.0
+        };
+
+        let issue_storage = context.issue_storage.write().await;
+        if request.release {
+            let released = issue_storage
+                .release_issue(&issue_name, &owner, request.force)
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "release issue"))?;
+            let message = if released {
+                format!("Released issue {issue_name}")
+            } else {
+                format!("Issue {issue_name} was not claimed")
+            };
+            tracing::info!("{}", message);
+            return Ok(create_success_response(message));
+        }
+
+        if !is_next {
+            let lease = issue_storage
+                .claim_issue(&issue_name, &owner, ttl_seconds)
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "claim issue"))?;
+            tracing::info!("Issue {} claimed by {}", lease.issue, lease.owner);
+            return Ok(create_success_response(Self::format_lease(&lease)));
+        }
+
+        // Another process can claim the next issue between picking and claiming it
+        for _ in 0..MAX_NEXT_ATTEMPTS {
+            let issue = match issue_storage
+                .next_issue()
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "get next issue"))?
+            {
+                NextIssue::Ready(issue) => issue,
+                NextIssue::AllDone => {
+                    return Ok(create_success_response(
+                        "No issue left to claim, all issues are completed",
+                    ));
+                }
+                NextIssue::Stalled(held) => {
+                    return Ok(create_success_response(format!(
+                        "No unclaimed issue is ready to work on:\n{}",
+                        describe_held_issues(&held)
+                    )));
+                }
+            };
+            match issue_storage
+                .claim_issue(&issue.name, &owner, ttl_seconds)
+                .await
+            {
+                Ok(lease) => {
+                    tracing::info!("Issue {} claimed by {}", lease.issue, lease.owner);
+                    return Ok(create_success_response(Self::format_lease(&lease)));
+                }
+                Err(SwissArmyHammerError::IssueLeased(reason)) => {
+                    tracing::debug!("Skipping issue {}: {}", issue.name, reason);
+                }
+                Err(e) => return Err(McpErrorHandler::handle_error(e, "claim issue")),
+            }
+        }
+
+        Err(McpError::invalid_params(
+            format!("Other agents claimed the next {MAX_NEXT_ATTEMPTS} issues first, try again"),
+            None,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[tokio::test]
+    async fn test_claim_tool_claims_and_releases() {
+        let context = create_test_context().await;
+        let name = "claim_tool_parallel";
+        context
+            .issue_storage
+            .write()
+            .await
+            .create_issue(name.to_string(), "# Parallel work".to_string())
+            .await
+            .unwrap();
+
+        let tool = ClaimIssueTool::new();
+        let arguments = |value: serde_json::Value| value.as_object().unwrap().clone();
+        tool.execute(
+            arguments(serde_json::json!({"name": name, "owner": "agent-1"})),
+            &context,
+        )
+        .await
+        .unwrap();
+        assert!(tool
+            .execute(
+                arguments(serde_json::json!({"name": name, "owner": "agent-2"})),
+                &context,
+            )
+            .await
+            .is_err());
+
+        let leases = context
+            .issue_storage
+            .read()
+            .await
+            .issue_leases()
+            .await
+            .unwrap();
+        let lease = leases.iter().find(|lease| lease.issue == name).unwrap();
+        assert_eq!(lease.owner, "agent-1");
+
+        tool.execute(
+            arguments(serde_json::json!({"name": name, "owner": "agent-2", "release": true, "force": true})),
+            &context,
+        )
+        .await
+        .unwrap();
+        let leases = context
+            .issue_storage
+            .read()
+            .await
+            .issue_leases()
+            .await
+            .unwrap();
+        assert!(leases.iter().all(|lease| lease.issue != name));
+    }
+}
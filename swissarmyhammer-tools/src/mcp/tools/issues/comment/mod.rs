@@ -0,0 +1,141 @@
+//! Issue comment tool for MCP operations
+//!
+//! This module provides the CommentIssueTool for adding notes to an issue's activity
+//! log without touching the issue body.
+
+use super::branch::current_issue_name;
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::CommentIssueRequest;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::issues::IssueActivity;
+
+/// Tool for commenting on issues
+#[derive(Default)]
+pub struct CommentIssueTool;
+
+impl CommentIssueTool {
+    /// Creates a new instance of the CommentIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for CommentIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_comment"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "comment")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Issue name. Use 'current' to comment on the issue for the current git branch."
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Comment text"
+                },
+                "author": {
+                    "type": "string",
+                    "description": "Who wrote the comment, e.g. reviewer or a person's name"
+                }
+            },
+            "required": ["name", "message"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: CommentIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(request.name.as_str(), "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+        McpValidation::validate_not_empty(&request.message, "comment")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate comment"))?;
+
+        let issue_name = if request.name.as_str() == "current" {
+            current_issue_name(context).await?
+        } else {
+            request.name.0
+        };
+        let author = request
+            .author
+            .map(|author| author.trim().to_string())
+            .filter(|author| !author.is_empty());
+
+        let issue_storage = context.issue_storage.write().await;
+        let entry = issue_storage
+            .add_issue_activity(
+                &issue_name,
+                IssueActivity::comment(request.message.trim(), author),
+            )
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "comment on issue"))?;
+
+        tracing::info!("Commented on issue {}", issue_name);
+        Ok(create_success_response(format!(
+            "Commented on issue {issue_name}:\n{entry}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+    use swissarmyhammer::issues::IssueActivityKind;
+
+    #[tokio::test]
+    async fn test_comment_tool_appends_to_activity_log() {
+        let context = create_test_context().await;
+        let name = "comment_tool_review";
+        context
+            .issue_storage
+            .write()
+            .await
+            .create_issue(name.to_string(), "# Review me".to_string())
+            .await
+            .unwrap();
+
+        let tool = CommentIssueTool::new();
+        let arguments = serde_json::json!({
+            "name": name,
+            "message": "Handle expired tokens",
+            "author": "reviewer"
+        });
+        tool.execute(arguments.as_object().unwrap().clone(), &context)
+            .await
+            .unwrap();
+
+        let issue_storage = context.issue_storage.read().await;
+        let activity = issue_storage.issue_activity(name).await.unwrap();
+        assert_eq!(activity.len(), 1);
+        assert_eq!(activity[0].kind, IssueActivityKind::Comment);
+        assert_eq!(activity[0].author.as_deref(), Some("reviewer"));
+        assert_eq!(
+            issue_storage.get_issue(name).await.unwrap().content,
+            "# Review me"
+        );
+
+        let empty = serde_json::json!({"name": name, "message": "  "});
+        assert!(tool
+            .execute(empty.as_object().unwrap().clone(), &context)
+            .await
+            .is_err());
+    }
+}
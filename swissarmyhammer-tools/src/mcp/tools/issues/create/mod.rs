@@ -10,6 +10,7 @@ use crate::mcp::utils::validate_issue_name;
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use swissarmyhammer::issues::{parse_issue_content, render_issue_content};
 
 /// Tool for creating new issues
 #[derive(Default)]
@@ -44,6 +45,37 @@ impl McpTool for CreateIssueTool {
                 "content": {
                     "type": "string",
                     "description": "Markdown content of the issue"
+                },
+                "priority": {
+                    "type": "string",
+                    "description": "Priority of the issue",
+                    "enum": ["critical", "high", "medium", "low"]
+                },
+                "labels": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Labels for the issue"
+                },
+                "estimate": {
+                    "type": "string",
+                    "description": "Effort estimate, e.g. 3, 4h or 2d"
+                },
+                "assignee": {
+                    "type": "string",
+                    "description": "Who will work on the issue"
+                },
+                "created_by": {
+                    "type": "string",
+                    "description": "Who created the issue"
+                },
+                "depends_on": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Names of issues that must be completed before this one"
+                },
+                "parent": {
+                    "type": "string",
+                    "description": "Name of the epic the issue belongs to"
                 }
             },
             "required": ["content"]
@@ -80,11 +112,18 @@ impl McpTool for CreateIssueTool {
 
         // Allow empty content for issues - issues can be created as placeholders and filled in later
 
+        // Merge metadata parameters into the frontmatter of the content
+        let update = request.metadata_update();
+        let content = if update.is_empty() {
+            request.content
+        } else {
+            let (mut metadata, body) = parse_issue_content(&request.content);
+            metadata.apply(&update);
+            render_issue_content(&metadata, body)
+        };
+
         let issue_storage = context.issue_storage.write().await;
-        match issue_storage
-            .create_issue(validated_name, request.content)
-            .await
-        {
+        match issue_storage.create_issue(validated_name, content).await {
             Ok(issue) => {
                 // Get the full issue info for the response (includes file path)
                 match issue_storage.get_issue_info(&issue.name).await {
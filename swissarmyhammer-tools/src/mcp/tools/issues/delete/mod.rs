@@ -0,0 +1,226 @@
+//! Issue deletion tool for MCP operations
+//!
+//! This module provides the DeleteIssueTool for permanently removing issues. Deletion
+//! is refused while the issue branch is checked out, and while it holds unmerged work
+//! unless forced.
+
+use super::branch::IssueBranchState;
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::DeleteIssueRequest;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+
+/// Tool for deleting issues
+#[derive(Default)]
+pub struct DeleteIssueTool;
+
+impl DeleteIssueTool {
+    /// Creates a new instance of the DeleteIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for DeleteIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_delete"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "delete")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Name of the issue to delete"
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Delete even when the issue branch has unmerged work",
+                    "default": false
+                },
+                "delete_branch": {
+                    "type": "boolean",
+                    "description": "Also delete the issue branch",
+                    "default": false
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: DeleteIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(request.name.as_str(), "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+
+        let branch_state = IssueBranchState::load(context, request.name.as_str()).await?;
+        if let Some(state) = &branch_state {
+            state.ensure_not_checked_out("delete")?;
+            if state.unmerged && !request.force {
+                return Err(McpError::invalid_params(
+                    format!(
+                        "Issue '{}' has unmerged work on branch '{}'. Merge it first, archive the issue instead, or pass force to delete anyway.",
+                        request.name, state.branch
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        let issue_storage = context.issue_storage.write().await;
+        issue_storage
+            .delete_issue(request.name.as_str())
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "delete issue"))?;
+        drop(issue_storage);
+
+        let mut message = format!("Deleted issue {}", request.name);
+        match branch_state {
+            Some(state) if state.exists && request.delete_branch => {
+                let git_ops = context.git_ops.lock().await;
+                if let Some(ops) = git_ops.as_ref() {
+                    ops.delete_branch(&state.branch, request.force)
+                        .map_err(|e| McpErrorHandler::handle_error(e, "delete issue branch"))?;
+                    message.push_str(&format!(" and branch '{}'", state.branch));
+                }
+            }
+            Some(state) if state.exists => {
+                message.push_str(&format!(", branch '{}' was kept", state.branch));
+            }
+            _ => {}
+        }
+
+        tracing::info!("{}", message);
+        Ok(create_success_response(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{commit_test_file, create_test_git_context};
+
+    fn delete_args(
+        name: &str,
+        force: bool,
+        delete_branch: bool,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        serde_json::json!({ "name": name, "force": force, "delete_branch": delete_branch })
+            .as_object()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_delete_tool_schema() {
+        let tool = DeleteIssueTool::new();
+        assert_eq!(tool.name(), "issue_delete");
+        assert_eq!(tool.cli_category(), Some("issue"));
+        assert_eq!(tool.cli_name(), "delete");
+
+        let schema = tool.schema();
+        assert_eq!(schema["properties"]["force"]["default"], false);
+        assert_eq!(schema["required"], serde_json::json!(["name"]));
+    }
+
+    #[tokio::test]
+    async fn test_delete_refused_on_checked_out_or_unmerged_branch() {
+        let (temp_dir, context) = create_test_git_context().await;
+        context
+            .issue_storage
+            .write()
+            .await
+            .create_issue("fix_bug".to_string(), "Fix the bug".to_string())
+            .await
+            .unwrap();
+        let git_ops = context.git_ops.lock().await;
+        let ops = git_ops.as_ref().unwrap();
+        ops.create_work_branch("fix_bug").unwrap();
+        drop(git_ops);
+
+        let tool = DeleteIssueTool::new();
+        let error = tool
+            .execute(delete_args("fix_bug", true, false), &context)
+            .await
+            .unwrap_err();
+        assert!(error.message.contains("is checked out"));
+
+        commit_test_file(temp_dir.path(), "fix.txt", "fixed");
+        let git_ops = context.git_ops.lock().await;
+        git_ops.as_ref().unwrap().checkout_branch("main").unwrap();
+        drop(git_ops);
+
+        let error = tool
+            .execute(delete_args("fix_bug", false, false), &context)
+            .await
+            .unwrap_err();
+        assert!(error.message.contains("unmerged work"));
+        let issue_storage = context.issue_storage.read().await;
+        assert!(issue_storage.get_issue("fix_bug").await.is_ok());
+        drop(issue_storage);
+
+        let result = tool
+            .execute(delete_args("fix_bug", true, false), &context)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.content[0].as_text().unwrap().text,
+            "Deleted issue fix_bug, branch 'issue/fix_bug' was kept"
+        );
+        let issue_storage = context.issue_storage.read().await;
+        assert!(issue_storage.get_issue("fix_bug").await.is_err());
+        let git_ops = context.git_ops.lock().await;
+        assert!(git_ops
+            .as_ref()
+            .unwrap()
+            .branch_exists("issue/fix_bug")
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_branch_only_when_asked() {
+        let (_temp_dir, context) = create_test_git_context().await;
+        context
+            .issue_storage
+            .write()
+            .await
+            .create_issue("tidy_up".to_string(), "Tidy up".to_string())
+            .await
+            .unwrap();
+        let git_ops = context.git_ops.lock().await;
+        let ops = git_ops.as_ref().unwrap();
+        ops.create_work_branch("tidy_up").unwrap();
+        ops.checkout_branch("main").unwrap();
+        drop(git_ops);
+
+        let result = DeleteIssueTool::new()
+            .execute(delete_args("tidy_up", false, true), &context)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.content[0].as_text().unwrap().text,
+            "Deleted issue tidy_up and branch 'issue/tidy_up'"
+        );
+        let git_ops = context.git_ops.lock().await;
+        assert!(!git_ops
+            .as_ref()
+            .unwrap()
+            .branch_exists("issue/tidy_up")
+            .unwrap());
+    }
+}
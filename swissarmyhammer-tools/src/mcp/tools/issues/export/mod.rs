@@ -0,0 +1,141 @@
+//! Issue export tool for MCP operations
+//!
+//! This module provides the ExportIssuesTool for writing issues as GitHub-compatible
+//! JSON, CSV or a markdown report.
+
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use swissarmyhammer::issues::{export_records, IssueExchangeFormat, IssueRecord};
+
+/// Request structure for exporting issues
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ExportIssuesRequest {
+    /// Output format (github-json, csv, markdown)
+    pub format: Option<String>,
+    /// File to write, the export is returned when omitted
+    pub output: Option<PathBuf>,
+    /// Include completed issues
+    pub include_completed: Option<bool>,
+}
+
+/// Tool for exporting issues
+#[derive(Default)]
+pub struct ExportIssuesTool;
+
+impl ExportIssuesTool {
+    /// Creates a new instance of the ExportIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for ExportIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_export"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "export")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "format": {
+                    "type": "string",
+                    "description": "Output format - github-json, csv or markdown",
+                    "default": "github-json",
+                    "enum": ["github-json", "csv", "markdown"]
+                },
+                "output": {
+                    "type": "string",
+                    "description": "File to write the export to. The export is returned when omitted."
+                },
+                "include_completed": {
+                    "type": "boolean",
+                    "description": "Include completed issues",
+                    "default": true
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: ExportIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let format = request
+            .format
+            .as_deref()
+            .map(str::parse::<IssueExchangeFormat>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?
+            .unwrap_or(IssueExchangeFormat::GithubJson);
+        let include_completed = request.include_completed.unwrap_or(true);
+
+        let issue_storage = context.issue_storage.read().await;
+        let records: Vec<IssueRecord> = issue_storage
+            .list_issues_info()
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?
+            .iter()
+            .filter(|info| include_completed || !info.completed)
+            .map(IssueRecord::from_issue_info)
+            .collect();
+        drop(issue_storage);
+
+        let exported = export_records(&records, format)
+            .map_err(|e| McpErrorHandler::handle_error(e, "export issues"))?;
+
+        let Some(output) = request.output else {
+            return Ok(BaseToolImpl::create_success_response(&exported));
+        };
+        std::fs::write(&output, exported).map_err(|e| {
+            McpError::internal_error(
+                format!("Failed to write export to {}: {e}", output.display()),
+                None,
+            )
+        })?;
+
+        let message = format!(
+            "Exported {} issue{} as {} to {}",
+            records.len(),
+            if records.len() == 1 { "" } else { "s" },
+            format,
+            output.display()
+        );
+        tracing::info!("{}", message);
+        Ok(BaseToolImpl::create_success_response(&message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_tool_schema() {
+        let tool = ExportIssuesTool::new();
+        assert_eq!(tool.name(), "issue_export");
+        assert_eq!(tool.cli_category(), Some("issue"));
+        assert_eq!(tool.cli_name(), "export");
+
+        let schema = tool.schema();
+        assert_eq!(
+            schema["properties"]["format"]["enum"],
+            serde_json::json!(["github-json", "csv", "markdown"])
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Issue dependency graph tool for MCP operations
+//!
+//! This module provides the GraphIssuesTool for rendering issue dependencies as Mermaid or DOT.
+
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use swissarmyhammer::issues::{DependencyGraph, GraphFormat};
+
+/// Request structure for rendering the issue graph
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GraphIssuesRequest {
+    /// Output format (mermaid, dot)
+    pub format: Option<String>,
+    /// Include completed issues in the graph
+    pub include_completed: Option<bool>,
+}
+
+/// Tool for rendering the issue dependency graph
+#[derive(Default)]
+pub struct GraphIssuesTool;
+
+impl GraphIssuesTool {
+    /// Creates a new instance of the GraphIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for GraphIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_graph"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "graph")
+            .unwrap_or("Render the dependency graph between issues as Mermaid or DOT")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "format": {
+                    "type": "string",
+                    "description": "Output format - mermaid or dot",
+                    "default": "mermaid",
+                    "enum": ["mermaid", "dot"]
+                },
+                "include_completed": {
+                    "type": "boolean",
+                    "description": "Include completed issues in the graph",
+                    "default": false
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: GraphIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let format = request
+            .format
+            .as_deref()
+            .map(str::parse::<GraphFormat>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?
+            .unwrap_or_default();
+
+        let issue_storage = context.issue_storage.read().await;
+        let issue_infos = issue_storage
+            .list_issues_info()
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+
+        let graph = DependencyGraph::from_issue_infos(&issue_infos);
+        let response = graph.render(format, request.include_completed.unwrap_or(false));
+
+        tracing::debug!("Rendered issue graph for {} issues", issue_infos.len());
+        Ok(BaseToolImpl::create_success_response(&response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_tool_schema() {
+        let tool = GraphIssuesTool::new();
+        assert_eq!(tool.name(), "issue_graph");
+        assert_eq!(tool.cli_category(), Some("issue"));
+        assert_eq!(tool.cli_name(), "graph");
+
+        let schema = tool.schema();
+        assert_eq!(
+            schema["properties"]["format"]["enum"],
+            serde_json::json!(["mermaid", "dot"])
+        );
+    }
+}
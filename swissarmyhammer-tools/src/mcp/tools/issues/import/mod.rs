@@ -0,0 +1,181 @@
+//! Issue import tool for MCP operations
+//!
+//! This module provides the ImportIssuesTool for creating and updating issues from
+//! GitHub-compatible JSON or CSV dumps, with a dry run that only reports the changes.
+
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use swissarmyhammer::issues::{
+    import_records, parse_records, ImportAction, ImportChange, IssueExchangeFormat,
+};
+
+/// Request structure for importing issues
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ImportIssuesRequest {
+    /// File to import
+    pub file: PathBuf,
+    /// Input format (github-json, csv), guessed from the file extension when omitted
+    pub format: Option<String>,
+    /// Only report what the import would change
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Tool for importing issues
+#[derive(Default)]
+pub struct ImportIssuesTool;
+
+impl ImportIssuesTool {
+    /// Creates a new instance of the ImportIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn format_changes(file: &str, changes: &[ImportChange], dry_run: bool) -> String {
+        let count = |wanted: fn(&ImportAction) -> bool| {
+            changes.iter().filter(|c| wanted(&c.action)).count()
+        };
+        let created = count(|action| matches!(action, ImportAction::Create));
+        let updated = count(|action| matches!(action, ImportAction::Update(_)));
+        let unchanged = count(|action| matches!(action, ImportAction::Unchanged));
+
+        let mut output = if dry_run {
+            format!(
+                "Dry run of {file}, nothing was written: {created} to create, {updated} to update, {unchanged} unchanged\n"
+            )
+        } else {
+            format!(
+                "Imported {file}: {created} created, {updated} updated, {unchanged} unchanged\n"
+            )
+        };
+        for change in changes
+            .iter()
+            .filter(|c| c.action != ImportAction::Unchanged)
+        {
+            output.push_str(&format!("{change}\n"));
+        }
+        output
+    }
+}
+
+#[async_trait]
+impl McpTool for ImportIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_import"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "import")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "file": {
+                    "type": "string",
+                    "description": "GitHub-compatible JSON or CSV file to import"
+                },
+                "format": {
+                    "type": "string",
+                    "description": "Input format, guessed from the file extension when omitted",
+                    "enum": ["github-json", "csv"]
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Only report which issues would be created or updated",
+                    "default": false
+                }
+            },
+            "required": ["file"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: ImportIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let file = request.file.display().to_string();
+        McpValidation::validate_not_empty(&file, "file")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate import file"))?;
+        let format = match request.format.as_deref() {
+            Some(format) => format
+                .parse::<IssueExchangeFormat>()
+                .map_err(|e| McpError::invalid_params(e, None))?,
+            None => IssueExchangeFormat::from_path(&request.file).ok_or_else(|| {
+                McpError::invalid_params(
+                    format!("Cannot tell the format of '{file}', pass format github-json or csv"),
+                    None,
+                )
+            })?,
+        };
+
+        let content = std::fs::read_to_string(&request.file)
+            .map_err(|e| McpError::invalid_params(format!("Failed to read '{file}': {e}"), None))?;
+        let records = parse_records(&content, format)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let issue_storage = context.issue_storage.write().await;
+        let changes = import_records(&**issue_storage, &records, request.dry_run)
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "import issues"))?;
+
+        let message = Self::format_changes(&file, &changes, request.dry_run);
+        tracing::info!("{}", message.lines().next().unwrap_or_default());
+        Ok(BaseToolImpl::create_success_response(&message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[tokio::test]
+    async fn test_import_tool_dry_run_then_import() {
+        let context = create_test_context().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("dump.csv");
+        std::fs::write(
+            &file,
+            "id,title,state,labels,body\nimport_tool_csv,CSV import,open,\"ops, infra\",Rotate keys\n",
+        )
+        .unwrap();
+
+        let tool = ImportIssuesTool::new();
+        let arguments = |dry_run: bool| {
+            serde_json::json!({"file": file, "dry_run": dry_run})
+                .as_object()
+                .unwrap()
+                .clone()
+        };
+        tool.execute(arguments(true), &context).await.unwrap();
+        assert!(context
+            .issue_storage
+            .read()
+            .await
+            .get_issue("import_tool_csv")
+            .await
+            .is_err());
+
+        tool.execute(arguments(false), &context).await.unwrap();
+        let issue = context
+            .issue_storage
+            .read()
+            .await
+            .get_issue("import_tool_csv")
+            .await
+            .unwrap();
+        assert_eq!(issue.metadata().labels, vec!["ops", "infra"]);
+        assert_eq!(issue.body(), "# CSV import\n\nRotate keys\n");
+    }
+}
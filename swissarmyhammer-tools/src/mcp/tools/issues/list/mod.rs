@@ -8,7 +8,14 @@ use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use serde::{Deserialize, Serialize};
-use swissarmyhammer::issues::{Issue, IssueInfo};
+use std::collections::HashMap;
+use swissarmyhammer::issues::{
+    DependencyGraph, Issue, IssueHierarchy, IssueInfo, IssueLease, IssueMetadata, IssuePriority,
+    IssueQuery, IssueSort, IssueStatus,
+};
+
+/// Unexpired leases by issue name
+type Leases = HashMap<String, IssueLease>;
 
 /// Request structure for listing issues
 #[derive(Debug, Deserialize, Serialize)]
@@ -17,8 +24,63 @@ pub struct ListIssuesRequest {
     pub show_completed: Option<bool>,
     /// Include active issues in the list
     pub show_active: Option<bool>,
-    /// Output format (table, json, markdown)
+    /// Output format (table, tree, json, markdown)
     pub format: Option<String>,
+    /// Only the epic with this name and its sub-issues
+    pub epic: Option<String>,
+    /// Only issues with this priority
+    pub priority: Option<String>,
+    /// Only issues carrying this label
+    pub label: Option<String>,
+    /// Only issues assigned to this person
+    pub assignee: Option<String>,
+    /// Only issues in this status (todo, in_progress, blocked, in_review, done, wontfix)
+    pub status: Option<String>,
+    /// Sort order (name, priority, created)
+    pub sort: Option<String>,
+}
+
+impl ListIssuesRequest {
+    /// Build the metadata query from the filter parameters
+    fn query(&self) -> std::result::Result<IssueQuery, McpError> {
+        let priority = self
+            .priority
+            .as_deref()
+            .map(str::parse::<IssuePriority>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let status = self
+            .status
+            .as_deref()
+            .map(str::parse::<IssueStatus>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let sort = self
+            .sort
+            .as_deref()
+            .map(str::parse::<IssueSort>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?
+            .unwrap_or_default();
+        Ok(IssueQuery {
+            priority,
+            label: self.label.clone(),
+            assignee: self.assignee.clone(),
+            status,
+            sort,
+        })
+    }
+}
+
+/// An issue together with its frontmatter metadata for JSON output
+#[derive(Debug, Serialize)]
+struct IssueWithMetadata<'a> {
+    #[serde(flatten)]
+    issue: &'a Issue,
+    status: IssueStatus,
+    metadata: &'a IssueMetadata,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lease: Option<&'a IssueLease>,
 }
 
 /// Tool for listing issues
@@ -31,18 +93,21 @@ impl ListIssuesTool {
         Self
     }
 
-    /// Format issues as a table
-    fn format_as_table(issue_infos: &[IssueInfo]) -> String {
+    /// Format issues as a table grouped by status
+    ///
+    /// Todo issues waiting on unfinished dependencies are listed as blocked.
+    fn format_as_table(
+        issue_infos: &[IssueInfo],
+        graph: &DependencyGraph,
+        leases: &Leases,
+    ) -> String {
         if issue_infos.is_empty() {
             return "No issues found.".to_string();
         }
 
-        let active_issue_infos: Vec<_> = issue_infos.iter().filter(|i| !i.completed).collect();
-        let completed_issue_infos: Vec<_> = issue_infos.iter().filter(|i| i.completed).collect();
-
         let total_issues = issue_infos.len();
-        let completed_count = completed_issue_infos.len();
-        let active_count = active_issue_infos.len();
+        let completed_count = issue_infos.iter().filter(|i| i.completed).count();
+        let active_count = total_issues - completed_count;
         let completion_percentage = if total_issues > 0 {
             (completed_count * 100) / total_issues
         } else {
@@ -56,30 +121,115 @@ impl ListIssuesTool {
         ));
         result.push_str(&format!("🔄 Active: {active_count}\n"));
 
-        if active_count > 0 {
-            result.push('\n');
-            result.push_str("Active Issues:\n");
-            for issue_info in active_issue_infos {
-                result.push_str(&format!("  🔄 {}\n", issue_info.issue.name));
+        for status in IssueStatus::ALL {
+            let mut group: Vec<_> = issue_infos
+                .iter()
+                .filter(|i| Self::display_status(i, graph) == status)
+                .collect();
+            if group.is_empty() {
+                continue;
             }
-        }
 
-        if completed_count > 0 {
             result.push('\n');
-            result.push_str("Recently Completed:\n");
-            let mut sorted_completed = completed_issue_infos;
-            sorted_completed.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            if status.is_closed() {
+                // Closed groups only show the most recent issues
+                group.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+                let shown = group.len().min(5);
+                if shown < group.len() {
+                    result.push_str(&format!(
+                        "{} ({}, {} most recent):\n",
+                        status.label(),
+                        group.len(),
+                        shown
+                    ));
+                } else {
+                    result.push_str(&format!("{} ({}):\n", status.label(), group.len()));
+                }
+                group.truncate(shown);
+            } else {
+                result.push_str(&format!("{} ({}):\n", status.label(), group.len()));
+            }
 
-            for issue_info in sorted_completed.iter().take(5) {
-                result.push_str(&format!("  ✅ {}\n", issue_info.issue.name));
+            for issue_info in group {
+                result.push_str(&Self::format_table_line(
+                    status.icon(),
+                    issue_info,
+                    leases.get(&issue_info.issue.name),
+                ));
+                let blockers = graph.blockers(&issue_info.issue.name);
+                if status == IssueStatus::Blocked && !blockers.is_empty() {
+                    let reasons: Vec<String> = blockers.iter().map(|b| b.to_string()).collect();
+                    result.push_str(&format!("      waiting: {}\n", reasons.join(", ")));
+                }
             }
         }
 
         result
     }
 
+    /// Status to display, listing todo issues waiting on dependencies as blocked
+    fn display_status(issue_info: &IssueInfo, graph: &DependencyGraph) -> IssueStatus {
+        match issue_info.status() {
+            IssueStatus::Todo if graph.is_blocked(&issue_info.issue.name) => IssueStatus::Blocked,
+            status => status,
+        }
+    }
+
+    /// Format one table line, with the metadata summary and lease holder when there are any
+    fn format_table_line(icon: &str, issue_info: &IssueInfo, lease: Option<&IssueLease>) -> String {
+        format!("  {icon} {}\n", Self::describe_issue(issue_info, lease))
+    }
+
+    /// Issue name followed by its metadata summary and lease holder
+    fn describe_issue(issue_info: &IssueInfo, lease: Option<&IssueLease>) -> String {
+        let mut line = issue_info.issue.name.clone();
+        let summary = issue_info.metadata.summary();
+        if !summary.is_empty() {
+            line.push_str(&format!(" [{summary}]"));
+        }
+        if let Some(lease) = lease {
+            line.push_str(&format!(" 🔒 {}", lease.owner));
+        }
+        line
+    }
+
+    /// Format issues as a tree of epics with their sub-issues indented beneath them
+    ///
+    /// Epic progress counts all sub-issues in `hierarchy`, including ones filtered out.
+    fn format_as_tree(
+        issue_infos: &[IssueInfo],
+        graph: &DependencyGraph,
+        hierarchy: &IssueHierarchy,
+        leases: &Leases,
+    ) -> String {
+        if issue_infos.is_empty() {
+            return "No issues found.".to_string();
+        }
+
+        let by_name: HashMap<&str, &IssueInfo> = issue_infos
+            .iter()
+            .map(|info| (info.issue.name.as_str(), info))
+            .collect();
+        let mut result = String::new();
+        for (depth, name) in IssueHierarchy::from_issue_infos(issue_infos).tree() {
+            let issue_info = by_name[name.as_str()];
+            let status = Self::display_status(issue_info, graph);
+            result.push_str(&format!(
+                "{}{} {}",
+                "    ".repeat(depth),
+                status.icon(),
+                Self::describe_issue(issue_info, leases.get(&name))
+            ));
+            if hierarchy.is_epic(&name) {
+                result.push_str(&format!(" ({} done)", hierarchy.progress(&name)));
+            }
+            result.push('\n');
+        }
+        result
+    }
+
     /// Format issues as markdown
-    fn format_as_markdown(issue_infos: &[IssueInfo]) -> String {
+    fn format_as_markdown(issue_infos: &[IssueInfo], leases: &Leases) -> String {
         let mut result = String::from("# Issues\n\n");
 
         if issue_infos.is_empty() {
@@ -88,16 +238,36 @@ impl ListIssuesTool {
         }
 
         for issue_info in issue_infos {
-            let status = if issue_info.completed { "✅" } else { "🔄" };
-            result.push_str(&format!("## {} - {}\n\n", status, issue_info.issue.name));
+            let status = issue_info.status();
             result.push_str(&format!(
-                "- **Status**: {}\n",
-                if issue_info.completed {
-                    "Completed"
-                } else {
-                    "Active"
-                }
+                "## {} - {}\n\n",
+                status.icon(),
+                issue_info.issue.name
             ));
+            result.push_str(&format!("- **Status**: {}\n", status.label()));
+            result.push_str(&format!(
+                "- **Priority**: {}\n",
+                issue_info.metadata.effective_priority()
+            ));
+            if !issue_info.metadata.labels.is_empty() {
+                result.push_str(&format!(
+                    "- **Labels**: {}\n",
+                    issue_info.metadata.labels.join(", ")
+                ));
+            }
+            if let Some(assignee) = &issue_info.metadata.assignee {
+                result.push_str(&format!("- **Assignee**: {assignee}\n"));
+            }
+            if let Some(estimate) = &issue_info.metadata.estimate {
+                result.push_str(&format!("- **Estimate**: {estimate}\n"));
+            }
+            if let Some(lease) = leases.get(&issue_info.issue.name) {
+                result.push_str(&format!(
+                    "- **Claimed by**: {} until {}\n",
+                    lease.owner,
+                    lease.expires_at().format("%Y-%m-%d %H:%M UTC")
+                ));
+            }
             result.push_str(&format!(
                 "- **Created**: {}\n",
                 issue_info.created_at.format("%Y-%m-%d")
@@ -107,9 +277,10 @@ impl ListIssuesTool {
                 issue_info.file_path.display()
             ));
 
-            if !issue_info.issue.content.is_empty() {
+            let body = issue_info.issue.body();
+            if !body.is_empty() {
                 result.push_str("### Content\n\n");
-                result.push_str(&issue_info.issue.content);
+                result.push_str(body);
                 result.push_str("\n\n");
             }
             result.push_str("---\n\n");
@@ -146,9 +317,37 @@ impl McpTool for ListIssuesTool {
                 },
                 "format": {
                     "type": "string",
-                    "description": "Output format - table, json, or markdown",
+                    "description": "Output format - table, tree (epics with their sub-issues), json, or markdown",
                     "default": "table",
-                    "enum": ["table", "json", "markdown"]
+                    "enum": ["table", "tree", "json", "markdown"]
+                },
+                "epic": {
+                    "type": "string",
+                    "description": "Only list this epic and its sub-issues"
+                },
+                "priority": {
+                    "type": "string",
+                    "description": "Only list issues with this priority",
+                    "enum": ["critical", "high", "medium", "low"]
+                },
+                "label": {
+                    "type": "string",
+                    "description": "Only list issues carrying this label"
+                },
+                "assignee": {
+                    "type": "string",
+                    "description": "Only list issues assigned to this person"
+                },
+                "status": {
+                    "type": "string",
+                    "description": "Only list issues in this status, overrides show_active and show_completed",
+                    "enum": ["todo", "in_progress", "blocked", "in_review", "done", "wontfix"]
+                },
+                "sort": {
+                    "type": "string",
+                    "description": "Sort order - name, priority (most urgent first), or created",
+                    "default": "name",
+                    "enum": ["name", "priority", "created"]
                 }
             },
             "required": []
@@ -161,6 +360,7 @@ impl McpTool for ListIssuesTool {
         context: &ToolContext,
     ) -> std::result::Result<CallToolResult, McpError> {
         let request: ListIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let query = request.query()?;
 
         // Apply rate limiting for issue listing
         context
@@ -183,16 +383,47 @@ impl McpTool for ListIssuesTool {
             .list_issues_info()
             .await
             .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+        let leases: Leases = issue_storage
+            .issue_leases()
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "list issue leases"))?
+            .into_iter()
+            .map(|lease| (lease.issue.clone(), lease))
+            .collect();
 
         let show_completed = request.show_completed.unwrap_or(false);
         let show_active = request.show_active.unwrap_or(true);
         let format = request.format.unwrap_or_else(|| "table".to_string());
 
+        let graph = DependencyGraph::from_issue_infos(&all_issue_infos);
+        let hierarchy = IssueHierarchy::from_issue_infos(&all_issue_infos);
+        let epic_scope = match request.epic.as_deref().map(str::trim) {
+            Some(epic) if !epic.is_empty() => {
+                if !all_issue_infos.iter().any(|info| info.issue.name == epic) {
+                    return Err(McpError::invalid_params(
+                        format!("Epic '{epic}' not found"),
+                        None,
+                    ));
+                }
+                let mut scope = hierarchy.descendants(epic);
+                scope.push(epic.to_string());
+                Some(scope)
+            }
+            _ => None,
+        };
+
         // Filter issues based on criteria
         let filtered_issue_infos: Vec<_> = all_issue_infos
             .into_iter()
             .filter(|issue_info| {
-                if show_completed && show_active {
+                epic_scope
+                    .as_ref()
+                    .map_or(true, |scope| scope.contains(&issue_info.issue.name))
+            })
+            .filter(|issue_info| {
+                if query.status.is_some() {
+                    true // the status filter decides
+                } else if show_completed && show_active {
                     true // show all
                 } else if show_completed {
                     issue_info.completed
@@ -203,20 +434,26 @@ impl McpTool for ListIssuesTool {
                 }
             })
             .collect();
+        let filtered_issue_infos = query.apply(filtered_issue_infos);
 
         let response = match format.as_str() {
             "json" => {
-                // Convert IssueInfo to Issue for JSON serialization
-                let issues_for_json: Vec<Issue> = filtered_issue_infos
+                let issues_for_json: Vec<IssueWithMetadata> = filtered_issue_infos
                     .iter()
-                    .map(|info| info.issue.clone())
+                    .map(|info| IssueWithMetadata {
+                        issue: &info.issue,
+                        status: info.status(),
+                        metadata: &info.metadata,
+                        lease: leases.get(&info.issue.name),
+                    })
                     .collect();
                 serde_json::to_string_pretty(&issues_for_json).map_err(|e| {
                     McpError::internal_error(format!("Failed to serialize issues: {e}"), None)
                 })?
             }
-            "markdown" => Self::format_as_markdown(&filtered_issue_infos),
-            _ => Self::format_as_table(&filtered_issue_infos),
+            "markdown" => Self::format_as_markdown(&filtered_issue_infos, &leases),
+            "tree" => Self::format_as_tree(&filtered_issue_infos, &graph, &hierarchy, &leases),
+            _ => Self::format_as_table(&filtered_issue_infos, &graph, &leases),
         };
 
         tracing::info!("Listed {} issues", filtered_issue_infos.len());
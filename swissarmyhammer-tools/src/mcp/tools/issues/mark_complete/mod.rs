@@ -10,6 +10,7 @@ use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use swissarmyhammer::config::Config;
+use swissarmyhammer::issues::{CriteriaProgress, IssueHierarchy};
 
 /// Tool for marking issues as complete
 #[derive(Default)]
@@ -44,6 +45,11 @@ impl McpTool for MarkCompleteIssueTool {
                 "name": {
                     "type": "string",
                     "description": "Issue name to mark as complete. Use 'current' to mark the current issue complete."
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Complete the issue even when acceptance criteria are still open",
+                    "default": false
                 }
             },
             "required": ["name"]
@@ -94,6 +100,61 @@ impl McpTool for MarkCompleteIssueTool {
         };
 
         let issue_storage = context.issue_storage.write().await;
+
+        // Open acceptance criteria mean the issue is not done yet
+        if !request.force {
+            let issue = issue_storage
+                .get_issue(&issue_name)
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "mark issue complete"))?;
+            let criteria = issue.criteria();
+            let open: Vec<String> = criteria
+                .iter()
+                .filter(|c| !c.checked)
+                .map(|c| format!("{}. {}", c.index, c.text))
+                .collect();
+            if !open.is_empty() {
+                return Err(McpError::invalid_params(
+                    format!(
+                        "Issue '{}' has {} open acceptance criteria ({} met): {}. Tick them with issue_check, or pass force to complete anyway.",
+                        issue_name,
+                        open.len(),
+                        CriteriaProgress::of(&criteria),
+                        open.join("; ")
+                    ),
+                    None,
+                ));
+            }
+
+            // Epics complete on their own once every sub-issue is done
+            let issue_infos = issue_storage
+                .list_issues_info()
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+            let hierarchy = IssueHierarchy::from_issue_infos(&issue_infos);
+            if hierarchy.has_open_children(&issue_name) {
+                let open: Vec<&str> = hierarchy
+                    .children(&issue_name)
+                    .iter()
+                    .filter(|child| {
+                        issue_infos
+                            .iter()
+                            .any(|info| &info.issue.name == *child && !info.completed)
+                    })
+                    .map(String::as_str)
+                    .collect();
+                return Err(McpError::invalid_params(
+                    format!(
+                        "Epic '{}' has open sub-issues ({} done): {}. It completes once they are, or pass force to complete it anyway.",
+                        issue_name,
+                        hierarchy.progress(&issue_name),
+                        open.join(", ")
+                    ),
+                    None,
+                ));
+            }
+        }
+
         match issue_storage.complete_issue(&issue_name).await {
             Ok(issue) => {
                 tracing::info!("Successfully marked issue '{}' as complete", issue.name);
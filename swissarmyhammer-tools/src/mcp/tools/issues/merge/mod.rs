@@ -9,6 +9,7 @@ use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use swissarmyhammer::common::create_abort_file_current_dir;
+use swissarmyhammer::issues::{IssueActivity, IssueActivityKind, IssueStatus};
 
 /// Tool for merging an issue work branch
 #[derive(Default)]
@@ -120,6 +121,8 @@ impl McpTool for MergeIssueTool {
                 return Err(McpError::invalid_params(error_msg, None));
             }
         };
+        // Release the read lock, completing the issue below needs write access
+        drop(issue_storage);
 
         // Auto-complete the issue if it's not already completed
         if !issue_info.completed {
@@ -171,6 +174,47 @@ impl McpTool for MergeIssueTool {
                             target_branch
                         );
 
+                        let issue_storage = context.issue_storage.read().await;
+                        if let Err(e) = issue_storage
+                            .add_issue_activity(
+                                &issue_name,
+                                IssueActivity::new(
+                                    IssueActivityKind::Merge,
+                                    format!("Merged {source_branch} into {target_branch}"),
+                                ),
+                            )
+                            .await
+                        {
+                            tracing::warn!(
+                                "Failed to record merge activity for issue '{}': {}",
+                                issue_name,
+                                e
+                            );
+                        }
+
+                        // A merged issue is done unless it was already closed
+                        match issue_storage.get_issue_info(&issue_name).await {
+                            Ok(info) if !info.status().is_closed() => {
+                                if let Err(e) = issue_storage
+                                    .set_issue_status(&issue_name, IssueStatus::Done)
+                                    .await
+                                {
+                                    tracing::warn!(
+                                        "Failed to mark merged issue '{}' as done: {}",
+                                        issue_name,
+                                        e
+                                    );
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(e) => tracing::warn!(
+                                "Failed to read merged issue '{}': {}",
+                                issue_name,
+                                e
+                            ),
+                        }
+                        drop(issue_storage);
+
                         let mut success_message = format!(
                             "Merged work branch for issue {issue_name} to {target_branch} (determined by git merge-base)"
                         );
@@ -0,0 +1,147 @@
+//! Issue migrate tool for MCP operations
+//!
+//! This module provides the MigrateIssuesTool for moving every issue between the
+//! markdown and database storage backends.
+
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use swissarmyhammer::issues::{
+    migrate_issues, open_issue_storage, IssueStorageBackend, ISSUES_BACKEND_CONFIG_KEY,
+};
+
+/// Request structure for migrating issues
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MigrateIssuesRequest {
+    /// Backend to move the issues to (fs, duckdb)
+    pub to: String,
+    /// Leave the issues in the old backend as well
+    #[serde(default)]
+    pub keep_source: bool,
+}
+
+/// Tool for migrating issues between storage backends
+#[derive(Default)]
+pub struct MigrateIssuesTool;
+
+impl MigrateIssuesTool {
+    /// Creates a new instance of the MigrateIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for MigrateIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_migrate"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "migrate")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "to": {
+                    "type": "string",
+                    "description": "Backend to move the issues to: 'fs' for markdown files or 'duckdb' for the embedded DuckDB database",
+                    "enum": ["fs", "duckdb"]
+                },
+                "keep_source": {
+                    "type": "boolean",
+                    "description": "Leave the issues in the old backend as well",
+                    "default": false
+                }
+            },
+            "required": ["to"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: MigrateIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let target_backend: IssueStorageBackend = request
+            .to
+            .parse()
+            .map_err(|e: String| McpError::invalid_params(e, None))?;
+
+        let mut issue_storage = context.issue_storage.write().await;
+        let current_backend = issue_storage.backend();
+        let issues_dir = issue_storage.issues_directory().to_path_buf();
+
+        // Both backends share the issues directory. When the session already uses the
+        // target, the issues come from the other one
+        let count = if current_backend == target_backend {
+            let source_backend = IssueStorageBackend::ALL
+                .into_iter()
+                .find(|backend| *backend != target_backend)
+                .unwrap_or_default();
+            let source = open_issue_storage(source_backend, issues_dir)
+                .map_err(|e| McpErrorHandler::handle_error(e, "open issue storage"))?;
+            migrate_issues(source.as_ref(), &**issue_storage, request.keep_source)
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "migrate issues"))?
+        } else {
+            let target = open_issue_storage(target_backend, issues_dir)
+                .map_err(|e| McpErrorHandler::handle_error(e, "open issue storage"))?;
+            let count = migrate_issues(&**issue_storage, target.as_ref(), request.keep_source)
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "migrate issues"))?;
+            *issue_storage = target;
+            count
+        };
+
+        let mut message = format!("Migrated {count} issues to the {target_backend} backend");
+        if IssueStorageBackend::configured().ok() != Some(target_backend) {
+            message.push_str(&format!(
+                "\nSet {ISSUES_BACKEND_CONFIG_KEY} = \"{target_backend}\" in sah.toml so later sessions use it"
+            ));
+        }
+        tracing::info!("{}", message.lines().next().unwrap_or_default());
+        Ok(BaseToolImpl::create_success_response(&message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_context, TestIssueEnvironment};
+
+    #[tokio::test]
+    async fn test_migrate_tool_switches_backend() {
+        let context = create_test_context().await;
+        let env = TestIssueEnvironment::new();
+        *context.issue_storage.write().await = Box::new(env.storage());
+        context
+            .issue_storage
+            .read()
+            .await
+            .create_issue("migrate_tool".into(), "# Migrate".into())
+            .await
+            .unwrap();
+
+        let tool = MigrateIssuesTool::new();
+        let arguments = |to: &str| serde_json::json!({"to": to}).as_object().unwrap().clone();
+        tool.execute(arguments("duckdb"), &context).await.unwrap();
+        {
+            let storage = context.issue_storage.read().await;
+            assert_eq!(storage.backend(), IssueStorageBackend::Database);
+            assert!(storage.get_issue("migrate_tool").await.is_ok());
+        }
+        assert!(!env.issues_dir.join("migrate_tool.md").exists());
+
+        tool.execute(arguments("fs"), &context).await.unwrap();
+        assert!(env.issues_dir.join("migrate_tool.md").exists());
+        assert!(tool.execute(arguments("postgres"), &context).await.is_err());
+    }
+}
@@ -13,6 +13,7 @@
 //! 3. **Updates**: `update` tool modifies issue content and tracking information
 //! 4. **Completion**: `mark_complete` tool moves issues to `./issues/complete/`
 //! 5. **Integration**: `merge` tool integrates completed work back to source branch
+//! 6. **Cleanup**: `reopen`, `archive` and `delete` tools revisit or retire issues
 //!
 //! ## Tool Implementation Pattern
 //!
@@ -64,12 +65,35 @@
 //! - **update**: Modify existing issue content and metadata
 //! - **work**: Switch to or create a work branch for an issue
 //! - **merge**: Merge completed issue work back to source branch
+//! - **graph**: Render the dependency graph between issues as Mermaid or DOT
+//! - **reopen**: Move a completed or archived issue back to the active issues
+//! - **archive**: Move an issue to `./issues/archive/`, out of listings and completion checks
+//! - **delete**: Delete an issue, guarded against checked out or unmerged issue branches
+//! - **search**: Find active and completed issues by keyword or semantic similarity
+//! - **check**: Tick or untick acceptance criteria checklist items
+//! - **comment**: Add a comment to an issue's activity log
+//! - **export**: Export issues as GitHub-compatible JSON, CSV or markdown
+//! - **import**: Create and update issues from GitHub-compatible JSON or CSV dumps
+//! - **claim**: Lease an issue so parallel agents skip it, or release the lease
+//! - **migrate**: Move all issues between the markdown and database storage backends
 
 pub mod all_complete;
+pub mod archive;
+mod branch;
+pub mod check;
+pub mod claim;
+pub mod comment;
 pub mod create;
+pub mod delete;
+pub mod export;
+pub mod graph;
+pub mod import;
 pub mod list;
 pub mod mark_complete;
 pub mod merge;
+pub mod migrate;
+pub mod reopen;
+pub mod search;
 pub mod show;
 pub mod update;
 pub mod work;
@@ -86,4 +110,15 @@ pub fn register_issue_tools(registry: &mut ToolRegistry) {
     registry.register(update::UpdateIssueTool::new());
     registry.register(work::WorkIssueTool::new());
     registry.register(merge::MergeIssueTool::new());
+    registry.register(graph::GraphIssuesTool::new());
+    registry.register(reopen::ReopenIssueTool::new());
+    registry.register(archive::ArchiveIssueTool::new());
+    registry.register(delete::DeleteIssueTool::new());
+    registry.register(search::SearchIssuesTool::new());
+    registry.register(check::CheckIssueTool::new());
+    registry.register(comment::CommentIssueTool::new());
+    registry.register(export::ExportIssuesTool::new());
+    registry.register(import::ImportIssuesTool::new());
+    registry.register(claim::ClaimIssueTool::new());
+    registry.register(migrate::MigrateIssuesTool::new());
 }
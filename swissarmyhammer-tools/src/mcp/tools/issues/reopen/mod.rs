@@ -0,0 +1,148 @@
+//! Issue reopening tool for MCP operations
+//!
+//! This module provides the ReopenIssueTool for moving completed or archived issues
+//! back to the active issues directory.
+
+use super::branch::IssueBranchState;
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::ReopenIssueRequest;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+
+/// Tool for reopening completed or archived issues
+#[derive(Default)]
+pub struct ReopenIssueTool;
+
+impl ReopenIssueTool {
+    /// Creates a new instance of the ReopenIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for ReopenIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_reopen"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "reopen")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Name of the completed or archived issue to reopen"
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: ReopenIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(request.name.as_str(), "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+
+        let issue_storage = context.issue_storage.write().await;
+        let issue = issue_storage
+            .reopen_issue(request.name.as_str())
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "reopen issue"))?;
+        drop(issue_storage);
+
+        // Tell the agent whether issue_work will resume an existing branch
+        let branch_note = match IssueBranchState::load(context, &issue.name).await? {
+            Some(state) if state.checked_out => {
+                format!(", continue on the checked out branch '{}'", state.branch)
+            }
+            Some(state) if state.exists => {
+                format!(", issue_work will resume branch '{}'", state.branch)
+            }
+            _ => String::new(),
+        };
+
+        tracing::info!("Reopened issue '{}'", issue.name);
+        Ok(create_success_response(format!(
+            "Reopened issue {} as todo{}",
+            issue.name, branch_note
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_git_context;
+
+    #[test]
+    fn test_reopen_tool_schema() {
+        let tool = ReopenIssueTool::new();
+        assert_eq!(tool.name(), "issue_reopen");
+        assert_eq!(tool.cli_category(), Some("issue"));
+        assert_eq!(tool.cli_name(), "reopen");
+        assert_eq!(tool.schema()["required"], serde_json::json!(["name"]));
+    }
+
+    #[tokio::test]
+    async fn test_reopen_reports_issue_branch() {
+        let (_temp_dir, context) = create_test_git_context().await;
+        let issue_storage = context.issue_storage.write().await;
+        issue_storage
+            .create_issue("fix_bug".to_string(), "Fix the bug".to_string())
+            .await
+            .unwrap();
+        issue_storage.complete_issue("fix_bug").await.unwrap();
+        drop(issue_storage);
+        let git_ops = context.git_ops.lock().await;
+        let ops = git_ops.as_ref().unwrap();
+        ops.create_work_branch("fix_bug").unwrap();
+        ops.checkout_branch("main").unwrap();
+        drop(git_ops);
+
+        let tool = ReopenIssueTool::new();
+        let args = serde_json::json!({ "name": "fix_bug" })
+            .as_object()
+            .unwrap()
+            .clone();
+        let result = tool.execute(args.clone(), &context).await.unwrap();
+        assert_eq!(
+            result.content[0].as_text().unwrap().text,
+            "Reopened issue fix_bug as todo, issue_work will resume branch 'issue/fix_bug'"
+        );
+
+        context
+            .issue_storage
+            .write()
+            .await
+            .complete_issue("fix_bug")
+            .await
+            .unwrap();
+        let git_ops = context.git_ops.lock().await;
+        git_ops
+            .as_ref()
+            .unwrap()
+            .checkout_branch("issue/fix_bug")
+            .unwrap();
+        drop(git_ops);
+
+        let result = tool.execute(args, &context).await.unwrap();
+        assert_eq!(
+            result.content[0].as_text().unwrap().text,
+            "Reopened issue fix_bug as todo, continue on the checked out branch 'issue/fix_bug'"
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Issue search tool for MCP operations
+//!
+//! This module provides the SearchIssuesTool for finding active and completed issues
+//! by keyword or by meaning.
+
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use swissarmyhammer::issues::{
+    IssueSearchMode, IssueSearchOptions, IssueSearchResult, IssueStatus,
+};
+
+/// Request structure for searching issues
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SearchIssuesRequest {
+    /// Search query
+    pub query: String,
+    /// Search mode (keyword, semantic)
+    pub mode: Option<String>,
+    /// Only issues in this status
+    pub status: Option<String>,
+    /// Only issues carrying all of these labels
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Maximum number of results
+    pub limit: Option<usize>,
+    /// Output format (markdown, json)
+    pub format: Option<String>,
+}
+
+impl SearchIssuesRequest {
+    /// Build the search options from the request parameters
+    fn options(&self) -> std::result::Result<IssueSearchOptions, McpError> {
+        let mode = self
+            .mode
+            .as_deref()
+            .map(str::parse::<IssueSearchMode>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?
+            .unwrap_or_default();
+        let status = self
+            .status
+            .as_deref()
+            .map(str::parse::<IssueStatus>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        Ok(IssueSearchOptions {
+            mode,
+            status,
+            labels: self.labels.clone(),
+            limit: self.limit.unwrap_or(SearchIssuesTool::DEFAULT_LIMIT).max(1),
+        })
+    }
+}
+
+/// Tool for searching issues
+#[derive(Default)]
+pub struct SearchIssuesTool;
+
+impl SearchIssuesTool {
+    /// Number of results returned when no limit is given
+    const DEFAULT_LIMIT: usize = 10;
+
+    /// Creates a new instance of the SearchIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn format_results(query: &str, mode: IssueSearchMode, results: &[IssueSearchResult]) -> String {
+        if results.is_empty() {
+            return format!("No issues found matching '{query}'");
+        }
+
+        let mut output = format!(
+            "Found {} issue{} matching '{}' ({} search):\n",
+            results.len(),
+            if results.len() == 1 { "" } else { "s" },
+            query,
+            mode
+        );
+        for result in results {
+            output.push_str(&format!(
+                "\n{} {} - {} ({}, score {:.2})\n",
+                result.status.icon(),
+                result.name,
+                result.title,
+                result.status,
+                result.score
+            ));
+            for highlight in &result.highlights {
+                output.push_str(&format!("   > {highlight}\n"));
+            }
+        }
+        output
+    }
+}
+
+#[async_trait]
+impl McpTool for SearchIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_search"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "search")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Search query, supports AND, OR and \"exact phrases\" in keyword mode"
+                },
+                "mode": {
+                    "type": "string",
+                    "description": "keyword for full-text matches, semantic for issues with similar meaning",
+                    "default": "keyword",
+                    "enum": ["keyword", "semantic"]
+                },
+                "status": {
+                    "type": "string",
+                    "description": "Only return issues in this status",
+                    "enum": ["todo", "in_progress", "blocked", "in_review", "done", "wontfix"]
+                },
+                "labels": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Only return issues carrying all of these labels"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of results",
+                    "default": 10,
+                    "minimum": 1
+                },
+                "format": {
+                    "type": "string",
+                    "description": "Output format - markdown or json",
+                    "default": "markdown",
+                    "enum": ["markdown", "json"]
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: SearchIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(&request.query, "search query")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate search query"))?;
+        let options = request.options()?;
+
+        let issue_storage = context.issue_storage.read().await;
+        let results = issue_storage
+            .search_issues(&request.query, &options)
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "search issues"))?;
+
+        tracing::debug!(
+            "Issue search for '{}' returned {} results",
+            request.query,
+            results.len()
+        );
+        let response = match request.format.as_deref().unwrap_or("markdown") {
+            "json" => serde_json::to_string_pretty(&results).map_err(|e| {
+                McpError::internal_error(format!("Failed to serialize results: {e}"), None)
+            })?,
+            "markdown" => Self::format_results(&request.query, options.mode, &results),
+            other => {
+                return Err(McpError::invalid_params(
+                    format!("Invalid format '{other}': expected markdown or json"),
+                    None,
+                ))
+            }
+        };
+        Ok(BaseToolImpl::create_success_response(&response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[test]
+    fn test_search_tool_schema() {
+        let tool = SearchIssuesTool::new();
+        assert_eq!(tool.name(), "issue_search");
+        assert_eq!(tool.cli_category(), Some("issue"));
+        assert_eq!(tool.cli_name(), "search");
+
+        let schema = tool.schema();
+        assert_eq!(
+            schema["properties"]["mode"]["enum"],
+            serde_json::json!(["keyword", "semantic"])
+        );
+        assert_eq!(schema["required"], serde_json::json!(["query"]));
+    }
+
+    #[tokio::test]
+    async fn test_search_tool_finds_issues_by_keyword() {
+        let context = create_test_context().await;
+        {
+            let issue_storage = context.issue_storage.write().await;
+            issue_storage
+                .create_issue(
+                    "search_tool_oauth_login".to_string(),
+                    "# OAuth login\n\nRefresh tokens expire too early.".to_string(),
+                )
+                .await
+                .unwrap();
+            issue_storage
+                .create_issue(
+                    "search_tool_dark_mode".to_string(),
+                    "# Dark mode\n\nAdd a dark theme.".to_string(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("query".to_string(), serde_json::json!("tokens"));
+        let result = SearchIssuesTool::new()
+            .execute(arguments, &context)
+            .await
+            .unwrap();
+
+        let text = match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        };
+        assert!(text.contains("search_tool_oauth_login"));
+        assert!(text.contains("**tokens**"));
+        assert!(!text.contains("search_tool_dark_mode"));
+    }
+}
@@ -9,7 +9,10 @@ use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use serde::{Deserialize, Serialize};
 use swissarmyhammer::config::Config;
-use swissarmyhammer::issues::IssueInfo;
+use swissarmyhammer::issues::{
+    describe_held_issues, format_duration, CriteriaProgress, IssueActivity, IssueActivityKind,
+    IssueHierarchy, IssueInfo, NextIssue,
+};
 
 /// Request structure for showing an issue
 #[derive(Debug, Deserialize, Serialize)]
@@ -18,6 +21,36 @@ pub struct ShowIssueRequest {
     pub name: String,
     /// Show raw content only without formatting
     pub raw: Option<bool>,
+    /// Only show activity entries of this kind
+    pub activity_kind: Option<String>,
+    /// Only show this many of the latest activity entries
+    pub activity_limit: Option<usize>,
+    /// Show the activity log without the issue details
+    #[serde(default)]
+    pub activity_only: bool,
+}
+
+impl ShowIssueRequest {
+    /// Select the activity entries to show, oldest first
+    fn select_activity(
+        &self,
+        activity: Vec<IssueActivity>,
+    ) -> std::result::Result<Vec<IssueActivity>, McpError> {
+        let kind = self
+            .activity_kind
+            .as_deref()
+            .map(str::parse::<IssueActivityKind>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let mut selected: Vec<IssueActivity> = activity
+            .into_iter()
+            .filter(|entry| !matches!(kind, Some(kind) if kind != entry.kind))
+            .collect();
+        if let Some(limit) = self.activity_limit {
+            selected = selected.split_off(selected.len().saturating_sub(limit));
+        }
+        Ok(selected)
+    }
 }
 
 /// Tool for showing issue details
@@ -39,17 +72,104 @@ impl ShowIssueTool {
         }
     }
 
+    /// Format activity entries in chronological order
+    fn format_activity(activity: &[IssueActivity]) -> String {
+        if activity.is_empty() {
+            return "No activity recorded.\n".to_string();
+        }
+        activity
+            .iter()
+            .map(|entry| format!("- {entry}\n"))
+            .collect()
+    }
+
     /// Format issue for display
-    fn format_issue_display(issue_info: &IssueInfo) -> String {
+    fn format_issue_display(
+        issue_info: &IssueInfo,
+        hierarchy: &IssueHierarchy,
+        activity: &[IssueActivity],
+    ) -> String {
         let status = Self::format_issue_status(issue_info.completed);
 
         let mut result = format!("{} Issue: {}\n", status, issue_info.issue.name);
         result.push_str(&format!("📁 File: {}\n", issue_info.file_path.display()));
         result.push_str(&format!(
-            "📅 Created: {}\n\n",
+            "📅 Created: {}\n",
             issue_info.created_at.format("%Y-%m-%d %H:%M:%S")
         ));
-        result.push_str(&issue_info.issue.content);
+
+        let status = issue_info.status();
+        result.push_str(&format!(
+            "📌 Status: {} {}\n",
+            status.icon(),
+            status.label()
+        ));
+
+        let metadata = &issue_info.metadata;
+        result.push_str(&format!("🚩 Priority: {}\n", metadata.effective_priority()));
+        if !metadata.labels.is_empty() {
+            result.push_str(&format!("🏷️ Labels: {}\n", metadata.labels.join(", ")));
+        }
+        if let Some(assignee) = &metadata.assignee {
+            result.push_str(&format!("👤 Assignee: {assignee}\n"));
+        }
+        if let Some(estimate) = &metadata.estimate {
+            result.push_str(&format!("⏱️ Estimate: {estimate}\n"));
+        }
+        if let Some(created_by) = &metadata.created_by {
+            result.push_str(&format!("✍️ Created by: {created_by}\n"));
+        }
+        if !metadata.depends_on.is_empty() {
+            result.push_str(&format!(
+                "🔗 Depends on: {}\n",
+                metadata.depends_on.join(", ")
+            ));
+        }
+        if let Some(parent) = &metadata.parent {
+            result.push_str(&format!("🧩 Epic: {parent}\n"));
+        }
+        let name = &issue_info.issue.name;
+        if hierarchy.is_epic(name) {
+            result.push_str(&format!(
+                "🗂️ Sub-issues: {} done\n",
+                hierarchy.progress(name)
+            ));
+            for child in hierarchy.children(name) {
+                result.push_str(&format!("   - {child}\n"));
+            }
+        }
+        if !metadata.transitions.is_empty() {
+            result.push_str("🕒 History:\n");
+            for transition in &metadata.transitions {
+                let from = transition
+                    .from
+                    .map(|from| format!("{from} → "))
+                    .unwrap_or_default();
+                result.push_str(&format!(
+                    "   {} {}{}\n",
+                    transition.at.format("%Y-%m-%d %H:%M:%S"),
+                    from,
+                    transition.status
+                ));
+            }
+            if let Some(cycle_time) = issue_info.flow_metrics().cycle_time() {
+                result.push_str(&format!("   Cycle time: {}\n", format_duration(cycle_time)));
+            }
+        }
+        let criteria = issue_info.issue.criteria();
+        if !criteria.is_empty() {
+            let progress = CriteriaProgress::of(&criteria);
+            result.push_str(&format!("☑️ Acceptance criteria: {progress} met\n"));
+            for criterion in criteria.iter().filter(|c| !c.checked) {
+                result.push_str(&format!("   {}. [ ] {}\n", criterion.index, criterion.text));
+            }
+        }
+        result.push('\n');
+        result.push_str(issue_info.issue.body());
+        if !activity.is_empty() {
+            result.push_str("\n\n## Activity\n\n");
+            result.push_str(&Self::format_activity(activity));
+        }
 
         result
     }
@@ -72,12 +192,27 @@ impl McpTool for ShowIssueTool {
             "properties": {
                 "name": {
                     "type": "string",
-                    "description": "Name of the issue to show. Use 'current' to show the issue for the current git branch. Use 'next' to show the next pending issue."
+                    "description": "Name of the issue to show. Use 'current' to show the issue for the current git branch. Use 'next' to show the highest priority pending issue."
                 },
                 "raw": {
                     "type": "boolean",
                     "description": "Show raw content only without formatting",
                     "default": false
+                },
+                "activity_kind": {
+                    "type": "string",
+                    "description": "Only show activity entries of this kind",
+                    "enum": ["comment", "status", "branch", "merge", "archive"]
+                },
+                "activity_limit": {
+                    "type": "integer",
+                    "description": "Only show this many of the latest activity entries",
+                    "minimum": 0
+                },
+                "activity_only": {
+                    "type": "boolean",
+                    "description": "Show the activity log without the issue details",
+                    "default": false
                 }
             },
             "required": ["name"]
@@ -148,7 +283,7 @@ impl McpTool for ShowIssueTool {
             // Get next pending issue and then get its info
             let issue_storage = context.issue_storage.read().await;
             match issue_storage.next_issue().await {
-                Ok(Some(next_issue)) => {
+                Ok(NextIssue::Ready(next_issue)) => {
                     // Get the full issue info for the next issue
                     match issue_storage.get_issue_info(&next_issue.name).await {
                         Ok(issue_info) => issue_info,
@@ -157,11 +292,18 @@ impl McpTool for ShowIssueTool {
                         }
                     }
                 }
-                Ok(None) => {
+                Ok(NextIssue::AllDone) => {
                     return Ok(BaseToolImpl::create_success_response(
                         "No pending issues found. All issues are completed!",
                     ));
                 }
+                Ok(NextIssue::Stalled(held)) => {
+                    // Pending issues remain but each one is waiting or claimed by another agent
+                    return Ok(BaseToolImpl::create_success_response(format!(
+                        "No pending issue is ready to work on:\n{}",
+                        describe_held_issues(&held)
+                    )));
+                }
                 Err(e) => {
                     return Err(McpErrorHandler::handle_error(e, "get next issue"));
                 }
@@ -180,10 +322,30 @@ impl McpTool for ShowIssueTool {
             }
         };
 
-        let response = if request.raw.unwrap_or(false) {
+        let response = if request.raw.unwrap_or(false) && !request.activity_only {
             issue_info.issue.content
         } else {
-            Self::format_issue_display(&issue_info)
+            let activity = context
+                .issue_storage
+                .read()
+                .await
+                .issue_activity(&issue_info.issue.name)
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "read issue activity"))?;
+            let activity = request.select_activity(activity)?;
+            if request.activity_only {
+                Self::format_activity(&activity)
+            } else {
+                let issue_infos = context
+                    .issue_storage
+                    .read()
+                    .await
+                    .list_issues_info()
+                    .await
+                    .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+                let hierarchy = IssueHierarchy::from_issue_infos(&issue_infos);
+                Self::format_issue_display(&issue_info, &hierarchy, &activity)
+            }
         };
 
         tracing::info!("Showed issue {}", issue_info.issue.name);
@@ -1,6 +1,7 @@
 //! Issue updating tool for MCP operations
 //!
-//! This module provides the UpdateIssueTool for updating existing issue content.
+//! This module provides the UpdateIssueTool for updating existing issue content
+//! and frontmatter metadata.
 
 use crate::mcp::responses::create_success_response;
 use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
@@ -9,6 +10,7 @@ use crate::mcp::types::UpdateIssueRequest;
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use swissarmyhammer::issues::{parse_issue_content, render_issue_content};
 
 /// Tool for updating issue content
 #[derive(Default)]
@@ -48,9 +50,41 @@ impl McpTool for UpdateIssueTool {
                     "type": "boolean",
                     "description": "If true, append to existing content instead of replacing",
                     "default": false
+                },
+                "priority": {
+                    "type": "string",
+                    "description": "New priority of the issue",
+                    "enum": ["critical", "high", "medium", "low"]
+                },
+                "labels": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Replacement labels, an empty list clears them"
+                },
+                "estimate": {
+                    "type": "string",
+                    "description": "New effort estimate, an empty string clears it"
+                },
+                "assignee": {
+                    "type": "string",
+                    "description": "New assignee, an empty string clears it"
+                },
+                "depends_on": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Replacement dependencies, an empty list clears them"
+                },
+                "parent": {
+                    "type": "string",
+                    "description": "Name of the epic the issue belongs to, an empty string detaches it"
+                },
+                "status": {
+                    "type": "string",
+                    "description": "New status, done and wontfix move the issue to the completed directory",
+                    "enum": ["todo", "in_progress", "blocked", "in_review", "done", "wontfix"]
                 }
             },
-            "required": ["name", "content"]
+            "required": ["name"]
         })
     }
 
@@ -64,37 +98,84 @@ impl McpTool for UpdateIssueTool {
         // Validate issue name and content
         McpValidation::validate_not_empty(request.name.as_str(), "issue name")
             .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
-        McpValidation::validate_not_empty(&request.content, "issue content")
-            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue content"))?;
+        if let Some(content) = &request.content {
+            McpValidation::validate_not_empty(content, "issue content")
+                .map_err(|e| McpErrorHandler::handle_error(e, "validate issue content"))?;
+        }
+
+        let update = request.metadata_update();
+        if request.content.is_none() && update.is_empty() && request.status.is_none() {
+            return Err(McpError::invalid_params(
+                "Provide content, a status or at least one metadata field to update".to_string(),
+                None,
+            ));
+        }
 
         let issue_storage = context.issue_storage.write().await;
 
-        // Handle append mode by reading existing content first
-        let final_content = if request.append {
-            match issue_storage.get_issue(request.name.as_str()).await {
-                Ok(existing_issue) => {
-                    format!("{}\n{}", existing_issue.content, request.content)
+        if request.content.is_none() && update.is_empty() {
+            if let Some(status) = request.status {
+                return match issue_storage
+                    .set_issue_status(request.name.as_str(), status)
+                    .await
+                {
+                    Ok(issue) => Ok(create_success_response(format!(
+                        "Updated issue {} (status {})",
+                        issue.name, status
+                    ))),
+                    Err(e) => Err(McpErrorHandler::handle_error(e, "update issue status")),
+                };
+            }
+        }
+
+        // Read the existing issue so its frontmatter survives content changes
+        let existing_issue = issue_storage
+            .get_issue(request.name.as_str())
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "update issue"))?;
+        let (mut metadata, existing_body) = parse_issue_content(&existing_issue.content);
+
+        let body = match &request.content {
+            None => existing_body.to_string(),
+            Some(content) if request.append => format!("{existing_body}\n{content}"),
+            Some(content) => {
+                // Frontmatter in replacement content takes over from the existing one
+                let (new_metadata, new_body) = parse_issue_content(content);
+                if !new_metadata.is_empty() {
+                    metadata = new_metadata;
                 }
-                Err(_) => request.content, // If can't read existing, just use new content
+                new_body.to_string()
             }
-        } else {
-            request.content
+        };
+        metadata.apply(&update);
+        let final_content = render_issue_content(&metadata, &body);
+
+        let mode = match &request.content {
+            None => "metadata only",
+            Some(_) if request.append => "append mode",
+            Some(_) => "replace mode",
         };
 
-        match issue_storage
+        let issue = issue_storage
             .update_issue(request.name.as_str(), final_content)
             .await
-        {
-            Ok(issue) => Ok(create_success_response(format!(
+            .map_err(|e| McpErrorHandler::handle_error(e, "update issue"))?;
+
+        match request.status {
+            Some(status) => {
+                let issue = issue_storage
+                    .set_issue_status(&issue.name, status)
+                    .await
+                    .map_err(|e| McpErrorHandler::handle_error(e, "update issue status"))?;
+                Ok(create_success_response(format!(
+                    "Updated issue {} ({}, status {})",
+                    issue.name, mode, status
+                )))
+            }
+            None => Ok(create_success_response(format!(
                 "Updated issue {} ({})",
-                issue.name,
-                if request.append {
-                    "append mode"
-                } else {
-                    "replace mode"
-                }
+                issue.name, mode
             ))),
-            Err(e) => Err(McpErrorHandler::handle_error(e, "update issue")),
         }
     }
 }
@@ -10,6 +10,10 @@ use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use swissarmyhammer::common::create_abort_file_current_dir;
+use swissarmyhammer::issues::{
+    default_lease_owner, describe_blockers, DependencyGraph, IssueActivity, IssueActivityKind,
+    IssueStatus, DEFAULT_LEASE_TTL_SECONDS,
+};
 
 /// Tool for switching to work on an issue
 #[derive(Default)]
@@ -20,6 +24,22 @@ impl WorkIssueTool {
     pub fn new() -> Self {
         Self
     }
+
+    /// Why an issue in `status` cannot be worked on, if it cannot
+    ///
+    /// Blocked issues wait on something outside the issue and in-review issues wait on a
+    /// reviewer, so starting work would silently drop either state.
+    fn status_refusal(name: &str, status: IssueStatus) -> Option<String> {
+        match status {
+            IssueStatus::Blocked => Some(format!(
+                "Issue '{name}' is blocked. Resolve what it waits on, then set its status back to todo with issue_update before working on it."
+            )),
+            IssueStatus::InReview => Some(format!(
+                "Issue '{name}' is in review. Finish the review, or set its status back to todo or in_progress with issue_update to rework it."
+            )),
+            _ => None,
+        }
+    }
 }
 
 #[async_trait]
@@ -40,6 +60,10 @@ impl McpTool for WorkIssueTool {
                 "name": {
                     "type": "string",
                     "description": "Issue name to work on"
+                },
+                "owner": {
+                    "type": "string",
+                    "description": "Who claims the issue. Defaults to SAH_AGENT_ID, or the host and process ID."
                 }
             },
             "required": ["name"]
@@ -93,14 +117,94 @@ impl McpTool for WorkIssueTool {
                 Err(e) => return Err(McpErrorHandler::handle_error(e, "get issue")),
             };
 
+            // Refuse issues whose dependencies are not completed yet
+            let issue_infos = issue_storage
+                .list_issues_info()
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+            let blockers = DependencyGraph::from_issue_infos(&issue_infos).blockers(&issue.name);
+            if !blockers.is_empty() {
+                return Err(McpError::invalid_params(
+                    describe_blockers(&issue.name, &blockers),
+                    None,
+                ));
+            }
+
+            let status = issue_infos
+                .iter()
+                .find(|info| info.issue.name == issue.name)
+                .map(|info| info.status());
+            if let Some(refusal) =
+                status.and_then(|status| Self::status_refusal(&issue.name, status))
+            {
+                return Err(McpError::invalid_params(refusal, None));
+            }
+
+            // Claim open issues so parallel agents leave them alone
+            let is_open = status.is_some_and(|status| !status.is_closed());
+            let owner = request
+                .owner
+                .map(|owner| owner.trim().to_string())
+                .filter(|owner| !owner.is_empty())
+                .unwrap_or_else(default_lease_owner);
+            if is_open {
+                issue_storage
+                    .claim_issue(&issue.name, &owner, DEFAULT_LEASE_TTL_SECONDS)
+                    .await
+                    .map_err(|e| McpErrorHandler::handle_error(e, "claim issue"))?;
+            }
+
             let branch_name = issue.name.clone();
 
             match git_ops.as_mut() {
                 Some(ops) => match ops.create_work_branch(&branch_name) {
-                    Ok(branch_name) => Ok(create_success_response(format!(
-                        "Switched to work branch: {branch_name}"
-                    ))),
-                    Err(e) => Err(McpErrorHandler::handle_error(e, "create work branch")),
+                    Ok(branch_name) => {
+                        // Record that work has started unless the issue is already closed
+                        if is_open {
+                            issue_storage
+                                .set_issue_status(&issue.name, IssueStatus::InProgress)
+                                .await
+                                .map_err(|e| {
+                                    McpErrorHandler::handle_error(e, "set issue status")
+                                })?;
+                        }
+
+                        if let Err(e) = issue_storage
+                            .add_issue_activity(
+                                &issue.name,
+                                IssueActivity::new(
+                                    IssueActivityKind::Branch,
+                                    format!("Working on branch {branch_name}"),
+                                ),
+                            )
+                            .await
+                        {
+                            tracing::warn!(
+                                "Failed to record branch activity for issue '{}': {}",
+                                issue.name,
+                                e
+                            );
+                        }
+
+                        Ok(create_success_response(format!(
+                            "Switched to work branch: {branch_name}"
+                        )))
+                    }
+                    Err(e) => {
+                        if is_open {
+                            if let Err(release_error) = issue_storage
+                                .release_issue(&issue.name, &owner, false)
+                                .await
+                            {
+                                tracing::warn!(
+                                    "Failed to release issue '{}': {}",
+                                    issue.name,
+                                    release_error
+                                );
+                            }
+                        }
+                        Err(McpErrorHandler::handle_error(e, "create work branch"))
+                    }
                 },
                 None => Err(McpError::internal_error(
                     "Git operations not available".to_string(),
@@ -119,6 +223,7 @@ impl McpTool for WorkIssueTool {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use swissarmyhammer::common::abort_utils::create_abort_file;
     use swissarmyhammer::test_utils::IsolatedTestHome;
     use tempfile::TempDir;
@@ -138,4 +243,21 @@ mod tests {
         let content = std::fs::read_to_string(&abort_file).unwrap();
         assert_eq!(content, reason);
     }
+
+    #[test]
+    fn test_status_refusal() {
+        let blocked = WorkIssueTool::status_refusal("login", IssueStatus::Blocked).unwrap();
+        assert!(blocked.starts_with("Issue 'login' is blocked"));
+        let in_review = WorkIssueTool::status_refusal("login", IssueStatus::InReview).unwrap();
+        assert!(in_review.starts_with("Issue 'login' is in review"));
+
+        for status in [
+            IssueStatus::Todo,
+            IssueStatus::InProgress,
+            IssueStatus::Done,
+            IssueStatus::WontFix,
+        ] {
+            assert!(WorkIssueTool::status_refusal("login", status).is_none());
+        }
+    }
 }
@@ -3,11 +3,12 @@
 //! This module provides the CreateMemoTool for creating new memos through the MCP protocol.
 
 use crate::mcp::memo_types::CreateMemoRequest;
-use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::shared_utils::{McpErrorHandler, McpFormatter};
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::MemoMetadata;
 
 /// Tool for creating new memos
 #[derive(Default)]
@@ -42,6 +43,15 @@ impl McpTool for CreateMemoTool {
                 "content": {
                     "type": "string",
                     "description": "Markdown content of the memo"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Tags for grouping and filtering the memo"
+                },
+                "folder": {
+                    "type": "string",
+                    "description": "Optional slash-separated folder path, such as 'projects/login'"
                 }
             },
             "required": ["title", "content"]
@@ -59,16 +69,20 @@ impl McpTool for CreateMemoTool {
 
         // Note: Both title and content can be empty - storage layer supports this
 
+        let metadata = MemoMetadata::new(&request.tags, request.folder.as_deref());
         let memo_storage = context.memo_storage.write().await;
         match memo_storage
-            .create_memo(request.title, request.content)
+            .create_memo_with_metadata(request.title, request.content, metadata)
             .await
         {
             Ok(memo) => {
                 tracing::info!("Created memo {}", memo.id);
+                let metadata = McpFormatter::format_memo_metadata(&memo)
+                    .map(|line| format!("\n{line}"))
+                    .unwrap_or_default();
                 Ok(BaseToolImpl::create_success_response(format!(
-                    "Successfully created memo '{}' with ID: {}\n\nTitle: {}\nContent: {}",
-                    memo.title, memo.id, memo.title, memo.content
+                    "Successfully created memo '{}' with ID: {}\n\nTitle: {}{}\nContent: {}",
+                    memo.title, memo.id, memo.title, metadata, memo.content
                 )))
             }
             Err(e) => Err(McpErrorHandler::handle_error(e, "create memo")),
@@ -122,6 +136,26 @@ mod tests {
         assert!(!call_result.content.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_create_memo_tool_execute_with_tags_and_folder() {
+        let tool = CreateMemoTool::new();
+        let context = create_test_context().await;
+
+        let arguments = serde_json::json!({
+            "title": "Tagged create memo",
+            "content": "Tagged content",
+            "tags": ["#auth", "design", "Auth"],
+            "folder": "/projects/login/"
+        });
+        let result = tool
+            .execute(arguments.as_object().unwrap().clone(), &context)
+            .await
+            .unwrap();
+
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.contains("Folder: projects/login | Tags: auth, design"));
+    }
+
     #[tokio::test]
     async fn test_create_memo_tool_execute_empty_title_and_content() {
         let tool = CreateMemoTool::new();
@@ -66,6 +66,14 @@ impl McpTool for DeleteMemoTool {
         match memo_storage.delete_memo(&memo_id).await {
             Ok(()) => {
                 tracing::info!("Deleted memo {}", request.id);
+                // Keep the semantic memo index in step; a stale row would also be
+                // dropped by the next semantic search, so failures are only logged
+                if let Err(e) = swissarmyhammer::memoranda::semantic::remove_memo_embedding(
+                    &swissarmyhammer::search::SemanticConfig::default(),
+                    &memo_id,
+                ) {
+                    tracing::warn!("Failed to remove embedding for memo {}: {}", request.id, e);
+                }
                 Ok(BaseToolImpl::create_success_response(format!(
                     "Successfully deleted memo with ID: {}",
                     request.id
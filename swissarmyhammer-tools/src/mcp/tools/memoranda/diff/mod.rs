@@ -0,0 +1,187 @@
+//! Memo diff tool for MCP operations
+//!
+//! This module provides the MemoDiffTool for comparing two versions of a memo as a unified
+//! diff through the MCP protocol.
+
+use crate::mcp::memo_types::MemoDiffRequest;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::{unified_diff, MemoId, MemoRevision};
+use swissarmyhammer::SwissArmyHammerError;
+
+/// Tool for comparing two versions of a memo
+#[derive(Default)]
+pub struct MemoDiffTool;
+
+impl MemoDiffTool {
+    /// Creates a new instance of the MemoDiffTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Content of a recorded revision
+    fn revision_content<'a>(
+        memo_id: &MemoId,
+        revisions: &'a [MemoRevision],
+        revision: u32,
+    ) -> std::result::Result<&'a str, McpError> {
+        revisions
+            .iter()
+            .find(|r| r.revision == revision)
+            .map(|r| r.content.as_str())
+            .ok_or_else(|| {
+                crate::mcp::shared_utils::McpErrorHandler::handle_error(
+                    SwissArmyHammerError::MemoRevisionNotFound {
+                        id: memo_id.to_string(),
+                        revision,
+                    },
+                    "diff memo",
+                )
+            })
+    }
+}
+
+#[async_trait]
+impl McpTool for MemoDiffTool {
+    fn name(&self) -> &'static str {
+        "memo_diff"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "diff")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Identifier of the memo"
+                },
+                "from": {
+                    "type": "integer",
+                    "description": "Older revision, the most recent revision when omitted",
+                    "minimum": 1
+                },
+                "to": {
+                    "type": "integer",
+                    "description": "Newer revision, the current content when omitted",
+                    "minimum": 1
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: MemoDiffRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        tracing::debug!("Diffing memo with ID: {}", request.id);
+
+        let memo_id = match MemoId::from_string(request.id.clone()) {
+            Ok(id) => id,
+            Err(_) => {
+                return Err(McpError::invalid_params(
+                    format!("Invalid memo ID format: {}", request.id),
+                    None,
+                ))
+            }
+        };
+
+        let memo_storage = context.memo_storage.read().await;
+        let memo = memo_storage
+            .get_memo(&memo_id)
+            .await
+            .map_err(|e| crate::mcp::shared_utils::McpErrorHandler::handle_error(e, "diff memo"))?;
+        let revisions = memo_storage
+            .memo_history(&memo_id)
+            .await
+            .map_err(|e| crate::mcp::shared_utils::McpErrorHandler::handle_error(e, "diff memo"))?;
+
+        let from = match request
+            .from
+            .or_else(|| revisions.last().map(|r| r.revision))
+        {
+            Some(from) => from,
+            None => {
+                return Ok(BaseToolImpl::create_success_response(format!(
+                    "Memo {memo_id} has no previous revisions to compare"
+                )))
+            }
+        };
+        let old = Self::revision_content(&memo_id, &revisions, from)?;
+        let old_label = format!("{memo_id} (revision {from})");
+        let (new, new_label) = match request.to {
+            Some(to) => (
+                Self::revision_content(&memo_id, &revisions, to)?,
+                format!("{memo_id} (revision {to})"),
+            ),
+            None => (memo.content.as_str(), format!("{memo_id} (current)")),
+        };
+
+        let diff = unified_diff(old, new, &old_label, &new_label);
+        if diff.is_empty() {
+            return Ok(BaseToolImpl::create_success_response(format!(
+                "No differences between {old_label} and {new_label}"
+            )));
+        }
+        Ok(BaseToolImpl::create_success_response(diff))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+    use swissarmyhammer::memoranda::MarkdownMemoStorage;
+
+    #[test]
+    fn test_memo_diff_tool_new() {
+        let tool = MemoDiffTool::new();
+        assert_eq!(tool.name(), "memo_diff");
+        assert!(!tool.description().is_empty());
+        assert_eq!(tool.schema()["required"], serde_json::json!(["id"]));
+    }
+
+    #[tokio::test]
+    async fn test_memo_diff_tool_compares_versions() {
+        let context = create_test_context().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        *context.memo_storage.write().await =
+            Box::new(MarkdownMemoStorage::new(temp_dir.path().join("memos")));
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .create_memo("Notes".to_string(), "keep\nold line\n".to_string())
+            .await
+            .unwrap();
+        memo_storage
+            .update_memo(&memo.id, "keep\nnew line\n".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage);
+
+        let tool = MemoDiffTool::new();
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!("Notes"));
+        let result = tool.execute(arguments, &context).await.unwrap();
+        assert_eq!(
+            result.content[0].as_text().unwrap().text,
+            "--- Notes (revision 1)\n+++ Notes (current)\n@@ -1,2 +1,2 @@\n keep\n-old line\n+new line\n"
+        );
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!("Notes"));
+        arguments.insert("from".to_string(), serde_json::json!(7));
+        let error = tool.execute(arguments, &context).await.unwrap_err();
+        assert!(error.message.contains("has no revision 7"));
+    }
+}
@@ -7,6 +7,7 @@ use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::{LinkIndex, LinkResolution, Memo};
 
 /// Tool for retrieving a memo by its unique ID
 #[derive(Default)]
@@ -17,6 +18,43 @@ impl GetMemoTool {
     pub fn new() -> Self {
         Self
     }
+
+    /// Outgoing links and backlinks of a memo, empty when it has neither
+    fn format_links(memo: &Memo, index: &LinkIndex) -> String {
+        let mut sections = String::new();
+
+        let links = index.outgoing(&memo.id);
+        if !links.is_empty() {
+            let link_list = links
+                .iter()
+                .map(|link| match index.resolve(&link.target) {
+                    LinkResolution::Memo(id) => format!(
+                        "• {} → {} ({id})",
+                        link.target,
+                        index.title(&id).unwrap_or(id.as_str())
+                    ),
+                    LinkResolution::Found => format!("• {}", link.target),
+                    LinkResolution::Broken(reason) => {
+                        format!("• {} (broken: {reason}, line {})", link.target, link.line)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            sections.push_str(&format!("\n\nLinks:\n{link_list}"));
+        }
+
+        let backlinks = index.memo_backlinks(&memo.id);
+        if !backlinks.is_empty() {
+            let backlink_list = backlinks
+                .iter()
+                .map(|id| format!("• {} ({id})", index.title(id).unwrap_or(id.as_str())))
+                .collect::<Vec<_>>()
+                .join("\n");
+            sections.push_str(&format!("\n\nBacklinks:\n{backlink_list}"));
+        }
+
+        sections
+    }
 }
 
 #[async_trait]
@@ -66,13 +104,28 @@ impl McpTool for GetMemoTool {
         match memo_storage.get_memo(&memo_id).await {
             Ok(memo) => {
                 tracing::info!("Retrieved memo {}", memo.id);
+                let metadata = crate::mcp::shared_utils::McpFormatter::format_memo_metadata(&memo)
+                    .map(|line| format!("{line}\n"))
+                    .unwrap_or_default();
+                let links = match memo_storage.list_memos().await {
+                    Ok(memos) => {
+                        let index = super::build_link_index(context, &memos).await;
+                        Self::format_links(&memo, &index)
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to list memos for links of {}: {}", memo.id, e);
+                        String::new()
+                    }
+                };
                 Ok(BaseToolImpl::create_success_response(format!(
-                    "Memo found:\n\nID: {}\nTitle: {}\nCreated: {}\nUpdated: {}\n\nContent:\n{}",
+                    "Memo found:\n\nID: {}\nTitle: {}\n{}Created: {}\nUpdated: {}\n\nContent:\n{}{}",
                     memo.id,
                     memo.title,
+                    metadata,
                     crate::mcp::shared_utils::McpFormatter::format_timestamp(memo.created_at),
                     crate::mcp::shared_utils::McpFormatter::format_timestamp(memo.updated_at),
-                    memo.content
+                    memo.content,
+                    links
                 )))
             }
             Err(e) => Err(crate::mcp::shared_utils::McpErrorHandler::handle_error(
@@ -86,6 +139,7 @@ impl McpTool for GetMemoTool {
 mod tests {
     use super::*;
     use crate::test_utils::create_test_context;
+    use swissarmyhammer::memoranda::MarkdownMemoStorage;
 
     #[test]
     fn test_get_memo_tool_new() {
@@ -131,6 +185,37 @@ mod tests {
         assert!(!call_result.content.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_get_memo_tool_shows_links_and_backlinks() {
+        let tool = GetMemoTool::new();
+        let context = create_test_context().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        *context.memo_storage.write().await =
+            Box::new(MarkdownMemoStorage::new(temp_dir.path().join("memos")));
+
+        let memo_storage = context.memo_storage.write().await;
+        let design = memo_storage
+            .create_memo(
+                "Design".to_string(),
+                "See [[Roadmap]] and [[Nowhere]]".to_string(),
+            )
+            .await
+            .unwrap();
+        memo_storage
+            .create_memo("Roadmap".to_string(), "Follows [[design]]".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage);
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!(design.id.to_string()));
+        let result = tool.execute(arguments, &context).await.unwrap();
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.contains("Links:\n• [[Roadmap]] → Roadmap (Roadmap)"));
+        assert!(text.contains("• [[Nowhere]] (broken: no memo titled 'Nowhere', line 1)"));
+        assert!(text.ends_with("Backlinks:\n• Roadmap (Roadmap)"));
+    }
+
     #[tokio::test]
     async fn test_get_memo_tool_execute_invalid_id_format() {
         let tool = GetMemoTool::new();
@@ -1,12 +1,14 @@
 //! Memo get all context tool for MCP operations
 //!
-//! This module provides the GetAllContextMemoTool for retrieving all memo content formatted for AI context consumption.
+//! This module provides the GetAllContextMemoTool for retrieving all memo content formatted for AI context consumption,
+//! optionally restricted to a tag or folder.
 
 use crate::mcp::memo_types::GetAllContextRequest;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::MemoFilter;
 
 /// Tool for getting all memo content formatted for AI context consumption
 #[derive(Default)]
@@ -33,7 +35,16 @@ impl McpTool for GetAllContextMemoTool {
     fn schema(&self) -> serde_json::Value {
         serde_json::json!({
             "type": "object",
-            "properties": {},
+            "properties": {
+                "tag": {
+                    "type": "string",
+                    "description": "Only include memos with this tag"
+                },
+                "folder": {
+                    "type": "string",
+                    "description": "Only include memos in this folder or its subfolders"
+                }
+            },
             "required": []
         })
     }
@@ -47,18 +58,21 @@ impl McpTool for GetAllContextMemoTool {
         arguments: serde_json::Map<String, serde_json::Value>,
         context: &ToolContext,
     ) -> std::result::Result<CallToolResult, McpError> {
-        let _request: GetAllContextRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let request: GetAllContextRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let filter = MemoFilter::new(request.tag.as_deref(), request.folder.as_deref());
 
-        tracing::debug!("Getting all memo context");
+        tracing::debug!("Getting memo context{}", filter.describe());
 
         let memo_storage = context.memo_storage.read().await;
         match memo_storage.list_memos().await {
             Ok(memos) => {
+                let memos = filter.apply(memos);
                 tracing::info!("Retrieved {} memos for context", memos.len());
                 if memos.is_empty() {
-                    Ok(BaseToolImpl::create_success_response(
-                        "No memos available".to_string(),
-                    ))
+                    Ok(BaseToolImpl::create_success_response(format!(
+                        "No memos available{}",
+                        filter.describe()
+                    )))
                 } else {
                     // Sort memos by updated_at descending (most recent first)
                     let mut sorted_memos = memos;
@@ -67,10 +81,15 @@ impl McpTool for GetAllContextMemoTool {
                     let context = sorted_memos
                         .iter()
                         .map(|memo| {
+                            let metadata =
+                                crate::mcp::shared_utils::McpFormatter::format_memo_metadata(memo)
+                                    .map(|line| format!("{line}\n"))
+                                    .unwrap_or_default();
                             format!(
-                                "=== {} (ID: {}) ===\nCreated: {}\nUpdated: {}\n\n{}",
+                                "=== {} (ID: {}) ===\n{}Created: {}\nUpdated: {}\n\n{}",
                                 memo.title,
                                 memo.id,
+                                metadata,
                                 crate::mcp::shared_utils::McpFormatter::format_timestamp(
                                     memo.created_at
                                 ),
@@ -86,7 +105,8 @@ impl McpTool for GetAllContextMemoTool {
                     let memo_count = sorted_memos.len();
                     let plural_suffix = if memo_count == 1 { "" } else { "s" };
                     Ok(BaseToolImpl::create_success_response(format!(
-                        "All memo context ({memo_count} memo{plural_suffix}):\n\n{context}"
+                        "All memo context{} ({memo_count} memo{plural_suffix}):\n\n{context}",
+                        filter.describe()
                     )))
                 }
             }
@@ -116,7 +136,8 @@ mod tests {
         let schema = tool.schema();
 
         assert_eq!(schema["type"], "object");
-        assert_eq!(schema["properties"], serde_json::json!({}));
+        assert!(schema["properties"]["tag"].is_object());
+        assert!(schema["properties"]["folder"].is_object());
         assert_eq!(schema["required"], serde_json::json!([]));
     }
 
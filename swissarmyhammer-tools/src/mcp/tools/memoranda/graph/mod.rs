@@ -0,0 +1,141 @@
+//! Memo graph tool for MCP operations
+//!
+//! This module provides the MemoGraphTool for exporting the links between memos, issues and
+//! files as a Mermaid flowchart through the MCP protocol.
+
+use crate::mcp::memo_types::MemoGraphRequest;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::{MemoFilter, MemoId};
+
+/// Tool for exporting the memo knowledge graph
+#[derive(Default)]
+pub struct MemoGraphTool;
+
+impl MemoGraphTool {
+    /// Creates a new instance of the MemoGraphTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for MemoGraphTool {
+    fn name(&self) -> &'static str {
+        "memo_graph"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "graph")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "tag": {
+                    "type": "string",
+                    "description": "Only graph the links of memos with this tag"
+                },
+                "folder": {
+                    "type": "string",
+                    "description": "Only graph the links of memos in this folder or its subfolders"
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: MemoGraphRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let filter = MemoFilter::new(request.tag.as_deref(), request.folder.as_deref());
+
+        tracing::debug!("Rendering memo graph{}", filter.describe());
+
+        let memo_storage = context.memo_storage.read().await;
+        let memos = memo_storage.list_memos().await.map_err(|e| {
+            crate::mcp::shared_utils::McpErrorHandler::handle_error(e, "render memo graph")
+        })?;
+
+        // Links from filtered memos may still point at memos outside the filter
+        let index = super::build_link_index(context, &memos).await;
+        let sources: Vec<MemoId> = filter
+            .apply(memos)
+            .into_iter()
+            .map(|memo| memo.id)
+            .collect();
+        if sources.is_empty() {
+            return Ok(BaseToolImpl::create_success_response(format!(
+                "No memos found{}",
+                filter.describe()
+            )));
+        }
+
+        tracing::info!("Rendered memo graph of {} memos", sources.len());
+        Ok(BaseToolImpl::create_success_response(
+            index.to_mermaid(&sources),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+    use swissarmyhammer::memoranda::MarkdownMemoStorage;
+
+    #[test]
+    fn test_memo_graph_tool_new() {
+        let tool = MemoGraphTool::new();
+        assert_eq!(tool.name(), "memo_graph");
+        assert!(!tool.description().is_empty());
+        assert!(tool.schema()["properties"]["tag"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_memo_graph_tool_renders_mermaid() {
+        let context = create_test_context().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        *context.memo_storage.write().await =
+            Box::new(MarkdownMemoStorage::new(temp_dir.path().join("memos")));
+
+        let tool = MemoGraphTool::new();
+        let result = tool
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .unwrap();
+        assert_eq!(result.content[0].as_text().unwrap().text, "No memos found");
+
+        let memo_storage = context.memo_storage.write().await;
+        memo_storage
+            .create_memo(
+                "Design".to_string(),
+                "See [[Roadmap]] and [[issue:no_such_issue]]".to_string(),
+            )
+            .await
+            .unwrap();
+        memo_storage
+            .create_memo("Roadmap".to_string(), "Plans".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage);
+
+        let result = tool
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .unwrap();
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.starts_with("graph TD\n"));
+        assert!(text.contains("[\"Design\"]:::memo"));
+        assert!(text.contains("[\"Roadmap\"]:::memo"));
+        assert!(text.contains("[\"[[issue:no_such_issue]]\"]:::broken"));
+        assert!(text.contains(" -.-> "));
+    }
+}
@@ -0,0 +1,162 @@
+//! Memo history tool for MCP operations
+//!
+//! This module provides the MemoHistoryTool for listing the previous revisions of a memo
+//! through the MCP protocol.
+
+use crate::mcp::memo_types::MemoHistoryRequest;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+
+/// Tool for listing the previous revisions of a memo
+#[derive(Default)]
+pub struct MemoHistoryTool;
+
+impl MemoHistoryTool {
+    /// Preview length for each revision (characters)
+    const REVISION_PREVIEW_LENGTH: usize = 80;
+
+    /// Creates a new instance of the MemoHistoryTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for MemoHistoryTool {
+    fn name(&self) -> &'static str {
+        "memo_history"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "history")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Identifier of the memo"
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: MemoHistoryRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        tracing::debug!("Listing history of memo with ID: {}", request.id);
+
+        let memo_id = match swissarmyhammer::memoranda::MemoId::from_string(request.id.clone()) {
+            Ok(id) => id,
+            Err(_) => {
+                return Err(McpError::invalid_params(
+                    format!("Invalid memo ID format: {}", request.id),
+                    None,
+                ))
+            }
+        };
+
+        let memo_storage = context.memo_storage.read().await;
+        match memo_storage.memo_history(&memo_id).await {
+            Ok(revisions) => {
+                tracing::info!("Memo {} has {} revisions", memo_id, revisions.len());
+                if revisions.is_empty() {
+                    return Ok(BaseToolImpl::create_success_response(format!(
+                        "Memo {memo_id} has no previous revisions"
+                    )));
+                }
+
+                let revision_list = revisions
+                    .iter()
+                    .rev()
+                    .map(|revision| {
+                        format!(
+                            "• Revision {}\n  Written: {}\n  Replaced: {}\n  Preview: {}",
+                            revision.revision,
+                            crate::mcp::shared_utils::McpFormatter::format_timestamp(
+                                revision.updated_at
+                            ),
+                            crate::mcp::shared_utils::McpFormatter::format_timestamp(
+                                revision.replaced_at
+                            ),
+                            crate::mcp::shared_utils::McpFormatter::format_preview(
+                                &revision.content,
+                                Self::REVISION_PREVIEW_LENGTH
+                            )
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                let summary = crate::mcp::shared_utils::McpFormatter::format_list_summary(
+                    "revision",
+                    revisions.len(),
+                    revisions.len(),
+                );
+                Ok(BaseToolImpl::create_success_response(format!(
+                    "{summary} of memo {memo_id}, newest first:\n\n{revision_list}"
+                )))
+            }
+            Err(e) => Err(crate::mcp::shared_utils::McpErrorHandler::handle_error(
+                e,
+                "list memo history",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+    use swissarmyhammer::memoranda::MarkdownMemoStorage;
+
+    #[test]
+    fn test_memo_history_tool_new() {
+        let tool = MemoHistoryTool::new();
+        assert_eq!(tool.name(), "memo_history");
+        assert!(!tool.description().is_empty());
+        assert_eq!(tool.schema()["required"], serde_json::json!(["id"]));
+    }
+
+    #[tokio::test]
+    async fn test_memo_history_tool_lists_revisions() {
+        let context = create_test_context().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        *context.memo_storage.write().await =
+            Box::new(MarkdownMemoStorage::new(temp_dir.path().join("memos")));
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .create_memo("Notes".to_string(), "first draft".to_string())
+            .await
+            .unwrap();
+        memo_storage
+            .update_memo(&memo.id, "second draft".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage);
+
+        let tool = MemoHistoryTool::new();
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!("Notes"));
+        let result = tool.execute(arguments, &context).await.unwrap();
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.starts_with("Found 1 revision of memo Notes"));
+        assert!(text.contains("• Revision 1"));
+        assert!(text.contains("Preview: first draft"));
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!("Missing"));
+        assert!(tool.execute(arguments, &context).await.is_err());
+    }
+}
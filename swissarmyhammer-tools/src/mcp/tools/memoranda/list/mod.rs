@@ -1,12 +1,14 @@
 //! Memo listing tool for MCP operations
 //!
-//! This module provides the ListMemoTool for listing all memos through the MCP protocol.
+//! This module provides the ListMemoTool for listing memos, optionally filtered by tag or
+//! folder, through the MCP protocol.
 
 use crate::mcp::memo_types::ListMemosRequest;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::MemoFilter;
 
 /// Tool for listing all memos
 #[derive(Default)]
@@ -36,7 +38,16 @@ impl McpTool for ListMemoTool {
     fn schema(&self) -> serde_json::Value {
         serde_json::json!({
             "type": "object",
-            "properties": {},
+            "properties": {
+                "tag": {
+                    "type": "string",
+                    "description": "Only include memos with this tag"
+                },
+                "folder": {
+                    "type": "string",
+                    "description": "Only include memos in this folder or its subfolders"
+                }
+            },
             "required": []
         })
     }
@@ -46,18 +57,21 @@ impl McpTool for ListMemoTool {
         arguments: serde_json::Map<String, serde_json::Value>,
         context: &ToolContext,
     ) -> std::result::Result<CallToolResult, McpError> {
-        let _request: ListMemosRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let request: ListMemosRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let filter = MemoFilter::new(request.tag.as_deref(), request.folder.as_deref());
 
-        tracing::debug!("Listing all memos");
+        tracing::debug!("Listing memos{}", filter.describe());
 
         let memo_storage = context.memo_storage.read().await;
         match memo_storage.list_memos().await {
             Ok(memos) => {
+                let memos = filter.apply(memos);
                 tracing::info!("Retrieved {} memos", memos.len());
                 if memos.is_empty() {
-                    Ok(BaseToolImpl::create_success_response(
-                        "No memos found".to_string(),
-                    ))
+                    Ok(BaseToolImpl::create_success_response(format!(
+                        "No memos found{}",
+                        filter.describe()
+                    )))
                 } else {
                     let memo_list = memos
                         .iter()
@@ -76,7 +90,8 @@ impl McpTool for ListMemoTool {
                         memos.len(),
                     );
                     Ok(BaseToolImpl::create_success_response(format!(
-                        "{summary}:\n\n{memo_list}"
+                        "{summary}{}:\n\n{memo_list}",
+                        filter.describe()
                     )))
                 }
             }
@@ -106,10 +121,57 @@ mod tests {
         let schema = tool.schema();
 
         assert_eq!(schema["type"], "object");
-        assert_eq!(schema["properties"], serde_json::json!({}));
+        assert!(schema["properties"]["tag"].is_object());
+        assert!(schema["properties"]["folder"].is_object());
         assert_eq!(schema["required"], serde_json::json!([]));
     }
 
+    #[tokio::test]
+    async fn test_list_memo_tool_filters_by_tag_and_folder() {
+        use swissarmyhammer::memoranda::{MarkdownMemoStorage, MemoMetadata};
+
+        let context = create_test_context().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        *context.memo_storage.write().await =
+            Box::new(MarkdownMemoStorage::new(temp_dir.path().join("memos")));
+        {
+            let storage = context.memo_storage.write().await;
+            for (title, tags, folder) in [
+                ("Login flow", vec!["auth"], Some("projects/login")),
+                ("Token rotation", vec!["auth", "ops"], Some("ops")),
+                ("Lunch", vec![], None),
+            ] {
+                let tags: Vec<String> = tags.into_iter().map(String::from).collect();
+                storage
+                    .create_memo_with_metadata(
+                        title.to_string(),
+                        "Body".to_string(),
+                        MemoMetadata::new(&tags, folder),
+                    )
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let tool = ListMemoTool::new();
+        let list = |arguments: serde_json::Value| {
+            let arguments = arguments.as_object().unwrap().clone();
+            let context = &context;
+            let tool = &tool;
+            async move {
+                let result = tool.execute(arguments, context).await.unwrap();
+                result.content[0].as_text().unwrap().text.clone()
+            }
+        };
+
+        let text = list(serde_json::json!({"tag": "auth"})).await;
+        assert!(text.starts_with("Found 2 memos tagged 'auth'"));
+        let text = list(serde_json::json!({"folder": "projects"})).await;
+        assert!(text.contains("Login flow") && !text.contains("Token rotation"));
+        let text = list(serde_json::json!({"tag": "auth", "folder": "archive"})).await;
+        assert_eq!(text, "No memos found tagged 'auth' in folder 'archive'");
+    }
+
     #[test]
     fn test_format_memo_preview() {
         use chrono::Utc;
@@ -121,6 +183,8 @@ mod tests {
             content: "This is a long piece of content that should be truncated in the preview to show only the first part".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            tags: Vec::new(),
+            folder: None,
         };
 
         let preview = crate::mcp::shared_utils::McpFormatter::format_memo_preview(&memo, 50);
@@ -15,6 +15,7 @@
 //! - **ULID**: Sortable unique identifier for chronological ordering
 //! - **Title**: Human-readable memo identifier
 //! - **Content**: Markdown-formatted memo body
+//! - **Tags and Folder**: Optional grouping used to filter list, search and context
 //! - **Metadata**: Creation timestamp and other system information
 //!
 //! ## Tool Categories
@@ -22,13 +23,20 @@
 //! ### CRUD Operations
 //! - **create**: Generate new memos with titles and content
 //! - **get**: Retrieve individual memos by ULID
-//! - **update**: Modify existing memo content (title remains unchanged)
+//! - **update**: Modify existing memo content, tags or folder (title remains unchanged)
 //! - **delete**: Permanently remove memos (irreversible operation)
 //!
+//! ### Revision History
+//! - **history**: List the previous revisions of a memo
+//! - **diff**: Compare two versions of a memo as a unified diff
+//! - **restore**: Bring back a previous revision
+//!
 //! ### Discovery & Search
 //! - **list**: Get all memos with metadata previews
 //! - **search**: Full-text search across titles and content
 //! - **get_all_context**: Retrieve all memo content for AI context consumption
+//! - **tags**: List the tags used across memos with their counts
+//! - **graph**: Export the links between memos, issues and files as a Mermaid graph
 //!
 //! ## MCP Integration Patterns
 //!
@@ -47,13 +55,19 @@
 
 pub mod create;
 pub mod delete;
+pub mod diff;
 pub mod get;
 pub mod get_all_context;
+pub mod graph;
+pub mod history;
 pub mod list;
+pub mod restore;
 pub mod search;
+pub mod tags;
 pub mod update;
 
-use crate::mcp::tool_registry::ToolRegistry;
+use crate::mcp::tool_registry::{ToolContext, ToolRegistry};
+use swissarmyhammer::memoranda::{LinkIndex, Memo};
 
 /// Register all memoranda-related tools with the registry
 pub fn register_memoranda_tools(registry: &mut ToolRegistry) {
@@ -64,4 +78,27 @@ pub fn register_memoranda_tools(registry: &mut ToolRegistry) {
     registry.register(update::UpdateMemoTool::new());
     registry.register(delete::DeleteMemoTool::new());
     registry.register(search::SearchMemoTool::new());
+    registry.register(tags::MemoTagsTool::new());
+    registry.register(history::MemoHistoryTool::new());
+    registry.register(diff::MemoDiffTool::new());
+    registry.register(restore::MemoRestoreTool::new());
+    registry.register(graph::MemoGraphTool::new());
+}
+
+/// Build the link index of `memos`, resolving issue links against the issue storage and
+/// file links against the repository root
+///
+/// Issues that cannot be listed are treated as missing, so their links show as broken.
+pub(crate) async fn build_link_index(context: &ToolContext, memos: &[Memo]) -> LinkIndex {
+    let issue_names = match context.issue_storage.read().await.list_issues().await {
+        Ok(issues) => issues.into_iter().map(|issue| issue.name).collect(),
+        Err(e) => {
+            tracing::warn!("Failed to list issues for memo links: {}", e);
+            Vec::new()
+        }
+    };
+    let root = swissarmyhammer::directory_utils::find_git_repository_root()
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default();
+    LinkIndex::build(memos, issue_names, root)
 }
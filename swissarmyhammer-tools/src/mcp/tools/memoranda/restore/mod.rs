@@ -0,0 +1,150 @@
+//! Memo restore tool for MCP operations
+//!
+//! This module provides the MemoRestoreTool for bringing back a previous revision of a memo
+//! through the MCP protocol.
+
+use crate::mcp::memo_types::MemoRestoreRequest;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+
+/// Tool for restoring a previous revision of a memo
+#[derive(Default)]
+pub struct MemoRestoreTool;
+
+impl MemoRestoreTool {
+    /// Creates a new instance of the MemoRestoreTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for MemoRestoreTool {
+    fn name(&self) -> &'static str {
+        "memo_restore"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "restore")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Identifier of the memo"
+                },
+                "revision": {
+                    "type": "integer",
+                    "description": "Revision to restore, as listed by memo_history",
+                    "minimum": 1
+                }
+            },
+            "required": ["id", "revision"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: MemoRestoreRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        tracing::debug!(
+            "Restoring revision {} of memo with ID: {}",
+            request.revision,
+            request.id
+        );
+
+        let memo_id = match swissarmyhammer::memoranda::MemoId::from_string(request.id.clone()) {
+            Ok(id) => id,
+            Err(_) => {
+                return Err(McpError::invalid_params(
+                    format!("Invalid memo ID format: {}", request.id),
+                    None,
+                ))
+            }
+        };
+
+        let memo_storage = context.memo_storage.write().await;
+        match memo_storage.restore_memo(&memo_id, request.revision).await {
+            Ok(memo) => {
+                tracing::info!("Restored revision {} of memo {}", request.revision, memo.id);
+                Ok(BaseToolImpl::create_success_response(format!(
+                    "Restored revision {} of memo {}. The replaced version was kept as a new revision.\n\nTitle: {}\nUpdated: {}\n\nContent:\n{}",
+                    request.revision,
+                    memo.id,
+                    memo.title,
+                    crate::mcp::shared_utils::McpFormatter::format_timestamp(memo.updated_at),
+                    memo.content
+                )))
+            }
+            Err(e) => Err(crate::mcp::shared_utils::McpErrorHandler::handle_error(
+                e,
+                "restore memo",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+    use swissarmyhammer::memoranda::MarkdownMemoStorage;
+
+    #[test]
+    fn test_memo_restore_tool_new() {
+        let tool = MemoRestoreTool::new();
+        assert_eq!(tool.name(), "memo_restore");
+        assert!(!tool.description().is_empty());
+        assert_eq!(
+            tool.schema()["required"],
+            serde_json::json!(["id", "revision"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memo_restore_tool_restores_revision() {
+        let context = create_test_context().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        *context.memo_storage.write().await =
+            Box::new(MarkdownMemoStorage::new(temp_dir.path().join("memos")));
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .create_memo("Notes".to_string(), "careful notes".to_string())
+            .await
+            .unwrap();
+        memo_storage
+            .update_memo(&memo.id, "clobbered".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage);
+
+        let tool = MemoRestoreTool::new();
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!("Notes"));
+        arguments.insert("revision".to_string(), serde_json::json!(1));
+        let result = tool.execute(arguments, &context).await.unwrap();
+        assert!(result.content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .starts_with("Restored revision 1 of memo Notes"));
+
+        let memo_storage = context.memo_storage.read().await;
+        assert_eq!(
+            memo_storage.get_memo(&memo.id).await.unwrap().content,
+            "careful notes"
+        );
+        let history = memo_storage.memo_history(&memo.id).await.unwrap();
+        assert_eq!(history.last().unwrap().content, "clobbered");
+    }
+}
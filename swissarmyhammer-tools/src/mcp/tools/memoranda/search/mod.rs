@@ -1,12 +1,20 @@
 //! Memo search tool for MCP operations
 //!
 //! This module provides the SearchMemoTool for searching memos by query string through the MCP protocol.
+//! Besides keyword search it supports semantic and hybrid search over memo embeddings stored in the
+//! semantic search database.
 
 use crate::mcp::memo_types::SearchMemosRequest;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use std::collections::HashMap;
+use swissarmyhammer::memoranda::{
+    reciprocal_rank_fusion, Memo, MemoEmbeddingIndex, MemoFilter, MemoId, MemoSearchMode,
+    MemoStorage, SearchOptions,
+};
+use swissarmyhammer::search::SemanticConfig;
 
 /// Tool for searching memos by query string
 #[derive(Default)]
@@ -16,10 +24,55 @@ impl SearchMemoTool {
     /// Preview length for memo search operations (characters)
     const MEMO_SEARCH_PREVIEW_LENGTH: usize = 200;
 
+    /// Default result limit for semantic and hybrid search
+    const DEFAULT_RANKED_LIMIT: usize = 10;
+
     /// Creates a new instance of the SearchMemoTool
     pub fn new() -> Self {
         Self
     }
+
+    /// Rank memos by embedding similarity, fused with keyword relevance in hybrid mode
+    ///
+    /// Memo embeddings are refreshed first, so memos created, updated or deleted since
+    /// the last search are re-embedded or dropped before ranking.
+    async fn ranked_search(
+        memo_storage: &dyn MemoStorage,
+        query: &str,
+        mode: MemoSearchMode,
+        filter: &MemoFilter,
+        limit: usize,
+    ) -> swissarmyhammer::Result<Vec<Memo>> {
+        let all_memos = memo_storage.list_memos().await?;
+        let index = MemoEmbeddingIndex::new(SemanticConfig::default()).await?;
+        index.refresh(&all_memos).await?;
+
+        let candidates = filter.apply(all_memos);
+        let semantic = index.search(query, &candidates, limit).await?;
+        if mode == MemoSearchMode::Semantic {
+            return Ok(semantic.into_iter().map(|(memo, _)| memo).collect());
+        }
+
+        let keyword: Vec<MemoId> = memo_storage
+            .search_memos_advanced(query, &SearchOptions::default())
+            .await?
+            .into_iter()
+            .filter(|result| filter.matches(&result.memo))
+            .take(limit)
+            .map(|result| result.memo.id)
+            .collect();
+        let semantic: Vec<MemoId> = semantic.into_iter().map(|(memo, _)| memo.id).collect();
+
+        let mut by_id: HashMap<_, _> = candidates
+            .into_iter()
+            .map(|memo| (memo.id.clone(), memo))
+            .collect();
+        Ok(reciprocal_rank_fusion(&[keyword, semantic])
+            .into_iter()
+            .filter_map(|(id, _)| by_id.remove(&id))
+            .take(limit)
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -40,6 +93,26 @@ impl McpTool for SearchMemoTool {
                 "query": {
                     "type": "string",
                     "description": "Search query string to match against memo titles and content"
+                },
+                "tag": {
+                    "type": "string",
+                    "description": "Only search memos with this tag"
+                },
+                "folder": {
+                    "type": "string",
+                    "description": "Only search memos in this folder or its subfolders"
+                },
+                "mode": {
+                    "type": "string",
+                    "enum": ["keyword", "semantic", "hybrid"],
+                    "description": "Ranking mode: keyword matching (default), embedding similarity, or both fused",
+                    "default": "keyword"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of results for semantic and hybrid search",
+                    "default": 10,
+                    "minimum": 1
                 }
             },
             "required": ["query"]
@@ -61,14 +134,40 @@ impl McpTool for SearchMemoTool {
                 crate::mcp::shared_utils::McpErrorHandler::handle_error(e, "validate search query")
             })?;
 
+        let mode = match request.mode.as_deref() {
+            Some(mode) => mode
+                .parse::<MemoSearchMode>()
+                .map_err(|e| McpError::invalid_params(e.to_string(), None))?,
+            None => MemoSearchMode::default(),
+        };
+        let limit = request.limit.unwrap_or(Self::DEFAULT_RANKED_LIMIT);
+
+        let filter = MemoFilter::new(request.tag.as_deref(), request.folder.as_deref());
         let memo_storage = context.memo_storage.read().await;
-        match memo_storage.search_memos(&request.query).await {
+        let results = match mode {
+            MemoSearchMode::Keyword => memo_storage
+                .search_memos(&request.query)
+                .await
+                .map(|memos| filter.apply(memos)),
+            MemoSearchMode::Semantic | MemoSearchMode::Hybrid => {
+                Self::ranked_search(memo_storage.as_ref(), &request.query, mode, &filter, limit)
+                    .await
+            }
+        };
+        let mode_suffix = match mode {
+            MemoSearchMode::Keyword => String::new(),
+            _ => format!(" ({mode} search)"),
+        };
+
+        match results {
             Ok(memos) => {
                 tracing::info!("Search returned {} memos", memos.len());
                 if memos.is_empty() {
                     Ok(BaseToolImpl::create_success_response(format!(
-                        "No memos found matching query: '{}'",
-                        request.query
+                        "No memos{} found matching query: '{}'{}",
+                        filter.describe(),
+                        request.query,
+                        mode_suffix
                     )))
                 } else {
                     let memo_list = memos
@@ -83,10 +182,12 @@ impl McpTool for SearchMemoTool {
                         .join("\n\n");
 
                     Ok(BaseToolImpl::create_success_response(format!(
-                        "Found {} memo{} matching '{}':\n\n{}",
+                        "Found {} memo{}{} matching '{}'{}:\n\n{}",
                         memos.len(),
                         if memos.len() == 1 { "" } else { "s" },
+                        filter.describe(),
                         request.query,
+                        mode_suffix,
                         memo_list
                     )))
                 }
@@ -118,6 +219,10 @@ mod tests {
 
         assert_eq!(schema["type"], "object");
         assert!(schema["properties"]["query"].is_object());
+        assert_eq!(
+            schema["properties"]["mode"]["enum"],
+            serde_json::json!(["keyword", "semantic", "hybrid"])
+        );
         assert_eq!(schema["required"], serde_json::json!(["query"]));
     }
 
@@ -132,6 +237,8 @@ mod tests {
             content: "This is a long piece of content that should be truncated in the preview to show only the first part".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            tags: Vec::new(),
+            folder: None,
         };
 
         let preview = crate::mcp::shared_utils::McpFormatter::format_memo_preview(&memo, 50);
@@ -212,6 +319,19 @@ mod tests {
         assert!(result.is_err()); // Should fail due to validation
     }
 
+    #[tokio::test]
+    async fn test_search_memo_tool_execute_invalid_mode() {
+        let tool = SearchMemoTool::new();
+        let context = create_test_context().await;
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("query".to_string(), serde_json::json!("content"));
+        arguments.insert("mode".to_string(), serde_json::json!("fuzzy"));
+
+        let error = tool.execute(arguments, &context).await.unwrap_err();
+        assert!(error.message.contains("Invalid search mode 'fuzzy'"));
+    }
+
     #[tokio::test]
     async fn test_search_memo_tool_execute_missing_required_field() {
         let tool = SearchMemoTool::new();
@@ -0,0 +1,159 @@
+//! Memo tags tool for MCP operations
+//!
+//! This module provides the MemoTagsTool for listing the tags used across memos with their
+//! counts through the MCP protocol.
+
+use crate::mcp::memo_types::MemoTagsRequest;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::{tag_counts, MemoFilter};
+
+/// Tool for listing memo tags with counts
+#[derive(Default)]
+pub struct MemoTagsTool;
+
+impl MemoTagsTool {
+    /// Creates a new instance of the MemoTagsTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for MemoTagsTool {
+    fn name(&self) -> &'static str {
+        "memo_tags"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "tags")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "folder": {
+                    "type": "string",
+                    "description": "Only count tags of memos in this folder or its subfolders"
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: MemoTagsRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let filter = MemoFilter::new(None, request.folder.as_deref());
+
+        tracing::debug!("Listing memo tags{}", filter.describe());
+
+        let memo_storage = context.memo_storage.read().await;
+        match memo_storage.list_memos().await {
+            Ok(memos) => {
+                let tags = tag_counts(&filter.apply(memos));
+                tracing::info!("Found {} memo tags", tags.len());
+                if tags.is_empty() {
+                    return Ok(BaseToolImpl::create_success_response(format!(
+                        "No memo tags found{}",
+                        filter.describe()
+                    )));
+                }
+
+                let tag_list = tags
+                    .iter()
+                    .map(|(tag, count)| format!("• {tag} ({count})"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let summary = crate::mcp::shared_utils::McpFormatter::format_list_summary(
+                    "tag",
+                    tags.len(),
+                    tags.len(),
+                );
+                Ok(BaseToolImpl::create_success_response(format!(
+                    "{summary}{}:\n\n{tag_list}",
+                    filter.describe()
+                )))
+            }
+            Err(e) => Err(crate::mcp::shared_utils::McpErrorHandler::handle_error(
+                e,
+                "list memo tags",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+    use swissarmyhammer::memoranda::{MarkdownMemoStorage, MemoMetadata};
+
+    #[test]
+    fn test_memo_tags_tool_new() {
+        let tool = MemoTagsTool::new();
+        assert_eq!(tool.name(), "memo_tags");
+        assert!(!tool.description().is_empty());
+        assert!(tool.schema()["properties"]["folder"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_memo_tags_tool_counts_tags() {
+        let context = create_test_context().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        *context.memo_storage.write().await =
+            Box::new(MarkdownMemoStorage::new(temp_dir.path().join("memos")));
+
+        let tool = MemoTagsTool::new();
+        let result = tool
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.content[0].as_text().unwrap().text,
+            "No memo tags found"
+        );
+
+        {
+            let storage = context.memo_storage.write().await;
+            for (title, tags, folder) in [
+                ("Login", vec!["auth", "design"], Some("projects")),
+                ("Tokens", vec!["auth"], None),
+            ] {
+                let tags: Vec<String> = tags.into_iter().map(String::from).collect();
+                storage
+                    .create_memo_with_metadata(
+                        title.to_string(),
+                        "Body".to_string(),
+                        MemoMetadata::new(&tags, folder),
+                    )
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let result = tool
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .unwrap();
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.starts_with("Found 2 tags:"));
+        assert!(text.contains("• auth (2)\n• design (1)"));
+
+        let arguments = serde_json::json!({"folder": "projects"});
+        let result = tool
+            .execute(arguments.as_object().unwrap().clone(), &context)
+            .await
+            .unwrap();
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.starts_with("Found 2 tags in folder 'projects':"));
+        assert!(text.contains("• auth (1)"));
+    }
+}
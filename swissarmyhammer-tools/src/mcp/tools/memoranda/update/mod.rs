@@ -1,14 +1,16 @@
 //! Memo update tool for MCP operations
 //!
-//! This module provides the UpdateMemoTool for updating memo content by ID through the MCP protocol.
+//! This module provides the UpdateMemoTool for updating memo content, tags and folder by ID
+//! through the MCP protocol.
 
 use crate::mcp::memo_types::UpdateMemoRequest;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::MemoMetadataUpdate;
 
-/// Tool for updating a memo's content by its ID
+/// Tool for updating a memo's content, tags or folder by its ID
 #[derive(Default)]
 pub struct UpdateMemoTool;
 
@@ -41,9 +43,18 @@ impl McpTool for UpdateMemoTool {
                 "content": {
                     "type": "string",
                     "description": "New markdown content for the memo"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Replacement tags for the memo"
+                },
+                "folder": {
+                    "type": "string",
+                    "description": "New folder path for the memo, empty to move it out of its folder"
                 }
             },
-            "required": ["id", "content"]
+            "required": ["id"]
         })
     }
 
@@ -56,14 +67,27 @@ impl McpTool for UpdateMemoTool {
 
         tracing::debug!("Updating memo with ID: {}", request.id);
 
+        let metadata_update = MemoMetadataUpdate {
+            tags: request.tags,
+            folder: request.folder,
+        };
+        if request.content.is_none() && metadata_update.is_empty() {
+            return Err(McpError::invalid_params(
+                "Nothing to update: provide content, tags or folder".to_string(),
+                None,
+            ));
+        }
+
         // Validate memo content using shared validation
-        crate::mcp::shared_utils::McpValidation::validate_not_empty(
-            &request.content,
-            "memo content",
-        )
-        .map_err(|e| {
-            crate::mcp::shared_utils::McpErrorHandler::handle_error(e, "validate memo content")
-        })?;
+        if let Some(content) = &request.content {
+            crate::mcp::shared_utils::McpValidation::validate_not_empty(content, "memo content")
+                .map_err(|e| {
+                    crate::mcp::shared_utils::McpErrorHandler::handle_error(
+                        e,
+                        "validate memo content",
+                    )
+                })?;
+        }
 
         let memo_id = match swissarmyhammer::memoranda::MemoId::from_string(request.id.clone()) {
             Ok(id) => id,
@@ -76,13 +100,29 @@ impl McpTool for UpdateMemoTool {
         };
 
         let memo_storage = context.memo_storage.write().await;
-        match memo_storage.update_memo(&memo_id, request.content).await {
+        let result = match request.content {
+            Some(content) => memo_storage.update_memo(&memo_id, content).await,
+            None => memo_storage.get_memo(&memo_id).await,
+        };
+        let result = match result {
+            Ok(_) if !metadata_update.is_empty() => {
+                memo_storage
+                    .update_memo_metadata(&memo_id, &metadata_update)
+                    .await
+            }
+            result => result,
+        };
+        match result {
             Ok(memo) => {
                 tracing::info!("Updated memo {}", memo.id);
+                let metadata = crate::mcp::shared_utils::McpFormatter::format_memo_metadata(&memo)
+                    .map(|line| format!("\n{line}"))
+                    .unwrap_or_default();
                 Ok(BaseToolImpl::create_success_response(format!(
-                    "Successfully updated memo:\n\nID: {}\nTitle: {}\nUpdated: {}\n\nContent:\n{}",
+                    "Successfully updated memo:\n\nID: {}\nTitle: {}{}\nUpdated: {}\n\nContent:\n{}",
                     memo.id,
                     memo.title,
+                    metadata,
                     crate::mcp::shared_utils::McpFormatter::format_timestamp(memo.updated_at),
                     memo.content
                 )))
@@ -115,7 +155,9 @@ mod tests {
         assert_eq!(schema["type"], "object");
         assert!(schema["properties"]["id"].is_object());
         assert!(schema["properties"]["content"].is_object());
-        assert_eq!(schema["required"], serde_json::json!(["id", "content"]));
+        assert!(schema["properties"]["tags"].is_object());
+        assert!(schema["properties"]["folder"].is_object());
+        assert_eq!(schema["required"], serde_json::json!(["id"]));
     }
 
     #[tokio::test]
@@ -149,6 +191,40 @@ mod tests {
         assert!(!call_result.content.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_update_memo_tool_execute_tags_only() {
+        let tool = UpdateMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo = context
+            .memo_storage
+            .write()
+            .await
+            .create_memo("Retag Memo".to_string(), "Kept content".to_string())
+            .await
+            .unwrap();
+
+        let arguments = serde_json::json!({
+            "id": memo.id.to_string(),
+            "tags": ["auth"],
+            "folder": "projects"
+        });
+        tool.execute(arguments.as_object().unwrap().clone(), &context)
+            .await
+            .unwrap();
+
+        let updated = context
+            .memo_storage
+            .read()
+            .await
+            .get_memo(&memo.id)
+            .await
+            .unwrap();
+        assert_eq!(updated.tags, vec!["auth"]);
+        assert_eq!(updated.folder.as_deref(), Some("projects"));
+        assert_eq!(updated.content, "Kept content");
+    }
+
     #[tokio::test]
     async fn test_update_memo_tool_execute_empty_content() {
         let tool = UpdateMemoTool::new();
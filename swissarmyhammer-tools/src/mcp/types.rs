@@ -48,6 +48,42 @@ pub struct CreateIssueRequest {
     pub name: Option<swissarmyhammer::issues::IssueName>,
     /// Markdown content of the issue
     pub content: String,
+    /// Priority: critical, high, medium or low
+    #[serde(default)]
+    pub priority: Option<swissarmyhammer::issues::IssuePriority>,
+    /// Labels for the issue
+    #[serde(default)]
+    pub labels: Option<Vec<String>>,
+    /// Effort estimate, e.g. `3`, `4h` or `2d`
+    #[serde(default)]
+    pub estimate: Option<String>,
+    /// Who will work on the issue
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Who created the issue
+    #[serde(default)]
+    pub created_by: Option<String>,
+    /// Names of issues that must be completed first
+    #[serde(default)]
+    pub depends_on: Option<Vec<String>>,
+    /// Name of the epic the issue belongs to
+    #[serde(default)]
+    pub parent: Option<String>,
+}
+
+impl CreateIssueRequest {
+    /// Frontmatter fields set by this request
+    pub fn metadata_update(&self) -> swissarmyhammer::issues::IssueMetadataUpdate {
+        swissarmyhammer::issues::IssueMetadataUpdate {
+            priority: self.priority,
+            labels: self.labels.clone(),
+            estimate: self.estimate.clone(),
+            assignee: self.assignee.clone(),
+            created_by: self.created_by.clone(),
+            depends_on: self.depends_on.clone(),
+            parent: self.parent.clone(),
+        }
+    }
 }
 
 /// Request to mark an issue as complete
@@ -55,12 +91,63 @@ pub struct CreateIssueRequest {
 pub struct MarkCompleteRequest {
     /// Issue name to mark as complete
     pub name: swissarmyhammer::issues::IssueName,
+    /// Complete even when acceptance criteria are still open (default: false)
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Request to tick or untick an acceptance criterion of an issue
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CheckIssueRequest {
+    /// Issue name
+    pub name: swissarmyhammer::issues::IssueName,
+    /// Position of the criterion, starting at 1
+    pub index: Option<usize>,
+    /// Text contained in exactly one criterion
+    pub text: Option<String>,
+    /// Whether to tick (default) or untick the criterion
+    #[serde(default = "default_checked")]
+    pub checked: bool,
+}
+
+fn default_checked() -> bool {
+    true
+}
+
+/// Request to comment on an issue
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CommentIssueRequest {
+    /// Issue name
+    pub name: swissarmyhammer::issues::IssueName,
+    /// Comment text
+    pub message: String,
+    /// Who wrote the comment
+    pub author: Option<String>,
+}
+
+/// Request to claim or release the lease on an issue
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ClaimIssueRequest {
+    /// Issue name, `next` or `current`
+    pub name: swissarmyhammer::issues::IssueName,
+    /// Who takes the lease, defaults to `SAH_AGENT_ID` or the host and process
+    pub owner: Option<String>,
+    /// How long the lease lasts without another claim
+    pub ttl_seconds: Option<u64>,
+    /// Give up the lease instead of taking it
+    #[serde(default)]
+    pub release: bool,
+    /// Release a lease held by another owner
+    #[serde(default)]
+    pub force: bool,
 }
 
 /// Request to check if all issues are complete
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct AllCompleteRequest {
-    // No parameters needed
+    /// Only check the sub-issues of this epic
+    #[serde(default)]
+    pub epic: Option<String>,
 }
 
 /// Request to update an issue
@@ -68,11 +155,48 @@ pub struct AllCompleteRequest {
 pub struct UpdateIssueRequest {
     /// Issue name to update
     pub name: swissarmyhammer::issues::IssueName,
-    /// New markdown content for the issue
-    pub content: String,
+    /// New markdown content for the issue, leaving it out keeps the current content
+    #[serde(default)]
+    pub content: Option<String>,
     /// If true, append to existing content instead of replacing
     #[serde(default)]
     pub append: bool,
+    /// New priority: critical, high, medium or low
+    #[serde(default)]
+    pub priority: Option<swissarmyhammer::issues::IssuePriority>,
+    /// Replacement labels, an empty list clears them
+    #[serde(default)]
+    pub labels: Option<Vec<String>>,
+    /// New estimate, an empty string clears it
+    #[serde(default)]
+    pub estimate: Option<String>,
+    /// New assignee, an empty string clears it
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Replacement dependencies, an empty list clears them
+    #[serde(default)]
+    pub depends_on: Option<Vec<String>>,
+    /// New epic, an empty string detaches the issue from its epic
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// New lifecycle status, recorded as a timestamped transition
+    #[serde(default)]
+    pub status: Option<swissarmyhammer::issues::IssueStatus>,
+}
+
+impl UpdateIssueRequest {
+    /// Frontmatter fields changed by this request
+    pub fn metadata_update(&self) -> swissarmyhammer::issues::IssueMetadataUpdate {
+        swissarmyhammer::issues::IssueMetadataUpdate {
+            priority: self.priority,
+            labels: self.labels.clone(),
+            estimate: self.estimate.clone(),
+            assignee: self.assignee.clone(),
+            created_by: None,
+            depends_on: self.depends_on.clone(),
+            parent: self.parent.clone(),
+        }
+    }
 }
 
 /// Request to work on an issue
@@ -80,6 +204,8 @@ pub struct UpdateIssueRequest {
 pub struct WorkIssueRequest {
     /// Issue name to work on
     pub name: swissarmyhammer::issues::IssueName,
+    /// Who claims the issue, defaults to `SAH_AGENT_ID` or the host and process
+    pub owner: Option<String>,
 }
 
 /// Request to merge an issue
@@ -92,6 +218,33 @@ pub struct MergeIssueRequest {
     pub delete_branch: bool,
 }
 
+/// Request to reopen a completed or archived issue
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ReopenIssueRequest {
+    /// Issue name to reopen
+    pub name: swissarmyhammer::issues::IssueName,
+}
+
+/// Request to archive an issue
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ArchiveIssueRequest {
+    /// Issue name to archive
+    pub name: swissarmyhammer::issues::IssueName,
+}
+
+/// Request to delete an issue
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DeleteIssueRequest {
+    /// Issue name to delete
+    pub name: swissarmyhammer::issues::IssueName,
+    /// Delete even when the issue branch has unmerged work (default: false)
+    #[serde(default)]
+    pub force: bool,
+    /// Also delete the issue branch (default: false)
+    #[serde(default)]
+    pub delete_branch: bool,
+}
+
 // Re-export IssueName for convenience
 pub use swissarmyhammer::issues::IssueName;
 
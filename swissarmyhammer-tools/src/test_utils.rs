@@ -118,3 +118,52 @@ impl TestIssueEnvironment {
         self.temp_dir.path().join(".swissarmyhammer")
     }
 }
+
+/// Creates a test context backed by a temporary git repository
+///
+/// The repository has an initial commit on `main`, git operations run in it and
+/// issues are stored in its `.swissarmyhammer/issues` directory. The returned
+/// directory must be kept alive for the duration of the test.
+#[cfg(test)]
+pub async fn create_test_git_context() -> (TempDir, ToolContext) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let path = temp_dir.path();
+
+    let mut options = git2::RepositoryInitOptions::new();
+    options.initial_head("main");
+    git2::Repository::init_opts(path, &options).expect("Failed to initialize git repository");
+    commit_test_file(path, "README.md", "# Test Repository");
+
+    let context = create_test_context().await;
+    *context.issue_storage.write().await = Box::new(
+        FileSystemIssueStorage::new(path.join(".swissarmyhammer").join("issues")).unwrap(),
+    );
+    *context.git_ops.lock().await = Some(GitOperations::with_work_dir(path.to_path_buf()).unwrap());
+
+    (temp_dir, context)
+}
+
+/// Writes a file and commits it on the checked out branch of a test repository
+#[cfg(test)]
+pub fn commit_test_file(repo_path: &Path, file_name: &str, content: &str) {
+    let repo = git2::Repository::open(repo_path).expect("Failed to open git repository");
+    std::fs::write(repo_path.join(file_name), content).expect("Failed to write file");
+
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(file_name)).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &format!("Add {file_name}"),
+        &tree,
+        &parents,
+    )
+    .expect("Failed to commit");
+}
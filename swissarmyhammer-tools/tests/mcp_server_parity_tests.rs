@@ -85,6 +85,17 @@ fn get_http_static_tools() -> Vec<String> {
         "issue_mark_complete",
         "issue_update",
         "issue_all_complete",
+        "issue_graph",
+        "issue_reopen",
+        "issue_archive",
+        "issue_delete",
+        "issue_search",
+        "issue_check",
+        "issue_comment",
+        "issue_export",
+        "issue_import",
+        "issue_claim",
+        "issue_migrate",
         "memo_create",
         "memo_list",
         "memo_get",
@@ -92,6 +103,11 @@ fn get_http_static_tools() -> Vec<String> {
         "memo_delete",
         "memo_search",
         "memo_get_all_context",
+        "memo_tags",
+        "memo_history",
+        "memo_diff",
+        "memo_restore",
+        "memo_graph",
         "search_index",
         "search_query",
         "web_search",
@@ -418,6 +418,8 @@ mod tests {
                 .to_string(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            tags: Vec::new(),
+            folder: None,
         };
 
         let stats = analyze_memo_content(&memo).await;
@@ -109,6 +109,43 @@ pub fn find_git_repository_root_from(start_dir: &Path) -> Option<PathBuf> {
     None
 }
 
+/// Find the Git directory shared by all worktrees of the repository containing a directory
+///
+/// Linked worktrees each have their own checkout and `.git` file, but they share
+/// one common Git directory. State kept there is seen from every worktree and is
+/// never part of a checkout, so it cannot be staged.
+///
+/// # Arguments
+///
+/// * `start_dir` - The directory to start searching from
+///
+/// # Returns
+///
+/// * `Option<(PathBuf, PathBuf)>` - The common Git directory and the working tree root
+///   of the worktree containing `start_dir`, None outside a non-bare Git repository
+pub fn find_git_common_directory_from(start_dir: &Path) -> Option<(PathBuf, PathBuf)> {
+    let repo = git2::Repository::discover(start_dir).ok()?;
+    let workdir = repo.workdir()?.to_path_buf();
+    Some((repo.commondir().to_path_buf(), workdir))
+}
+
+/// Create a directory whose contents Git never stages
+///
+/// A `.gitignore` matching everything, itself included, is written into the
+/// directory, so files kept there stay out of commits wherever it lives.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to create
+pub fn create_git_ignored_directory(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        std::fs::write(gitignore, "*\n")?;
+    }
+    Ok(())
+}
+
 /// Find the SwissArmyHammer directory for the current Git repository
 ///
 /// Returns None if not in a Git repository or if no .swissarmyhammer directory exists.
@@ -696,4 +733,27 @@ mod tests {
             other => panic!("Expected NotInGitRepository error, got {:?}", other),
         }
     }
+
+    #[test]
+    fn test_create_git_ignored_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let dir = temp_dir.path().join("state");
+
+        create_git_ignored_directory(&dir).unwrap();
+        fs::write(dir.join("lease.json"), "{}").unwrap();
+
+        assert!(repo.is_path_ignored("state/lease.json").unwrap());
+        assert!(repo.is_path_ignored("state/.gitignore").unwrap());
+
+        let (common_dir, workdir) = find_git_common_directory_from(&dir).unwrap();
+        assert_eq!(
+            common_dir.canonicalize().unwrap(),
+            repo.path().canonicalize().unwrap()
+        );
+        assert_eq!(
+            workdir.canonicalize().unwrap(),
+            temp_dir.path().canonicalize().unwrap()
+        );
+    }
 }
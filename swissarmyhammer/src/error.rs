@@ -57,6 +57,26 @@ pub enum SwissArmyHammerError {
     #[error("Issue already exists: {0}")]
     IssueAlreadyExists(u32),
 
+    /// Issue dependencies would form a cycle
+    #[error("Issue dependency cycle: {0}")]
+    IssueDependencyCycle(String),
+
+    /// Issue parents would form a cycle
+    #[error("Issue parent cycle: {0}")]
+    IssueParentCycle(String),
+
+    /// Issue is waiting on unfinished dependencies
+    #[error("{0}")]
+    IssueBlocked(String),
+
+    /// Issue is claimed by another agent
+    #[error("{0}")]
+    IssueLeased(String),
+
+    /// Open issues still depend on the issue
+    #[error("{0}")]
+    IssueHasDependents(String),
+
     /// Git operation failed
     #[error("Git operation '{operation}' failed: {details}")]
     GitOperationFailed {
@@ -171,6 +191,15 @@ pub enum SwissArmyHammerError {
     #[error("Memo validation failed: {0}")]
     MemoValidationFailed(String),
 
+    /// A memo revision that was never recorded or has been pruned
+    #[error("Memo {id} has no revision {revision}")]
+    MemoRevisionNotFound {
+        /// The memo identifier
+        id: String,
+        /// The requested revision number
+        revision: u32,
+    },
+
     /// Semantic search error
     #[error("Semantic search error: {0}")]
     Semantic(#[from] crate::search::SemanticError),
@@ -32,11 +32,13 @@ use std::path::{Path, PathBuf};
 /// Maximum file size to load (10MB)
 const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
-/// Source of a file (builtin, user, local, or dynamic)
+/// Source of a file (builtin, package, user, local, or dynamic)
 #[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub enum FileSource {
     /// Builtin files embedded in the binary
     Builtin,
+    /// Files from an installed package, by package name
+    Package(String),
     /// User files from ~/.swissarmyhammer
     User,
     /// Local files from .swissarmyhammer directories
@@ -49,6 +51,7 @@ impl std::fmt::Display for FileSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             FileSource::Builtin => write!(f, "builtin"),
+            FileSource::Package(name) => write!(f, "package:{name}"),
             FileSource::User => write!(f, "user"),
             FileSource::Local => write!(f, "local"),
             FileSource::Dynamic => write!(f, "dynamic"),
@@ -56,6 +59,29 @@ impl std::fmt::Display for FileSource {
     }
 }
 
+impl FileSource {
+    /// Whether this source satisfies a source filter
+    ///
+    /// A package filter with an empty name matches files from any package.
+    pub fn matches_filter(&self, filter: &FileSource) -> bool {
+        match (self, filter) {
+            (FileSource::Package(_), FileSource::Package(name)) if name.is_empty() => true,
+            _ => self == filter,
+        }
+    }
+}
+
+/// A file that replaced a same-named file from another source
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct FileOverride {
+    /// The logical name of the file
+    pub name: String,
+    /// The source whose file was replaced
+    pub overridden: FileSource,
+    /// The source that now provides the file
+    pub by: FileSource,
+}
+
 /// Represents a file with its metadata
 #[derive(Debug, Clone)]
 pub struct FileEntry {
@@ -205,9 +231,13 @@ impl FileEntry {
 ///
 /// Files are loaded with the following precedence (later sources override earlier):
 /// 1. Builtin files (embedded in the binary)
-/// 2. User files (from ~/.swissarmyhammer)
-/// 3. Local files (from .swissarmyhammer directories in parent paths)
-/// 4. Dynamic files (programmatically added)
+/// 2. Package files (user packages, then local packages)
+/// 3. User files (from ~/.swissarmyhammer)
+/// 4. Local files (from .swissarmyhammer directories in parent paths)
+/// 5. Dynamic files (programmatically added)
+///
+/// Every replacement of a file by one from a different source is recorded
+/// and available through [`VirtualFileSystem::overrides`].
 pub struct VirtualFileSystem {
     /// The subdirectory to look for (e.g., "prompts" or "workflows")
     pub subdirectory: String,
@@ -215,6 +245,8 @@ pub struct VirtualFileSystem {
     pub files: HashMap<String, FileEntry>,
     /// Track sources for each file
     pub file_sources: HashMap<String, FileSource>,
+    /// Files that replaced a file from another source, in load order
+    pub overrides: Vec<FileOverride>,
 }
 
 impl VirtualFileSystem {
@@ -224,6 +256,7 @@ impl VirtualFileSystem {
             subdirectory: subdirectory.into(),
             files: HashMap::new(),
             file_sources: HashMap::new(),
+            overrides: Vec::new(),
         }
     }
 
@@ -241,6 +274,22 @@ impl VirtualFileSystem {
 
     /// Add a file entry
     pub fn add_file(&mut self, entry: FileEntry) {
+        if let Some(previous) = self.file_sources.get(&entry.name) {
+            if *previous != entry.source {
+                tracing::debug!(
+                    "{} '{}' from {} overrides {}",
+                    self.subdirectory,
+                    entry.name,
+                    entry.source,
+                    previous
+                );
+                self.overrides.push(FileOverride {
+                    name: entry.name.clone(),
+                    overridden: previous.clone(),
+                    by: entry.source.clone(),
+                });
+            }
+        }
         self.file_sources
             .insert(entry.name.clone(), entry.source.clone());
         self.files.insert(entry.name.clone(), entry);
@@ -261,6 +310,11 @@ impl VirtualFileSystem {
         self.files.values().collect()
     }
 
+    /// Files that replaced a same-named file from another source
+    pub fn overrides(&self) -> &[FileOverride] {
+        &self.overrides
+    }
+
     /// Load files from a directory
     pub fn load_directory(&mut self, base_path: &Path, source: FileSource) -> Result<()> {
         let target_dir = base_path.join(&self.subdirectory);
@@ -313,50 +367,82 @@ impl VirtualFileSystem {
         // Load builtin files (least precedence)
         // Note: Builtin files are typically added via add_builtin method
 
+        let user_dir = Self::user_directory();
+        let local_dir = Self::local_directory();
+
+        // Load installed packages, user packages first so local ones win
+        for root in user_dir.iter().chain(local_dir.iter()) {
+            self.load_packages(root)?;
+        }
+
         // Load user files from home directory
-        if let Ok(home_str) = std::env::var("HOME") {
-            let home = PathBuf::from(home_str);
-            let user_dir = home.join(".swissarmyhammer");
-            self.load_directory(&user_dir, FileSource::User)?;
-        } else if let Some(home) = dirs::home_dir() {
-            let user_dir = home.join(".swissarmyhammer");
-            self.load_directory(&user_dir, FileSource::User)?;
+        if let Some(user_dir) = &user_dir {
+            self.load_directory(user_dir, FileSource::User)?;
         }
 
-        // Load local files by walking up the directory tree
-        self.load_local_files()?;
+        // Load local files from the repository .swissarmyhammer directory
+        if let Some(local_dir) = &local_dir {
+            self.load_directory(local_dir, FileSource::Local)?;
+        }
 
         Ok(())
     }
 
-    /// Load local files from the Git repository .swissarmyhammer directory
-    fn load_local_files(&mut self) -> Result<()> {
+    /// Load files from every active package installed under a .swissarmyhammer root
+    pub fn load_packages(&mut self, root: &Path) -> Result<()> {
+        for (name, package_dir) in crate::package::active_package_dirs(root) {
+            self.load_directory(&package_dir, FileSource::Package(name))?;
+        }
+        Ok(())
+    }
+
+    /// The user .swissarmyhammer directory
+    fn user_directory() -> Option<PathBuf> {
+        if let Ok(home_str) = std::env::var("HOME") {
+            Some(PathBuf::from(home_str).join(".swissarmyhammer"))
+        } else {
+            dirs::home_dir().map(|home| home.join(".swissarmyhammer"))
+        }
+    }
+
+    /// The local .swissarmyhammer directory of the Git repository
+    fn local_directory() -> Option<PathBuf> {
         // Primary approach: Find the Git repository .swissarmyhammer directory
         if let Some(swissarmyhammer_dir) = find_swissarmyhammer_directory() {
-            self.load_directory(&swissarmyhammer_dir, FileSource::Local)?;
-            return Ok(());
+            return Some(swissarmyhammer_dir);
         }
 
         // Fallback approach: If Git-centric approach fails (e.g., in tests),
         // look for .swissarmyhammer in the current directory
-        if let Ok(current_dir) = std::env::current_dir() {
-            let current_swissarmyhammer = current_dir.join(".swissarmyhammer");
-            if current_swissarmyhammer.exists() && current_swissarmyhammer.is_dir() {
-                tracing::debug!(
-                    "Using fallback directory detection: {}",
-                    current_swissarmyhammer.display()
-                );
-                self.load_directory(&current_swissarmyhammer, FileSource::Local)?;
-            }
+        let current_swissarmyhammer = std::env::current_dir().ok()?.join(".swissarmyhammer");
+        if current_swissarmyhammer.is_dir() {
+            tracing::debug!(
+                "Using fallback directory detection: {}",
+                current_swissarmyhammer.display()
+            );
+            return Some(current_swissarmyhammer);
         }
 
-        Ok(())
+        None
     }
 
     /// Get all directories that are being monitored
     pub fn get_directories(&self) -> Result<Vec<PathBuf>> {
         let mut directories = Vec::new();
 
+        // Installed package directories
+        for root in Self::user_directory()
+            .into_iter()
+            .chain(Self::local_directory())
+        {
+            for (_, package_dir) in crate::package::active_package_dirs(&root) {
+                let subdir = package_dir.join(&self.subdirectory);
+                if subdir.is_dir() {
+                    directories.push(subdir);
+                }
+            }
+        }
+
         // User directory
         if let Ok(home_str) = std::env::var("HOME") {
             let home = PathBuf::from(home_str);
@@ -418,6 +504,10 @@ mod tests {
         assert_eq!(FileSource::User.to_string(), "user");
         assert_eq!(FileSource::Local.to_string(), "local");
         assert_eq!(FileSource::Dynamic.to_string(), "dynamic");
+        assert_eq!(
+            FileSource::Package("review-kit".to_string()).to_string(),
+            "package:review-kit"
+        );
     }
 
     #[test]
@@ -548,4 +638,36 @@ mod tests {
         assert_eq!(vfs.get_source("test"), Some(&FileSource::Builtin));
         assert_eq!(vfs.get_source("nonexistent"), None);
     }
+
+    #[test]
+    fn test_virtual_file_system_records_overrides() {
+        let mut vfs = VirtualFileSystem::new("workflows");
+        vfs.add_builtin("review", "builtin content");
+        vfs.add_file(FileEntry::new(
+            "review",
+            PathBuf::from("/repo/.swissarmyhammer/packages/kit/1.0.0/workflows/review.md"),
+            "package content".to_string(),
+            FileSource::Package("kit".to_string()),
+        ));
+        vfs.add_file(FileEntry::new(
+            "review",
+            PathBuf::from("/repo/.swissarmyhammer/workflows/review.md"),
+            "local content".to_string(),
+            FileSource::Local,
+        ));
+
+        assert_eq!(vfs.get("review").unwrap().content, "local content");
+        let overrides = vfs.overrides();
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides[0].overridden, FileSource::Builtin);
+        assert_eq!(
+            overrides[1].overridden,
+            FileSource::Package("kit".to_string())
+        );
+        assert_eq!(overrides[1].by, FileSource::Local);
+        assert!(overrides[1]
+            .overridden
+            .matches_filter(&FileSource::Package(String::new())));
+        assert!(!FileSource::Local.matches_filter(&FileSource::Package(String::new())));
+    }
 }
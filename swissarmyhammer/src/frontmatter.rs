@@ -83,6 +83,21 @@ pub fn parse_frontmatter(content: &str) -> Result<Frontmatter> {
     })
 }
 
+/// Split content into its raw YAML frontmatter and the body after it
+///
+/// Returns `None` when the content does not start with a `---` block.
+pub(crate) fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content.strip_prefix("---\n")?;
+    if let Some(body) = rest.strip_prefix("---\n") {
+        return Some(("", body));
+    }
+    let end = rest.find("\n---\n").map(|i| (i, i + 5)).or_else(|| {
+        rest.strip_suffix("\n---")
+            .map(|yaml| (yaml.len(), rest.len()))
+    })?;
+    Some((&rest[..end.0], &rest[end.1..]))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,238 @@
+//! Per-issue activity log of comments and lifecycle events
+//!
+//! Discussion about an issue is kept out of its markdown. Every issue has a JSON
+//! lines log next to its file that moves with it between directories:
+//!
+//! ```text
+//! issues/
+//! ├── FEATURE_001_login.md
+//! └── FEATURE_001_login.log.jsonl
+//! ```
+//!
+//! Each line is one entry:
+//!
+//! ```json
+//! {"at":"2025-01-06T09:00:00Z","kind":"comment","author":"reviewer","message":"Handle expired tokens"}
+//! ```
+//!
+//! Comments are added explicitly, status changes, branch creation and merges are
+//! recorded automatically.
+
+use crate::error::{Result, SwissArmyHammerError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::warn;
+
+/// Extension of activity log files, replacing the `.md` of the issue file
+pub const ACTIVITY_LOG_EXTENSION: &str = "log.jsonl";
+
+/// What an activity entry records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueActivityKind {
+    /// A note from an agent or a person
+    Comment,
+    /// A change of status
+    Status,
+    /// The issue branch was created or switched to
+    Branch,
+    /// The issue branch was merged
+    Merge,
+    /// The issue was archived
+    Archive,
+}
+
+impl IssueActivityKind {
+    /// All kinds in declaration order
+    pub const ALL: [IssueActivityKind; 5] = [
+        IssueActivityKind::Comment,
+        IssueActivityKind::Status,
+        IssueActivityKind::Branch,
+        IssueActivityKind::Merge,
+        IssueActivityKind::Archive,
+    ];
+
+    /// Name used in log files and parameters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueActivityKind::Comment => "comment",
+            IssueActivityKind::Status => "status",
+            IssueActivityKind::Branch => "branch",
+            IssueActivityKind::Merge => "merge",
+            IssueActivityKind::Archive => "archive",
+        }
+    }
+
+    /// Emoji shown next to the entry
+    pub fn icon(&self) -> &'static str {
+        match self {
+            IssueActivityKind::Comment => "💬",
+            IssueActivityKind::Status => "📌",
+            IssueActivityKind::Branch => "🌿",
+            IssueActivityKind::Merge => "🔀",
+            IssueActivityKind::Archive => "🗄️",
+        }
+    }
+}
+
+impl fmt::Display for IssueActivityKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IssueActivityKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let normalized = s.trim().to_ascii_lowercase();
+        IssueActivityKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == normalized)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid activity kind '{s}': expected one of comment, status, branch, merge, archive"
+                )
+            })
+    }
+}
+
+/// One entry of an issue's activity log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueActivity {
+    /// When the entry was recorded
+    pub at: DateTime<Utc>,
+    /// What the entry records
+    pub kind: IssueActivityKind,
+    /// Who wrote a comment, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Comment text or event description
+    pub message: String,
+}
+
+impl IssueActivity {
+    /// Create an entry recorded now
+    pub fn new(kind: IssueActivityKind, message: impl Into<String>) -> Self {
+        Self {
+            at: Utc::now(),
+            kind,
+            author: None,
+            message: message.into(),
+        }
+    }
+
+    /// Create a comment recorded now
+    pub fn comment(message: impl Into<String>, author: Option<String>) -> Self {
+        Self {
+            author,
+            ..Self::new(IssueActivityKind::Comment, message)
+        }
+    }
+}
+
+impl fmt::Display for IssueActivity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ",
+            self.at.format("%Y-%m-%d %H:%M:%S"),
+            self.kind.icon()
+        )?;
+        if let Some(author) = &self.author {
+            write!(f, "{author}: ")?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// Path of the activity log belonging to an issue file
+pub fn activity_log_path(issue_file: &Path) -> PathBuf {
+    issue_file.with_extension(ACTIVITY_LOG_EXTENSION)
+}
+
+/// Read an activity log in chronological order, empty when the log does not exist
+///
+/// Lines that cannot be parsed are skipped with a warning so a hand edit cannot
+/// hide the rest of the log.
+pub fn read_activity_log(path: &Path) -> Result<Vec<IssueActivity>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path).map_err(SwissArmyHammerError::Io)?;
+    let mut entries: Vec<IssueActivity> = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(number, line)| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                warn!(
+                    "Skipping malformed activity entry {}:{}: {}",
+                    path.display(),
+                    number + 1,
+                    e
+                );
+                None
+            }
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.at);
+    Ok(entries)
+}
+
+/// Append an entry to an activity log, creating the log when needed
+pub fn append_activity_log(path: &Path, entry: &IssueActivity) -> Result<()> {
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(SwissArmyHammerError::Io)?;
+    file.write_all(line.as_bytes())
+        .map_err(SwissArmyHammerError::Io)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_activity_log_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = activity_log_path(&temp_dir.path().join("login.md"));
+        assert_eq!(path.file_name().unwrap(), "login.log.jsonl");
+        assert!(read_activity_log(&path).unwrap().is_empty());
+
+        let mut late = IssueActivity::comment("Handle expired tokens", Some("reviewer".into()));
+        late.at = Utc.timestamp_opt(7200, 0).unwrap();
+        let mut early = IssueActivity::new(IssueActivityKind::Status, "todo → in_progress");
+        early.at = Utc.timestamp_opt(3600, 0).unwrap();
+        append_activity_log(&path, &late).unwrap();
+        append_activity_log(&path, &early).unwrap();
+        fs::write(&path, fs::read_to_string(&path).unwrap() + "not json\n").unwrap();
+
+        let entries = read_activity_log(&path).unwrap();
+        assert_eq!(entries, vec![early, late]);
+        assert_eq!(
+            entries[1].to_string(),
+            "1970-01-01 02:00:00 💬 reviewer: Handle expired tokens"
+        );
+        assert_eq!(
+            "Merge".parse::<IssueActivityKind>(),
+            Ok(IssueActivityKind::Merge)
+        );
+        assert!("review".parse::<IssueActivityKind>().is_err());
+    }
+}
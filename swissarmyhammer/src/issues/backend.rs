@@ -0,0 +1,266 @@
+//! Selecting and switching the issue storage backend
+//!
+//! Issues are markdown files by default. Projects with many issues can keep them
+//! in an embedded DuckDB database instead, chosen in `sah.toml`:
+//!
+//! ```toml
+//! [issues]
+//! backend = "duckdb"
+//! ```
+//!
+//! or with the `SAH_ISSUES_BACKEND` environment variable. Existing issues are moved
+//! between backends with [`migrate_issues`], exposed as `sah issue migrate`.
+
+use super::activity::IssueActivity;
+use super::database::DatabaseIssueStorage;
+use super::filesystem::{FileSystemIssueStorage, Issue, IssueStorage};
+use crate::error::{Result, SwissArmyHammerError};
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tracing::{debug, warn};
+
+/// Configuration key selecting the issue storage backend
+pub const ISSUES_BACKEND_CONFIG_KEY: &str = "issues.backend";
+
+/// Where issues are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IssueStorageBackend {
+    /// Markdown files in the issues directory
+    #[default]
+    Markdown,
+    /// An embedded DuckDB database next to the issues directory
+    Database,
+}
+
+impl IssueStorageBackend {
+    /// All backends in declaration order
+    pub const ALL: [IssueStorageBackend; 2] =
+        [IssueStorageBackend::Markdown, IssueStorageBackend::Database];
+
+    /// Name used in configuration and parameters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueStorageBackend::Markdown => "fs",
+            IssueStorageBackend::Database => "duckdb",
+        }
+    }
+
+    /// The backend selected by `issues.backend`, markdown when it is not set
+    ///
+    /// Configuration that cannot be loaded falls back to markdown with a warning,
+    /// an unknown backend name is an error.
+    pub fn configured() -> Result<Self> {
+        let context = match swissarmyhammer_config::load_configuration() {
+            Ok(context) => context,
+            Err(e) => {
+                warn!("Failed to load configuration, storing issues as markdown: {e}");
+                return Ok(Self::default());
+            }
+        };
+
+        match context.get(ISSUES_BACKEND_CONFIG_KEY) {
+            None => Ok(Self::default()),
+            Some(serde_json::Value::String(name)) => {
+                name.parse().map_err(SwissArmyHammerError::Config)
+            }
+            Some(other) => Err(SwissArmyHammerError::Config(format!(
+                "Invalid {ISSUES_BACKEND_CONFIG_KEY} '{other}': expected \"fs\" or \"duckdb\""
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for IssueStorageBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IssueStorageBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "fs" | "filesystem" | "markdown" => Ok(IssueStorageBackend::Markdown),
+            "duckdb" | "database" | "db" => Ok(IssueStorageBackend::Database),
+            "sqlite" => Err(format!(
+                "Invalid issue storage backend '{s}': the embedded database is DuckDB, use \"duckdb\""
+            )),
+            _ => Err(format!(
+                "Invalid issue storage backend '{s}': expected \"fs\" or \"duckdb\""
+            )),
+        }
+    }
+}
+
+/// Open the issue storage of a backend in an issues directory
+pub fn open_issue_storage(
+    backend: IssueStorageBackend,
+    issues_dir: PathBuf,
+) -> Result<Box<dyn IssueStorage>> {
+    Ok(match backend {
+        IssueStorageBackend::Markdown => Box::new(FileSystemIssueStorage::new(issues_dir)?),
+        IssueStorageBackend::Database => Box::new(DatabaseIssueStorage::new(issues_dir)?),
+    })
+}
+
+/// Where a stored issue lives within its backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IssueLocation {
+    /// Open issues
+    Active,
+    /// Issues with a closed status
+    Completed,
+    /// Issues hidden from listings
+    Archived,
+}
+
+impl IssueLocation {
+    /// Name stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueLocation::Active => "active",
+            IssueLocation::Completed => "completed",
+            IssueLocation::Archived => "archived",
+        }
+    }
+}
+
+impl FromStr for IssueLocation {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "active" => Ok(IssueLocation::Active),
+            "completed" => Ok(IssueLocation::Completed),
+            "archived" => Ok(IssueLocation::Archived),
+            _ => Err(format!("Invalid issue location '{s}'")),
+        }
+    }
+}
+
+/// Everything a backend stores about one issue
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredIssue {
+    /// Name and markdown content, frontmatter included
+    pub issue: Issue,
+    /// Whether the issue is open, completed or archived
+    pub location: IssueLocation,
+    /// When the issue was created
+    pub created_at: DateTime<Utc>,
+    /// The issue's activity log in chronological order
+    pub activity: Vec<IssueActivity>,
+}
+
+/// Copy every issue from one backend to another, returning how many were moved
+///
+/// The target must not hold any issues yet. Unless `keep_source` is set the source
+/// is emptied once every issue has been copied, so a failed copy loses nothing.
+pub async fn migrate_issues(
+    source: &dyn IssueStorage,
+    target: &dyn IssueStorage,
+    keep_source: bool,
+) -> Result<usize> {
+    let existing = target.snapshot_issues().await?.len();
+    if existing > 0 {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Cannot migrate into the {} backend: it already holds {existing} issues",
+            target.backend()
+        )));
+    }
+
+    let issues = source.snapshot_issues().await?;
+    let count = issues.len();
+    target.restore_issues(issues).await?;
+    if !keep_source {
+        source.remove_all_issues().await?;
+    }
+
+    debug!(
+        "Migrated {} issues from {} to {}",
+        count,
+        source.backend(),
+        target.backend()
+    );
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::activity::IssueActivityKind;
+    use crate::issues::status::IssueStatus;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_migrate_between_backends() {
+        assert_eq!("DuckDB".parse(), Ok(IssueStorageBackend::Database));
+        assert_eq!(IssueStorageBackend::Database.to_string(), "duckdb");
+        assert!("sqlite".parse::<IssueStorageBackend>().is_err());
+        assert_eq!("markdown".parse(), Ok(IssueStorageBackend::Markdown));
+        assert!("postgres".parse::<IssueStorageBackend>().is_err());
+
+        let temp_dir = TempDir::new().unwrap();
+        let issues_dir = temp_dir.path().join("issues");
+        let markdown =
+            open_issue_storage(IssueStorageBackend::Markdown, issues_dir.clone()).unwrap();
+        let database = open_issue_storage(IssueStorageBackend::Database, issues_dir).unwrap();
+
+        markdown
+            .create_issue("login".into(), "# Login".into())
+            .await
+            .unwrap();
+        markdown
+            .create_issue("tokens".into(), "# Tokens".into())
+            .await
+            .unwrap();
+        markdown
+            .create_issue("old".into(), "# Old".into())
+            .await
+            .unwrap();
+        markdown
+            .set_issue_status("login", IssueStatus::Done)
+            .await
+            .unwrap();
+        markdown
+            .add_issue_activity("tokens", IssueActivity::comment("Rotate them", None))
+            .await
+            .unwrap();
+        markdown.archive_issue("old").await.unwrap();
+        let before = markdown.snapshot_issues().await.unwrap();
+
+        assert_eq!(
+            migrate_issues(markdown.as_ref(), database.as_ref(), false)
+                .await
+                .unwrap(),
+            3
+        );
+        assert!(markdown.snapshot_issues().await.unwrap().is_empty());
+        assert!(database.get_issue_info("login").await.unwrap().completed);
+        assert!(database.get_issue("old").await.is_err());
+        let activity = database.issue_activity("tokens").await.unwrap();
+        assert_eq!(activity.last().unwrap().kind, IssueActivityKind::Comment);
+
+        // Moving back restores everything but file creation times
+        migrate_issues(database.as_ref(), markdown.as_ref(), false)
+            .await
+            .unwrap();
+        let after = markdown.snapshot_issues().await.unwrap();
+        assert_eq!(after.len(), before.len());
+        for (after, before) in after.iter().zip(&before) {
+            assert_eq!(after.issue, before.issue);
+            assert_eq!(after.location, before.location);
+            assert_eq!(after.activity, before.activity);
+        }
+
+        database
+            .create_issue("new".into(), "# New".into())
+            .await
+            .unwrap();
+        assert!(migrate_issues(markdown.as_ref(), database.as_ref(), true)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,204 @@
+//! Acceptance criteria parsed from issue checklists
+//!
+//! Markdown task list items in an issue body are its acceptance criteria:
+//!
+//! ```markdown
+//! ## Acceptance Criteria
+//! - [x] Users can log in
+//! - [ ] Sessions expire after 30 minutes
+//! ```
+//!
+//! Criteria are numbered from 1 in document order. Items inside fenced code blocks
+//! are ignored.
+
+use regex::Regex;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// A single checklist item of an issue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptanceCriterion {
+    /// Position among the issue's criteria, starting at 1
+    pub index: usize,
+    /// Text after the checkbox
+    pub text: String,
+    /// Whether the box is ticked
+    pub checked: bool,
+}
+
+/// How many criteria of an issue are met
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CriteriaProgress {
+    /// Ticked criteria
+    pub done: usize,
+    /// All criteria
+    pub total: usize,
+}
+
+impl CriteriaProgress {
+    /// Progress of a list of criteria
+    pub fn of(criteria: &[AcceptanceCriterion]) -> Self {
+        Self {
+            done: criteria.iter().filter(|c| c.checked).count(),
+            total: criteria.len(),
+        }
+    }
+
+    /// Whether every criterion is ticked, trivially true without criteria
+    pub fn is_complete(&self) -> bool {
+        self.done == self.total
+    }
+}
+
+impl fmt::Display for CriteriaProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.done, self.total)
+    }
+}
+
+/// Identifies a criterion by position or by text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CriterionSelector {
+    /// Position starting at 1
+    Index(usize),
+    /// Case-insensitive text contained in exactly one criterion
+    Text(String),
+}
+
+fn checkbox_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^(\s*[-*+]\s+\[)([ xX])(\]\s+)(.*?)\s*$").expect("valid checkbox pattern")
+    })
+}
+
+/// Visit every checklist line outside fenced code blocks with its line number
+fn checklist_lines(body: &str) -> impl Iterator<Item = (usize, regex::Captures<'_>)> {
+    let mut in_fence = false;
+    body.lines()
+        .enumerate()
+        .filter_map(move |(line_number, line)| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+                return None;
+            }
+            if in_fence {
+                return None;
+            }
+            checkbox_pattern()
+                .captures(line)
+                .map(|captures| (line_number, captures))
+        })
+}
+
+/// Parse the acceptance criteria of an issue body
+pub fn parse_criteria(body: &str) -> Vec<AcceptanceCriterion> {
+    checklist_lines(body)
+        .enumerate()
+        .map(|(position, (_, captures))| AcceptanceCriterion {
+            index: position + 1,
+            text: captures[4].to_string(),
+            checked: &captures[2] != " ",
+        })
+        .collect()
+}
+
+/// Find the criterion a selector refers to
+pub fn find_criterion<'a>(
+    criteria: &'a [AcceptanceCriterion],
+    selector: &CriterionSelector,
+) -> Result<&'a AcceptanceCriterion, String> {
+    match selector {
+        CriterionSelector::Index(index) => {
+            criteria
+                .iter()
+                .find(|c| c.index == *index)
+                .ok_or_else(|| match criteria.len() {
+                    0 => "The issue has no acceptance criteria".to_string(),
+                    count => format!("No criterion {index}: expected 1 to {count}"),
+                })
+        }
+        CriterionSelector::Text(text) => {
+            let needle = text.trim().to_lowercase();
+            let matches: Vec<&AcceptanceCriterion> = criteria
+                .iter()
+                .filter(|c| c.text.to_lowercase().contains(&needle))
+                .collect();
+            match matches.as_slice() {
+                [criterion] => Ok(criterion),
+                [] => Err(format!("No criterion matches '{text}'")),
+                several => Err(format!(
+                    "'{text}' matches {} criteria ({}), use an index instead",
+                    several.len(),
+                    several
+                        .iter()
+                        .map(|c| c.index.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            }
+        }
+    }
+}
+
+/// Tick or untick the criterion at `index`, returning the updated body
+pub fn set_criterion(body: &str, index: usize, checked: bool) -> Option<String> {
+    let (line_number, _) = checklist_lines(body).nth(index.checked_sub(1)?)?;
+    let mark = if checked { "x" } else { " " };
+
+    let mut updated = String::with_capacity(body.len());
+    for (number, line) in body.split_inclusive('\n').enumerate() {
+        if number == line_number {
+            let content = line.trim_end_matches(['\r', '\n']);
+            let ending = &line[content.len()..];
+            let replaced = checkbox_pattern().replace(content, |caps: &regex::Captures| {
+                format!("{}{mark}{}{}", &caps[1], &caps[3], &caps[4])
+            });
+            updated.push_str(&replaced);
+            updated.push_str(ending);
+        } else {
+            updated.push_str(line);
+        }
+    }
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = "# Login\n\n## Acceptance Criteria\n- [x] Users can log in\n- [ ] Sessions expire\n  * [ ] Expired sessions redirect to login\n\n```markdown\n- [ ] not a criterion\n```\n";
+
+    #[test]
+    fn test_parse_criteria_and_progress() {
+        let criteria = parse_criteria(BODY);
+        assert_eq!(criteria.len(), 3);
+        assert!(criteria[0].checked);
+        assert_eq!(criteria[2].index, 3);
+        assert_eq!(criteria[2].text, "Expired sessions redirect to login");
+
+        let progress = CriteriaProgress::of(&criteria);
+        assert_eq!(progress.to_string(), "1/3");
+        assert!(!progress.is_complete());
+        assert!(CriteriaProgress::of(&[]).is_complete());
+    }
+
+    #[test]
+    fn test_find_and_set_criterion() {
+        let criteria = parse_criteria(BODY);
+        let selector = CriterionSelector::Text("sessions EXPIRE".to_string());
+        assert_eq!(find_criterion(&criteria, &selector).unwrap().index, 2);
+        assert!(find_criterion(&criteria, &CriterionSelector::Text("session".into())).is_err());
+        assert!(find_criterion(&criteria, &CriterionSelector::Index(4)).is_err());
+
+        let updated = set_criterion(BODY, 3, true).unwrap();
+        assert!(updated.contains("  * [x] Expired sessions redirect to login\n"));
+        assert!(updated.contains("- [ ] not a criterion"));
+        assert_eq!(CriteriaProgress::of(&parse_criteria(&updated)).done, 2);
+
+        let reverted = set_criterion(&updated, 1, false).unwrap();
+        assert!(reverted.contains("- [ ] Users can log in\n"));
+        assert_eq!(set_criterion(BODY, 0, true), None);
+    }
+}
@@ -0,0 +1,1126 @@
+//! Issue storage in an embedded database
+//!
+//! The database backend keeps issues in a DuckDB file, `issues.duckdb`, instead
+//! of one markdown file per issue. The file is binary and rewritten in place, so
+//! it lives next to the issues directory in `issues-db/`, a directory that ignores
+//! itself, rather than in the git-tracked issues directory. Each issue is a row holding
+//! its markdown, frontmatter included, next to indexed columns for its location,
+//! status, priority and epic, so listings do not rescan the disk:
+//!
+//! ```text
+//! issues(name, content, location, status, priority, parent, created_at, updated_at)
+//! issue_activity(id, issue, at, kind, author, message)
+//! ```
+//!
+//! Every operation opens its own connection, so several `sah` processes can share
+//! the database; one that finds it locked retries for a moment without blocking
+//! the async runtime. Leases and the search index are the same as with markdown
+//! storage.
+
+use super::activity::{IssueActivity, IssueActivityKind};
+use super::backend::{IssueLocation, IssueStorageBackend, StoredIssue};
+use super::dependencies::DependencyGraph;
+use super::filesystem::{Issue, IssueInfo, IssueStorage};
+use super::hierarchy::IssueHierarchy;
+use super::lease::{IssueLease, LeaseStore};
+use super::metadata::{parse_issue_content, render_issue_content};
+use super::next::NextIssue;
+use super::search::{IssueSearchEngine, IssueSearchOptions, IssueSearchResult};
+use super::status::{resolve_status, IssueStatus};
+use crate::common::generate_monotonic_ulid_string;
+use crate::directory_utils::create_git_ignored_directory;
+use crate::error::{Result, SwissArmyHammerError};
+use chrono::{DateTime, SecondsFormat, Utc};
+use duckdb::{params, Connection, ToSql};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::{Mutex, OnceCell};
+use tracing::{debug, warn};
+
+/// Name of the database file
+pub const DATABASE_FILE_NAME: &str = "issues.duckdb";
+
+/// Suffix of the directory next to the issues directory holding the database
+pub const DATABASE_DIR_SUFFIX: &str = "-db";
+
+/// Where the database of an issues directory lives
+///
+/// `.swissarmyhammer/issues` keeps its database in `.swissarmyhammer/issues-db/`.
+pub fn issue_database_path(issues_dir: &Path) -> PathBuf {
+    let name = issues_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "issues".to_string());
+    issues_dir
+        .with_file_name(format!("{name}{DATABASE_DIR_SUFFIX}"))
+        .join(DATABASE_FILE_NAME)
+}
+
+/// How often to retry opening a database another process has locked
+const LOCK_RETRIES: u32 = 20;
+
+/// Pause between attempts to open a locked database
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+const SCHEMA: &str = r#"
+    CREATE TABLE IF NOT EXISTS issues (
+        name TEXT PRIMARY KEY,
+        content TEXT NOT NULL,
+        location TEXT NOT NULL,
+        status TEXT NOT NULL,
+        priority TEXT,
+        parent TEXT,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_issues_location ON issues(location);
+    CREATE INDEX IF NOT EXISTS idx_issues_status ON issues(status);
+    CREATE INDEX IF NOT EXISTS idx_issues_parent ON issues(parent);
+    CREATE SEQUENCE IF NOT EXISTS issue_activity_id;
+    CREATE TABLE IF NOT EXISTS issue_activity (
+        id BIGINT PRIMARY KEY DEFAULT nextval('issue_activity_id'),
+        issue TEXT NOT NULL,
+        at TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        author TEXT,
+        message TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_issue_activity_issue ON issue_activity(issue);
+"#;
+
+/// Rows that are not archived
+const NOT_ARCHIVED: &str = "location <> 'archived'";
+
+/// Move a database kept inside the issues directory to `db_path`
+fn move_legacy_database(issues_dir: &Path, db_path: &Path) -> Result<()> {
+    let legacy_path = issues_dir.join(DATABASE_FILE_NAME);
+    if !legacy_path.exists() || db_path.exists() {
+        return Ok(());
+    }
+
+    fs::rename(&legacy_path, db_path).map_err(SwissArmyHammerError::Io)?;
+    let legacy_wal = legacy_path.with_extension("duckdb.wal");
+    if legacy_wal.exists() {
+        fs::rename(&legacy_wal, db_path.with_extension("duckdb.wal"))
+            .map_err(SwissArmyHammerError::Io)?;
+    }
+    warn!(
+        "Moved the issue database from {} to {}, remove it from git if it was committed",
+        legacy_path.display(),
+        db_path.display()
+    );
+    Ok(())
+}
+
+/// An issue as stored in the `issues` table
+#[derive(Debug, Clone)]
+struct IssueRow {
+    issue: Issue,
+    location: IssueLocation,
+    created_at: DateTime<Utc>,
+}
+
+/// Embedded database issue storage implementation
+#[derive(Debug)]
+pub struct DatabaseIssueStorage {
+    /// Issues directory the database belongs to
+    issues_dir: PathBuf,
+    /// Path to the database file
+    db_path: PathBuf,
+    /// Search index, opened on first search
+    search_engine: OnceCell<IssueSearchEngine>,
+    /// Leases held by agents working on issues
+    leases: LeaseStore,
+    /// Locking mechanism for thread safety
+    lock: Mutex<()>,
+}
+
+impl DatabaseIssueStorage {
+    /// Open or create the issue database of an issues directory
+    ///
+    /// A database left inside the issues directory by earlier versions is moved out.
+    pub fn new(issues_dir: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&issues_dir).map_err(SwissArmyHammerError::Io)?;
+        let db_path = issue_database_path(&issues_dir);
+        if let Some(db_dir) = db_path.parent() {
+            create_git_ignored_directory(db_dir).map_err(SwissArmyHammerError::Io)?;
+        }
+        move_legacy_database(&issues_dir, &db_path)?;
+
+        let storage = Self {
+            db_path,
+            leases: LeaseStore::new(&issues_dir),
+            issues_dir,
+            search_engine: OnceCell::new(),
+            lock: Mutex::new(()),
+        };
+        storage
+            .connect_blocking()?
+            .execute_batch(SCHEMA)
+            .map_err(|e| db_error("create the issue tables", e))?;
+        Ok(storage)
+    }
+
+    /// Path to the database file
+    pub fn database_path(&self) -> &PathBuf {
+        &self.db_path
+    }
+
+    /// Open a connection, waiting briefly while another process holds the database
+    ///
+    /// Opening touches the disk, so each attempt runs on the blocking pool and the
+    /// wait between attempts yields the runtime worker.
+    async fn connect(&self) -> Result<Connection> {
+        let mut attempt = 0;
+        loop {
+            let db_path = self.db_path.clone();
+            let opened = tokio::task::spawn_blocking(move || Connection::open(db_path))
+                .await
+                .map_err(|e| {
+                    SwissArmyHammerError::Storage(format!("Failed to open the issue database: {e}"))
+                })?;
+            match opened {
+                Ok(connection) => return Ok(connection),
+                Err(e) if attempt < LOCK_RETRIES && is_lock_conflict(&e) => {
+                    attempt += 1;
+                    tokio::time::sleep(LOCK_RETRY_DELAY).await;
+                }
+                Err(e) => return Err(db_error("open the issue database", e)),
+            }
+        }
+    }
+
+    /// Blocking counterpart of [`Self::connect`] for the constructor
+    fn connect_blocking(&self) -> Result<Connection> {
+        let mut attempt = 0;
+        loop {
+            match Connection::open(&self.db_path) {
+                Ok(connection) => return Ok(connection),
+                Err(e) if attempt < LOCK_RETRIES && is_lock_conflict(&e) => {
+                    attempt += 1;
+                    std::thread::sleep(LOCK_RETRY_DELAY);
+                }
+                Err(e) => return Err(db_error("open the issue database", e)),
+            }
+        }
+    }
+
+    /// Stand-in for the file path of an issue, naming the database and the issue
+    fn issue_path(&self, name: &str) -> PathBuf {
+        PathBuf::from(format!("{}#{}", self.db_path.display(), name))
+    }
+
+    fn query_rows(
+        conn: &Connection,
+        condition: &str,
+        params: &[&dyn ToSql],
+    ) -> Result<Vec<IssueRow>> {
+        let sql = format!(
+            "SELECT name, content, location, created_at FROM issues WHERE {condition} ORDER BY name"
+        );
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| db_error("prepare the issue query", e))?;
+        let rows = stmt
+            .query_map(params, |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .map_err(|e| db_error("query issues", e))?;
+
+        let mut issues = Vec::new();
+        for row in rows {
+            let (name, content, location, created_at) =
+                row.map_err(|e| db_error("read an issue", e))?;
+            issues.push(IssueRow {
+                issue: Issue { name, content },
+                location: location.parse().map_err(SwissArmyHammerError::Storage)?,
+                created_at: parse_timestamp(&created_at)?,
+            });
+        }
+        Ok(issues)
+    }
+
+    /// Find an issue that is not archived
+    fn find_issue(conn: &Connection, name: &str) -> Result<Option<IssueRow>> {
+        let condition = format!("name = ? AND {NOT_ARCHIVED}");
+        Ok(Self::query_rows(conn, &condition, &[&name])?
+            .into_iter()
+            .next())
+    }
+
+    /// Find an issue that is not archived, failing when there is none
+    fn require_issue(conn: &Connection, name: &str) -> Result<IssueRow> {
+        Self::find_issue(conn, name)?
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))
+    }
+
+    /// Load active and completed issues with extended information, sorted by name
+    fn load_issue_infos(&self, conn: &Connection) -> Result<Vec<IssueInfo>> {
+        Ok(Self::query_rows(conn, NOT_ARCHIVED, &[])?
+            .into_iter()
+            .map(|row| self.issue_info(row))
+            .collect())
+    }
+
+    fn issue_info(&self, row: IssueRow) -> IssueInfo {
+        IssueInfo {
+            file_path: self.issue_path(&row.issue.name),
+            completed: row.location == IssueLocation::Completed,
+            created_at: row.created_at,
+            metadata: row.issue.metadata(),
+            issue: row.issue,
+        }
+    }
+
+    fn insert_issue(
+        conn: &Connection,
+        issue: &Issue,
+        location: IssueLocation,
+        created_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let metadata = issue.metadata();
+        let status = resolve_status(&metadata, location == IssueLocation::Completed);
+        conn.execute(
+            "INSERT INTO issues (name, content, location, status, priority, parent, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                issue.name,
+                issue.content,
+                location.as_str(),
+                status.as_str(),
+                metadata.priority.map(|p| p.as_str()),
+                metadata.parent,
+                format_timestamp(created_at),
+                format_timestamp(Utc::now()),
+            ],
+        )
+        .map_err(|e| db_error("insert an issue", e))?;
+        Ok(())
+    }
+
+    fn save_issue(conn: &Connection, issue: &Issue, location: IssueLocation) -> Result<()> {
+        let metadata = issue.metadata();
+        let status = resolve_status(&metadata, location == IssueLocation::Completed);
+        conn.execute(
+            "UPDATE issues SET content = ?, location = ?, status = ?, priority = ?, parent = ?, updated_at = ? WHERE name = ?",
+            params![
+                issue.content,
+                location.as_str(),
+                status.as_str(),
+                metadata.priority.map(|p| p.as_str()),
+                metadata.parent,
+                format_timestamp(Utc::now()),
+                issue.name,
+            ],
+        )
+        .map_err(|e| db_error("update an issue", e))?;
+        Ok(())
+    }
+
+    fn insert_activity(conn: &Connection, name: &str, entry: &IssueActivity) -> Result<()> {
+        conn.execute(
+            "INSERT INTO issue_activity (issue, at, kind, author, message) VALUES (?, ?, ?, ?, ?)",
+            params![
+                name,
+                format_timestamp(entry.at),
+                entry.kind.as_str(),
+                entry.author,
+                entry.message,
+            ],
+        )
+        .map_err(|e| db_error("record activity", e))?;
+        Ok(())
+    }
+
+    fn load_activity(conn: &Connection, name: &str) -> Result<Vec<IssueActivity>> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT at, kind, author, message FROM issue_activity WHERE issue = ? ORDER BY at, id",
+            )
+            .map_err(|e| db_error("prepare the activity query", e))?;
+        let rows = stmt
+            .query_map([name], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .map_err(|e| db_error("query activity", e))?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (at, kind, author, message) = row.map_err(|e| db_error("read activity", e))?;
+            entries.push(IssueActivity {
+                at: parse_timestamp(&at)?,
+                kind: kind.parse().map_err(SwissArmyHammerError::Storage)?,
+                author,
+                message,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Append an automatic entry to an issue's activity
+    ///
+    /// The change the entry describes already happened, so a failed write is only logged.
+    fn record_activity(conn: &Connection, name: &str, entry: IssueActivity) {
+        if let Err(e) = Self::insert_activity(conn, name, &entry) {
+            warn!("Failed to record activity for issue '{}': {}", name, e);
+        }
+    }
+
+    /// Reject removing an issue that open issues still depend on
+    fn check_no_open_dependents(&self, conn: &Connection, name: &str, action: &str) -> Result<()> {
+        DependencyGraph::from_issue_infos(&self.load_issue_infos(conn)?)
+            .ensure_no_open_dependents(name, action)
+            .map_err(SwissArmyHammerError::IssueHasDependents)
+    }
+
+    /// Reject content whose `depends_on` would make the dependency graph cyclic
+    fn check_dependency_cycle(&self, conn: &Connection, name: &str, content: &str) -> Result<()> {
+        let depends_on = parse_issue_content(content).0.depends_on;
+        if depends_on.is_empty() {
+            return Ok(());
+        }
+
+        let graph = DependencyGraph::from_issue_infos(&self.load_issue_infos(conn)?);
+        match graph.find_cycle(name, &depends_on) {
+            Some(cycle) => Err(SwissArmyHammerError::IssueDependencyCycle(
+                cycle.join(" -> "),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Reject content whose `parent` would make an issue its own ancestor
+    fn check_parent_cycle(&self, conn: &Connection, name: &str, content: &str) -> Result<()> {
+        let Some(parent) = parse_issue_content(content).0.parent else {
+            return Ok(());
+        };
+
+        let hierarchy = IssueHierarchy::from_issue_infos(&self.load_issue_infos(conn)?);
+        match hierarchy.find_cycle(name, &parent) {
+            Some(cycle) => Err(SwissArmyHammerError::IssueParentCycle(cycle.join(" -> "))),
+            None => Ok(()),
+        }
+    }
+
+    /// Derive an epic's completion from its children after one of them changed
+    ///
+    /// The child's change already happened, so a failure is only logged.
+    fn sync_epic_status(&self, conn: &Connection, epic: &str) {
+        if let Err(e) = self.try_sync_epic_status(conn, epic) {
+            warn!("Failed to update the status of epic '{}': {}", epic, e);
+        }
+    }
+
+    fn try_sync_epic_status(&self, conn: &Connection, epic: &str) -> Result<()> {
+        let Some(row) = Self::find_issue(conn, epic)? else {
+            return Ok(());
+        };
+        let hierarchy = IssueHierarchy::from_issue_infos(&self.load_issue_infos(conn)?);
+        if !hierarchy.is_epic(epic) {
+            return Ok(());
+        }
+
+        let completed = row.location == IssueLocation::Completed;
+        let children_open = hierarchy.has_open_children(epic);
+        if completed != children_open {
+            return Ok(());
+        }
+        let status = if children_open {
+            IssueStatus::Todo
+        } else {
+            IssueStatus::Done
+        };
+        self.apply_status(conn, row, status)?;
+        Ok(())
+    }
+
+    /// Record a status transition in an issue and move it to the matching location
+    ///
+    /// The issue's epic, if any, is completed or reopened to match its children.
+    fn apply_status(&self, conn: &Connection, row: IssueRow, status: IssueStatus) -> Result<Issue> {
+        let completed = row.location == IssueLocation::Completed;
+        let (mut metadata, body) = parse_issue_content(&row.issue.content);
+        let current = resolve_status(&metadata, completed);
+        if current == status {
+            return Ok(row.issue);
+        }
+
+        metadata.record_status(current, status, Utc::now());
+        let parent = metadata.parent.clone();
+        let updated = Issue {
+            content: render_issue_content(&metadata, body),
+            name: row.issue.name,
+        };
+
+        let location = if status.is_closed() {
+            IssueLocation::Completed
+        } else {
+            IssueLocation::Active
+        };
+        Self::save_issue(conn, &updated, location)?;
+        Self::record_activity(
+            conn,
+            &updated.name,
+            IssueActivity::new(IssueActivityKind::Status, format!("{current} → {status}")),
+        );
+        if status.is_closed() {
+            self.drop_lease(&updated.name);
+        }
+        if let Some(parent) = parent {
+            self.sync_epic_status(conn, &parent);
+        }
+
+        debug!(
+            "Moved issue '{}' from {} to {}",
+            updated.name, current, status
+        );
+        Ok(updated)
+    }
+
+    /// Remove the lease on an issue that no longer needs one
+    ///
+    /// A lease left behind expires on its own, so a failed removal is only logged.
+    fn drop_lease(&self, name: &str) {
+        if let Err(e) = self.leases.remove(name) {
+            warn!("Failed to remove the lease on issue '{}': {}", name, e);
+        }
+    }
+
+    /// Validate that an issue name is acceptable
+    fn validate_issue_name(name: &str) -> Result<()> {
+        if name.is_empty() || name.contains('/') || name.contains('\\') || name.contains('\0') {
+            return Err(SwissArmyHammerError::IssueNotFound(name.to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl IssueStorage for DatabaseIssueStorage {
+    async fn list_issues(&self) -> Result<Vec<Issue>> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        Ok(Self::query_rows(&conn, NOT_ARCHIVED, &[])?
+            .into_iter()
+            .map(|row| row.issue)
+            .collect())
+    }
+
+    async fn list_issues_info(&self) -> Result<Vec<IssueInfo>> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+        self.load_issue_infos(&conn)
+    }
+
+    async fn get_issue(&self, name: &str) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+        Ok(Self::require_issue(&conn, name)?.issue)
+    }
+
+    async fn get_issue_info(&self, name: &str) -> Result<IssueInfo> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+        Ok(self.issue_info(Self::require_issue(&conn, name)?))
+    }
+
+    async fn create_issue(&self, name: String, content: String) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let issue_name = if name.is_empty() {
+            generate_monotonic_ulid_string()
+        } else {
+            Self::validate_issue_name(&name)?;
+            name
+        };
+
+        // Names are unique across locations, archived issues included
+        if !Self::query_rows(&conn, "name = ?", &[&issue_name])?.is_empty() {
+            return Err(SwissArmyHammerError::IssueAlreadyExists(0));
+        }
+        self.check_dependency_cycle(&conn, &issue_name, &content)?;
+        self.check_parent_cycle(&conn, &issue_name, &content)?;
+
+        let issue = Issue {
+            name: issue_name.clone(),
+            content,
+        };
+        Self::insert_issue(&conn, &issue, IssueLocation::Active, Utc::now())?;
+        if let Some(parent) = issue.metadata().parent {
+            self.sync_epic_status(&conn, &parent);
+        }
+
+        debug!(
+            "Created issue '{}' in {}",
+            issue_name,
+            self.db_path.display()
+        );
+        Ok(issue)
+    }
+
+    async fn update_issue(&self, name: &str, content: String) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let row = Self::require_issue(&conn, name)?;
+        self.check_dependency_cycle(&conn, name, &content)?;
+        self.check_parent_cycle(&conn, name, &content)?;
+        let previous_parent = row.issue.metadata().parent;
+
+        let issue = Issue {
+            name: name.to_string(),
+            content,
+        };
+        Self::save_issue(&conn, &issue, row.location)?;
+        let parent = issue.metadata().parent;
+        for epic in previous_parent.iter().chain(parent.iter()) {
+            self.sync_epic_status(&conn, epic);
+        }
+        debug!("Updated issue '{}'", name);
+        Ok(issue)
+    }
+
+    async fn delete_issue(&self, name: &str) -> Result<()> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let row = Self::require_issue(&conn, name)?;
+        self.check_no_open_dependents(&conn, name, "delete")?;
+        conn.execute("DELETE FROM issue_activity WHERE issue = ?", [name])
+            .map_err(|e| db_error("delete activity", e))?;
+        conn.execute("DELETE FROM issues WHERE name = ?", [name])
+            .map_err(|e| db_error("delete an issue", e))?;
+        self.drop_lease(name);
+        if let Some(parent) = row.issue.metadata().parent {
+            self.sync_epic_status(&conn, &parent);
+        }
+        debug!("Deleted issue '{}'", name);
+        Ok(())
+    }
+
+    async fn complete_issue(&self, name: &str) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let row = Self::require_issue(&conn, name)?;
+        if row.location == IssueLocation::Completed {
+            return Ok(row.issue);
+        }
+
+        self.apply_status(&conn, row, IssueStatus::Done)
+    }
+
+    async fn set_issue_status(&self, name: &str, status: IssueStatus) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let row = Self::require_issue(&conn, name)?;
+        self.apply_status(&conn, row, status)
+    }
+
+    async fn reopen_issue(&self, name: &str) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let row = Self::query_rows(&conn, "name = ?", &[&name])?
+            .into_iter()
+            .next()
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+        if row.location == IssueLocation::Active {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Issue '{name}' is already open"
+            )));
+        }
+
+        let (mut metadata, body) = parse_issue_content(&row.issue.content);
+        let current = if row.location == IssueLocation::Archived {
+            metadata.status.unwrap_or(IssueStatus::Todo)
+        } else {
+            resolve_status(&metadata, true)
+        };
+        if current != IssueStatus::Todo {
+            metadata.record_status(current, IssueStatus::Todo, Utc::now());
+        }
+        let reopened = Issue {
+            content: render_issue_content(&metadata, body),
+            name: row.issue.name,
+        };
+
+        Self::save_issue(&conn, &reopened, IssueLocation::Active)?;
+        Self::record_activity(
+            &conn,
+            name,
+            IssueActivity::new(
+                IssueActivityKind::Status,
+                format!("{current} → {} (reopened)", IssueStatus::Todo),
+            ),
+        );
+        if let Some(parent) = &metadata.parent {
+            self.sync_epic_status(&conn, parent);
+        }
+        debug!("Reopened issue '{}'", name);
+        Ok(reopened)
+    }
+
+    async fn archive_issue(&self, name: &str) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let row = Self::require_issue(&conn, name)?;
+        self.check_no_open_dependents(&conn, name, "archive")?;
+        Self::save_issue(&conn, &row.issue, IssueLocation::Archived)?;
+        Self::record_activity(
+            &conn,
+            name,
+            IssueActivity::new(IssueActivityKind::Archive, "Archived"),
+        );
+        self.drop_lease(name);
+        if let Some(parent) = row.issue.metadata().parent {
+            self.sync_epic_status(&conn, &parent);
+        }
+        debug!("Archived issue '{}'", name);
+        Ok(row.issue)
+    }
+
+    async fn search_issues(
+        &self,
+        query: &str,
+        options: &IssueSearchOptions,
+    ) -> Result<Vec<IssueSearchResult>> {
+        let issue_infos = {
+            let _lock = self.lock.lock().await;
+            self.load_issue_infos(&self.connect().await?)?
+        };
+
+        let engine = self
+            .search_engine
+            .get_or_try_init(|| IssueSearchEngine::open_for_issues(&self.issues_dir))
+            .await?;
+        engine.search(query, options, &issue_infos).await
+    }
+
+    async fn add_issue_activity(&self, name: &str, entry: IssueActivity) -> Result<IssueActivity> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        Self::require_issue(&conn, name)?;
+        Self::insert_activity(&conn, name, &entry)?;
+        debug!("Recorded {} activity for issue '{}'", entry.kind, name);
+        Ok(entry)
+    }
+
+    async fn issue_activity(&self, name: &str) -> Result<Vec<IssueActivity>> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        Self::require_issue(&conn, name)?;
+        Self::load_activity(&conn, name)
+    }
+
+    async fn claim_issue(&self, name: &str, owner: &str, ttl_seconds: u64) -> Result<IssueLease> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let row = Self::require_issue(&conn, name)?;
+        if row.location != IssueLocation::Active {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Issue '{name}' is completed and cannot be claimed"
+            )));
+        }
+
+        let lease = self.leases.claim(name, owner, ttl_seconds, Utc::now())?;
+        debug!("Issue '{}' claimed by '{}'", name, owner);
+        Ok(lease)
+    }
+
+    async fn release_issue(&self, name: &str, owner: &str, force: bool) -> Result<bool> {
+        let _lock = self.lock.lock().await;
+
+        let released = self.leases.release(name, owner, force, Utc::now())?;
+        if released {
+            debug!("Issue '{}' released by '{}'", name, owner);
+        }
+        Ok(released)
+    }
+
+    async fn issue_leases(&self) -> Result<Vec<IssueLease>> {
+        self.leases.active(Utc::now())
+    }
+
+    async fn next_issue(&self) -> Result<NextIssue> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let issue_infos = self.load_issue_infos(&conn)?;
+        let leases = self.leases.active(Utc::now())?;
+        Ok(NextIssue::pick(issue_infos, &leases))
+    }
+
+    async fn all_issues_completed(&self) -> Result<bool> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let open: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM issues WHERE location = 'active'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| db_error("count open issues", e))?;
+        Ok(open == 0)
+    }
+
+    fn backend(&self) -> IssueStorageBackend {
+        IssueStorageBackend::Database
+    }
+
+    fn issues_directory(&self) -> &Path {
+        &self.issues_dir
+    }
+
+    async fn snapshot_issues(&self) -> Result<Vec<StoredIssue>> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        let mut stored = Vec::new();
+        for row in Self::query_rows(&conn, "TRUE", &[])? {
+            stored.push(StoredIssue {
+                activity: Self::load_activity(&conn, &row.issue.name)?,
+                issue: row.issue,
+                location: row.location,
+                created_at: row.created_at,
+            });
+        }
+        Ok(stored)
+    }
+
+    async fn restore_issues(&self, issues: Vec<StoredIssue>) -> Result<()> {
+        let _lock = self.lock.lock().await;
+        let mut conn = self.connect().await?;
+
+        let existing: BTreeSet<String> = Self::query_rows(&conn, "TRUE", &[])?
+            .into_iter()
+            .map(|row| row.issue.name)
+            .collect();
+        for stored in &issues {
+            Self::validate_issue_name(&stored.issue.name)?;
+            if existing.contains(&stored.issue.name) {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Issue '{}' already exists",
+                    stored.issue.name
+                )));
+            }
+        }
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| db_error("start a transaction", e))?;
+        for stored in &issues {
+            Self::insert_issue(&tx, &stored.issue, stored.location, stored.created_at)?;
+            for entry in &stored.activity {
+                Self::insert_activity(&tx, &stored.issue.name, entry)?;
+            }
+        }
+        tx.commit()
+            .map_err(|e| db_error("commit restored issues", e))?;
+
+        debug!(
+            "Restored {} issues in {}",
+            issues.len(),
+            self.db_path.display()
+        );
+        Ok(())
+    }
+
+    async fn remove_all_issues(&self) -> Result<()> {
+        let _lock = self.lock.lock().await;
+        let conn = self.connect().await?;
+
+        conn.execute_batch("DELETE FROM issue_activity; DELETE FROM issues;")
+            .map_err(|e| db_error("remove all issues", e))?;
+        debug!("Removed all issues from {}", self.db_path.display());
+        Ok(())
+    }
+}
+
+fn db_error(action: &str, e: duckdb::Error) -> SwissArmyHammerError {
+    SwissArmyHammerError::Storage(format!("Failed to {action}: {e}"))
+}
+
+/// Whether opening failed because another process holds the database file
+///
+/// DuckDB reports a conflicting file lock as an IO error rather than with a
+/// dedicated result code, so the busy and locked codes are checked first and the
+/// IO error is recognised by its message.
+fn is_lock_conflict(e: &duckdb::Error) -> bool {
+    match e {
+        duckdb::Error::DuckDBFailure(error, message) => {
+            matches!(
+                error.code,
+                duckdb::ffi::ErrorCode::DatabaseBusy | duckdb::ffi::ErrorCode::DatabaseLocked
+            ) || message
+                .as_deref()
+                .is_some_and(|message| message.contains("Could not set lock on file"))
+        }
+        _ => false,
+    }
+}
+
+/// Timestamps are stored as fixed-width RFC 3339 text so they sort chronologically
+fn format_timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+fn parse_timestamp(text: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|at| at.with_timezone(&Utc))
+        .map_err(|e| SwissArmyHammerError::Storage(format!("Invalid timestamp '{text}': {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_database_storage_lifecycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+        assert!(storage.database_path().exists());
+
+        storage
+            .create_issue("auth".into(), "# Auth".into())
+            .await
+            .unwrap();
+        storage
+            .create_issue("login".into(), "---\nparent: auth\n---\n# Login".into())
+            .await
+            .unwrap();
+        storage
+            .create_issue(
+                "tokens".into(),
+                "---\npriority: high\ndepends_on: [login]\n---\n# Tokens".into(),
+            )
+            .await
+            .unwrap();
+        assert!(storage
+            .create_issue("login".into(), "# Again".into())
+            .await
+            .is_err());
+        assert!(matches!(
+            storage
+                .update_issue("auth", "---\nparent: login\n---\n# Auth".into())
+                .await,
+            Err(SwissArmyHammerError::IssueParentCycle(_))
+        ));
+
+        // The epic waits for its sub-issue and `tokens` waits for `login`
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "login"
+        );
+        storage
+            .set_issue_status("login", IssueStatus::Done)
+            .await
+            .unwrap();
+        assert!(storage.get_issue_info("auth").await.unwrap().completed);
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "tokens"
+        );
+
+        storage
+            .add_issue_activity("tokens", IssueActivity::comment("Rotate them", None))
+            .await
+            .unwrap();
+        storage.archive_issue("tokens").await.unwrap();
+        assert!(storage.get_issue("tokens").await.is_err());
+        assert!(storage.all_issues_completed().await.unwrap());
+
+        let reopened = storage.reopen_issue("tokens").await.unwrap();
+        assert_eq!(reopened.metadata().priority.unwrap().as_str(), "high");
+        let kinds: Vec<_> = storage
+            .issue_activity("tokens")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                IssueActivityKind::Comment,
+                IssueActivityKind::Archive,
+                IssueActivityKind::Status
+            ]
+        );
+
+        // A second storage on the same file sees the same issues
+        let again = DatabaseIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+        let names: Vec<_> = again
+            .list_issues()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|issue| issue.name)
+            .collect();
+        assert_eq!(names, vec!["auth", "login", "tokens"]);
+
+        storage.delete_issue("tokens").await.unwrap();
+        assert!(storage.issue_activity("tokens").await.is_err());
+    }
+    #[tokio::test]
+    async fn test_next_issue_only_picks_todo_issues() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+
+        for name in ["alpha", "beta", "gamma", "delta"] {
+            storage
+                .create_issue(name.to_string(), format!("# {name}"))
+                .await
+                .unwrap();
+        }
+        storage
+            .set_issue_status("alpha", IssueStatus::Blocked)
+            .await
+            .unwrap();
+        storage
+            .set_issue_status("beta", IssueStatus::InReview)
+            .await
+            .unwrap();
+        // In progress without a lease is still someone's work
+        storage
+            .set_issue_status("gamma", IssueStatus::InProgress)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "delta"
+        );
+
+        storage
+            .set_issue_status("delta", IssueStatus::InProgress)
+            .await
+            .unwrap();
+        // Open issues remain, so the work loop must stop instead of finishing
+        assert!(storage.next_issue().await.unwrap().is_stalled());
+        assert!(!storage.all_issues_completed().await.unwrap());
+
+        storage
+            .set_issue_status("alpha", IssueStatus::Todo)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "alpha"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_and_delete_refused_while_open_issues_depend() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+        storage
+            .create_issue("schema".into(), "# Schema".into())
+            .await
+            .unwrap();
+        storage
+            .create_issue("api".into(), "---\ndepends_on: [schema]\n---\n# API".into())
+            .await
+            .unwrap();
+        storage.complete_issue("schema").await.unwrap();
+
+        // A completed dependency still unblocks `api` only while it exists
+        for result in [
+            storage.archive_issue("schema").await.map(|_| ()),
+            storage.delete_issue("schema").await,
+        ] {
+            let error = result.unwrap_err();
+            assert!(matches!(error, SwissArmyHammerError::IssueHasDependents(_)));
+            assert!(error.to_string().contains("open issues depend on it: api"));
+        }
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "api"
+        );
+
+        // Once the dependent is done, the dependency can go
+        storage.complete_issue("api").await.unwrap();
+        storage.archive_issue("schema").await.unwrap();
+        storage.delete_issue("api").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_database_lives_outside_the_issues_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let issues_dir = temp_dir.path().join(".swissarmyhammer/issues");
+
+        // A database from an earlier version is moved out of the issues directory
+        let legacy = DatabaseIssueStorage::new(issues_dir.clone()).unwrap();
+        legacy
+            .create_issue("login".into(), "# Login".into())
+            .await
+            .unwrap();
+        let db_path = legacy.database_path().clone();
+        drop(legacy);
+        fs::rename(&db_path, issues_dir.join(DATABASE_FILE_NAME)).unwrap();
+
+        let storage = DatabaseIssueStorage::new(issues_dir.clone()).unwrap();
+        assert_eq!(
+            storage.database_path(),
+            &temp_dir
+                .path()
+                .join(".swissarmyhammer/issues-db")
+                .join(DATABASE_FILE_NAME)
+        );
+        assert!(!issues_dir.join(DATABASE_FILE_NAME).exists());
+        assert_eq!(storage.get_issue("login").await.unwrap().content, "# Login");
+        assert!(repo
+            .is_path_ignored(".swissarmyhammer/issues-db/issues.duckdb")
+            .unwrap());
+    }
+}
@@ -0,0 +1,427 @@
+//! Dependency graph between issues
+//!
+//! Issues declare prerequisites with `depends_on` in their frontmatter. An issue
+//! is blocked while any of its dependencies is still pending or does not exist,
+//! so issues cannot be archived or deleted while open issues depend on them.
+//! The graph also detects cycles and renders as Mermaid or DOT.
+
+use super::filesystem::IssueInfo;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+/// Output format for a rendered dependency graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphFormat {
+    /// Mermaid flowchart
+    #[default]
+    Mermaid,
+    /// Graphviz DOT
+    Dot,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mermaid" => Ok(GraphFormat::Mermaid),
+            "dot" | "graphviz" => Ok(GraphFormat::Dot),
+            _ => Err(format!(
+                "Invalid graph format '{s}': expected mermaid or dot"
+            )),
+        }
+    }
+}
+
+/// Why an issue cannot be worked on yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Blocker {
+    /// The dependency exists but is not completed
+    Pending(String),
+    /// No issue with this name exists
+    Missing(String),
+}
+
+impl fmt::Display for Blocker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Blocker::Pending(name) => write!(f, "'{name}' is not completed"),
+            Blocker::Missing(name) => write!(f, "'{name}' does not exist"),
+        }
+    }
+}
+
+/// Explain why an issue is blocked, e.g. for tool error messages
+pub fn describe_blockers(name: &str, blockers: &[Blocker]) -> String {
+    let reasons: Vec<String> = blockers.iter().map(|b| b.to_string()).collect();
+    format!(
+        "Issue '{name}' is blocked by its dependencies: {}",
+        reasons.join(", ")
+    )
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    completed: bool,
+    depends_on: Vec<String>,
+}
+
+/// Dependency edges between all known issues
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    nodes: BTreeMap<String, Node>,
+}
+
+impl DependencyGraph {
+    /// Build the graph from pending and completed issues
+    pub fn from_issue_infos(issue_infos: &[IssueInfo]) -> Self {
+        let nodes = issue_infos
+            .iter()
+            .map(|info| {
+                (
+                    info.issue.name.clone(),
+                    Node {
+                        completed: info.completed,
+                        depends_on: info.metadata.depends_on.clone(),
+                    },
+                )
+            })
+            .collect();
+        Self { nodes }
+    }
+
+    /// Whether an issue with this name is known
+    pub fn contains(&self, name: &str) -> bool {
+        self.nodes.contains_key(name)
+    }
+
+    /// Dependencies of an issue that are not satisfied yet
+    pub fn blockers(&self, name: &str) -> Vec<Blocker> {
+        let Some(node) = self.nodes.get(name) else {
+            return Vec::new();
+        };
+        node.depends_on
+            .iter()
+            .filter_map(|dependency| match self.nodes.get(dependency) {
+                Some(dep) if dep.completed => None,
+                Some(_) => Some(Blocker::Pending(dependency.clone())),
+                None => Some(Blocker::Missing(dependency.clone())),
+            })
+            .collect()
+    }
+
+    /// Whether an issue has unsatisfied dependencies
+    pub fn is_blocked(&self, name: &str) -> bool {
+        !self.blockers(name).is_empty()
+    }
+
+    /// Open issues that list `name` among their dependencies
+    pub fn open_dependents(&self, name: &str) -> Vec<String> {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| !node.completed && node.depends_on.iter().any(|d| d == name))
+            .map(|(dependent, _)| dependent.clone())
+            .collect()
+    }
+
+    /// Refuse to remove an issue from the graph while open issues depend on it
+    ///
+    /// Archived and deleted issues are no longer known, so their dependents would
+    /// stay blocked on a missing dependency for good.
+    pub fn ensure_no_open_dependents(&self, name: &str, action: &str) -> Result<(), String> {
+        let dependents = self.open_dependents(name);
+        if dependents.is_empty() {
+            return Ok(());
+        }
+        Err(format!(
+            "Cannot {action} issue '{name}' while open issues depend on it: {}. Remove it from their depends_on first.",
+            dependents.join(", ")
+        ))
+    }
+
+    /// Pending issues that are waiting on dependencies, with the reasons
+    pub fn blocked_issues(&self) -> Vec<(String, Vec<Blocker>)> {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| !node.completed)
+            .filter_map(|(name, _)| {
+                let blockers = self.blockers(name);
+                (!blockers.is_empty()).then(|| (name.clone(), blockers))
+            })
+            .collect()
+    }
+
+    /// Find the cycle that giving `name` these dependencies would create
+    ///
+    /// Returns the cycle as a path starting and ending at `name`.
+    pub fn find_cycle(&self, name: &str, depends_on: &[String]) -> Option<Vec<String>> {
+        let mut visited = BTreeSet::new();
+        for dependency in depends_on {
+            let mut path = vec![name.to_string()];
+            if self.reaches(dependency, name, &mut path, &mut visited) {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// Depth-first search from `current` towards `target` along dependency edges
+    fn reaches(
+        &self,
+        current: &str,
+        target: &str,
+        path: &mut Vec<String>,
+        visited: &mut BTreeSet<String>,
+    ) -> bool {
+        path.push(current.to_string());
+        if current == target {
+            return true;
+        }
+        if visited.insert(current.to_string()) {
+            let edges = match self.nodes.get(current) {
+                Some(node) => node.depends_on.as_slice(),
+                None => &[],
+            };
+            for next in edges {
+                if self.reaches(next, target, path, visited) {
+                    return true;
+                }
+            }
+        }
+        path.pop();
+        false
+    }
+
+    /// Render the graph; edges point from a dependency to the issue waiting on it
+    pub fn render(&self, format: GraphFormat, include_completed: bool) -> String {
+        match format {
+            GraphFormat::Mermaid => self.to_mermaid(include_completed),
+            GraphFormat::Dot => self.to_dot(include_completed),
+        }
+    }
+
+    /// Render as a Mermaid flowchart
+    pub fn to_mermaid(&self, include_completed: bool) -> String {
+        let view = self.view(include_completed);
+        let mut out = String::from("graph TD\n");
+        for (index, (name, state)) in view.nodes.iter().enumerate() {
+            let label = name.replace('"', "'");
+            out.push_str(&format!("    n{index}[\"{label}\"]:::{}\n", state.class()));
+        }
+        for (from, to) in &view.edges {
+            out.push_str(&format!(
+                "    n{} --> n{}\n",
+                view.index(from),
+                view.index(to)
+            ));
+        }
+        out.push_str("    classDef completed fill:#d4edda,stroke:#28a745\n");
+        out.push_str("    classDef ready fill:#cce5ff,stroke:#004085\n");
+        out.push_str("    classDef blocked fill:#fff3cd,stroke:#856404\n");
+        out.push_str("    classDef missing fill:#f8d7da,stroke:#721c24,stroke-dasharray:3\n");
+        out
+    }
+
+    /// Render as a Graphviz DOT digraph
+    pub fn to_dot(&self, include_completed: bool) -> String {
+        let view = self.view(include_completed);
+        let mut out = String::from("digraph issues {\n    rankdir=LR;\n    node [shape=box];\n");
+        for (name, state) in &view.nodes {
+            let style = match state {
+                NodeState::Completed => "style=filled, fillcolor=\"#d4edda\"",
+                NodeState::Ready => "style=filled, fillcolor=\"#cce5ff\"",
+                NodeState::Blocked => "style=filled, fillcolor=\"#fff3cd\"",
+                NodeState::Missing => "style=dashed, color=\"#721c24\"",
+            };
+            out.push_str(&format!("    {} [{style}];\n", dot_id(name)));
+        }
+        for (from, to) in &view.edges {
+            out.push_str(&format!("    {} -> {};\n", dot_id(from), dot_id(to)));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn view(&self, include_completed: bool) -> GraphView {
+        let mut nodes = BTreeMap::new();
+        let mut edges = Vec::new();
+        for (name, node) in &self.nodes {
+            if node.completed && !include_completed {
+                continue;
+            }
+            let state = if node.completed {
+                NodeState::Completed
+            } else if self.is_blocked(name) {
+                NodeState::Blocked
+            } else {
+                NodeState::Ready
+            };
+            nodes.insert(name.clone(), state);
+        }
+        for (name, node) in &self.nodes {
+            if !nodes.contains_key(name) {
+                continue;
+            }
+            for dependency in &node.depends_on {
+                match self.nodes.get(dependency) {
+                    Some(dep) if dep.completed && !include_completed => continue,
+                    Some(_) => {}
+                    None => {
+                        nodes.insert(dependency.clone(), NodeState::Missing);
+                    }
+                }
+                edges.push((dependency.clone(), name.clone()));
+            }
+        }
+        GraphView {
+            nodes: nodes.into_iter().collect(),
+            edges,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum NodeState {
+    Completed,
+    Ready,
+    Blocked,
+    Missing,
+}
+
+impl NodeState {
+    fn class(self) -> &'static str {
+        match self {
+            NodeState::Completed => "completed",
+            NodeState::Ready => "ready",
+            NodeState::Blocked => "blocked",
+            NodeState::Missing => "missing",
+        }
+    }
+}
+
+struct GraphView {
+    nodes: Vec<(String, NodeState)>,
+    edges: Vec<(String, String)>,
+}
+
+impl GraphView {
+    fn index(&self, name: &str) -> usize {
+        self.nodes
+            .iter()
+            .position(|(n, _)| n == name)
+            .unwrap_or_default()
+    }
+}
+
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::filesystem::Issue;
+    use std::path::PathBuf;
+
+    fn info(name: &str, completed: bool, depends_on: &[&str]) -> IssueInfo {
+        let content = if depends_on.is_empty() {
+            format!("# {name}")
+        } else {
+            format!(
+                "---\ndepends_on: [{}]\n---\n# {name}",
+                depends_on.join(", ")
+            )
+        };
+        IssueInfo {
+            metadata: crate::issues::parse_issue_content(&content).0,
+            issue: Issue {
+                name: name.to_string(),
+                content,
+            },
+            completed,
+            file_path: PathBuf::from(format!("{name}.md")),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_blockers_pending_and_missing() {
+        let graph = DependencyGraph::from_issue_infos(&[
+            info("a", true, &[]),
+            info("b", false, &["a"]),
+            info("c", false, &["b", "ghost"]),
+        ]);
+
+        assert!(!graph.is_blocked("b"));
+        assert_eq!(
+            graph.blockers("c"),
+            vec![
+                Blocker::Pending("b".to_string()),
+                Blocker::Missing("ghost".to_string())
+            ]
+        );
+        assert_eq!(graph.blocked_issues().len(), 1);
+        assert!(describe_blockers("c", &graph.blockers("c")).contains("'ghost' does not exist"));
+    }
+
+    #[test]
+    fn test_open_dependents() {
+        let graph = DependencyGraph::from_issue_infos(&[
+            info("a", true, &[]),
+            info("b", false, &["a"]),
+            info("c", true, &["a"]),
+            info("d", false, &[]),
+        ]);
+
+        assert_eq!(graph.open_dependents("a"), vec!["b".to_string()]);
+        let error = graph.ensure_no_open_dependents("a", "archive").unwrap_err();
+        assert!(error.starts_with("Cannot archive issue 'a' while open issues depend on it: b."));
+        assert!(graph.ensure_no_open_dependents("d", "delete").is_ok());
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let graph = DependencyGraph::from_issue_infos(&[
+            info("a", false, &[]),
+            info("b", false, &["a"]),
+            info("c", false, &["b"]),
+        ]);
+
+        assert_eq!(
+            graph.find_cycle("a", &["c".to_string()]),
+            Some(vec![
+                "a".to_string(),
+                "c".to_string(),
+                "b".to_string(),
+                "a".to_string()
+            ])
+        );
+        assert_eq!(
+            graph.find_cycle("d", &["d".to_string()]),
+            Some(vec!["d".to_string(), "d".to_string()])
+        );
+        assert_eq!(graph.find_cycle("d", &["c".to_string()]), None);
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let graph = DependencyGraph::from_issue_infos(&[
+            info("setup", true, &[]),
+            info("api", false, &["setup"]),
+            info("ui", false, &["api"]),
+        ]);
+
+        let mermaid = graph.render(GraphFormat::Mermaid, false);
+        assert!(mermaid.starts_with("graph TD\n"));
+        assert!(mermaid.contains("n0[\"api\"]:::ready"));
+        assert!(mermaid.contains("n1[\"ui\"]:::blocked"));
+        assert!(mermaid.contains("n0 --> n1"));
+        assert!(!mermaid.contains("setup"));
+
+        let dot = graph.render(GraphFormat::Dot, true);
+        assert!(dot.contains("\"setup\" -> \"api\";"));
+        assert!(dot.contains("\"api\" -> \"ui\";"));
+        assert_eq!("DOT".parse::<GraphFormat>(), Ok(GraphFormat::Dot));
+    }
+}
@@ -0,0 +1,787 @@
+//! Import and export of issues in GitHub-compatible formats
+//!
+//! Issues are exchanged as records holding a title, body, labels, assignee and
+//! state, in one of three formats:
+//!
+//! - `github-json`: an array of objects shaped like GitHub issues, as produced by
+//!   the REST API or `gh issue list --json number,title,body,labels,state`
+//! - `csv`: one row per issue with `id,name,title,state,state_reason,labels,assignee,body`
+//! - `markdown`: a readable report, export only
+//!
+//! Every record carries a stable ID so importing the same dump again updates the
+//! issues it created instead of duplicating them. The ID is the `external_id`
+//! frontmatter key, `github#<number>` for GitHub dumps, and falls back to the
+//! issue name for issues that never came from another tracker.
+
+use super::filesystem::{IssueInfo, IssueStorage};
+use super::metadata::{
+    parse_issue_content, render_issue_content, IssueMetadata, IssueMetadataUpdate,
+};
+use super::status::IssueStatus;
+use crate::error::{Result, SwissArmyHammerError};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Prefix of stable IDs derived from GitHub issue numbers
+const GITHUB_ID_PREFIX: &str = "github#";
+
+/// Columns of the CSV format, in order
+const CSV_HEADERS: [&str; 8] = [
+    "id",
+    "name",
+    "title",
+    "state",
+    "state_reason",
+    "labels",
+    "assignee",
+    "body",
+];
+
+/// File format used to exchange issues
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueExchangeFormat {
+    /// Array of GitHub issue objects
+    GithubJson,
+    /// Comma separated values with a header row
+    Csv,
+    /// Markdown report, export only
+    Markdown,
+}
+
+impl IssueExchangeFormat {
+    /// Name used in parameters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueExchangeFormat::GithubJson => "github-json",
+            IssueExchangeFormat::Csv => "csv",
+            IssueExchangeFormat::Markdown => "markdown",
+        }
+    }
+
+    /// Guess the format from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(IssueExchangeFormat::GithubJson),
+            "csv" => Some(IssueExchangeFormat::Csv),
+            "md" | "markdown" => Some(IssueExchangeFormat::Markdown),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for IssueExchangeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IssueExchangeFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "github-json" | "json" => Ok(IssueExchangeFormat::GithubJson),
+            "csv" => Ok(IssueExchangeFormat::Csv),
+            "markdown" | "md" => Ok(IssueExchangeFormat::Markdown),
+            _ => Err(format!(
+                "Invalid format '{s}': expected one of github-json, csv, markdown"
+            )),
+        }
+    }
+}
+
+/// State of an exchanged issue, following GitHub's `state` and `state_reason`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueRecordState {
+    /// Not closed yet
+    Open,
+    /// Closed as done
+    Completed,
+    /// Closed without being done
+    NotPlanned,
+}
+
+impl IssueRecordState {
+    /// State an issue status is exported as
+    pub fn from_status(status: IssueStatus) -> Self {
+        match status {
+            IssueStatus::Done => IssueRecordState::Completed,
+            IssueStatus::WontFix => IssueRecordState::NotPlanned,
+            _ => IssueRecordState::Open,
+        }
+    }
+
+    /// Parse GitHub's `state` and `state_reason`, case-insensitively
+    fn parse(state: &str, reason: Option<&str>) -> std::result::Result<Self, String> {
+        match state.trim().to_ascii_lowercase().as_str() {
+            "" | "open" => Ok(IssueRecordState::Open),
+            "closed" => Ok(match reason.map(|r| r.trim().to_ascii_lowercase()) {
+                Some(reason) if reason == "not_planned" => IssueRecordState::NotPlanned,
+                _ => IssueRecordState::Completed,
+            }),
+            other => Err(format!("Invalid state '{other}': expected open or closed")),
+        }
+    }
+
+    /// GitHub `state` value
+    pub fn state(&self) -> &'static str {
+        match self {
+            IssueRecordState::Open => "open",
+            IssueRecordState::Completed | IssueRecordState::NotPlanned => "closed",
+        }
+    }
+
+    /// GitHub `state_reason` value, only set for closed issues
+    pub fn state_reason(&self) -> Option<&'static str> {
+        match self {
+            IssueRecordState::Open => None,
+            IssueRecordState::Completed => Some("completed"),
+            IssueRecordState::NotPlanned => Some("not_planned"),
+        }
+    }
+
+    /// Status an issue currently in `current` moves to, `None` when it already matches
+    ///
+    /// Open issues keep their open status, e.g. `in_progress` stays as it is.
+    fn target_status(&self, current: IssueStatus) -> Option<IssueStatus> {
+        let target = match self {
+            IssueRecordState::Open if current.is_closed() => IssueStatus::Todo,
+            IssueRecordState::Open => current,
+            IssueRecordState::Completed => IssueStatus::Done,
+            IssueRecordState::NotPlanned => IssueStatus::WontFix,
+        };
+        (target != current).then_some(target)
+    }
+}
+
+impl fmt::Display for IssueRecordState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.state_reason() {
+            Some(reason) => write!(f, "{} ({reason})", self.state()),
+            None => f.write_str(self.state()),
+        }
+    }
+}
+
+/// An issue as exchanged with other trackers
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueRecord {
+    /// Stable ID used to match records to issues
+    pub id: String,
+    /// Issue name, when the record came from SwissArmyHammer
+    pub name: Option<String>,
+    /// Issue title, the first `# ` heading of the issue body
+    pub title: String,
+    /// Issue body without the title heading
+    pub body: String,
+    /// Labels
+    pub labels: Vec<String>,
+    /// Who the issue is assigned to
+    pub assignee: Option<String>,
+    /// Open or closed
+    pub state: IssueRecordState,
+}
+
+impl IssueRecord {
+    /// Build the record for an issue
+    pub fn from_issue_info(info: &IssueInfo) -> Self {
+        let (title, body) = split_title(info.issue.body());
+        Self {
+            id: stable_id(&info.metadata, &info.issue.name),
+            name: Some(info.issue.name.clone()),
+            title: title.unwrap_or(&info.issue.name).to_string(),
+            body: body.trim().to_string(),
+            labels: info.metadata.labels.clone(),
+            assignee: info.metadata.assignee.clone(),
+            state: IssueRecordState::from_status(info.status()),
+        }
+    }
+
+    /// Issue name to create the record under
+    fn issue_name(&self) -> String {
+        if let Some(name) = self.name.as_deref().filter(|n| !n.trim().is_empty()) {
+            return name.trim().to_string();
+        }
+        match self.id.strip_prefix(GITHUB_ID_PREFIX) {
+            Some(number) => format!("{:0>6}_{}", number, slugify(&self.title)),
+            None => slugify(&self.id),
+        }
+    }
+
+    /// Issue body with the title heading
+    fn issue_body(&self) -> String {
+        if self.body.is_empty() {
+            format!("# {}\n", self.title)
+        } else {
+            format!("# {}\n\n{}\n", self.title, self.body)
+        }
+    }
+}
+
+/// A field an import changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    /// Name of the field
+    pub field: &'static str,
+    /// Value before the import
+    pub from: String,
+    /// Value after the import
+    pub to: String,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} → {}", self.field, self.from, self.to)
+    }
+}
+
+/// What an import does with a record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportAction {
+    /// A new issue is created
+    Create,
+    /// An existing issue changes
+    Update(Vec<FieldChange>),
+    /// The issue already matches the record
+    Unchanged,
+}
+
+/// The outcome of importing one record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportChange {
+    /// Stable ID of the record
+    pub id: String,
+    /// Name of the issue the record maps to
+    pub name: String,
+    /// What happens to the issue
+    pub action: ImportAction,
+}
+
+impl fmt::Display for ImportChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.action {
+            ImportAction::Create => write!(f, "+ {} ({})", self.name, self.id),
+            ImportAction::Unchanged => write!(f, "= {} ({})", self.name, self.id),
+            ImportAction::Update(changes) => {
+                write!(f, "~ {} ({})", self.name, self.id)?;
+                for change in changes {
+                    write!(f, "\n    {change}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Stable ID of an issue, its `external_id` or else its name
+fn stable_id(metadata: &IssueMetadata, name: &str) -> String {
+    metadata
+        .external_id
+        .clone()
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Split the leading `# ` heading off an issue body
+fn split_title(body: &str) -> (Option<&str>, &str) {
+    let trimmed = body.trim_start();
+    let first_line = trimmed.lines().next().unwrap_or_default();
+    match first_line.strip_prefix("# ") {
+        Some(title) => (
+            Some(title.trim()),
+            trimmed[first_line.len()..].trim_start_matches(['\r', '\n']),
+        ),
+        None => (None, body),
+    }
+}
+
+/// Lowercase, dash separated form of a title or ID usable as an issue name
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug.trim_end_matches('-').chars().take(50).collect();
+    match slug.trim_end_matches('-') {
+        "" => "issue".to_string(),
+        slug => slug.to_string(),
+    }
+}
+
+/// Label as found in GitHub dumps, a plain name or an object with a name
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum GithubLabel {
+    Name(String),
+    Object { name: String },
+}
+
+/// User as found in GitHub dumps
+#[derive(Debug, Serialize, Deserialize)]
+struct GithubUser {
+    login: String,
+}
+
+/// Issue object as found in GitHub dumps, extra fields are ignored
+#[derive(Debug, Serialize, Deserialize)]
+struct GithubIssue {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    number: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    external_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    state_reason: Option<String>,
+    #[serde(default)]
+    labels: Vec<GithubLabel>,
+    #[serde(default)]
+    assignees: Vec<GithubUser>,
+    #[serde(default, skip_serializing)]
+    assignee: Option<GithubUser>,
+}
+
+impl GithubIssue {
+    fn from_record(record: &IssueRecord) -> Self {
+        Self {
+            number: record
+                .id
+                .strip_prefix(GITHUB_ID_PREFIX)
+                .and_then(|number| number.parse().ok()),
+            external_id: Some(record.id.clone()),
+            name: record.name.clone(),
+            title: record.title.clone(),
+            body: Some(record.body.clone()),
+            state: record.state.state().to_string(),
+            state_reason: record.state.state_reason().map(str::to_string),
+            labels: record
+                .labels
+                .iter()
+                .map(|name| GithubLabel::Object { name: name.clone() })
+                .collect(),
+            assignees: record
+                .assignee
+                .iter()
+                .map(|login| GithubUser {
+                    login: login.clone(),
+                })
+                .collect(),
+            assignee: None,
+        }
+    }
+
+    fn into_record(self, position: usize) -> std::result::Result<IssueRecord, String> {
+        let id = match (self.external_id, self.number, &self.name) {
+            (Some(id), _, _) if !id.trim().is_empty() => id.trim().to_string(),
+            (_, Some(number), _) => format!("{GITHUB_ID_PREFIX}{number}"),
+            (_, _, Some(name)) if !name.trim().is_empty() => name.trim().to_string(),
+            _ => {
+                return Err(format!(
+                    "Issue {position} has no external_id, number or name"
+                ))
+            }
+        };
+        let state = IssueRecordState::parse(&self.state, self.state_reason.as_deref())
+            .map_err(|e| format!("Issue {position}: {e}"))?;
+        let assignee = self
+            .assignee
+            .or_else(|| self.assignees.into_iter().next())
+            .map(|user| user.login);
+        Ok(IssueRecord {
+            id,
+            name: self.name,
+            title: self.title.trim().to_string(),
+            body: self.body.unwrap_or_default().trim().to_string(),
+            labels: self
+                .labels
+                .into_iter()
+                .map(|label| match label {
+                    GithubLabel::Name(name) | GithubLabel::Object { name } => name,
+                })
+                .collect(),
+            assignee,
+            state,
+        })
+    }
+}
+
+fn exchange_error(format: IssueExchangeFormat, message: impl fmt::Display) -> SwissArmyHammerError {
+    SwissArmyHammerError::Other(format!("Invalid {format} issue data: {message}"))
+}
+
+/// Render records in an exchange format
+pub fn export_records(records: &[IssueRecord], format: IssueExchangeFormat) -> Result<String> {
+    match format {
+        IssueExchangeFormat::GithubJson => {
+            let issues: Vec<GithubIssue> = records.iter().map(GithubIssue::from_record).collect();
+            Ok(serde_json::to_string_pretty(&issues)? + "\n")
+        }
+        IssueExchangeFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            let csv_error = |e: csv::Error| exchange_error(format, e);
+            writer.write_record(CSV_HEADERS).map_err(csv_error)?;
+            for record in records {
+                writer
+                    .write_record([
+                        record.id.as_str(),
+                        record.name.as_deref().unwrap_or_default(),
+                        record.title.as_str(),
+                        record.state.state(),
+                        record.state.state_reason().unwrap_or_default(),
+                        record.labels.join(", ").as_str(),
+                        record.assignee.as_deref().unwrap_or_default(),
+                        record.body.as_str(),
+                    ])
+                    .map_err(csv_error)?;
+            }
+            let bytes = writer.into_inner().map_err(|e| exchange_error(format, e))?;
+            String::from_utf8(bytes).map_err(|e| exchange_error(format, e))
+        }
+        IssueExchangeFormat::Markdown => {
+            let mut output = String::from("# Issues\n");
+            for record in records {
+                output.push_str(&format!("\n## {}\n\n", record.title));
+                output.push_str(&format!("- **ID:** {}\n", record.id));
+                if let Some(name) = &record.name {
+                    output.push_str(&format!("- **Name:** {name}\n"));
+                }
+                output.push_str(&format!("- **State:** {}\n", record.state));
+                if !record.labels.is_empty() {
+                    output.push_str(&format!("- **Labels:** {}\n", record.labels.join(", ")));
+                }
+                if let Some(assignee) = &record.assignee {
+                    output.push_str(&format!("- **Assignee:** {assignee}\n"));
+                }
+                if !record.body.is_empty() {
+                    output.push_str(&format!("\n{}\n", record.body));
+                }
+            }
+            Ok(output)
+        }
+    }
+}
+
+/// Parse records from an exchange format
+pub fn parse_records(content: &str, format: IssueExchangeFormat) -> Result<Vec<IssueRecord>> {
+    let records: Vec<IssueRecord> = match format {
+        IssueExchangeFormat::GithubJson => {
+            let issues: Vec<GithubIssue> =
+                serde_json::from_str(content).map_err(|e| exchange_error(format, e))?;
+            issues
+                .into_iter()
+                .enumerate()
+                .map(|(i, issue)| issue.into_record(i + 1))
+                .collect::<std::result::Result<_, _>>()
+                .map_err(|e| exchange_error(format, e))?
+        }
+        IssueExchangeFormat::Csv => {
+            let mut reader = csv::Reader::from_reader(content.as_bytes());
+            let mut records = Vec::new();
+            for (i, row) in reader.deserialize::<HashMap<String, String>>().enumerate() {
+                let row = row.map_err(|e| exchange_error(format, e))?;
+                let field = |key: &str| row.get(key).map(|v| v.trim()).unwrap_or_default();
+                let name = Some(field("name").to_string()).filter(|n| !n.is_empty());
+                let id = match (field("id"), &name) {
+                    ("", Some(name)) => name.clone(),
+                    ("", None) => {
+                        return Err(exchange_error(
+                            format,
+                            format!("Row {} has no id or name", i + 1),
+                        ))
+                    }
+                    (id, _) => id.to_string(),
+                };
+                let state_reason = Some(field("state_reason")).filter(|r| !r.is_empty());
+                records.push(IssueRecord {
+                    id,
+                    name,
+                    title: field("title").to_string(),
+                    body: field("body").to_string(),
+                    labels: field("labels")
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|l| !l.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                    assignee: Some(field("assignee").to_string()).filter(|a| !a.is_empty()),
+                    state: IssueRecordState::parse(field("state"), state_reason)
+                        .map_err(|e| exchange_error(format, format!("Row {}: {e}", i + 1)))?,
+                });
+            }
+            records
+        }
+        IssueExchangeFormat::Markdown => {
+            return Err(SwissArmyHammerError::Other(
+                "Markdown exports cannot be imported, use github-json or csv".to_string(),
+            ))
+        }
+    };
+
+    let mut seen = HashSet::new();
+    for record in &records {
+        if record.title.is_empty() {
+            return Err(exchange_error(
+                format,
+                format!("'{}' has no title", record.id),
+            ));
+        }
+        if !seen.insert(record.id.as_str()) {
+            return Err(exchange_error(
+                format,
+                format!("'{}' appears more than once", record.id),
+            ));
+        }
+    }
+    Ok(records)
+}
+
+/// Metadata after applying a record, along with the changed fields
+fn merge_metadata(
+    metadata: &IssueMetadata,
+    record: &IssueRecord,
+) -> (IssueMetadata, Vec<FieldChange>) {
+    let mut merged = metadata.clone();
+    merged.apply(&IssueMetadataUpdate {
+        labels: Some(record.labels.clone()),
+        assignee: Some(record.assignee.clone().unwrap_or_default()),
+        ..Default::default()
+    });
+    if merged.external_id.is_some() || !record.name.as_ref().is_some_and(|n| n == &record.id) {
+        merged.external_id = Some(record.id.clone());
+    }
+
+    let mut changes = Vec::new();
+    if merged.labels != metadata.labels {
+        changes.push(FieldChange {
+            field: "labels",
+            from: metadata.labels.join(", "),
+            to: merged.labels.join(", "),
+        });
+    }
+    if merged.assignee != metadata.assignee {
+        changes.push(FieldChange {
+            field: "assignee",
+            from: metadata.assignee.clone().unwrap_or_default(),
+            to: merged.assignee.clone().unwrap_or_default(),
+        });
+    }
+    (merged, changes)
+}
+
+/// Work out, and unless `dry_run` is set apply, what importing records changes
+///
+/// Records are matched to active and completed issues by stable ID. Existing
+/// issues keep their other metadata, status history and open status; only the
+/// title, body, labels, assignee and whether the issue is closed are taken from
+/// the record.
+pub async fn import_records(
+    storage: &dyn IssueStorage,
+    records: &[IssueRecord],
+    dry_run: bool,
+) -> Result<Vec<ImportChange>> {
+    let existing: HashMap<String, IssueInfo> = storage
+        .list_issues_info()
+        .await?
+        .into_iter()
+        .map(|info| (stable_id(&info.metadata, &info.issue.name), info))
+        .collect();
+
+    let mut changes = Vec::new();
+    for record in records {
+        let Some(info) = existing.get(&record.id) else {
+            let name = record.issue_name();
+            if !dry_run {
+                let (metadata, _) = merge_metadata(&IssueMetadata::default(), record);
+                storage
+                    .create_issue(
+                        name.clone(),
+                        render_issue_content(&metadata, &record.issue_body()),
+                    )
+                    .await?;
+                if let Some(status) = record.state.target_status(IssueStatus::Todo) {
+                    storage.set_issue_status(&name, status).await?;
+                }
+            }
+            changes.push(ImportChange {
+                id: record.id.clone(),
+                name,
+                action: ImportAction::Create,
+            });
+            continue;
+        };
+
+        let name = info.issue.name.clone();
+        let (metadata, body) = parse_issue_content(&info.issue.content);
+        let current = IssueRecord::from_issue_info(info);
+        let mut field_changes = Vec::new();
+        if current.title != record.title {
+            field_changes.push(FieldChange {
+                field: "title",
+                from: current.title.clone(),
+                to: record.title.clone(),
+            });
+        }
+        if current.body != record.body {
+            field_changes.push(FieldChange {
+                field: "body",
+                from: format!("{} lines", current.body.lines().count()),
+                to: format!("{} lines", record.body.lines().count()),
+            });
+        }
+        let (merged, metadata_changes) = merge_metadata(&metadata, record);
+        field_changes.extend(metadata_changes);
+        let target_status = record.state.target_status(info.status());
+        if let Some(status) = target_status {
+            field_changes.push(FieldChange {
+                field: "status",
+                from: info.status().to_string(),
+                to: status.to_string(),
+            });
+        }
+
+        if field_changes.is_empty() && merged == metadata {
+            changes.push(ImportChange {
+                id: record.id.clone(),
+                name,
+                action: ImportAction::Unchanged,
+            });
+            continue;
+        }
+
+        if !dry_run {
+            let body = if current.title == record.title && current.body == record.body {
+                body.to_string()
+            } else {
+                record.issue_body()
+            };
+            storage
+                .update_issue(&name, render_issue_content(&merged, &body))
+                .await?;
+            match target_status {
+                Some(IssueStatus::Todo) => {
+                    storage.reopen_issue(&name).await?;
+                }
+                Some(status) => {
+                    storage.set_issue_status(&name, status).await?;
+                }
+                None => {}
+            }
+        }
+        changes.push(ImportChange {
+            id: record.id.clone(),
+            name,
+            action: ImportAction::Update(field_changes),
+        });
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::FileSystemIssueStorage;
+    use tempfile::TempDir;
+
+    const GITHUB_DUMP: &str = r#"[
+        {"number": 12, "title": "Fix login", "body": "Tokens expire.\r\n", "state": "OPEN",
+         "labels": [{"name": "bug", "color": "d73a4a"}], "assignees": [{"login": "alice"}]},
+        {"number": 13, "title": "Drop IE support", "body": null, "state": "closed",
+         "state_reason": "not_planned", "labels": ["legacy"]}
+    ]"#;
+
+    #[test]
+    fn test_parse_github_json_and_csv_round_trip() {
+        let records = parse_records(GITHUB_DUMP, IssueExchangeFormat::GithubJson).unwrap();
+        assert_eq!(records[0].id, "github#12");
+        assert_eq!(records[0].issue_name(), "000012_fix-login");
+        assert_eq!(records[0].labels, vec!["bug"]);
+        assert_eq!(records[0].assignee.as_deref(), Some("alice"));
+        assert_eq!(records[0].body, "Tokens expire.");
+        assert_eq!(records[1].state, IssueRecordState::NotPlanned);
+        assert_eq!(records[1].body, "");
+
+        let csv = export_records(&records, IssueExchangeFormat::Csv).unwrap();
+        assert!(csv.starts_with("id,name,title,state,state_reason,labels,assignee,body\n"));
+        assert_eq!(
+            parse_records(&csv, IssueExchangeFormat::Csv).unwrap(),
+            records
+        );
+
+        let json = export_records(&records, IssueExchangeFormat::GithubJson).unwrap();
+        assert_eq!(
+            parse_records(&json, IssueExchangeFormat::GithubJson).unwrap(),
+            records
+        );
+
+        let markdown = export_records(&records, IssueExchangeFormat::Markdown).unwrap();
+        assert!(markdown.contains("## Drop IE support"));
+        assert!(markdown.contains("- **State:** closed (not_planned)"));
+        assert!(parse_records(&markdown, IssueExchangeFormat::Markdown).is_err());
+
+        let duplicated = r#"[{"number": 1, "title": "A"}, {"number": 1, "title": "B"}]"#;
+        assert!(parse_records(duplicated, IssueExchangeFormat::GithubJson).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_is_idempotent_and_updates_by_stable_id() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileSystemIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+        let records = parse_records(GITHUB_DUMP, IssueExchangeFormat::GithubJson).unwrap();
+
+        let planned = import_records(&storage, &records, true).await.unwrap();
+        assert!(planned.iter().all(|c| c.action == ImportAction::Create));
+        assert!(storage.list_issues().await.unwrap().is_empty());
+
+        import_records(&storage, &records, false).await.unwrap();
+        let info = storage.get_issue_info("000012_fix-login").await.unwrap();
+        assert_eq!(info.metadata.external_id.as_deref(), Some("github#12"));
+        assert_eq!(info.issue.body(), "# Fix login\n\nTokens expire.\n");
+        let dropped = storage
+            .get_issue_info("000013_drop-ie-support")
+            .await
+            .unwrap();
+        assert_eq!(dropped.status(), IssueStatus::WontFix);
+
+        let again = import_records(&storage, &records, false).await.unwrap();
+        assert!(again.iter().all(|c| c.action == ImportAction::Unchanged));
+        assert_eq!(storage.list_issues().await.unwrap().len(), 2);
+
+        let mut edited = records.clone();
+        edited[0].title = "Fix login tokens".to_string();
+        edited[0].state = IssueRecordState::Completed;
+        let changes = import_records(&storage, &edited, false).await.unwrap();
+        let ImportAction::Update(fields) = &changes[0].action else {
+            panic!("expected an update, got {:?}", changes[0].action);
+        };
+        let names: Vec<_> = fields.iter().map(|c| c.field).collect();
+        assert_eq!(names, vec!["title", "status"]);
+        let info = storage.get_issue_info("000012_fix-login").await.unwrap();
+        assert!(info.completed);
+        assert_eq!(info.metadata.labels, vec!["bug"]);
+
+        // Native issues export under their name and import back unchanged
+        storage
+            .create_issue("native".to_string(), "Plain body".to_string())
+            .await
+            .unwrap();
+        let infos = storage.list_issues_info().await.unwrap();
+        let exported: Vec<IssueRecord> = infos.iter().map(IssueRecord::from_issue_info).collect();
+        let changes = import_records(&storage, &exported, false).await.unwrap();
+        assert!(changes.iter().all(|c| c.action == ImportAction::Unchanged));
+        assert_eq!(
+            storage.get_issue("native").await.unwrap().content,
+            "Plain body"
+        );
+    }
+}
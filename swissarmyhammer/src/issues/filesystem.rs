@@ -1,10 +1,22 @@
+use super::activity::{
+    activity_log_path, append_activity_log, read_activity_log, IssueActivity, IssueActivityKind,
+};
+use super::backend::{IssueLocation, IssueStorageBackend, StoredIssue};
+use super::criteria::{parse_criteria, AcceptanceCriterion, CriteriaProgress};
+use super::dependencies::DependencyGraph;
+use super::hierarchy::IssueHierarchy;
+use super::lease::{IssueLease, LeaseStore};
+use super::metadata::{parse_issue_content, render_issue_content, IssueMetadata};
+use super::next::NextIssue;
+use super::search::{IssueSearchEngine, IssueSearchOptions, IssueSearchResult};
+use super::status::{resolve_status, FlowMetrics, IssueStatus};
 use crate::common::generate_monotonic_ulid_string;
 use crate::error::{Result, SwissArmyHammerError};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OnceCell};
 use tracing::{debug, warn};
 
 // IssueNumber type eliminated - we now use issue names (filename without .md) as the primary identifier
@@ -19,6 +31,26 @@ pub struct Issue {
 }
 
 impl Issue {
+    /// Metadata from the issue's frontmatter, empty when there is none
+    pub fn metadata(&self) -> IssueMetadata {
+        parse_issue_content(&self.content).0
+    }
+
+    /// The issue content without its frontmatter
+    pub fn body(&self) -> &str {
+        parse_issue_content(&self.content).1
+    }
+
+    /// Acceptance criteria from the checklist items in the body
+    pub fn criteria(&self) -> Vec<AcceptanceCriterion> {
+        parse_criteria(self.body())
+    }
+
+    /// How many acceptance criteria are ticked
+    pub fn criteria_progress(&self) -> CriteriaProgress {
+        CriteriaProgress::of(&self.criteria())
+    }
+
     /// Check if this issue is completed based on file path location
     pub fn is_completed(&self, file_path: &Path, completed_dir: &Path) -> bool {
         file_path
@@ -59,6 +91,8 @@ pub struct IssueInfo {
     pub file_path: PathBuf,
     /// When this issue was created
     pub created_at: DateTime<Utc>,
+    /// Metadata parsed from the issue's frontmatter
+    pub metadata: IssueMetadata,
 }
 
 impl IssueInfo {
@@ -66,14 +100,26 @@ impl IssueInfo {
     pub fn from_issue_and_path(issue: Issue, file_path: PathBuf, completed_dir: &Path) -> Self {
         let completed = issue.is_completed(&file_path, completed_dir);
         let created_at = Issue::get_created_at(&file_path);
+        let metadata = issue.metadata();
 
         Self {
             issue,
             completed,
             file_path,
             created_at,
+            metadata,
         }
     }
+
+    /// Lifecycle status from the frontmatter and the file location
+    pub fn status(&self) -> IssueStatus {
+        resolve_status(&self.metadata, self.completed)
+    }
+
+    /// Flow timings from the recorded status transitions
+    pub fn flow_metrics(&self) -> FlowMetrics {
+        FlowMetrics::from_transitions(&self.metadata.transitions)
+    }
 }
 
 /// Represents the current state of the issue system
@@ -83,6 +129,8 @@ pub struct IssueState {
     pub issues_dir: PathBuf,
     /// Path to the completed issues directory
     pub completed_dir: PathBuf,
+    /// Path to the archived issues directory
+    pub archived_dir: PathBuf,
 }
 
 /// Trait for issue storage operations
@@ -112,11 +160,68 @@ pub trait IssueStorage: Send + Sync {
     /// Mark an issue as completed by name
     async fn complete_issue(&self, name: &str) -> Result<Issue>;
 
-    /// Get the next available issue (first pending issue alphabetically)
-    async fn next_issue(&self) -> Result<Option<Issue>>;
+    /// Move an issue to a new status, recording the transition
+    ///
+    /// Closed statuses move the issue to the completed directory and open ones move
+    /// it back. Setting the status an issue already has changes nothing.
+    async fn set_issue_status(&self, name: &str, status: IssueStatus) -> Result<Issue>;
+
+    /// Move a completed or archived issue back to the active directory as `todo`
+    async fn reopen_issue(&self, name: &str) -> Result<Issue>;
+
+    /// Move an issue to the archive, hiding it from listings and completion checks
+    async fn archive_issue(&self, name: &str) -> Result<Issue>;
+
+    /// Search active and completed issues
+    async fn search_issues(
+        &self,
+        query: &str,
+        options: &IssueSearchOptions,
+    ) -> Result<Vec<IssueSearchResult>>;
+
+    /// Append an entry to an issue's activity log
+    async fn add_issue_activity(&self, name: &str, entry: IssueActivity) -> Result<IssueActivity>;
+
+    /// Read an issue's activity log in chronological order
+    async fn issue_activity(&self, name: &str) -> Result<Vec<IssueActivity>>;
+
+    /// Take or renew the lease on an open issue for `owner`
+    ///
+    /// Fails while another owner holds an unexpired lease on the issue.
+    async fn claim_issue(&self, name: &str, owner: &str, ttl_seconds: u64) -> Result<IssueLease>;
+
+    /// Give up the lease on an issue, returning whether there was one
+    ///
+    /// Only the owner can release an unexpired lease unless `force` is set.
+    async fn release_issue(&self, name: &str, owner: &str, force: bool) -> Result<bool>;
+
+    /// All unexpired issue leases, sorted by issue name
+    async fn issue_leases(&self) -> Result<Vec<IssueLease>>;
+
+    /// Get the next available issue (most urgent unblocked and unclaimed pending issue
+    /// without open sub-issues, then alphabetically), or why none can be worked on
+    async fn next_issue(&self) -> Result<NextIssue>;
 
     /// Check if all issues are completed
     async fn all_issues_completed(&self) -> Result<bool>;
+
+    /// The backend this storage belongs to
+    fn backend(&self) -> IssueStorageBackend;
+
+    /// Directory holding the issues, their leases and the search index
+    fn issues_directory(&self) -> &Path;
+
+    /// Every issue, archived ones included, with its location and activity log,
+    /// sorted by name
+    async fn snapshot_issues(&self) -> Result<Vec<StoredIssue>>;
+
+    /// Store issues exactly as given, as when migrating from another backend
+    ///
+    /// Fails without storing anything if one of the issues already exists.
+    async fn restore_issues(&self, issues: Vec<StoredIssue>) -> Result<()>;
+
+    /// Delete every issue and activity log, archived ones included
+    async fn remove_all_issues(&self) -> Result<()>;
 }
 
 /// File-system based issue storage implementation
@@ -126,6 +231,12 @@ pub struct FileSystemIssueStorage {
     issues_dir: PathBuf,
     /// Directory for completed issues
     completed_dir: PathBuf,
+    /// Directory for archived issues
+    archived_dir: PathBuf,
+    /// Search index, opened on first search
+    search_engine: OnceCell<IssueSearchEngine>,
+    /// Leases held by agents working on issues
+    leases: LeaseStore,
     /// Locking mechanism for thread safety
     lock: Mutex<()>,
 }
@@ -134,14 +245,18 @@ impl FileSystemIssueStorage {
     /// Create a new FileSystemIssueStorage with a specific directory
     pub fn new(issues_dir: PathBuf) -> Result<Self> {
         let completed_dir = issues_dir.join("complete");
+        let archived_dir = issues_dir.join("archive");
 
         // Create directories if they don't exist
         fs::create_dir_all(&issues_dir).map_err(SwissArmyHammerError::Io)?;
         fs::create_dir_all(&completed_dir).map_err(SwissArmyHammerError::Io)?;
 
         Ok(Self {
+            leases: LeaseStore::new(&issues_dir),
             issues_dir,
             completed_dir,
+            archived_dir,
+            search_engine: OnceCell::new(),
             lock: Mutex::new(()),
         })
     }
@@ -180,6 +295,7 @@ impl FileSystemIssueStorage {
         IssueState {
             issues_dir: self.issues_dir.clone(),
             completed_dir: self.completed_dir.clone(),
+            archived_dir: self.archived_dir.clone(),
         }
     }
 
@@ -246,6 +362,200 @@ impl FileSystemIssueStorage {
         Ok(None)
     }
 
+    /// Load active and completed issues with extended information, sorted by name
+    fn load_issue_infos(&self) -> Result<Vec<IssueInfo>> {
+        let mut issues_info = Vec::new();
+
+        let active_files = Self::list_markdown_files(&self.issues_dir)?;
+        let completed_files = Self::list_markdown_files(&self.completed_dir)?;
+        for file_path in active_files.into_iter().chain(completed_files) {
+            match self.load_issue_from_path(&file_path) {
+                Ok(issue) => {
+                    let issue_info =
+                        IssueInfo::from_issue_and_path(issue, file_path, &self.completed_dir);
+                    issues_info.push(issue_info);
+                }
+                Err(e) => {
+                    warn!("Failed to load issue from {}: {}", file_path.display(), e);
+                }
+            }
+        }
+
+        // Sort by name for consistent ordering
+        issues_info.sort_by(|a, b| a.issue.name.cmp(&b.issue.name));
+        Ok(issues_info)
+    }
+
+    /// Reject removing an issue that open issues still depend on
+    fn check_no_open_dependents(&self, name: &str, action: &str) -> Result<()> {
+        DependencyGraph::from_issue_infos(&self.load_issue_infos()?)
+            .ensure_no_open_dependents(name, action)
+            .map_err(SwissArmyHammerError::IssueHasDependents)
+    }
+
+    /// Reject content whose `depends_on` would make the dependency graph cyclic
+    fn check_dependency_cycle(&self, name: &str, content: &str) -> Result<()> {
+        let depends_on = parse_issue_content(content).0.depends_on;
+        if depends_on.is_empty() {
+            return Ok(());
+        }
+
+        let graph = DependencyGraph::from_issue_infos(&self.load_issue_infos()?);
+        match graph.find_cycle(name, &depends_on) {
+            Some(cycle) => Err(SwissArmyHammerError::IssueDependencyCycle(
+                cycle.join(" -> "),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Reject content whose `parent` would make an issue its own ancestor
+    fn check_parent_cycle(&self, name: &str, content: &str) -> Result<()> {
+        let Some(parent) = parse_issue_content(content).0.parent else {
+            return Ok(());
+        };
+
+        let hierarchy = IssueHierarchy::from_issue_infos(&self.load_issue_infos()?);
+        match hierarchy.find_cycle(name, &parent) {
+            Some(cycle) => Err(SwissArmyHammerError::IssueParentCycle(cycle.join(" -> "))),
+            None => Ok(()),
+        }
+    }
+
+    /// Derive an epic's completion from its children after one of them changed
+    ///
+    /// The epic is completed once every child is, and reopened when a child is open
+    /// again. The child's change already happened, so a failure is only logged.
+    fn sync_epic_status(&self, epic: &str) {
+        if let Err(e) = self.try_sync_epic_status(epic) {
+            warn!("Failed to update the status of epic '{}': {}", epic, e);
+        }
+    }
+
+    fn try_sync_epic_status(&self, epic: &str) -> Result<()> {
+        let Some(file_path) = self.find_issue_file(epic)? else {
+            return Ok(());
+        };
+        let hierarchy = IssueHierarchy::from_issue_infos(&self.load_issue_infos()?);
+        if !hierarchy.is_epic(epic) {
+            return Ok(());
+        }
+
+        let completed = file_path.parent() == Some(&self.completed_dir);
+        let children_open = hierarchy.has_open_children(epic);
+        if completed != children_open {
+            return Ok(());
+        }
+        let status = if children_open {
+            IssueStatus::Todo
+        } else {
+            IssueStatus::Done
+        };
+        self.apply_status(&file_path, status)?;
+        Ok(())
+    }
+
+    /// Record a status transition in an issue file and move it to the matching directory
+    ///
+    /// The issue's epic, if any, is completed or reopened to match its children.
+    fn apply_status(&self, file_path: &Path, status: IssueStatus) -> Result<Issue> {
+        let issue = self.load_issue_from_path(file_path)?;
+        let completed = issue.is_completed(file_path, &self.completed_dir);
+        let (mut metadata, body) = parse_issue_content(&issue.content);
+        let current = resolve_status(&metadata, completed);
+        if current == status {
+            return Ok(issue);
+        }
+
+        metadata.record_status(current, status, Utc::now());
+        let parent = metadata.parent.clone();
+        let updated = Issue {
+            content: render_issue_content(&metadata, body),
+            name: issue.name,
+        };
+
+        let target_dir = if status.is_closed() {
+            &self.completed_dir
+        } else {
+            &self.issues_dir
+        };
+        let target_path = self.move_issue(&updated, file_path, target_dir)?;
+        self.record_activity(
+            &target_path,
+            IssueActivity::new(IssueActivityKind::Status, format!("{current} → {status}")),
+        );
+        if status.is_closed() {
+            self.drop_lease(&updated.name);
+        }
+        if let Some(parent) = parent {
+            self.sync_epic_status(&parent);
+        }
+
+        debug!(
+            "Moved issue '{}' from {} to {} at {}",
+            updated.name,
+            current,
+            status,
+            target_path.display()
+        );
+        Ok(updated)
+    }
+
+    /// Append an automatic entry to the activity log next to an issue file
+    ///
+    /// The change the entry describes already happened, so a failed write is only logged.
+    fn record_activity(&self, issue_file: &Path, entry: IssueActivity) {
+        let log_path = activity_log_path(issue_file);
+        if let Err(e) = append_activity_log(&log_path, &entry) {
+            warn!("Failed to record activity in {}: {}", log_path.display(), e);
+        }
+    }
+
+    /// Remove the lease on an issue that no longer needs one
+    ///
+    /// A lease left behind expires on its own, so a failed removal is only logged.
+    fn drop_lease(&self, name: &str) {
+        if let Err(e) = self.leases.remove(name) {
+            warn!("Failed to remove the lease on issue '{}': {}", name, e);
+        }
+    }
+
+    /// Write an issue to `file_path` and move it into `target_dir`, returning the new path
+    ///
+    /// The issue's activity log moves along with it.
+    fn move_issue(&self, issue: &Issue, file_path: &Path, target_dir: &Path) -> Result<PathBuf> {
+        let target_path = target_dir.join(format!("{}.md", issue.name));
+        if target_path != file_path && target_path.exists() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Cannot move issue '{}' to {}: file already exists",
+                issue.name,
+                target_path.display()
+            )));
+        }
+
+        self.save_issue_to_file(issue, file_path)?;
+        if target_path != file_path {
+            fs::create_dir_all(target_dir).map_err(SwissArmyHammerError::Io)?;
+            fs::rename(file_path, &target_path).map_err(SwissArmyHammerError::Io)?;
+
+            let log_path = activity_log_path(file_path);
+            if log_path.exists() {
+                fs::rename(&log_path, activity_log_path(&target_path))
+                    .map_err(SwissArmyHammerError::Io)?;
+            }
+        }
+        Ok(target_path)
+    }
+
+    /// Directory holding the issues at a location
+    fn location_dir(&self, location: IssueLocation) -> &Path {
+        match location {
+            IssueLocation::Active => &self.issues_dir,
+            IssueLocation::Completed => &self.completed_dir,
+            IssueLocation::Archived => &self.archived_dir,
+        }
+    }
+
     /// Generate a unique issue name using ULID
     fn generate_issue_name(&self) -> String {
         generate_monotonic_ulid_string()
@@ -302,42 +612,7 @@ impl IssueStorage for FileSystemIssueStorage {
 
     async fn list_issues_info(&self) -> Result<Vec<IssueInfo>> {
         let _lock = self.lock.lock().await;
-
-        let mut issues_info = Vec::new();
-
-        // Load active issues
-        let active_files = Self::list_markdown_files(&self.issues_dir)?;
-        for file_path in active_files {
-            match self.load_issue_from_path(&file_path) {
-                Ok(issue) => {
-                    let issue_info =
-                        IssueInfo::from_issue_and_path(issue, file_path, &self.completed_dir);
-                    issues_info.push(issue_info);
-                }
-                Err(e) => {
-                    warn!("Failed to load issue from {}: {}", file_path.display(), e);
-                }
-            }
-        }
-
-        // Load completed issues
-        let completed_files = Self::list_markdown_files(&self.completed_dir)?;
-        for file_path in completed_files {
-            match self.load_issue_from_path(&file_path) {
-                Ok(issue) => {
-                    let issue_info =
-                        IssueInfo::from_issue_and_path(issue, file_path, &self.completed_dir);
-                    issues_info.push(issue_info);
-                }
-                Err(e) => {
-                    warn!("Failed to load issue from {}: {}", file_path.display(), e);
-                }
-            }
-        }
-
-        // Sort by name for consistent ordering
-        issues_info.sort_by(|a, b| a.issue.name.cmp(&b.issue.name));
-        Ok(issues_info)
+        self.load_issue_infos()
     }
 
     async fn get_issue(&self, name: &str) -> Result<Issue> {
@@ -379,6 +654,8 @@ impl IssueStorage for FileSystemIssueStorage {
         if self.find_issue_file(&issue_name)?.is_some() {
             return Err(SwissArmyHammerError::IssueAlreadyExists(0));
         }
+        self.check_dependency_cycle(&issue_name, &content)?;
+        self.check_parent_cycle(&issue_name, &content)?;
 
         let issue = Issue {
             name: issue_name.clone(),
@@ -387,6 +664,9 @@ impl IssueStorage for FileSystemIssueStorage {
 
         let file_path = self.issues_dir.join(format!("{}.md", issue_name));
         self.save_issue_to_file(&issue, &file_path)?;
+        if let Some(parent) = issue.metadata().parent {
+            self.sync_epic_status(&parent);
+        }
 
         debug!("Created issue '{}' at {}", issue_name, file_path.display());
         Ok(issue)
@@ -398,6 +678,9 @@ impl IssueStorage for FileSystemIssueStorage {
         let file_path = self
             .find_issue_file(name)?
             .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+        self.check_dependency_cycle(name, &content)?;
+        self.check_parent_cycle(name, &content)?;
+        let previous_parent = self.load_issue_from_path(&file_path)?.metadata().parent;
 
         let issue = Issue {
             name: name.to_string(),
@@ -405,6 +688,10 @@ impl IssueStorage for FileSystemIssueStorage {
         };
 
         self.save_issue_to_file(&issue, &file_path)?;
+        let parent = issue.metadata().parent;
+        for epic in previous_parent.iter().chain(parent.iter()) {
+            self.sync_epic_status(epic);
+        }
         debug!("Updated issue '{}' at {}", name, file_path.display());
         Ok(issue)
     }
@@ -415,8 +702,18 @@ impl IssueStorage for FileSystemIssueStorage {
         let file_path = self
             .find_issue_file(name)?
             .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+        self.check_no_open_dependents(name, "delete")?;
+        let parent = self.load_issue_from_path(&file_path)?.metadata().parent;
 
         fs::remove_file(&file_path).map_err(SwissArmyHammerError::Io)?;
+        let log_path = activity_log_path(&file_path);
+        if log_path.exists() {
+            fs::remove_file(&log_path).map_err(SwissArmyHammerError::Io)?;
+        }
+        self.drop_lease(name);
+        if let Some(parent) = parent {
+            self.sync_epic_status(&parent);
+        }
         debug!("Deleted issue '{}' from {}", name, file_path.display());
         Ok(())
     }
@@ -428,38 +725,167 @@ impl IssueStorage for FileSystemIssueStorage {
             .find_issue_file(name)?
             .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
 
-        let issue = self.load_issue_from_path(&file_path)?;
-
         // If already completed, just return it
         if file_path.parent() == Some(&self.completed_dir) {
-            return Ok(issue);
+            return self.load_issue_from_path(&file_path);
         }
 
-        // Move to completed directory
-        let completed_path = self.completed_dir.join(format!("{}.md", name));
-        fs::rename(&file_path, &completed_path).map_err(SwissArmyHammerError::Io)?;
+        self.apply_status(&file_path, IssueStatus::Done)
+    }
 
-        debug!(
-            "Completed issue '{}': moved from {} to {}",
-            name,
-            file_path.display(),
-            completed_path.display()
+    async fn set_issue_status(&self, name: &str, status: IssueStatus) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+
+        let file_path = self
+            .find_issue_file(name)?
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+
+        self.apply_status(&file_path, status)
+    }
+
+    async fn reopen_issue(&self, name: &str) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+
+        let archived_path = self.archived_dir.join(format!("{name}.md"));
+        let file_path = match self.find_issue_file(name)? {
+            Some(path) => path,
+            None if archived_path.exists() => archived_path.clone(),
+            None => return Err(SwissArmyHammerError::IssueNotFound(name.to_string())),
+        };
+        if file_path.parent() == Some(&self.issues_dir) {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Issue '{name}' is already open"
+            )));
+        }
+
+        let issue = self.load_issue_from_path(&file_path)?;
+        let (mut metadata, body) = parse_issue_content(&issue.content);
+        let current = if file_path == archived_path {
+            metadata.status.unwrap_or(IssueStatus::Todo)
+        } else {
+            resolve_status(&metadata, true)
+        };
+        if current != IssueStatus::Todo {
+            metadata.record_status(current, IssueStatus::Todo, Utc::now());
+        }
+        let reopened = Issue {
+            content: render_issue_content(&metadata, body),
+            name: issue.name,
+        };
+
+        let target_path = self.move_issue(&reopened, &file_path, &self.issues_dir)?;
+        self.record_activity(
+            &target_path,
+            IssueActivity::new(
+                IssueActivityKind::Status,
+                format!("{current} → {} (reopened)", IssueStatus::Todo),
+            ),
         );
+        if let Some(parent) = &metadata.parent {
+            self.sync_epic_status(parent);
+        }
+        debug!("Reopened issue '{}' at {}", name, target_path.display());
+        Ok(reopened)
+    }
+
+    async fn archive_issue(&self, name: &str) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+
+        let file_path = self
+            .find_issue_file(name)?
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+        self.check_no_open_dependents(name, "archive")?;
 
+        let issue = self.load_issue_from_path(&file_path)?;
+        let target_path = self.move_issue(&issue, &file_path, &self.archived_dir)?;
+        self.record_activity(
+            &target_path,
+            IssueActivity::new(IssueActivityKind::Archive, "Archived"),
+        );
+        self.drop_lease(name);
+        if let Some(parent) = issue.metadata().parent {
+            self.sync_epic_status(&parent);
+        }
+        debug!("Archived issue '{}' at {}", name, target_path.display());
         Ok(issue)
     }
 
-    async fn next_issue(&self) -> Result<Option<Issue>> {
+    async fn search_issues(
+        &self,
+        query: &str,
+        options: &IssueSearchOptions,
+    ) -> Result<Vec<IssueSearchResult>> {
+        let issue_infos = {
+            let _lock = self.lock.lock().await;
+            self.load_issue_infos()?
+        };
+
+        let engine = self
+            .search_engine
+            .get_or_try_init(|| IssueSearchEngine::open_for_issues(&self.issues_dir))
+            .await?;
+        engine.search(query, options, &issue_infos).await
+    }
+
+    async fn add_issue_activity(&self, name: &str, entry: IssueActivity) -> Result<IssueActivity> {
         let _lock = self.lock.lock().await;
 
-        let active_files = Self::list_markdown_files(&self.issues_dir)?;
-        if active_files.is_empty() {
-            return Ok(None);
+        let file_path = self
+            .find_issue_file(name)?
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+
+        append_activity_log(&activity_log_path(&file_path), &entry)?;
+        debug!("Recorded {} activity for issue '{}'", entry.kind, name);
+        Ok(entry)
+    }
+
+    async fn issue_activity(&self, name: &str) -> Result<Vec<IssueActivity>> {
+        let _lock = self.lock.lock().await;
+
+        let file_path = self
+            .find_issue_file(name)?
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+
+        read_activity_log(&activity_log_path(&file_path))
+    }
+
+    async fn claim_issue(&self, name: &str, owner: &str, ttl_seconds: u64) -> Result<IssueLease> {
+        let _lock = self.lock.lock().await;
+
+        let file_path = self
+            .find_issue_file(name)?
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+        if file_path.parent() != Some(&self.issues_dir) {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Issue '{name}' is completed and cannot be claimed"
+            )));
         }
 
-        // Return the first issue (files are sorted)
-        let issue = self.load_issue_from_path(&active_files[0])?;
-        Ok(Some(issue))
+        let lease = self.leases.claim(name, owner, ttl_seconds, Utc::now())?;
+        debug!("Issue '{}' claimed by '{}'", name, owner);
+        Ok(lease)
+    }
+
+    async fn release_issue(&self, name: &str, owner: &str, force: bool) -> Result<bool> {
+        let _lock = self.lock.lock().await;
+
+        let released = self.leases.release(name, owner, force, Utc::now())?;
+        if released {
+            debug!("Issue '{}' released by '{}'", name, owner);
+        }
+        Ok(released)
+    }
+
+    async fn issue_leases(&self) -> Result<Vec<IssueLease>> {
+        self.leases.active(Utc::now())
+    }
+
+    async fn next_issue(&self) -> Result<NextIssue> {
+        let _lock = self.lock.lock().await;
+
+        let issue_infos = self.load_issue_infos()?;
+        let leases = self.leases.active(Utc::now())?;
+        Ok(NextIssue::pick(issue_infos, &leases))
     }
 
     async fn all_issues_completed(&self) -> Result<bool> {
@@ -468,6 +894,92 @@ impl IssueStorage for FileSystemIssueStorage {
         let active_files = Self::list_markdown_files(&self.issues_dir)?;
         Ok(active_files.is_empty())
     }
+
+    fn backend(&self) -> IssueStorageBackend {
+        IssueStorageBackend::Markdown
+    }
+
+    fn issues_directory(&self) -> &Path {
+        &self.issues_dir
+    }
+
+    async fn snapshot_issues(&self) -> Result<Vec<StoredIssue>> {
+        let _lock = self.lock.lock().await;
+
+        let mut stored = Vec::new();
+        for location in [
+            IssueLocation::Active,
+            IssueLocation::Completed,
+            IssueLocation::Archived,
+        ] {
+            for file_path in Self::list_markdown_files(self.location_dir(location))? {
+                stored.push(StoredIssue {
+                    issue: self.load_issue_from_path(&file_path)?,
+                    location,
+                    created_at: Issue::get_created_at(&file_path),
+                    activity: read_activity_log(&activity_log_path(&file_path))?,
+                });
+            }
+        }
+
+        stored.sort_by(|a, b| a.issue.name.cmp(&b.issue.name));
+        Ok(stored)
+    }
+
+    async fn restore_issues(&self, issues: Vec<StoredIssue>) -> Result<()> {
+        let _lock = self.lock.lock().await;
+
+        for stored in &issues {
+            Self::validate_issue_name(&stored.issue.name)?;
+            let archived_path = self.archived_dir.join(format!("{}.md", stored.issue.name));
+            if self.find_issue_file(&stored.issue.name)?.is_some() || archived_path.exists() {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Issue '{}' already exists",
+                    stored.issue.name
+                )));
+            }
+        }
+
+        // File creation times cannot be set, so `created_at` starts over
+        for stored in &issues {
+            let file_path = self
+                .location_dir(stored.location)
+                .join(format!("{}.md", stored.issue.name));
+            self.save_issue_to_file(&stored.issue, &file_path)?;
+            let log_path = activity_log_path(&file_path);
+            for entry in &stored.activity {
+                append_activity_log(&log_path, entry)?;
+            }
+        }
+
+        debug!(
+            "Restored {} issues in {}",
+            issues.len(),
+            self.issues_dir.display()
+        );
+        Ok(())
+    }
+
+    async fn remove_all_issues(&self) -> Result<()> {
+        let _lock = self.lock.lock().await;
+
+        for location in [
+            IssueLocation::Active,
+            IssueLocation::Completed,
+            IssueLocation::Archived,
+        ] {
+            for file_path in Self::list_markdown_files(self.location_dir(location))? {
+                fs::remove_file(&file_path).map_err(SwissArmyHammerError::Io)?;
+                let log_path = activity_log_path(&file_path);
+                if log_path.exists() {
+                    fs::remove_file(&log_path).map_err(SwissArmyHammerError::Io)?;
+                }
+            }
+        }
+
+        debug!("Removed all issues from {}", self.issues_dir.display());
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -546,7 +1058,7 @@ mod tests {
 
         // No issues initially
         let next = storage.next_issue().await.unwrap();
-        assert!(next.is_none());
+        assert_eq!(next, NextIssue::AllDone);
 
         // Create some issues
         storage
@@ -559,11 +1071,386 @@ mod tests {
             .unwrap();
 
         // Should get the first alphabetically
-        let next = storage.next_issue().await.unwrap();
+        let next = storage.next_issue().await.unwrap().into_issue();
         assert!(next.is_some());
         assert_eq!(next.unwrap().name, "a-issue");
     }
 
+    #[tokio::test]
+    async fn test_next_issue_respects_priority() {
+        let (storage, _temp_dir) = create_temp_storage();
+
+        storage
+            .create_issue("a-issue".to_string(), "No frontmatter".to_string())
+            .await
+            .unwrap();
+        storage
+            .create_issue(
+                "c-issue".to_string(),
+                "---\npriority: low\n---\nLow".to_string(),
+            )
+            .await
+            .unwrap();
+        storage
+            .create_issue(
+                "d-issue".to_string(),
+                "---\npriority: critical\n---\nCritical".to_string(),
+            )
+            .await
+            .unwrap();
+
+        let next = storage.next_issue().await.unwrap().into_issue().unwrap();
+        assert_eq!(next.name, "d-issue");
+        assert_eq!(next.body(), "Critical");
+
+        let info = storage.get_issue_info("c-issue").await.unwrap();
+        assert_eq!(
+            info.metadata.priority,
+            Some(crate::issues::IssuePriority::Low)
+        );
+
+        // Without the critical issue, unprioritized issues count as medium
+        storage.complete_issue("d-issue").await.unwrap();
+        let next = storage.next_issue().await.unwrap().into_issue().unwrap();
+        assert_eq!(next.name, "a-issue");
+    }
+
+    #[tokio::test]
+    async fn test_next_issue_skips_blocked_and_rejects_cycles() {
+        let (storage, _temp_dir) = create_temp_storage();
+
+        storage
+            .create_issue(
+                "a-issue".to_string(),
+                "---\npriority: critical\ndepends_on: [b-issue]\n---\nA".to_string(),
+            )
+            .await
+            .unwrap();
+        storage
+            .create_issue("b-issue".to_string(), "B".to_string())
+            .await
+            .unwrap();
+
+        // a-issue is more urgent but waits on b-issue
+        let next = storage.next_issue().await.unwrap().into_issue().unwrap();
+        assert_eq!(next.name, "b-issue");
+
+        let result = storage
+            .update_issue("b-issue", "---\ndepends_on: [a-issue]\n---\nB".to_string())
+            .await;
+        assert!(matches!(
+            result,
+            Err(SwissArmyHammerError::IssueDependencyCycle(cycle))
+                if cycle == "b-issue -> a-issue -> b-issue"
+        ));
+
+        storage.complete_issue("b-issue").await.unwrap();
+        let next = storage.next_issue().await.unwrap().into_issue().unwrap();
+        assert_eq!(next.name, "a-issue");
+    }
+
+    #[tokio::test]
+    async fn test_set_issue_status_records_transitions() {
+        let (storage, _temp_dir) = create_temp_storage();
+
+        storage
+            .create_issue("status-issue".to_string(), "# Body".to_string())
+            .await
+            .unwrap();
+        let info = storage.get_issue_info("status-issue").await.unwrap();
+        assert_eq!(info.status(), IssueStatus::Todo);
+
+        storage
+            .set_issue_status("status-issue", IssueStatus::InProgress)
+            .await
+            .unwrap();
+        storage
+            .set_issue_status("status-issue", IssueStatus::InProgress)
+            .await
+            .unwrap();
+        storage
+            .set_issue_status("status-issue", IssueStatus::WontFix)
+            .await
+            .unwrap();
+
+        let info = storage.get_issue_info("status-issue").await.unwrap();
+        assert!(info.completed);
+        assert_eq!(info.status(), IssueStatus::WontFix);
+        assert_eq!(info.issue.body(), "# Body");
+        let statuses: Vec<_> = info
+            .metadata
+            .transitions
+            .iter()
+            .map(|t| (t.from, t.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (Some(IssueStatus::Todo), IssueStatus::InProgress),
+                (Some(IssueStatus::InProgress), IssueStatus::WontFix)
+            ]
+        );
+        assert!(info.flow_metrics().cycle_time().is_some());
+
+        // Reopening moves the file back to the active directory
+        storage
+            .set_issue_status("status-issue", IssueStatus::Todo)
+            .await
+            .unwrap();
+        let info = storage.get_issue_info("status-issue").await.unwrap();
+        assert!(!info.completed);
+        assert_eq!(info.status(), IssueStatus::Todo);
+    }
+
+    #[tokio::test]
+    async fn test_reopen_and_archive_issue() {
+        let (storage, _temp_dir) = create_temp_storage();
+
+        storage
+            .create_issue("review".to_string(), "# Review".to_string())
+            .await
+            .unwrap();
+        storage
+            .create_issue("obsolete".to_string(), "# Obsolete".to_string())
+            .await
+            .unwrap();
+        storage.complete_issue("review").await.unwrap();
+        assert!(storage.reopen_issue("obsolete").await.is_err());
+
+        let reopened = storage.reopen_issue("review").await.unwrap();
+        let metadata = reopened.metadata();
+        assert_eq!(metadata.status, Some(IssueStatus::Todo));
+        assert_eq!(
+            metadata.transitions.last().unwrap().from,
+            Some(IssueStatus::Done)
+        );
+        assert!(!storage.get_issue_info("review").await.unwrap().completed);
+
+        storage.archive_issue("obsolete").await.unwrap();
+        storage.archive_issue("review").await.unwrap();
+        assert!(storage.all_issues_completed().await.unwrap());
+        assert!(storage.list_issues().await.unwrap().is_empty());
+        assert!(matches!(
+            storage.get_issue("obsolete").await,
+            Err(SwissArmyHammerError::IssueNotFound(_))
+        ));
+
+        storage.reopen_issue("obsolete").await.unwrap();
+        assert!(!storage.all_issues_completed().await.unwrap());
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "obsolete"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_issue_activity_follows_issue() {
+        let (storage, temp_dir) = create_temp_storage();
+
+        storage
+            .create_issue("chatty".to_string(), "# Chatty".to_string())
+            .await
+            .unwrap();
+        storage
+            .add_issue_activity(
+                "chatty",
+                IssueActivity::comment("Looks good", Some("reviewer".to_string())),
+            )
+            .await
+            .unwrap();
+        storage.complete_issue("chatty").await.unwrap();
+        assert!(temp_dir
+            .path()
+            .join("issues/complete/chatty.log.jsonl")
+            .exists());
+
+        let kinds: Vec<_> = storage
+            .issue_activity("chatty")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.kind, entry.message))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (IssueActivityKind::Comment, "Looks good".to_string()),
+                (IssueActivityKind::Status, "todo → done".to_string())
+            ]
+        );
+        assert_eq!(storage.list_issues().await.unwrap().len(), 1);
+
+        storage.delete_issue("chatty").await.unwrap();
+        assert!(!temp_dir
+            .path()
+            .join("issues/complete/chatty.log.jsonl")
+            .exists());
+        assert!(storage.issue_activity("chatty").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_next_issue_skips_claimed_issues() {
+        let (storage, _temp_dir) = create_temp_storage();
+
+        for name in ["alpha", "beta"] {
+            storage
+                .create_issue(name.to_string(), format!("# {name}"))
+                .await
+                .unwrap();
+        }
+
+        storage.claim_issue("alpha", "agent-1", 60).await.unwrap();
+        assert!(storage.claim_issue("alpha", "agent-2", 60).await.is_err());
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "beta"
+        );
+        assert_eq!(storage.issue_leases().await.unwrap().len(), 1);
+
+        assert!(storage
+            .release_issue("alpha", "agent-2", false)
+            .await
+            .is_err());
+        assert!(storage
+            .release_issue("alpha", "agent-1", false)
+            .await
+            .unwrap());
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "alpha"
+        );
+
+        // Completing an issue gives up its lease
+        storage.claim_issue("beta", "agent-1", 60).await.unwrap();
+        storage.complete_issue("beta").await.unwrap();
+        assert!(storage.issue_leases().await.unwrap().is_empty());
+        assert!(storage.claim_issue("beta", "agent-1", 60).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_next_issue_only_picks_todo_issues() {
+        let (storage, _temp_dir) = create_temp_storage();
+
+        for name in ["alpha", "beta", "gamma", "delta"] {
+            storage
+                .create_issue(name.to_string(), format!("# {name}"))
+                .await
+                .unwrap();
+        }
+        storage
+            .set_issue_status("alpha", IssueStatus::Blocked)
+            .await
+            .unwrap();
+        storage
+            .set_issue_status("beta", IssueStatus::InReview)
+            .await
+            .unwrap();
+        // In progress without a lease is still someone's work
+        storage
+            .set_issue_status("gamma", IssueStatus::InProgress)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "delta"
+        );
+
+        storage
+            .set_issue_status("delta", IssueStatus::InProgress)
+            .await
+            .unwrap();
+        // Open issues remain, so the work loop must stop instead of finishing
+        assert!(storage.next_issue().await.unwrap().is_stalled());
+        assert!(!storage.all_issues_completed().await.unwrap());
+
+        storage
+            .set_issue_status("alpha", IssueStatus::Todo)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "alpha"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_epic_completion_follows_children() {
+        let (storage, _temp_dir) = create_temp_storage();
+
+        storage
+            .create_issue("auth".to_string(), "# Auth epic".to_string())
+            .await
+            .unwrap();
+        for child in ["login", "logout"] {
+            storage
+                .create_issue(
+                    child.to_string(),
+                    format!("---\nparent: auth\n---\n# {child}"),
+                )
+                .await
+                .unwrap();
+        }
+        assert!(matches!(
+            storage
+                .update_issue("auth", "---\nparent: login\n---\n# Auth epic".to_string())
+                .await,
+            Err(SwissArmyHammerError::IssueParentCycle(_))
+        ));
+
+        // The epic is never picked itself while children are open
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "login"
+        );
+
+        storage.complete_issue("login").await.unwrap();
+        assert!(!storage.get_issue_info("auth").await.unwrap().completed);
+        storage.complete_issue("logout").await.unwrap();
+        let epic = storage.get_issue_info("auth").await.unwrap();
+        assert!(epic.completed);
+        assert_eq!(epic.status(), IssueStatus::Done);
+
+        // Reopening a child reopens the epic
+        storage.reopen_issue("logout").await.unwrap();
+        assert!(!storage.get_issue_info("auth").await.unwrap().completed);
+    }
+
     #[tokio::test]
     async fn test_all_issues_completed() {
         let (storage, _temp_dir) = create_temp_storage();
@@ -601,4 +1488,43 @@ mod tests {
         assert!(!issue.name.is_empty());
         assert!(issue.name.len() > 10); // ULIDs are longer than this
     }
+
+    #[tokio::test]
+    async fn test_archive_and_delete_refused_while_open_issues_depend() {
+        let (storage, _temp_dir) = create_temp_storage();
+        storage
+            .create_issue("schema".into(), "# Schema".into())
+            .await
+            .unwrap();
+        storage
+            .create_issue("api".into(), "---\ndepends_on: [schema]\n---\n# API".into())
+            .await
+            .unwrap();
+        storage.complete_issue("schema").await.unwrap();
+
+        // A completed dependency still unblocks `api` only while it exists
+        for result in [
+            storage.archive_issue("schema").await.map(|_| ()),
+            storage.delete_issue("schema").await,
+        ] {
+            let error = result.unwrap_err();
+            assert!(matches!(error, SwissArmyHammerError::IssueHasDependents(_)));
+            assert!(error.to_string().contains("open issues depend on it: api"));
+        }
+        assert_eq!(
+            storage
+                .next_issue()
+                .await
+                .unwrap()
+                .into_issue()
+                .unwrap()
+                .name,
+            "api"
+        );
+
+        // Once the dependent is done, the dependency can go
+        storage.complete_issue("api").await.unwrap();
+        storage.archive_issue("schema").await.unwrap();
+        storage.delete_issue("api").await.unwrap();
+    }
 }
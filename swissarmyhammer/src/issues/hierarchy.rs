@@ -0,0 +1,267 @@
+//! Epics and their sub-issues
+//!
+//! An issue becomes a sub-issue by naming its epic in `parent` frontmatter:
+//!
+//! ```yaml
+//! ---
+//! parent: EPIC_000100_authentication
+//! ---
+//! ```
+//!
+//! Any issue with children is an epic. Its completion is derived from its
+//! children: it is complete once every child is, and a child that is reopened
+//! reopens it. Epics nest, so an epic can itself be the child of another epic.
+
+use super::filesystem::IssueInfo;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// How many of an epic's sub-issues are completed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EpicProgress {
+    /// Completed sub-issues
+    pub completed: usize,
+    /// All sub-issues
+    pub total: usize,
+}
+
+impl EpicProgress {
+    /// Whether every sub-issue is completed
+    pub fn is_complete(&self) -> bool {
+        self.completed == self.total
+    }
+}
+
+impl fmt::Display for EpicProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.completed, self.total)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    completed: bool,
+    parent: Option<String>,
+}
+
+/// Parent and child links between issues
+#[derive(Debug, Clone, Default)]
+pub struct IssueHierarchy {
+    /// Issue names in the order they were given
+    order: Vec<String>,
+    nodes: BTreeMap<String, Node>,
+    children: BTreeMap<String, Vec<String>>,
+}
+
+impl IssueHierarchy {
+    /// Build the hierarchy from issues, keeping their order for children and roots
+    pub fn from_issue_infos(issue_infos: &[IssueInfo]) -> Self {
+        let mut hierarchy = Self::default();
+        for info in issue_infos {
+            let name = info.issue.name.clone();
+            let parent = info.metadata.parent.clone();
+            if let Some(parent) = &parent {
+                hierarchy
+                    .children
+                    .entry(parent.clone())
+                    .or_default()
+                    .push(name.clone());
+            }
+            hierarchy.order.push(name.clone());
+            hierarchy.nodes.insert(
+                name,
+                Node {
+                    completed: info.completed,
+                    parent,
+                },
+            );
+        }
+        hierarchy
+    }
+
+    /// The epic an issue belongs to, if it names one
+    pub fn parent(&self, name: &str) -> Option<&str> {
+        self.nodes.get(name)?.parent.as_deref()
+    }
+
+    /// Direct children of an issue
+    pub fn children(&self, name: &str) -> &[String] {
+        self.children.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Whether an issue has children
+    pub fn is_epic(&self, name: &str) -> bool {
+        !self.children(name).is_empty()
+    }
+
+    /// Whether any direct child of an issue is still open
+    pub fn has_open_children(&self, name: &str) -> bool {
+        self.children(name)
+            .iter()
+            .any(|child| self.nodes.get(child).is_some_and(|node| !node.completed))
+    }
+
+    /// All issues below an epic, depth first
+    pub fn descendants(&self, name: &str) -> Vec<String> {
+        let mut visited = BTreeSet::from([name.to_string()]);
+        let mut descendants = Vec::new();
+        let mut stack: Vec<&String> = self.children(name).iter().rev().collect();
+        while let Some(child) = stack.pop() {
+            if !visited.insert(child.clone()) {
+                continue;
+            }
+            descendants.push(child.clone());
+            stack.extend(self.children(child).iter().rev());
+        }
+        descendants
+    }
+
+    /// How many issues below an epic are completed
+    pub fn progress(&self, name: &str) -> EpicProgress {
+        let descendants = self.descendants(name);
+        EpicProgress {
+            completed: descendants
+                .iter()
+                .filter(|d| self.nodes.get(*d).is_some_and(|node| node.completed))
+                .count(),
+            total: descendants.len(),
+        }
+    }
+
+    /// Issues with no parent among the known issues
+    pub fn roots(&self) -> Vec<String> {
+        self.order
+            .iter()
+            .filter(|name| {
+                !matches!(
+                    self.parent(name),
+                    Some(parent) if parent != name.as_str() && self.nodes.contains_key(parent)
+                )
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Issues in tree order with their depth, roots first and children beneath them
+    ///
+    /// Issues caught in a parent cycle are listed as roots so none go missing.
+    pub fn tree(&self) -> Vec<(usize, String)> {
+        let mut visited = BTreeSet::new();
+        let mut tree = Vec::new();
+        for root in self.roots() {
+            self.walk(&root, 0, &mut visited, &mut tree);
+        }
+        for name in &self.order {
+            if !visited.contains(name) {
+                self.walk(name, 0, &mut visited, &mut tree);
+            }
+        }
+        tree
+    }
+
+    fn walk(
+        &self,
+        name: &str,
+        depth: usize,
+        visited: &mut BTreeSet<String>,
+        tree: &mut Vec<(usize, String)>,
+    ) {
+        if !visited.insert(name.to_string()) {
+            return;
+        }
+        tree.push((depth, name.to_string()));
+        for child in self.children(name) {
+            if self.nodes.contains_key(child) {
+                self.walk(child, depth + 1, visited, tree);
+            }
+        }
+    }
+
+    /// Find the cycle that giving `name` this parent would create
+    ///
+    /// Returns the cycle as a path of parents starting and ending at `name`.
+    pub fn find_cycle(&self, name: &str, parent: &str) -> Option<Vec<String>> {
+        let mut path = vec![name.to_string()];
+        let mut current = Some(parent);
+        while let Some(ancestor) = current {
+            path.push(ancestor.to_string());
+            if ancestor == name {
+                return Some(path);
+            }
+            if path[..path.len() - 1].iter().any(|p| p == ancestor) {
+                // An existing cycle that does not involve `name`
+                return None;
+            }
+            current = self.parent(ancestor);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::filesystem::Issue;
+    use std::path::PathBuf;
+
+    fn info(name: &str, completed: bool, parent: Option<&str>) -> IssueInfo {
+        let content = match parent {
+            Some(parent) => format!("---\nparent: {parent}\n---\n# {name}"),
+            None => format!("# {name}"),
+        };
+        IssueInfo {
+            metadata: crate::issues::parse_issue_content(&content).0,
+            issue: Issue {
+                name: name.to_string(),
+                content,
+            },
+            completed,
+            file_path: PathBuf::from(format!("{name}.md")),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_tree_progress_and_cycles() {
+        let hierarchy = IssueHierarchy::from_issue_infos(&[
+            info("auth", false, None),
+            info("login", true, Some("auth")),
+            info("tokens", false, Some("auth")),
+            info("refresh", false, Some("tokens")),
+            info("docs", false, None),
+        ]);
+
+        assert_eq!(hierarchy.roots(), vec!["auth", "docs"]);
+        assert_eq!(
+            hierarchy.tree(),
+            vec![
+                (0, "auth".to_string()),
+                (1, "login".to_string()),
+                (1, "tokens".to_string()),
+                (2, "refresh".to_string()),
+                (0, "docs".to_string()),
+            ]
+        );
+        assert!(hierarchy.is_epic("tokens"));
+        assert!(hierarchy.has_open_children("auth"));
+        assert_eq!(
+            hierarchy.progress("auth"),
+            EpicProgress {
+                completed: 1,
+                total: 3
+            }
+        );
+        assert_eq!(hierarchy.progress("auth").to_string(), "1/3");
+
+        assert_eq!(
+            hierarchy.find_cycle("auth", "refresh"),
+            Some(vec![
+                "auth".to_string(),
+                "refresh".to_string(),
+                "tokens".to_string(),
+                "auth".to_string()
+            ])
+        );
+        assert_eq!(hierarchy.find_cycle("docs", "refresh"), None);
+    }
+}
@@ -0,0 +1,455 @@
+//! Issue leases for agents working in parallel
+//!
+//! An agent claims an issue before working on it. The claim is a lease file that
+//! every process sharing the issues is meant to see. Agents working in parallel
+//! usually run in their own Git worktree, so inside a repository leases live in
+//! `sah/leases` under the common Git directory, which all worktrees share and no
+//! checkout contains. Outside a repository they sit in `.leases` next to the
+//! issues, a directory that ignores itself so lease files are never staged:
+//!
+//! ```json
+//! {"issue":"FEATURE_001_login","owner":"agent-1","acquired_at":"2025-01-06T09:00:00Z","heartbeat_at":"2025-01-06T09:20:00Z","ttl_seconds":1800}
+//! ```
+//!
+//! A lease lasts `ttl_seconds` past its last heartbeat. The owner renews it by
+//! claiming the issue again; once it lapses, for example because the agent died,
+//! anyone can claim the issue. Renewing, taking over and releasing an existing
+//! lease happen while holding its `.lock` file, so two agents can never both take
+//! over the same expired lease.
+
+use crate::directory_utils::{create_git_ignored_directory, find_git_common_directory_from};
+use crate::error::{Result, SwissArmyHammerError};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::warn;
+
+/// Directory inside the issues directory holding lease files outside a Git repository
+pub const LEASE_DIR: &str = ".leases";
+
+/// Directory inside the common Git directory holding lease files
+pub const SHARED_LEASE_DIR: &str = "sah/leases";
+
+/// Age after which a lease lock is taken to be left behind by a dead process
+const STALE_LOCK_SECONDS: u64 = 30;
+
+/// Lease duration used when none is given
+pub const DEFAULT_LEASE_TTL_SECONDS: u64 = 30 * 60;
+
+/// Longest lease duration, longer requests are shortened to it
+pub const MAX_LEASE_TTL_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// Environment variable naming the agent that owns leases taken by this process
+pub const LEASE_OWNER_ENV: &str = "SAH_AGENT_ID";
+
+/// A time-limited claim on an issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueLease {
+    /// Name of the claimed issue
+    pub issue: String,
+    /// Who holds the lease
+    pub owner: String,
+    /// When the lease was first taken
+    pub acquired_at: DateTime<Utc>,
+    /// When the owner last renewed the lease
+    pub heartbeat_at: DateTime<Utc>,
+    /// How long the lease lasts after each heartbeat
+    pub ttl_seconds: u64,
+}
+
+impl IssueLease {
+    /// When the lease lapses without another heartbeat
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.heartbeat_at + Duration::seconds(self.ttl_seconds.min(MAX_LEASE_TTL_SECONDS) as i64)
+    }
+
+    /// Whether the lease has lapsed at `now`
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at() <= now
+    }
+
+    /// Whether `owner` holds the lease
+    pub fn is_held_by(&self, owner: &str) -> bool {
+        self.owner == owner
+    }
+}
+
+/// Owner used for leases when the caller names none
+///
+/// `SAH_AGENT_ID` when set, so the processes of one agent loop share their
+/// leases, otherwise the host and process ID.
+pub fn default_lease_owner() -> String {
+    if let Ok(owner) = std::env::var(LEASE_OWNER_ENV) {
+        if !owner.trim().is_empty() {
+            return owner.trim().to_string();
+        }
+    }
+    let host = std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_else(|_| "localhost".to_string());
+    format!("{host}:{}", std::process::id())
+}
+
+/// Lease files of one issues directory
+#[derive(Debug, Clone)]
+pub struct LeaseStore {
+    dir: PathBuf,
+}
+
+/// Lock on one lease file, removed when dropped
+struct LeaseLock {
+    path: PathBuf,
+}
+
+impl Drop for LeaseLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl LeaseStore {
+    /// Lease store for an issues directory
+    ///
+    /// Every worktree of a repository maps the same issues directory to the same
+    /// lease directory in the common Git directory.
+    pub fn new(issues_dir: &Path) -> Self {
+        Self {
+            dir: shared_lease_dir(issues_dir).unwrap_or_else(|| issues_dir.join(LEASE_DIR)),
+        }
+    }
+
+    /// Directory holding the lease files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn lease_path(&self, issue: &str) -> PathBuf {
+        self.dir.join(format!("{issue}.json"))
+    }
+
+    /// Lock the lease file of an issue against other processes
+    ///
+    /// Creating the lock file fails while someone else holds it, so the caller is
+    /// told to try again rather than kept waiting. A lock older than
+    /// `STALE_LOCK_SECONDS` was left by a process that died and is broken once.
+    fn lock(&self, issue: &str) -> Result<LeaseLock> {
+        let path = self.dir.join(format!("{issue}.lock"));
+        for _ in 0..2 {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(_) => return Ok(LeaseLock { path }),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let stale = fs::metadata(&path)
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age.as_secs() >= STALE_LOCK_SECONDS);
+                    if !stale {
+                        break;
+                    }
+                    warn!("Breaking stale lease lock {}", path.display());
+                    let _ = fs::remove_file(&path);
+                }
+                Err(e) => return Err(SwissArmyHammerError::Io(e)),
+            }
+        }
+        Err(SwissArmyHammerError::IssueLeased(format!(
+            "Issue '{issue}' is being claimed by another agent, try again"
+        )))
+    }
+
+    /// Create a lease file atomically, failing if one exists
+    ///
+    /// Returns whether the lease was created.
+    fn create(&self, path: &Path, lease: &IssueLease) -> Result<bool> {
+        // Linking a complete temporary file creates the lease atomically or not at all
+        let temp_path = self.write_temp(path, lease)?;
+        let linked = fs::hard_link(&temp_path, path);
+        let _ = fs::remove_file(&temp_path);
+        match linked {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(SwissArmyHammerError::Io(e)),
+        }
+    }
+
+    fn read(&self, path: &Path) -> Option<IssueLease> {
+        let content = fs::read_to_string(path).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(lease) => Some(lease),
+            Err(e) => {
+                warn!("Ignoring unreadable lease {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Write a lease to a temporary file next to its lease file
+    fn write_temp(&self, path: &Path, lease: &IssueLease) -> Result<PathBuf> {
+        static NEXT_TEMP: AtomicU64 = AtomicU64::new(0);
+        let temp_path = path.with_extension(format!(
+            "json.{}.{}.tmp",
+            std::process::id(),
+            NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp_path, serde_json::to_string(lease)?).map_err(SwissArmyHammerError::Io)?;
+        Ok(temp_path)
+    }
+
+    /// Replace a lease file so readers never see half of it
+    fn write(&self, path: &Path, lease: &IssueLease) -> Result<()> {
+        let temp_path = self.write_temp(path, lease)?;
+        fs::rename(&temp_path, path).map_err(SwissArmyHammerError::Io)?;
+        Ok(())
+    }
+
+    /// The unexpired lease on an issue, if any
+    pub fn get(&self, issue: &str, now: DateTime<Utc>) -> Option<IssueLease> {
+        self.read(&self.lease_path(issue))
+            .filter(|lease| !lease.is_expired(now))
+    }
+
+    /// All unexpired leases, sorted by issue name
+    pub fn active(&self, now: DateTime<Utc>) -> Result<Vec<IssueLease>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut leases = Vec::new();
+        for entry in fs::read_dir(&self.dir).map_err(SwissArmyHammerError::Io)? {
+            let path = entry.map_err(SwissArmyHammerError::Io)?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                leases.extend(self.read(&path).filter(|lease| !lease.is_expired(now)));
+            }
+        }
+        leases.sort_by(|a, b| a.issue.cmp(&b.issue));
+        Ok(leases)
+    }
+
+    /// Take or renew the lease on an issue
+    ///
+    /// A new lease is created atomically, so of two processes claiming a free
+    /// issue only one succeeds. The owner renews its lease, and an
+    /// expired lease is taken over. Both replace the lease file under its lock,
+    /// so only one process can act on what it read. A lease held by someone else
+    /// is refused.
+    pub fn claim(
+        &self,
+        issue: &str,
+        owner: &str,
+        ttl_seconds: u64,
+        now: DateTime<Utc>,
+    ) -> Result<IssueLease> {
+        create_git_ignored_directory(&self.dir).map_err(SwissArmyHammerError::Io)?;
+        let path = self.lease_path(issue);
+        let mut lease = IssueLease {
+            issue: issue.to_string(),
+            owner: owner.to_string(),
+            acquired_at: now,
+            heartbeat_at: now,
+            ttl_seconds: ttl_seconds.min(MAX_LEASE_TTL_SECONDS),
+        };
+
+        if self.create(&path, &lease)? {
+            return Ok(lease);
+        }
+
+        // The lease exists, so whatever is read below stays current until the lock is dropped
+        let _lock = self.lock(issue)?;
+        match self.read(&path) {
+            Some(current) if current.is_held_by(owner) => {
+                lease.acquired_at = current.acquired_at;
+            }
+            Some(current) if !current.is_expired(now) => {
+                return Err(SwissArmyHammerError::IssueLeased(format!(
+                    "Issue '{issue}' is claimed by '{}' until {}",
+                    current.owner,
+                    current.expires_at().format("%Y-%m-%d %H:%M:%S UTC")
+                )));
+            }
+            Some(_) => {}
+            None => {
+                // Released meanwhile, so it is free to be created by whoever comes first
+                if self.create(&path, &lease)? {
+                    return Ok(lease);
+                }
+                return Err(SwissArmyHammerError::IssueLeased(format!(
+                    "Issue '{issue}' was claimed by another agent first"
+                )));
+            }
+        }
+        self.write(&path, &lease)?;
+        Ok(lease)
+    }
+
+    /// Give up the lease on an issue
+    ///
+    /// Only the owner can release an unexpired lease unless `force` is set.
+    /// Returns whether there was a lease to remove.
+    pub fn release(
+        &self,
+        issue: &str,
+        owner: &str,
+        force: bool,
+        now: DateTime<Utc>,
+    ) -> Result<bool> {
+        let path = self.lease_path(issue);
+        if !path.exists() {
+            return Ok(false);
+        }
+        let _lock = self.lock(issue)?;
+        let Some(current) = self.read(&path) else {
+            return Ok(false);
+        };
+        if !force && !current.is_held_by(owner) && !current.is_expired(now) {
+            return Err(SwissArmyHammerError::IssueLeased(format!(
+                "Issue '{issue}' is claimed by '{}', not '{owner}'",
+                current.owner
+            )));
+        }
+        self.remove(issue)?;
+        Ok(true)
+    }
+
+    /// Remove the lease on an issue regardless of its owner
+    ///
+    /// Used once an issue is done with, so no lock is taken.
+    pub fn remove(&self, issue: &str) -> Result<()> {
+        match fs::remove_file(self.lease_path(issue)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(SwissArmyHammerError::Io(e)),
+        }
+    }
+}
+
+/// Lease directory in the common Git directory for an issues directory
+///
+/// The issues directory's place in its worktree keys the leases, so the same
+/// issues in every worktree share them. None outside a Git repository.
+fn shared_lease_dir(issues_dir: &Path) -> Option<PathBuf> {
+    let (common_dir, workdir) = find_git_common_directory_from(issues_dir)?;
+    let issues_dir = issues_dir
+        .canonicalize()
+        .unwrap_or_else(|_| issues_dir.to_path_buf());
+    let workdir = workdir.canonicalize().unwrap_or(workdir);
+    let common_dir = common_dir.canonicalize().unwrap_or(common_dir);
+    let relative = issues_dir.strip_prefix(&workdir).ok()?;
+    Some(common_dir.join(SHARED_LEASE_DIR).join(relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_claim_renew_expire_and_release() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = LeaseStore::new(temp_dir.path());
+        let start = Utc::now();
+
+        let lease = store.claim("login", "agent-1", 60, start).unwrap();
+        assert_eq!(lease.expires_at(), start + Duration::seconds(60));
+        assert!(store.claim("login", "agent-2", 60, start).is_err());
+        assert!(store.release("login", "agent-2", false, start).is_err());
+
+        // A heartbeat keeps the original acquisition time
+        let later = start + Duration::seconds(45);
+        let renewed = store.claim("login", "agent-1", 60, later).unwrap();
+        assert_eq!(renewed.acquired_at, start);
+        assert!(store.get("login", start + Duration::seconds(90)).is_some());
+        assert_eq!(store.active(later).unwrap(), vec![renewed]);
+
+        // Once it lapses another agent takes over
+        let expired = later + Duration::seconds(61);
+        assert!(store.get("login", expired).is_none());
+        let taken = store.claim("login", "agent-2", 60, expired).unwrap();
+        assert_eq!(taken.owner, "agent-2");
+
+        assert!(store.release("login", "agent-2", false, expired).unwrap());
+        assert!(!store.release("login", "agent-2", false, expired).unwrap());
+        assert!(store.active(expired).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_expired_lease_is_taken_over_by_one_agent() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = LeaseStore::new(temp_dir.path());
+        let start = Utc::now();
+        store.claim("login", "agent-0", 60, start).unwrap();
+        let expired = start + Duration::seconds(61);
+
+        // A takeover in progress elsewhere makes the claim back off
+        let lock = store.lock("login").unwrap();
+        assert!(matches!(
+            store.claim("login", "agent-1", 60, expired),
+            Err(SwissArmyHammerError::IssueLeased(_))
+        ));
+        drop(lock);
+
+        let winners: Vec<String> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (1..=8)
+                .map(|i| {
+                    let store = &store;
+                    scope.spawn(move || store.claim("login", &format!("agent-{i}"), 60, expired))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .filter_map(|handle| handle.join().unwrap().ok())
+                .map(|lease| lease.owner)
+                .collect()
+        });
+        assert_eq!(winners.len(), 1);
+        assert_eq!(store.get("login", expired).unwrap().owner, winners[0]);
+    }
+
+    #[test]
+    fn test_leases_are_shared_by_worktrees_and_never_staged() {
+        let temp_dir = TempDir::new().unwrap();
+        let main_dir = temp_dir.path().join("main");
+        let repo = git2::Repository::init(&main_dir).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        let worktree_dir = temp_dir.path().join("worktree");
+        repo.worktree("agent-2", &worktree_dir, None).unwrap();
+
+        let main_issues = main_dir.join(".swissarmyhammer/issues");
+        let worktree_issues = worktree_dir.join(".swissarmyhammer/issues");
+        fs::create_dir_all(&main_issues).unwrap();
+        fs::create_dir_all(&worktree_issues).unwrap();
+        let main_store = LeaseStore::new(&main_issues);
+        let worktree_store = LeaseStore::new(&worktree_issues);
+        assert_eq!(main_store.dir(), worktree_store.dir());
+        assert!(!main_store
+            .dir()
+            .starts_with(&main_dir.canonicalize().unwrap().join(".swissarmyhammer")));
+
+        let now = Utc::now();
+        main_store.claim("login", "agent-1", 60, now).unwrap();
+        assert!(worktree_store.claim("login", "agent-2", 60, now).is_err());
+        assert_eq!(worktree_store.active(now).unwrap().len(), 1);
+        assert!(repo.statuses(None).unwrap().is_empty());
+
+        // Without a repository leases stay next to the issues and ignore themselves
+        let plain_issues = temp_dir.path().join("plain/issues");
+        let plain_store = LeaseStore::new(&plain_issues);
+        assert_eq!(plain_store.dir(), plain_issues.join(LEASE_DIR));
+        plain_store.claim("login", "agent-1", 60, now).unwrap();
+        assert_eq!(
+            fs::read_to_string(plain_issues.join(LEASE_DIR).join(".gitignore")).unwrap(),
+            "*\n"
+        );
+    }
+}
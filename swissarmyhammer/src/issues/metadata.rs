@@ -0,0 +1,482 @@
+//! Optional YAML frontmatter metadata for issues
+//!
+//! Issue files may start with a frontmatter block:
+//!
+//! ```markdown
+//! ---
+//! priority: high
+//! labels: [auth, backend]
+//! estimate: 2d
+//! assignee: alice
+//! created_by: bob
+//! depends_on: [setup_database]
+//! external_id: github#42
+//! ---
+//! # Fix login
+//! ```
+//!
+//! Files without frontmatter keep working and get empty metadata. Unknown keys
+//! are preserved when the metadata is rewritten.
+
+use super::filesystem::IssueInfo;
+use super::status::{IssueStatus, StatusTransition};
+use crate::frontmatter::split_frontmatter;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Issue priority, from most to least urgent
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Default,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum IssuePriority {
+    /// Must be worked on before anything else
+    Critical,
+    /// Should be worked on soon
+    High,
+    /// Normal priority, assumed for issues without a priority
+    #[default]
+    Medium,
+    /// Can wait
+    Low,
+}
+
+impl IssuePriority {
+    /// All priorities, from most to least urgent
+    pub const ALL: [IssuePriority; 4] = [
+        IssuePriority::Critical,
+        IssuePriority::High,
+        IssuePriority::Medium,
+        IssuePriority::Low,
+    ];
+
+    /// The lowercase name used in frontmatter and tool parameters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssuePriority::Critical => "critical",
+            IssuePriority::High => "high",
+            IssuePriority::Medium => "medium",
+            IssuePriority::Low => "low",
+        }
+    }
+}
+
+impl fmt::Display for IssuePriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for IssuePriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IssuePriority::ALL
+            .into_iter()
+            .find(|p| p.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!("Invalid priority '{s}': expected one of critical, high, medium, low")
+            })
+    }
+}
+
+/// Typed issue metadata parsed from frontmatter
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IssueMetadata {
+    /// Priority of the issue
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<IssuePriority>,
+    /// Free-form labels
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Effort estimate, e.g. `3`, `4h` or `2d`
+    #[serde(
+        default,
+        deserialize_with = "deserialize_scalar_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub estimate: Option<String>,
+    /// Who is working on the issue
+    #[serde(
+        default,
+        deserialize_with = "deserialize_scalar_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub assignee: Option<String>,
+    /// Who created the issue
+    #[serde(
+        default,
+        deserialize_with = "deserialize_scalar_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub created_by: Option<String>,
+    /// Names of issues that must be completed before this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Name of the epic this issue belongs to
+    #[serde(
+        default,
+        deserialize_with = "deserialize_scalar_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub parent: Option<String>,
+    /// Stable identifier of the issue in an external tracker, e.g. `github#123`
+    #[serde(
+        default,
+        deserialize_with = "deserialize_scalar_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub external_id: Option<String>,
+    /// Lifecycle status, see [`crate::issues::status`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<IssueStatus>,
+    /// Recorded status changes, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<StatusTransition>,
+    /// Frontmatter keys this version does not know about, kept on rewrite
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+impl IssueMetadata {
+    /// Whether there is nothing to write as frontmatter
+    pub fn is_empty(&self) -> bool {
+        self == &IssueMetadata::default()
+    }
+
+    /// Priority used for ordering, treating a missing priority as medium
+    pub fn effective_priority(&self) -> IssuePriority {
+        self.priority.unwrap_or_default()
+    }
+
+    /// Whether the issue carries a label, ignoring case
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels.iter().any(|l| l.eq_ignore_ascii_case(label))
+    }
+
+    /// Move to a new status and record the transition
+    pub fn record_status(&mut self, from: IssueStatus, to: IssueStatus, at: DateTime<Utc>) {
+        self.status = Some(to);
+        self.transitions.push(StatusTransition {
+            status: to,
+            from: Some(from),
+            at,
+        });
+    }
+
+    /// Apply an update; empty strings clear a field
+    pub fn apply(&mut self, update: &IssueMetadataUpdate) {
+        if let Some(priority) = update.priority {
+            self.priority = Some(priority);
+        }
+        if let Some(labels) = &update.labels {
+            self.labels = normalize_labels(labels);
+        }
+        if let Some(estimate) = &update.estimate {
+            self.estimate = non_empty(estimate);
+        }
+        if let Some(assignee) = &update.assignee {
+            self.assignee = non_empty(assignee);
+        }
+        if let Some(created_by) = &update.created_by {
+            self.created_by = non_empty(created_by);
+        }
+        if let Some(depends_on) = &update.depends_on {
+            self.depends_on = normalize_dependencies(depends_on);
+        }
+        if let Some(parent) = &update.parent {
+            self.parent = non_empty(parent);
+        }
+    }
+
+    /// One line summary such as `high | auth, backend | @alice | 2d`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(priority) = self.priority {
+            parts.push(priority.to_string());
+        }
+        if !self.labels.is_empty() {
+            parts.push(self.labels.join(", "));
+        }
+        if let Some(assignee) = &self.assignee {
+            parts.push(format!("@{assignee}"));
+        }
+        if let Some(estimate) = &self.estimate {
+            parts.push(estimate.clone());
+        }
+        parts.join(" | ")
+    }
+}
+
+/// Accept numbers and booleans where a string is expected, e.g. `estimate: 3`
+fn deserialize_scalar_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Option::<serde_yaml::Value>::deserialize(deserializer)? {
+        None | Some(serde_yaml::Value::Null) => Ok(None),
+        Some(serde_yaml::Value::String(s)) => Ok(Some(s)),
+        Some(serde_yaml::Value::Number(n)) => Ok(Some(n.to_string())),
+        Some(serde_yaml::Value::Bool(b)) => Ok(Some(b.to_string())),
+        Some(_) => Err(serde::de::Error::custom("expected a string")),
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn normalize_labels(labels: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for label in labels.iter().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        if !normalized.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+            normalized.push(label.to_string());
+        }
+    }
+    normalized
+}
+
+fn normalize_dependencies(depends_on: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for name in depends_on
+        .iter()
+        .map(|n| n.trim())
+        .filter(|n| !n.is_empty())
+    {
+        if !normalized.iter().any(|n| n == name) {
+            normalized.push(name.to_string());
+        }
+    }
+    normalized
+}
+
+/// Changes to apply to issue metadata; `None` leaves a field untouched
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IssueMetadataUpdate {
+    /// New priority
+    pub priority: Option<IssuePriority>,
+    /// Replacement labels
+    pub labels: Option<Vec<String>>,
+    /// New estimate, empty to clear
+    pub estimate: Option<String>,
+    /// New assignee, empty to clear
+    pub assignee: Option<String>,
+    /// New creator, empty to clear
+    pub created_by: Option<String>,
+    /// Replacement dependencies
+    pub depends_on: Option<Vec<String>>,
+    /// New epic, empty to detach the issue from its epic
+    pub parent: Option<String>,
+}
+
+impl IssueMetadataUpdate {
+    /// Whether the update changes nothing
+    pub fn is_empty(&self) -> bool {
+        self == &IssueMetadataUpdate::default()
+    }
+}
+
+/// Split issue content into metadata and body
+///
+/// Content without frontmatter, or with frontmatter that does not parse,
+/// yields default metadata and the full content as body.
+pub fn parse_issue_content(content: &str) -> (IssueMetadata, &str) {
+    let Some((yaml, body)) = split_frontmatter(content) else {
+        return (IssueMetadata::default(), content);
+    };
+
+    match serde_yaml::from_str::<Option<IssueMetadata>>(yaml) {
+        Ok(metadata) => (metadata.unwrap_or_default(), body),
+        Err(e) => {
+            tracing::warn!("Ignoring invalid issue frontmatter: {}", e);
+            (IssueMetadata::default(), content)
+        }
+    }
+}
+
+/// Render metadata and body back into issue content
+///
+/// Empty metadata produces the body alone, so issues that never had
+/// frontmatter are written unchanged.
+pub fn render_issue_content(metadata: &IssueMetadata, body: &str) -> String {
+    if metadata.is_empty() {
+        return body.to_string();
+    }
+    let yaml = serde_yaml::to_string(metadata).unwrap_or_default();
+    format!("---\n{yaml}---\n{body}")
+}
+
+/// Field used to order issue listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IssueSort {
+    /// Alphabetically by name
+    #[default]
+    Name,
+    /// Most urgent first, then by name
+    Priority,
+    /// Oldest first
+    Created,
+}
+
+impl FromStr for IssueSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "name" => Ok(IssueSort::Name),
+            "priority" => Ok(IssueSort::Priority),
+            "created" => Ok(IssueSort::Created),
+            _ => Err(format!(
+                "Invalid sort '{s}': expected one of name, priority, created"
+            )),
+        }
+    }
+}
+
+/// Metadata filters and ordering for issue listings
+#[derive(Debug, Clone, Default)]
+pub struct IssueQuery {
+    /// Only issues with this priority
+    pub priority: Option<IssuePriority>,
+    /// Only issues carrying this label
+    pub label: Option<String>,
+    /// Only issues assigned to this person
+    pub assignee: Option<String>,
+    /// Only issues in this status
+    pub status: Option<IssueStatus>,
+    /// Ordering of the result
+    pub sort: IssueSort,
+}
+
+impl IssueQuery {
+    /// Whether an issue passes the filters
+    pub fn matches(&self, info: &IssueInfo) -> bool {
+        let metadata = &info.metadata;
+        if let Some(status) = self.status {
+            if info.status() != status {
+                return false;
+            }
+        }
+        if let Some(priority) = self.priority {
+            if metadata.effective_priority() != priority {
+                return false;
+            }
+        }
+        if let Some(label) = &self.label {
+            if !metadata.has_label(label) {
+                return false;
+            }
+        }
+        if let Some(assignee) = &self.assignee {
+            if !metadata
+                .assignee
+                .as_deref()
+                .is_some_and(|a| a.eq_ignore_ascii_case(assignee))
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Filter and sort issue infos
+    pub fn apply(&self, issue_infos: Vec<IssueInfo>) -> Vec<IssueInfo> {
+        let mut filtered: Vec<IssueInfo> = issue_infos
+            .into_iter()
+            .filter(|info| self.matches(info))
+            .collect();
+        match self.sort {
+            IssueSort::Name => filtered.sort_by(|a, b| a.issue.name.cmp(&b.issue.name)),
+            IssueSort::Priority => filtered.sort_by(|a, b| {
+                a.metadata
+                    .effective_priority()
+                    .cmp(&b.metadata.effective_priority())
+                    .then_with(|| a.issue.name.cmp(&b.issue.name))
+            }),
+            IssueSort::Created => filtered.sort_by(|a, b| {
+                a.created_at
+                    .cmp(&b.created_at)
+                    .then_with(|| a.issue.name.cmp(&b.issue.name))
+            }),
+        }
+        filtered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_issue_content_with_and_without_frontmatter() {
+        let content = "---\npriority: high\nlabels: [auth, backend]\nestimate: 3\nassignee: alice\nsprint: 12\n---\n# Fix login\n";
+        let (metadata, body) = parse_issue_content(content);
+        assert_eq!(metadata.priority, Some(IssuePriority::High));
+        assert_eq!(metadata.labels, vec!["auth", "backend"]);
+        assert_eq!(metadata.estimate.as_deref(), Some("3"));
+        assert_eq!(metadata.assignee.as_deref(), Some("alice"));
+        assert!(metadata.extra.contains_key("sprint"));
+        assert_eq!(body, "# Fix login\n");
+
+        let (metadata, body) = parse_issue_content("# Plain issue\n\n---\nnot frontmatter");
+        assert!(metadata.is_empty());
+        assert_eq!(body, "# Plain issue\n\n---\nnot frontmatter");
+
+        let (metadata, body) = parse_issue_content("---\npriority: [oops\n---\nBody");
+        assert!(metadata.is_empty());
+        assert_eq!(body, "---\npriority: [oops\n---\nBody");
+    }
+
+    #[test]
+    fn test_render_round_trip_preserves_unknown_keys() {
+        let content = "---\npriority: low\nsprint: 12\n---\nBody\n";
+        let (mut metadata, body) = parse_issue_content(content);
+        metadata.apply(&IssueMetadataUpdate {
+            priority: Some(IssuePriority::Critical),
+            labels: Some(vec!["ui".to_string(), " UI ".to_string(), "".to_string()]),
+            assignee: Some("bob".to_string()),
+            ..Default::default()
+        });
+
+        let rendered = render_issue_content(&metadata, body);
+        let (reparsed, body) = parse_issue_content(&rendered);
+        assert_eq!(reparsed.priority, Some(IssuePriority::Critical));
+        assert_eq!(reparsed.labels, vec!["ui"]);
+        assert_eq!(
+            reparsed.extra.get("sprint"),
+            Some(&serde_yaml::Value::Number(12.into()))
+        );
+        assert_eq!(body, "Body\n");
+
+        assert_eq!(
+            render_issue_content(&IssueMetadata::default(), "Body"),
+            "Body"
+        );
+    }
+
+    #[test]
+    fn test_priority_ordering_and_parsing() {
+        assert!(IssuePriority::Critical < IssuePriority::Low);
+        assert_eq!("HIGH".parse::<IssuePriority>(), Ok(IssuePriority::High));
+        assert!("urgent".parse::<IssuePriority>().is_err());
+        assert_eq!(
+            IssueMetadata::default().effective_priority(),
+            IssuePriority::Medium
+        );
+    }
+}
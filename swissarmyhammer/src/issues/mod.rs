@@ -7,6 +7,8 @@
 //! ## Features
 //!
 //! - **Markdown-based Storage**: Issues are stored as markdown files with automatic numbering
+//! - **Pluggable Backends**: An embedded database can replace the markdown files, selected
+//!   with `issues.backend` in the configuration
 //! - **Git Integration**: Automatic branch creation and management for issue workflows
 //! - **Performance Monitoring**: Built-in metrics collection for performance analysis
 //! - **Batch Operations**: Efficient batch creation, retrieval, and updates for large projects
@@ -218,21 +220,99 @@ impl AsRef<str> for IssueName {
     }
 }
 
+/// Per-issue activity log of comments and lifecycle events
+pub mod activity;
+/// Selecting and switching the issue storage backend
+pub mod backend;
+/// Acceptance criteria parsed from issue checklists
+pub mod criteria;
+/// Issue storage in an embedded database
+pub mod database;
+/// Dependency graph between issues
+pub mod dependencies;
+/// Import and export of issues in GitHub-compatible formats
+pub mod exchange;
 /// Filesystem-based issue storage implementation
 pub mod filesystem;
+/// Epics and their sub-issues
+pub mod hierarchy;
+/// Issue leases for agents working in parallel
+pub mod lease;
+/// Frontmatter metadata for issues
+pub mod metadata;
 /// Performance metrics collection and analysis
 pub mod metrics;
+/// Picking the next issue to work on
+pub mod next;
+/// Full-text and semantic issue search
+pub mod search;
+/// Issue lifecycle status and flow metrics
+pub mod status;
 /// Shared utilities for issue management
 pub mod utils;
 
+// Export activity log types
+pub use activity::{
+    activity_log_path, read_activity_log, IssueActivity, IssueActivityKind,
+    ACTIVITY_LOG_EXTENSION,
+};
+
+// Export storage backend types
+pub use backend::{
+    migrate_issues, open_issue_storage, IssueLocation, IssueStorageBackend, StoredIssue,
+    ISSUES_BACKEND_CONFIG_KEY,
+};
+
+// Export acceptance criteria types
+pub use criteria::{
+    find_criterion, parse_criteria, set_criterion, AcceptanceCriterion, CriteriaProgress,
+    CriterionSelector,
+};
+
+// Export dependency graph types
+pub use dependencies::{describe_blockers, Blocker, DependencyGraph, GraphFormat};
+
+// Export import and export types
+pub use exchange::{
+    export_records, import_records, parse_records, FieldChange, ImportAction, ImportChange,
+    IssueExchangeFormat, IssueRecord, IssueRecordState,
+};
+
+// Export epic hierarchy types
+pub use hierarchy::{EpicProgress, IssueHierarchy};
+
+// Export lease types
+pub use lease::{
+    default_lease_owner, IssueLease, LeaseStore, DEFAULT_LEASE_TTL_SECONDS, LEASE_OWNER_ENV,
+    MAX_LEASE_TTL_SECONDS,
+};
+
+// Export next issue selection types
+pub use next::{describe_held_issues, HeldIssue, HoldReason, NextIssue};
+
 // Re-export main types from the filesystem module
 pub use filesystem::{FileSystemIssueStorage, Issue, IssueInfo, IssueState, IssueStorage};
 
+// Export database storage types
+pub use database::{issue_database_path, DatabaseIssueStorage, DATABASE_FILE_NAME};
+
+// Export search types
+pub use search::{IssueSearchEngine, IssueSearchMode, IssueSearchOptions, IssueSearchResult};
+
+// Export metadata types
+pub use metadata::{
+    parse_issue_content, render_issue_content, IssueMetadata, IssueMetadataUpdate, IssuePriority,
+    IssueQuery, IssueSort,
+};
+
+// Export status types
+pub use status::{format_duration, resolve_status, FlowMetrics, IssueStatus, StatusTransition};
+
 // Export metrics types
 pub use metrics::{MetricsSnapshot, Operation, PerformanceMetrics};
 
 // Export utilities
 pub use utils::{
     format_issue_status, get_content_from_args, get_current_issue_from_branch, get_project_status,
-    work_on_issue, ContentSource, IssueBranchResult, IssueMergeResult, ProjectStatus,
+    work_on_issue, ContentSource, IssueBranchResult, IssueFlow, IssueMergeResult, ProjectStatus,
 };
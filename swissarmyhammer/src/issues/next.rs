@@ -0,0 +1,223 @@
+//! Picking the next issue to work on
+//!
+//! An open issue is workable when it is still to do, its dependencies are
+//! satisfied, it has no open sub-issues and no other agent holds a lease on it.
+//! When open issues remain but none is workable, the work loop has nothing to
+//! claim, so that case is reported apart from every issue being done.
+
+use super::dependencies::{describe_blockers, Blocker, DependencyGraph};
+use super::filesystem::{Issue, IssueInfo};
+use super::hierarchy::IssueHierarchy;
+use super::lease::IssueLease;
+use super::status::IssueStatus;
+use std::fmt;
+
+/// Outcome of looking for the next issue to work on
+#[derive(Debug, Clone, PartialEq)]
+pub enum NextIssue {
+    /// The most urgent workable issue
+    Ready(Issue),
+    /// Open issues remain, but none of them can be worked on now
+    Stalled(Vec<HeldIssue>),
+    /// No open issues remain
+    AllDone,
+}
+
+impl NextIssue {
+    /// Pick the next issue among all issues and the active leases
+    ///
+    /// The most urgent workable issue wins, ties go to the first name.
+    pub fn pick(issue_infos: Vec<IssueInfo>, leases: &[IssueLease]) -> Self {
+        Self::pick_where(issue_infos, leases, |_| true)
+    }
+
+    /// Pick the next issue, only considering open issues accepted by `in_scope`
+    ///
+    /// Dependencies and sub-issues outside the scope still count, so all issues
+    /// must be passed in.
+    pub fn pick_where(
+        issue_infos: Vec<IssueInfo>,
+        leases: &[IssueLease],
+        in_scope: impl Fn(&str) -> bool,
+    ) -> Self {
+        let graph = DependencyGraph::from_issue_infos(&issue_infos);
+        let hierarchy = IssueHierarchy::from_issue_infos(&issue_infos);
+
+        let mut ready = Vec::new();
+        let mut held = Vec::new();
+        for info in issue_infos {
+            let name = info.issue.name.as_str();
+            if info.completed || !in_scope(name) {
+                continue;
+            }
+            let status = info.status();
+            let blockers = graph.blockers(name);
+            let reason = if status != IssueStatus::Todo {
+                Some(HoldReason::Status(status))
+            } else if !blockers.is_empty() {
+                Some(HoldReason::Dependencies(blockers))
+            } else if hierarchy.has_open_children(name) {
+                // Epics are done through their sub-issues, so they are never picked themselves
+                Some(HoldReason::OpenSubIssues)
+            } else {
+                leases
+                    .iter()
+                    .find(|lease| lease.issue == name)
+                    .map(|lease| HoldReason::Claimed(lease.clone()))
+            };
+            match reason {
+                Some(reason) => held.push(HeldIssue {
+                    name: name.to_string(),
+                    reason,
+                }),
+                None => ready.push(info),
+            }
+        }
+
+        // Infos are sorted by name, so the stable sort keeps names in order per priority
+        ready.sort_by_key(|info| info.metadata.effective_priority());
+        match ready.into_iter().next() {
+            Some(info) => NextIssue::Ready(info.issue),
+            None if held.is_empty() => NextIssue::AllDone,
+            None => NextIssue::Stalled(held),
+        }
+    }
+
+    /// The picked issue, if one is workable
+    pub fn into_issue(self) -> Option<Issue> {
+        match self {
+            NextIssue::Ready(issue) => Some(issue),
+            NextIssue::Stalled(_) | NextIssue::AllDone => None,
+        }
+    }
+
+    /// Whether open issues remain that cannot be worked on now
+    pub fn is_stalled(&self) -> bool {
+        matches!(self, NextIssue::Stalled(_))
+    }
+}
+
+/// An open issue that cannot be worked on now
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeldIssue {
+    /// Name of the issue
+    pub name: String,
+    /// Why it cannot be worked on
+    pub reason: HoldReason,
+}
+
+/// Why an open issue cannot be worked on now
+#[derive(Debug, Clone, PartialEq)]
+pub enum HoldReason {
+    /// The issue is blocked, in review or already in progress
+    Status(IssueStatus),
+    /// Dependencies are pending or missing
+    Dependencies(Vec<Blocker>),
+    /// The issue is an epic with open sub-issues
+    OpenSubIssues,
+    /// Another agent holds a lease on the issue
+    Claimed(IssueLease),
+}
+
+impl fmt::Display for HeldIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            HoldReason::Status(status) => write!(f, "{} is {status}", self.name),
+            HoldReason::Dependencies(blockers) => {
+                f.write_str(&describe_blockers(&self.name, blockers))
+            }
+            HoldReason::OpenSubIssues => write!(f, "{} has open sub-issues", self.name),
+            HoldReason::Claimed(lease) => write!(
+                f,
+                "{} is claimed by {} until {}",
+                self.name,
+                lease.owner,
+                lease.expires_at().format("%Y-%m-%d %H:%M UTC")
+            ),
+        }
+    }
+}
+
+/// List held issues as markdown bullets, one per line
+pub fn describe_held_issues(held: &[HeldIssue]) -> String {
+    held.iter().map(|issue| format!("- {issue}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn info(name: &str, completed: bool, frontmatter: &str) -> IssueInfo {
+        let content = if frontmatter.is_empty() {
+            format!("# {name}")
+        } else {
+            format!("---\n{frontmatter}\n---\n# {name}")
+        };
+        IssueInfo {
+            metadata: crate::issues::parse_issue_content(&content).0,
+            issue: Issue {
+                name: name.to_string(),
+                content,
+            },
+            completed,
+            file_path: PathBuf::from(format!("{name}.md")),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn lease(issue: &str) -> IssueLease {
+        IssueLease {
+            issue: issue.to_string(),
+            owner: "agent-2".to_string(),
+            acquired_at: Utc::now(),
+            heartbeat_at: Utc::now(),
+            ttl_seconds: 600,
+        }
+    }
+
+    #[test]
+    fn test_pick_reports_all_done_apart_from_stalled() {
+        assert_eq!(
+            NextIssue::pick(vec![info("a", true, "")], &[]),
+            NextIssue::AllDone
+        );
+
+        let next = NextIssue::pick(
+            vec![
+                info("a", false, "status: blocked"),
+                info("b", false, "depends_on: [ghost]"),
+                info("c", false, ""),
+            ],
+            &[lease("c")],
+        );
+        let held = match next {
+            NextIssue::Stalled(held) => held,
+            other => panic!("expected a stalled pick, got {other:?}"),
+        };
+        assert_eq!(held.len(), 3);
+        assert_eq!(held[0].to_string(), "a is blocked");
+        assert_eq!(
+            held[1].to_string(),
+            "Issue 'b' is blocked by its dependencies: 'ghost' does not exist"
+        );
+        assert!(describe_held_issues(&held).ends_with(&format!("- {}\n", held[2])));
+        assert!(held[2]
+            .to_string()
+            .starts_with("c is claimed by agent-2 until "));
+        assert!(matches!(held[2].reason, HoldReason::Claimed(_)));
+    }
+
+    #[test]
+    fn test_pick_where_keeps_dependencies_outside_scope() {
+        let infos = vec![
+            info("a", false, ""),
+            info("b", false, "depends_on: [a]"),
+            info("c", false, "priority: critical"),
+        ];
+        let next = NextIssue::pick_where(infos.clone(), &[], |name| name == "b");
+        assert!(next.is_stalled());
+        assert_eq!(NextIssue::pick(infos, &[]).into_issue().unwrap().name, "c");
+    }
+}
@@ -0,0 +1,777 @@
+//! Full-text and semantic search over issues
+//!
+//! Keyword search uses a Tantivy index over issue titles and bodies, like
+//! [`AdvancedMemoSearchEngine`](crate::memoranda::AdvancedMemoSearchEngine) does for
+//! memos. Semantic search ranks issues by cosine similarity between embeddings from
+//! [`EmbeddingEngine`]. Both indexes are refreshed incrementally before every search:
+//! each issue is stored with a hash of its content, so only issues that were created,
+//! changed or removed since the last search are re-indexed or re-embedded.
+//!
+//! The index writer is only taken while the index is brought up to date, so the
+//! MCP server and a `sah issue search` can share one index. The index is a cache,
+//! so it lives in `.swissarmyhammer/cache`, which Git ignores, rather than next to
+//! the issues it indexes.
+
+use super::filesystem::IssueInfo;
+use super::status::IssueStatus;
+use crate::common::mcp_errors::McpResultExt;
+use crate::directory_utils::{create_git_ignored_directory, find_swissarmyhammer_directory_from};
+use crate::error::{Result, SwissArmyHammerError};
+use crate::search::{EmbeddingEngine, FileHasher, SemanticUtils};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use tantivy::{
+    collector::TopDocs,
+    directory::{error::LockError, MmapDirectory},
+    doc,
+    query::{AllQuery, QueryParser},
+    schema::{Field, Schema, Value},
+    Index, IndexReader, IndexWriter, TantivyError, Term,
+};
+use tokio::sync::{Mutex, OnceCell, RwLock};
+use tracing::{debug, warn};
+
+/// Directory under `.swissarmyhammer` holding caches
+pub const CACHE_DIR: &str = "cache";
+
+/// Name of the search index directory
+pub const SEARCH_INDEX_DIR: &str = "search_index";
+
+/// Search index directory inside an issues directory outside a Git repository, and
+/// where earlier versions kept it
+pub const LEGACY_SEARCH_INDEX_DIR: &str = ".search_index";
+
+/// Buffer size for the index writer
+const DEFAULT_WRITER_BUFFER_SIZE: usize = 15_000_000;
+
+/// How often to retry taking the index writer while another process holds it
+const WRITER_LOCK_RETRIES: u32 = 20;
+
+/// Pause between attempts to take the index writer
+const WRITER_LOCK_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// File in the index directory holding cached issue embeddings
+const EMBEDDINGS_FILE: &str = "embeddings.json";
+
+/// Maximum number of highlighted excerpts per result
+const MAX_HIGHLIGHTS: usize = 3;
+
+/// Characters of context shown on each side of a highlighted match
+const EXCERPT_CONTEXT: usize = 40;
+
+/// How search queries are matched against issues
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IssueSearchMode {
+    /// Full-text search with Tantivy
+    #[default]
+    Keyword,
+    /// Similarity between embeddings of the query and the issues
+    Semantic,
+}
+
+impl FromStr for IssueSearchMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "keyword" => Ok(IssueSearchMode::Keyword),
+            "semantic" => Ok(IssueSearchMode::Semantic),
+            _ => Err(format!(
+                "Invalid search mode '{s}': expected keyword or semantic"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for IssueSearchMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueSearchMode::Keyword => f.write_str("keyword"),
+            IssueSearchMode::Semantic => f.write_str("semantic"),
+        }
+    }
+}
+
+/// Options for searching issues
+#[derive(Debug, Clone)]
+pub struct IssueSearchOptions {
+    /// How the query is matched
+    pub mode: IssueSearchMode,
+    /// Only return issues in this status
+    pub status: Option<IssueStatus>,
+    /// Only return issues carrying all of these labels
+    pub labels: Vec<String>,
+    /// Maximum number of results
+    pub limit: usize,
+}
+
+impl Default for IssueSearchOptions {
+    fn default() -> Self {
+        Self {
+            mode: IssueSearchMode::default(),
+            status: None,
+            labels: Vec::new(),
+            limit: 10,
+        }
+    }
+}
+
+impl IssueSearchOptions {
+    /// Whether an issue passes the status and label filters
+    pub fn matches(&self, info: &IssueInfo) -> bool {
+        if self.status.is_some_and(|status| info.status() != status) {
+            return false;
+        }
+        self.labels.iter().all(|label| {
+            info.metadata
+                .labels
+                .iter()
+                .any(|l| l.eq_ignore_ascii_case(label))
+        })
+    }
+}
+
+/// An issue matching a search
+#[derive(Debug, Clone, Serialize)]
+pub struct IssueSearchResult {
+    /// Issue name
+    pub name: String,
+    /// First heading of the issue, or its name
+    pub title: String,
+    /// Lifecycle status
+    pub status: IssueStatus,
+    /// Whether the issue is in the completed directory
+    pub completed: bool,
+    /// Relevance score, BM25 for keyword and cosine similarity for semantic search
+    pub score: f32,
+    /// Excerpts with query terms in bold
+    pub highlights: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedEmbedding {
+    hash: String,
+    vector: Vec<f32>,
+}
+
+/// Search engine over issues with a keyword index and cached embeddings
+pub struct IssueSearchEngine {
+    index: Index,
+    reader: IndexReader,
+    /// Keeps syncs of this engine from competing for the index writer
+    sync_lock: Mutex<()>,
+    name_field: Field,
+    title_field: Field,
+    content_field: Field,
+    hash_field: Field,
+    embeddings_path: Option<PathBuf>,
+    embeddings: RwLock<BTreeMap<String, CachedEmbedding>>,
+    embedding_engine: OnceCell<EmbeddingEngine>,
+}
+
+impl fmt::Debug for IssueSearchEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IssueSearchEngine")
+            .field("embeddings_path", &self.embeddings_path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IssueSearchEngine {
+    /// Create a search engine whose index lives in memory
+    pub async fn new_in_memory() -> Result<Self> {
+        let index = Index::create_in_ram(Self::build_schema());
+        Self::new_from_index(index, None)
+    }
+
+    /// Open the search engine of an issues directory
+    ///
+    /// Inside a repository with a `.swissarmyhammer` directory the index lives in
+    /// `.swissarmyhammer/cache/<issues directory name>/search_index`, otherwise in
+    /// `.search_index` in the issues directory. Either directory is ignored by Git,
+    /// and an index earlier versions kept in the issues directory is removed.
+    pub async fn open_for_issues(issues_dir: &Path) -> Result<Self> {
+        let index_path = match find_swissarmyhammer_directory_from(issues_dir) {
+            Some(sah_dir) => {
+                let cache_dir = sah_dir.join(CACHE_DIR);
+                create_git_ignored_directory(&cache_dir)?;
+                let legacy_path = issues_dir.join(LEGACY_SEARCH_INDEX_DIR);
+                if legacy_path.is_dir() {
+                    debug!("Removing old issue search index {}", legacy_path.display());
+                    if let Err(e) = std::fs::remove_dir_all(&legacy_path) {
+                        warn!("Failed to remove {}: {}", legacy_path.display(), e);
+                    }
+                }
+                let name = issues_dir
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "issues".to_string());
+                cache_dir.join(name).join(SEARCH_INDEX_DIR)
+            }
+            None => {
+                let index_path = issues_dir.join(LEGACY_SEARCH_INDEX_DIR);
+                create_git_ignored_directory(&index_path)?;
+                index_path
+            }
+        };
+        Self::new_persistent(index_path).await
+    }
+
+    /// Create or open a search engine stored in `index_path`
+    pub async fn new_persistent(index_path: impl AsRef<Path>) -> Result<Self> {
+        let path = index_path.as_ref();
+        std::fs::create_dir_all(path)?;
+
+        let directory = MmapDirectory::open(path).with_tantivy_context()?;
+        let index =
+            Index::open_or_create(directory, Self::build_schema()).with_tantivy_context()?;
+        Self::new_from_index(index, Some(path.join(EMBEDDINGS_FILE)))
+    }
+
+    fn new_from_index(index: Index, embeddings_path: Option<PathBuf>) -> Result<Self> {
+        let schema = index.schema();
+        let field = |name: &str| {
+            schema.get_field(name).map_err(|_| {
+                SwissArmyHammerError::Other(format!("Missing {name} field in issue index schema"))
+            })
+        };
+        let name_field = field("name")?;
+        let title_field = field("title")?;
+        let content_field = field("content")?;
+        let hash_field = field("hash")?;
+
+        let reader = index.reader().with_tantivy_context()?;
+
+        // A missing or unreadable cache only means issues get embedded again
+        let embeddings = embeddings_path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+
+        Ok(Self {
+            index,
+            reader,
+            sync_lock: Mutex::new(()),
+            name_field,
+            title_field,
+            content_field,
+            hash_field,
+            embeddings_path,
+            embeddings: RwLock::new(embeddings),
+            embedding_engine: OnceCell::new(),
+        })
+    }
+
+    fn build_schema() -> Schema {
+        use tantivy::schema::*;
+
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("name", STRING | STORED);
+        schema_builder.add_text_field("title", TEXT);
+        schema_builder.add_text_field("content", TEXT);
+        schema_builder.add_text_field("hash", STORED);
+        schema_builder.build()
+    }
+
+    /// Bring the keyword index up to date, returning how many issues were re-indexed or removed
+    ///
+    /// The writer is only taken when something changed and is released before
+    /// returning. When another process keeps holding it, that process is updating
+    /// the index, so nothing is re-indexed here.
+    pub async fn sync(&self, issue_infos: &[IssueInfo]) -> Result<usize> {
+        let _sync = self.sync_lock.lock().await;
+        let indexed = self.indexed_hashes()?;
+        let changed: Vec<(&IssueInfo, String)> = issue_infos
+            .iter()
+            .map(|info| (info, content_hash(info)))
+            .filter(|(info, hash)| indexed.get(&info.issue.name) != Some(hash))
+            .collect();
+        let removed: Vec<&String> = indexed
+            .keys()
+            .filter(|name| !issue_infos.iter().any(|info| &info.issue.name == *name))
+            .collect();
+        if changed.is_empty() && removed.is_empty() {
+            return Ok(0);
+        }
+
+        let Some(mut writer) = self.take_writer().await? else {
+            debug!("Another process is updating the issue index, searching it as it is");
+            return Ok(0);
+        };
+        let changes = changed.len() + removed.len();
+        for (info, hash) in changed {
+            // Issue names like `000123_oauth-login` are searchable as words too
+            let title = format!(
+                "{} {}",
+                info.issue.name.replace(['_', '-'], " "),
+                issue_title(info)
+            );
+            writer.delete_term(Term::from_field_text(self.name_field, &info.issue.name));
+            writer
+                .add_document(doc!(
+                    self.name_field => info.issue.name.clone(),
+                    self.title_field => title,
+                    self.content_field => info.issue.body().to_string(),
+                    self.hash_field => hash,
+                ))
+                .with_tantivy_context()?;
+        }
+        for name in removed {
+            writer.delete_term(Term::from_field_text(self.name_field, name));
+        }
+
+        writer.commit().with_tantivy_context()?;
+        // Waiting for merges releases the writer lock for other processes
+        writer.wait_merging_threads().with_tantivy_context()?;
+        self.reader.reload().with_tantivy_context()?;
+        debug!("Re-indexed {} issues", changes);
+        Ok(changes)
+    }
+
+    /// Take the index writer, waiting briefly while another process holds it
+    ///
+    /// Returns None when it stays busy.
+    async fn take_writer(&self) -> Result<Option<IndexWriter>> {
+        let mut attempt = 0;
+        loop {
+            match self.index.writer(DEFAULT_WRITER_BUFFER_SIZE) {
+                Ok(writer) => return Ok(Some(writer)),
+                Err(TantivyError::LockFailure(LockError::LockBusy, _)) => {
+                    if attempt >= WRITER_LOCK_RETRIES {
+                        return Ok(None);
+                    }
+                    attempt += 1;
+                    tokio::time::sleep(WRITER_LOCK_RETRY_DELAY).await;
+                }
+                Err(e) => return Err(e).with_tantivy_context(),
+            }
+        }
+    }
+
+    /// Content hash of every issue currently in the index
+    fn indexed_hashes(&self) -> Result<HashMap<String, String>> {
+        let searcher = self.reader.searcher();
+        let count = searcher.num_docs() as usize;
+        if count == 0 {
+            return Ok(HashMap::new());
+        }
+
+        let docs = searcher
+            .search(&AllQuery, &TopDocs::with_limit(count))
+            .with_tantivy_context()?;
+        let mut hashes = HashMap::new();
+        for (_, address) in docs {
+            let doc = searcher
+                .doc::<tantivy::TantivyDocument>(address)
+                .with_tantivy_context()?;
+            let text = |field| {
+                doc.get_first(field)
+                    .and_then(|value| value.as_str())
+                    .map(str::to_string)
+            };
+            if let (Some(name), Some(hash)) = (text(self.name_field), text(self.hash_field)) {
+                hashes.insert(name, hash);
+            }
+        }
+        Ok(hashes)
+    }
+
+    /// Search issues, refreshing the index for the given issues first
+    pub async fn search(
+        &self,
+        query: &str,
+        options: &IssueSearchOptions,
+        issue_infos: &[IssueInfo],
+    ) -> Result<Vec<IssueSearchResult>> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        match options.mode {
+            IssueSearchMode::Keyword => self.keyword_search(query, options, issue_infos).await,
+            IssueSearchMode::Semantic => self.semantic_search(query, options, issue_infos).await,
+        }
+    }
+
+    async fn keyword_search(
+        &self,
+        query: &str,
+        options: &IssueSearchOptions,
+        issue_infos: &[IssueInfo],
+    ) -> Result<Vec<IssueSearchResult>> {
+        self.sync(issue_infos).await?;
+
+        let searcher = self.reader.searcher();
+        let count = searcher.num_docs() as usize;
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut parser =
+            QueryParser::for_index(&self.index, vec![self.title_field, self.content_field]);
+        parser.set_field_boost(self.title_field, 2.0);
+        // Lenient parsing keeps queries with stray syntax characters like `C++` usable
+        let (parsed, _) = parser.parse_query_lenient(query);
+
+        // Filters are applied after scoring, so collect every hit before limiting
+        let top_docs = searcher
+            .search(&*parsed, &TopDocs::with_limit(count))
+            .with_tantivy_context()?;
+        let infos: HashMap<&str, &IssueInfo> = issue_infos
+            .iter()
+            .map(|info| (info.issue.name.as_str(), info))
+            .collect();
+
+        let mut results = Vec::new();
+        for (score, address) in top_docs {
+            let doc = searcher
+                .doc::<tantivy::TantivyDocument>(address)
+                .with_tantivy_context()?;
+            let Some(info) = doc
+                .get_first(self.name_field)
+                .and_then(|value| value.as_str())
+                .and_then(|name| infos.get(name))
+            else {
+                continue;
+            };
+            if !options.matches(info) {
+                continue;
+            }
+            results.push(search_result(info, score, query));
+            if results.len() >= options.limit {
+                break;
+            }
+        }
+
+        debug!(
+            "Keyword search for '{}' found {} issues",
+            query,
+            results.len()
+        );
+        Ok(results)
+    }
+
+    async fn semantic_search(
+        &self,
+        query: &str,
+        options: &IssueSearchOptions,
+        issue_infos: &[IssueInfo],
+    ) -> Result<Vec<IssueSearchResult>> {
+        let engine = self
+            .embedding_engine
+            .get_or_try_init(EmbeddingEngine::new)
+            .await?;
+        self.sync_embeddings(engine, issue_infos).await?;
+
+        let query_vector = engine.embed_text(query).await?;
+        let embeddings = self.embeddings.read().await;
+        let mut results: Vec<IssueSearchResult> = issue_infos
+            .iter()
+            .filter(|info| options.matches(info))
+            .filter_map(|info| {
+                let cached = embeddings.get(&info.issue.name)?;
+                let score = SemanticUtils::cosine_similarity(&query_vector, &cached.vector);
+                Some(search_result(info, score, query))
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(options.limit);
+        debug!(
+            "Semantic search for '{}' found {} issues",
+            query,
+            results.len()
+        );
+        Ok(results)
+    }
+
+    /// Embed issues that are new or changed and drop embeddings of removed issues
+    async fn sync_embeddings(
+        &self,
+        engine: &EmbeddingEngine,
+        issue_infos: &[IssueInfo],
+    ) -> Result<()> {
+        let mut embeddings = self.embeddings.write().await;
+
+        let stale: Vec<(&IssueInfo, String)> = issue_infos
+            .iter()
+            .map(|info| (info, content_hash(info)))
+            .filter(|(info, hash)| {
+                !matches!(embeddings.get(&info.issue.name), Some(cached) if &cached.hash == hash)
+            })
+            .collect();
+        let before = embeddings.len();
+        embeddings.retain(|name, _| issue_infos.iter().any(|info| &info.issue.name == name));
+        if stale.is_empty() && embeddings.len() == before {
+            return Ok(());
+        }
+
+        let texts: Vec<String> = stale
+            .iter()
+            .map(|(info, _)| format!("{}\n\n{}", issue_title(info), info.issue.body()))
+            .collect();
+        let text_refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+        let vectors = engine.embed_batch(&text_refs).await?;
+        for ((info, hash), vector) in stale.into_iter().zip(vectors) {
+            embeddings.insert(info.issue.name.clone(), CachedEmbedding { hash, vector });
+        }
+
+        if let Some(path) = &self.embeddings_path {
+            let json = serde_json::to_string(&*embeddings)?;
+            std::fs::write(path, json)?;
+        }
+        debug!("Issue embeddings refreshed, {} cached", embeddings.len());
+        Ok(())
+    }
+}
+
+fn content_hash(info: &IssueInfo) -> String {
+    FileHasher::hash_string(&info.issue.content).0
+}
+
+/// First markdown heading of an issue, falling back to its name
+fn issue_title(info: &IssueInfo) -> String {
+    info.issue
+        .body()
+        .lines()
+        .find_map(|line| line.trim().strip_prefix('#'))
+        .map(|heading| heading.trim_start_matches('#').trim().to_string())
+        .filter(|heading| !heading.is_empty())
+        .unwrap_or_else(|| info.issue.name.clone())
+}
+
+fn search_result(info: &IssueInfo, score: f32, query: &str) -> IssueSearchResult {
+    IssueSearchResult {
+        name: info.issue.name.clone(),
+        title: issue_title(info),
+        status: info.status(),
+        completed: info.completed,
+        score,
+        highlights: highlights(info.issue.body(), query),
+    }
+}
+
+/// Excerpts around query terms with the terms in bold
+fn highlights(text: &str, query: &str) -> Vec<String> {
+    let mut excerpts = Vec::new();
+    for term in query_terms(query) {
+        let Ok(pattern) = regex::RegexBuilder::new(&regex::escape(&term))
+            .case_insensitive(true)
+            .build()
+        else {
+            continue;
+        };
+        for found in pattern.find_iter(text) {
+            let start = char_boundary_before(text, found.start().saturating_sub(EXCERPT_CONTEXT));
+            let end = char_boundary_after(text, (found.end() + EXCERPT_CONTEXT).min(text.len()));
+            let excerpt = pattern.replace_all(&text[start..end], "**$0**");
+            let excerpt = excerpt.split_whitespace().collect::<Vec<_>>().join(" ");
+            let prefix = if start > 0 { "..." } else { "" };
+            let suffix = if end < text.len() { "..." } else { "" };
+            let excerpt = format!("{prefix}{excerpt}{suffix}");
+            if !excerpts.contains(&excerpt) {
+                excerpts.push(excerpt);
+            }
+            if excerpts.len() >= MAX_HIGHLIGHTS {
+                return excerpts;
+            }
+        }
+    }
+    excerpts
+}
+
+/// Words of a query without boolean operators and query syntax
+fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .filter(|word| !matches!(*word, "AND" | "OR" | "NOT"))
+        .map(|word| {
+            word.trim_matches(|c: char| "\"*+-()".contains(c))
+                .to_string()
+        })
+        .filter(|word| word.chars().count() > 1)
+        .collect()
+}
+
+fn char_boundary_before(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn char_boundary_after(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::filesystem::Issue;
+
+    fn info(name: &str, completed: bool, content: &str) -> IssueInfo {
+        IssueInfo {
+            metadata: crate::issues::parse_issue_content(content).0,
+            issue: Issue {
+                name: name.to_string(),
+                content: content.to_string(),
+            },
+            completed,
+            file_path: PathBuf::from(format!("{name}.md")),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn sample_issues() -> Vec<IssueInfo> {
+        vec![
+            info(
+                "000001_login",
+                false,
+                "---\nlabels: [auth]\n---\n# Login fails\n\nLogin with OAuth tokens returns 500.",
+            ),
+            info(
+                "000002_cache",
+                true,
+                "# Cache warmup\n\nWarm the cache before serving traffic.",
+            ),
+            info(
+                "000003_logout",
+                false,
+                "---\nlabels: [auth, ui]\n---\n# Logout button\n\nThe logout button ignores OAuth sessions.",
+            ),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_keyword_search_with_filters_and_highlights() {
+        let engine = IssueSearchEngine::new_in_memory().await.unwrap();
+        let issues = sample_issues();
+
+        let results = engine
+            .search("oauth", &IssueSearchOptions::default(), &issues)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].highlights[0].contains("**OAuth**"));
+
+        let options = IssueSearchOptions {
+            labels: vec!["ui".to_string()],
+            ..Default::default()
+        };
+        let results = engine.search("oauth", &options, &issues).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "000003_logout");
+        assert_eq!(results[0].title, "Logout button");
+
+        let options = IssueSearchOptions {
+            status: Some(IssueStatus::Done),
+            ..Default::default()
+        };
+        let results = engine.search("cache", &options, &issues).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].completed);
+    }
+
+    #[tokio::test]
+    async fn test_sync_is_incremental() {
+        let engine = IssueSearchEngine::new_in_memory().await.unwrap();
+        let mut issues = sample_issues();
+
+        assert_eq!(engine.sync(&issues).await.unwrap(), 3);
+        assert_eq!(engine.sync(&issues).await.unwrap(), 0);
+
+        issues[1] = info("000002_cache", true, "# Cache warmup\n\nUse Redis.");
+        issues.pop();
+        assert_eq!(engine.sync(&issues).await.unwrap(), 2);
+
+        let options = IssueSearchOptions::default();
+        assert_eq!(
+            engine
+                .search("redis", &options, &issues)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(engine
+            .search("logout", &options, &issues)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_engines_share_a_persistent_index() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let index_path = temp_dir.path().join("search_index");
+        let first = IssueSearchEngine::new_persistent(&index_path)
+            .await
+            .unwrap();
+        let second = IssueSearchEngine::new_persistent(&index_path)
+            .await
+            .unwrap();
+        let mut issues = sample_issues();
+        let options = IssueSearchOptions::default();
+
+        assert_eq!(first.sync(&issues).await.unwrap(), 3);
+        assert_eq!(
+            second
+                .search("oauth", &options, &issues)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // While another process writes, a search uses the index as it is
+        let writer: IndexWriter = first.index.writer(DEFAULT_WRITER_BUFFER_SIZE).unwrap();
+        issues.pop();
+        assert_eq!(second.sync(&issues).await.unwrap(), 0);
+        drop(writer);
+        assert_eq!(second.sync(&issues).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_index_lives_in_the_ignored_cache() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let issues_dir = temp_dir.path().join(".swissarmyhammer/issues");
+        let legacy_path = issues_dir.join(LEGACY_SEARCH_INDEX_DIR);
+        std::fs::create_dir_all(&legacy_path).unwrap();
+
+        let engine = IssueSearchEngine::open_for_issues(&issues_dir)
+            .await
+            .unwrap();
+        engine.sync(&sample_issues()).await.unwrap();
+
+        assert!(!legacy_path.exists());
+        assert!(temp_dir
+            .path()
+            .join(".swissarmyhammer/cache/issues/search_index/meta.json")
+            .exists());
+        assert!(repo
+            .is_path_ignored(".swissarmyhammer/cache/issues/search_index/meta.json")
+            .unwrap());
+    }
+
+    #[test]
+    fn test_highlights_respect_char_boundaries() {
+        let text = "Ünïcödé prefix text that is long enough — the parser crashes on émoji 🚀 input";
+        let highlights = highlights(text, "parser AND crash*");
+        assert_eq!(highlights.len(), 2);
+        assert!(highlights[0].contains("**parser**"));
+        assert!(highlights[1].contains("**crash**es"));
+        assert_eq!(
+            "Semantic".parse::<IssueSearchMode>(),
+            Ok(IssueSearchMode::Semantic)
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! Issue lifecycle status and flow metrics
+//!
+//! An issue moves through `todo`, `in_progress`, `blocked` and `in_review` while its
+//! file lives in the issues directory, and ends as `done` or `wontfix` once it is
+//! moved to `complete/`. Every change is recorded as a timestamped transition in
+//! the issue frontmatter:
+//!
+//! ```markdown
+//! ---
+//! status: done
+//! transitions:
+//!   - status: in_progress
+//!     from: todo
+//!     at: 2025-01-06T09:00:00Z
+//!   - status: done
+//!     from: in_progress
+//!     at: 2025-01-07T15:30:00Z
+//! ---
+//! ```
+//!
+//! The directory stays authoritative for whether an issue is closed, so files
+//! written before statuses existed resolve to `todo` or `done`.
+
+use super::metadata::IssueMetadata;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Lifecycle status of an issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueStatus {
+    /// Not started yet
+    Todo,
+    /// Being worked on
+    InProgress,
+    /// Waiting on something outside the issue
+    Blocked,
+    /// Work finished and awaiting review
+    InReview,
+    /// Completed
+    Done,
+    /// Closed without being done
+    #[serde(rename = "wontfix")]
+    WontFix,
+}
+
+impl IssueStatus {
+    /// All statuses in display order, open ones first
+    pub const ALL: [IssueStatus; 6] = [
+        IssueStatus::InProgress,
+        IssueStatus::InReview,
+        IssueStatus::Todo,
+        IssueStatus::Blocked,
+        IssueStatus::Done,
+        IssueStatus::WontFix,
+    ];
+
+    /// Name used in frontmatter and parameters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueStatus::Todo => "todo",
+            IssueStatus::InProgress => "in_progress",
+            IssueStatus::Blocked => "blocked",
+            IssueStatus::InReview => "in_review",
+            IssueStatus::Done => "done",
+            IssueStatus::WontFix => "wontfix",
+        }
+    }
+
+    /// Human readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            IssueStatus::Todo => "Todo",
+            IssueStatus::InProgress => "In Progress",
+            IssueStatus::Blocked => "Blocked",
+            IssueStatus::InReview => "In Review",
+            IssueStatus::Done => "Done",
+            IssueStatus::WontFix => "Won't Fix",
+        }
+    }
+
+    /// Emoji shown next to the status
+    pub fn icon(&self) -> &'static str {
+        match self {
+            IssueStatus::Todo => "📋",
+            IssueStatus::InProgress => "🔄",
+            IssueStatus::Blocked => "⛔",
+            IssueStatus::InReview => "👀",
+            IssueStatus::Done => "✅",
+            IssueStatus::WontFix => "🚫",
+        }
+    }
+
+    /// Whether the issue belongs in the completed directory
+    pub fn is_closed(&self) -> bool {
+        matches!(self, IssueStatus::Done | IssueStatus::WontFix)
+    }
+}
+
+impl fmt::Display for IssueStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IssueStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_ascii_lowercase().replace(['-', ' '], "_");
+        let normalized = if normalized == "wont_fix" {
+            "wontfix".to_string()
+        } else {
+            normalized
+        };
+        IssueStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == normalized)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid status '{s}': expected one of todo, in_progress, blocked, in_review, done, wontfix"
+                )
+            })
+    }
+}
+
+/// A recorded change of status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusTransition {
+    /// Status the issue moved to
+    pub status: IssueStatus,
+    /// Status the issue moved from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<IssueStatus>,
+    /// When the change happened
+    pub at: DateTime<Utc>,
+}
+
+/// Status of an issue from its metadata and whether its file is in `complete/`
+pub fn resolve_status(metadata: &IssueMetadata, completed: bool) -> IssueStatus {
+    match (metadata.status, completed) {
+        (Some(status), true) if status.is_closed() => status,
+        (_, true) => IssueStatus::Done,
+        (Some(status), false) if !status.is_closed() => status,
+        (_, false) => IssueStatus::Todo,
+    }
+}
+
+/// Flow timings derived from an issue's transitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FlowMetrics {
+    /// First time the issue went in progress
+    pub started_at: Option<DateTime<Utc>>,
+    /// Last time the issue was closed
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl FlowMetrics {
+    /// Compute timings from recorded transitions
+    pub fn from_transitions(transitions: &[StatusTransition]) -> Self {
+        let started_at = transitions
+            .iter()
+            .find(|t| t.status == IssueStatus::InProgress)
+            .map(|t| t.at);
+        let finished_at = transitions
+            .iter()
+            .rev()
+            .find(|t| t.status.is_closed())
+            .map(|t| t.at);
+        Self {
+            started_at,
+            finished_at,
+        }
+    }
+
+    /// Time from starting work to closing the issue
+    pub fn cycle_time(&self) -> Option<Duration> {
+        match (self.started_at, self.finished_at) {
+            (Some(start), Some(end)) if end >= start => Some(end - start),
+            _ => None,
+        }
+    }
+
+    /// Time from creating the issue to closing it
+    pub fn lead_time(&self, created_at: DateTime<Utc>) -> Option<Duration> {
+        self.finished_at
+            .filter(|end| *end >= created_at)
+            .map(|end| end - created_at)
+    }
+}
+
+/// Format a duration compactly, e.g. `2d 3h`, `4h 10m` or `45m`
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / 1440, (minutes % 1440) / 60, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_status_parsing_and_resolution() {
+        assert_eq!(
+            "in-progress".parse::<IssueStatus>(),
+            Ok(IssueStatus::InProgress)
+        );
+        assert_eq!("wont-fix".parse::<IssueStatus>(), Ok(IssueStatus::WontFix));
+        assert!("started".parse::<IssueStatus>().is_err());
+
+        let mut metadata = IssueMetadata::default();
+        assert_eq!(resolve_status(&metadata, false), IssueStatus::Todo);
+        assert_eq!(resolve_status(&metadata, true), IssueStatus::Done);
+
+        metadata.status = Some(IssueStatus::WontFix);
+        assert_eq!(resolve_status(&metadata, true), IssueStatus::WontFix);
+        assert_eq!(resolve_status(&metadata, false), IssueStatus::Todo);
+    }
+
+    #[test]
+    fn test_flow_metrics() {
+        let at = |h: i64| Utc.timestamp_opt(h * 3600, 0).unwrap();
+        let transitions = vec![
+            StatusTransition {
+                status: IssueStatus::InProgress,
+                from: Some(IssueStatus::Todo),
+                at: at(10),
+            },
+            StatusTransition {
+                status: IssueStatus::InReview,
+                from: Some(IssueStatus::InProgress),
+                at: at(30),
+            },
+            StatusTransition {
+                status: IssueStatus::Done,
+                from: Some(IssueStatus::InReview),
+                at: at(36),
+            },
+        ];
+
+        let metrics = FlowMetrics::from_transitions(&transitions);
+        assert_eq!(metrics.cycle_time(), Some(Duration::hours(26)));
+        assert_eq!(metrics.lead_time(at(0)), Some(Duration::hours(36)));
+        assert_eq!(format_duration(Duration::hours(26)), "1d 2h");
+        assert_eq!(format_duration(Duration::minutes(90)), "1h 30m");
+        assert_eq!(
+            FlowMetrics::from_transitions(&[]).cycle_time(),
+            None::<Duration>
+        );
+    }
+}
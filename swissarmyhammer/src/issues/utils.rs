@@ -4,8 +4,12 @@
 //! to ensure consistent behavior and reduce code duplication.
 
 use crate::git::GitOperations;
-use crate::issues::{Issue, IssueInfo, IssueStorage};
+use crate::issues::{
+    describe_blockers, format_duration, DependencyGraph, Issue, IssueInfo, IssueStatus,
+    IssueStorage,
+};
 use crate::{Result, SwissArmyHammerError};
+use chrono::Duration;
 use std::io::{self, Read};
 use std::path::PathBuf;
 
@@ -106,6 +110,16 @@ pub async fn work_on_issue<S: IssueStorage>(
     // Get the issue to ensure it exists
     let issue = storage.get_issue(issue_name).await?;
 
+    // Refuse to start an issue whose dependencies are not done yet
+    let graph = DependencyGraph::from_issue_infos(&storage.list_issues_info().await?);
+    let blockers = graph.blockers(&issue.name);
+    if !blockers.is_empty() {
+        return Err(SwissArmyHammerError::IssueBlocked(describe_blockers(
+            &issue.name,
+            &blockers,
+        )));
+    }
+
     // Create work branch with format: issue/{issue_name}
     let branch_name = format!("issue/{}", issue.name);
     let current_branch = git_ops.current_branch()?;
@@ -114,6 +128,16 @@ pub async fn work_on_issue<S: IssueStorage>(
     // Create or switch to the work branch (branch from current HEAD)
     let actual_branch_name = git_ops.create_work_branch(&issue.name)?;
 
+    // Record that work has started unless the issue is already closed
+    let status = storage.get_issue_info(&issue.name).await?.status();
+    let issue = if status.is_closed() {
+        issue
+    } else {
+        storage
+            .set_issue_status(&issue.name, IssueStatus::InProgress)
+            .await?
+    };
+
     Ok(IssueBranchResult {
         issue,
         branch_name: actual_branch_name,
@@ -153,6 +177,23 @@ pub struct ProjectStatus {
     pub active_issues: Vec<Issue>,
     /// List of completed issues
     pub completed_issues: Vec<Issue>,
+    /// Number of issues in each status, in display order
+    pub status_counts: Vec<(IssueStatus, usize)>,
+    /// Flow timings of closed issues that recorded their transitions
+    pub flow: Vec<IssueFlow>,
+}
+
+/// Flow timings of a single issue
+#[derive(Debug, Clone)]
+pub struct IssueFlow {
+    /// Issue name
+    pub name: String,
+    /// Current status
+    pub status: IssueStatus,
+    /// Time from starting work to closing the issue
+    pub cycle_time: Option<Duration>,
+    /// Time from creating the issue to closing it
+    pub lead_time: Option<Duration>,
 }
 
 impl ProjectStatus {
@@ -181,6 +222,29 @@ impl ProjectStatus {
 
         let all_complete = active_count == 0 && total_issues > 0;
 
+        let status_counts = IssueStatus::ALL
+            .into_iter()
+            .map(|status| {
+                let count = issue_infos.iter().filter(|i| i.status() == status).count();
+                (status, count)
+            })
+            .collect();
+
+        let flow = issue_infos
+            .iter()
+            .filter_map(|info| {
+                let metrics = info.flow_metrics();
+                let cycle_time = metrics.cycle_time();
+                let lead_time = metrics.lead_time(info.created_at);
+                (cycle_time.is_some() || lead_time.is_some()).then(|| IssueFlow {
+                    name: info.issue.name.clone(),
+                    status: info.status(),
+                    cycle_time,
+                    lead_time,
+                })
+            })
+            .collect();
+
         Self {
             total_issues,
             completed_count,
@@ -189,7 +253,60 @@ impl ProjectStatus {
             all_complete,
             active_issues,
             completed_issues,
+            status_counts,
+            flow,
+        }
+    }
+
+    /// Average cycle time over issues that recorded one
+    pub fn average_cycle_time(&self) -> Option<Duration> {
+        let cycle_times: Vec<Duration> = self.flow.iter().filter_map(|f| f.cycle_time).collect();
+        if cycle_times.is_empty() {
+            return None;
+        }
+        let total: Duration = cycle_times.iter().fold(Duration::zero(), |acc, d| acc + *d);
+        Some(total / cycle_times.len() as i32)
+    }
+
+    /// Status breakdown and cycle times, appended to the detailed report
+    fn flow_report(&self) -> String {
+        let counts = self
+            .status_counts
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(status, count)| format!("• {} {}: {}", status.icon(), status.label(), count))
+            .collect::<Vec<_>>();
+        if counts.is_empty() {
+            return String::new();
+        }
+
+        let mut report = format!("\n\n📈 By Status:\n{}", counts.join("\n"));
+        if self.flow.is_empty() {
+            return report;
+        }
+
+        report.push_str("\n\n⏱️ Flow Metrics:");
+        if let Some(average) = self.average_cycle_time() {
+            report.push_str(&format!(
+                "\n• Average cycle time: {}",
+                format_duration(average)
+            ));
         }
+        for flow in &self.flow {
+            let cycle = flow
+                .cycle_time
+                .map(format_duration)
+                .unwrap_or_else(|| "-".to_string());
+            let lead = flow
+                .lead_time
+                .map(format_duration)
+                .unwrap_or_else(|| "-".to_string());
+            report.push_str(&format!(
+                "\n• {} ({}): cycle {}, lead {}",
+                flow.name, flow.status, cycle, lead
+            ));
+        }
+        report
     }
 
     /// Generate a simple status summary text
@@ -202,7 +319,7 @@ impl ProjectStatus {
 
     /// Generate a detailed status report
     pub fn detailed_report(&self) -> String {
-        if self.all_complete {
+        let report = if self.all_complete {
             format!(
                 "🎉 All issues are complete!\n\n📊 Project Status:\n• Total Issues: {}\n• Completed: {} (100%)\n• Active: 0\n\n✅ Completed Issues:\n{}",
                 self.total_issues,
@@ -240,7 +357,8 @@ impl ProjectStatus {
                 active_list,
                 completed_list
             )
-        }
+        };
+        report + &self.flow_report()
     }
 }
 
@@ -424,18 +542,21 @@ mod tests {
                 completed: false,
                 file_path: PathBuf::from("/test/active1.md"),
                 created_at: Utc::now(),
+                metadata: Default::default(),
             },
             IssueInfo {
                 issue: active_issue2.clone(),
                 completed: false,
                 file_path: PathBuf::from("/test/active2.md"),
                 created_at: Utc::now(),
+                metadata: Default::default(),
             },
             IssueInfo {
                 issue: completed_issue.clone(),
                 completed: true,
                 file_path: PathBuf::from("/test/completed/completed1.md"),
                 created_at: Utc::now(),
+                metadata: Default::default(),
             },
         ];
         let status = ProjectStatus::from_issue_infos(issue_infos);
@@ -464,6 +585,7 @@ mod tests {
             completed: true,
             file_path: PathBuf::from("/test/completed/completed1.md"),
             created_at: Utc::now(),
+            metadata: Default::default(),
         }];
         let all_complete_status = ProjectStatus::from_issue_infos(all_completed);
         assert!(all_complete_status.all_complete);
@@ -476,6 +598,38 @@ mod tests {
         assert!(!empty_status.all_complete); // Empty project is not "complete"
     }
 
+    #[test]
+    fn test_detailed_report_flow_metrics() {
+        use chrono::{TimeZone, Utc};
+        use std::path::PathBuf;
+
+        let at = |h: i64| Utc.timestamp_opt(h * 3600, 0).unwrap();
+        let content = format!(
+            "---\nstatus: done\ntransitions:\n  - status: in_progress\n    from: todo\n    at: {}\n  - status: done\n    from: in_progress\n    at: {}\n---\nBody",
+            at(2).to_rfc3339(),
+            at(50).to_rfc3339()
+        );
+        let issue = Issue {
+            name: "finished".to_string(),
+            content,
+        };
+        let info = IssueInfo {
+            metadata: issue.metadata(),
+            issue,
+            completed: true,
+            file_path: PathBuf::from("/test/complete/finished.md"),
+            created_at: at(0),
+        };
+
+        let status = ProjectStatus::from_issue_infos(vec![info]);
+        assert_eq!(status.average_cycle_time(), Some(Duration::hours(48)));
+
+        let report = status.detailed_report();
+        assert!(report.contains("✅ Done: 1"));
+        assert!(report.contains("Average cycle time: 2d 0h"));
+        assert!(report.contains("finished (done): cycle 2d 0h, lead 2d 2h"));
+    }
+
     #[test]
     fn test_format_issue_status() {
         assert_eq!(format_issue_status(true), "✅ Completed");
@@ -69,6 +69,9 @@ pub mod outline;
 /// Plugin system for extensibility
 pub mod plugins;
 
+/// Installable packages of workflows, prompts and partials
+pub mod package;
+
 /// Workflow system for state-based execution
 pub mod workflow;
 
@@ -0,0 +1,392 @@
+//! Revision history for memos
+//!
+//! Every change to a memo keeps the version it replaced in a JSON lines sidecar
+//! under the memo directory, one file per memo:
+//!
+//! ```text
+//! memos/
+//! ├── Session_handling.md
+//! └── .history/
+//!     └── Session_handling.jsonl
+//! ```
+//!
+//! Revisions are numbered from 1 and never renumbered, so a revision keeps its
+//! number while newer ones are added. Only the most recent revisions are kept,
+//! configured in `sah.toml`:
+//!
+//! ```toml
+//! [memoranda]
+//! history_limit = 20
+//! ```
+//!
+//! A limit of 0 turns history off.
+
+use super::{Memo, MemoId};
+use crate::error::{Result, SwissArmyHammerError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Configuration key for the number of revisions kept per memo
+pub const MEMO_HISTORY_LIMIT_CONFIG_KEY: &str = "memoranda.history_limit";
+
+/// Revisions kept per memo when `memoranda.history_limit` is not set
+pub const DEFAULT_MEMO_HISTORY_LIMIT: usize = 20;
+
+/// Directory under the memo directory holding history sidecars
+pub const MEMO_HISTORY_DIR: &str = ".history";
+
+/// Lines of unchanged context around each change in a unified diff
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// A previous version of a memo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoRevision {
+    /// Revision number, starting at 1 for the oldest change
+    pub revision: u32,
+    /// When this version was written
+    pub updated_at: DateTime<Utc>,
+    /// When this version was replaced
+    pub replaced_at: DateTime<Utc>,
+    /// Content of this version
+    pub content: String,
+    /// Tags of this version
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Folder of this version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+}
+
+impl MemoRevision {
+    /// Apply this revision's content, tags and folder to a memo
+    pub fn apply_to(&self, memo: &mut Memo) {
+        memo.content = self.content.clone();
+        memo.tags = self.tags.clone();
+        memo.folder = self.folder.clone();
+        memo.updated_at = Utc::now();
+    }
+}
+
+/// History sidecars of the memos in one directory
+#[derive(Debug, Clone)]
+pub struct MemoHistory {
+    dir: PathBuf,
+    limit: usize,
+}
+
+impl MemoHistory {
+    /// History for the memos in `memos_dir`, keeping at most `limit` revisions per memo
+    pub fn new(memos_dir: &Path, limit: usize) -> Self {
+        Self {
+            dir: memos_dir.join(MEMO_HISTORY_DIR),
+            limit,
+        }
+    }
+
+    /// The revision limit from `memoranda.history_limit`, or the default
+    ///
+    /// Configuration that cannot be loaded or holds an invalid limit falls back to
+    /// the default with a warning.
+    pub fn configured_limit() -> usize {
+        let context = match swissarmyhammer_config::load_configuration() {
+            Ok(context) => context,
+            Err(e) => {
+                warn!("Failed to load configuration, keeping {DEFAULT_MEMO_HISTORY_LIMIT} memo revisions: {e}");
+                return DEFAULT_MEMO_HISTORY_LIMIT;
+            }
+        };
+
+        match context.get(MEMO_HISTORY_LIMIT_CONFIG_KEY) {
+            None => DEFAULT_MEMO_HISTORY_LIMIT,
+            Some(value) => match value.as_u64() {
+                Some(limit) => limit as usize,
+                None => {
+                    warn!(
+                        "Invalid {MEMO_HISTORY_LIMIT_CONFIG_KEY} '{value}', keeping {DEFAULT_MEMO_HISTORY_LIMIT} memo revisions"
+                    );
+                    DEFAULT_MEMO_HISTORY_LIMIT
+                }
+            },
+        }
+    }
+
+    /// Maximum number of revisions kept per memo
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    fn path(&self, id: &MemoId) -> PathBuf {
+        self.dir.join(format!("{}.jsonl", id.as_str()))
+    }
+
+    /// Recorded revisions of a memo, oldest first
+    pub async fn revisions(&self, id: &MemoId) -> Result<Vec<MemoRevision>> {
+        let path = self.path(id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = tokio::fs::read_to_string(&path).await?;
+        let mut revisions = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<MemoRevision>(line) {
+                Ok(revision) => revisions.push(revision),
+                Err(e) => warn!(
+                    "Skipping invalid revision on line {} of {}: {}",
+                    index + 1,
+                    path.display(),
+                    e
+                ),
+            }
+        }
+        Ok(revisions)
+    }
+
+    /// One recorded revision of a memo
+    pub async fn revision(&self, id: &MemoId, revision: u32) -> Result<MemoRevision> {
+        self.revisions(id)
+            .await?
+            .into_iter()
+            .find(|r| r.revision == revision)
+            .ok_or_else(|| SwissArmyHammerError::MemoRevisionNotFound {
+                id: id.as_str().to_string(),
+                revision,
+            })
+    }
+
+    /// Keep the current version of a memo before it is replaced
+    ///
+    /// The oldest revisions are dropped once the memo has more than the limit.
+    pub async fn record(&self, memo: &Memo) -> Result<()> {
+        if self.limit == 0 {
+            return Ok(());
+        }
+
+        let mut revisions = self.revisions(&memo.id).await?;
+        let next = revisions.last().map_or(1, |r| r.revision + 1);
+        revisions.push(MemoRevision {
+            revision: next,
+            updated_at: memo.updated_at,
+            replaced_at: Utc::now(),
+            content: memo.content.clone(),
+            tags: memo.tags.clone(),
+            folder: memo.folder.clone(),
+        });
+        let excess = revisions.len().saturating_sub(self.limit);
+        revisions.drain(..excess);
+
+        let mut lines = String::new();
+        for revision in &revisions {
+            lines.push_str(&serde_json::to_string(revision)?);
+            lines.push('\n');
+        }
+
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let path = self.path(&memo.id);
+        let temp_path = path.with_extension("jsonl.tmp");
+        tokio::fs::write(&temp_path, lines).await?;
+        tokio::fs::rename(&temp_path, &path).await?;
+        Ok(())
+    }
+
+    /// Forget the history of a deleted memo
+    pub async fn remove(&self, id: &MemoId) -> Result<()> {
+        let path = self.path(id);
+        if path.exists() {
+            tokio::fs::remove_file(path).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Unified diff between two texts, empty when they are equal
+///
+/// Lines are compared exactly; each hunk carries up to three lines of context.
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let lines = diff_lines(&old_lines, &new_lines);
+
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (change, _))| *change != LineChange::Same)
+        .map(|(index, _)| index)
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // Group changes whose context overlaps into hunks of line ranges
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for index in changed {
+        let start = index.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (index + DIFF_CONTEXT_LINES + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut output = format!("--- {old_label}\n+++ {new_label}\n");
+    for (start, end) in hunks {
+        let old_start = count_lines(&lines[..start], LineChange::Added);
+        let new_start = count_lines(&lines[..start], LineChange::Removed);
+        let old_count = count_lines(&lines[start..end], LineChange::Added);
+        let new_count = count_lines(&lines[start..end], LineChange::Removed);
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_count),
+            hunk_range(new_start, new_count)
+        ));
+        for (change, text) in &lines[start..end] {
+            let marker = match change {
+                LineChange::Same => ' ',
+                LineChange::Removed => '-',
+                LineChange::Added => '+',
+            };
+            output.push(marker);
+            output.push_str(text);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineChange {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Line-by-line edit script from a longest common subsequence
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(LineChange, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j] is the common subsequence length of old_middle[i..] and new_middle[j..]
+    let (n, m) = (old_middle.len(), new_middle.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_middle[i] == new_middle[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines: Vec<(LineChange, &str)> = old[..prefix]
+        .iter()
+        .map(|line| (LineChange::Same, *line))
+        .collect();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_middle[i] == new_middle[j] {
+            lines.push((LineChange::Same, old_middle[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push((LineChange::Removed, old_middle[i]));
+            i += 1;
+        } else {
+            lines.push((LineChange::Added, new_middle[j]));
+            j += 1;
+        }
+    }
+    lines.extend(
+        old_middle[i..]
+            .iter()
+            .map(|line| (LineChange::Removed, *line)),
+    );
+    lines.extend(
+        new_middle[j..]
+            .iter()
+            .map(|line| (LineChange::Added, *line)),
+    );
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| (LineChange::Same, *line)),
+    );
+    lines
+}
+
+/// Lines of one side of a diff, skipping those only on the other side
+fn count_lines(lines: &[(LineChange, &str)], other_side: LineChange) -> usize {
+    lines
+        .iter()
+        .filter(|(change, _)| *change != other_side)
+        .count()
+}
+
+/// Hunk header range in unified diff notation
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unified_diff() {
+        let old: String = (1..=12).map(|n| format!("{n}\n")).collect();
+        let new = format!("{}13\n", old.replace("2\n3\n", "two\n3\n"));
+
+        assert_eq!(
+            unified_diff(&old, &new, "revision 1", "current"),
+            "--- revision 1\n+++ current\n\
+             @@ -1,5 +1,5 @@\n 1\n-2\n+two\n 3\n 4\n 5\n\
+             @@ -10,3 +10,4 @@\n 10\n 11\n 12\n+13\n"
+        );
+        assert_eq!(unified_diff(&old, &old, "a", "b"), "");
+        assert_eq!(
+            unified_diff("", "new\n", "a", "b"),
+            "--- a\n+++ b\n@@ -0,0 +1 @@\n+new\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_keeps_latest_revisions() {
+        let temp_dir = TempDir::new().unwrap();
+        let history = MemoHistory::new(temp_dir.path(), 2);
+        let mut memo = Memo::new("Notes".into(), "v1".into());
+
+        for content in ["v2", "v3", "v4"] {
+            history.record(&memo).await.unwrap();
+            memo.update_content(content.into());
+        }
+
+        let revisions = history.revisions(&memo.id).await.unwrap();
+        let numbers: Vec<u32> = revisions.iter().map(|r| r.revision).collect();
+        assert_eq!(numbers, vec![2, 3]);
+        assert_eq!(revisions[1].content, "v3");
+        assert!(matches!(
+            history.revision(&memo.id, 1).await,
+            Err(SwissArmyHammerError::MemoRevisionNotFound { revision: 1, .. })
+        ));
+
+        history.remove(&memo.id).await.unwrap();
+        assert!(history.revisions(&memo.id).await.unwrap().is_empty());
+    }
+}
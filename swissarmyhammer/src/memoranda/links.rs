@@ -0,0 +1,470 @@
+//! Wiki-style links between memos, issues and files
+//!
+//! Memo content refers to other knowledge with double-bracket links:
+//!
+//! ```markdown
+//! See [[Session handling]] for the token format, tracked in
+//! [[issue:FEATURE_001_login]] and implemented in [[file:src/auth.rs#L10]].
+//! ```
+//!
+//! A memo link names a memo by title (or ID), `issue:` links name an issue and
+//! `file:` links name a path relative to the project root, optionally with a
+//! `#L<line>` anchor. `[[target|label]]` attaches a display label. Links inside
+//! fenced code blocks and inline code are ignored.
+//!
+//! [`LinkIndex`] is built from the memo collection on demand, resolves links to
+//! find broken ones, answers backlink queries and renders the graph as Mermaid.
+
+use super::{Memo, MemoId};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// What a link points to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LinkTarget {
+    /// A memo, by title or ID
+    Memo(String),
+    /// An issue, by name
+    Issue(String),
+    /// A file relative to the project root, optionally at a line
+    File {
+        /// Path as written in the link
+        path: String,
+        /// 1-based line from a `#L<line>` anchor
+        line: Option<usize>,
+    },
+}
+
+impl LinkTarget {
+    /// Parse the inside of `[[...]]`, without any `|label`
+    fn parse(target: &str) -> Option<Self> {
+        let target = target.trim();
+        let target = if let Some(name) = strip_prefix_ignore_case(target, "issue:") {
+            LinkTarget::Issue(name.trim().to_string())
+        } else if let Some(file) = strip_prefix_ignore_case(target, "file:") {
+            let file = file.trim();
+            match file.rsplit_once("#L") {
+                Some((path, line)) if line.parse::<usize>().is_ok_and(|l| l > 0) => {
+                    LinkTarget::File {
+                        path: path.to_string(),
+                        line: line.parse().ok(),
+                    }
+                }
+                _ => LinkTarget::File {
+                    path: file.to_string(),
+                    line: None,
+                },
+            }
+        } else {
+            LinkTarget::Memo(target.to_string())
+        };
+
+        let empty = match &target {
+            LinkTarget::Memo(name) | LinkTarget::Issue(name) => name.is_empty(),
+            LinkTarget::File { path, .. } => path.is_empty(),
+        };
+        (!empty).then_some(target)
+    }
+
+    /// Whether two targets name the same memo, issue or file, ignoring line anchors
+    fn same_place(&self, other: &LinkTarget) -> bool {
+        match (self, other) {
+            (LinkTarget::Memo(a), LinkTarget::Memo(b)) => a.eq_ignore_ascii_case(b),
+            (LinkTarget::Issue(a), LinkTarget::Issue(b)) => a == b,
+            (LinkTarget::File { path: a, .. }, LinkTarget::File { path: b, .. }) => {
+                normalize_path(a) == normalize_path(b)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for LinkTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkTarget::Memo(title) => write!(f, "[[{title}]]"),
+            LinkTarget::Issue(name) => write!(f, "[[issue:{name}]]"),
+            LinkTarget::File {
+                path,
+                line: Some(line),
+            } => write!(f, "[[file:{path}#L{line}]]"),
+            LinkTarget::File { path, line: None } => write!(f, "[[file:{path}]]"),
+        }
+    }
+}
+
+/// A link found in memo content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoLink {
+    /// What the link points to
+    pub target: LinkTarget,
+    /// 1-based line of the memo content holding the link
+    pub line: usize,
+}
+
+/// Outcome of resolving a link against memos, issues and files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkResolution {
+    /// The memo with this ID
+    Memo(MemoId),
+    /// An existing issue or file
+    Found,
+    /// Nothing matches; the reason says what is missing
+    Broken(String),
+}
+
+impl LinkResolution {
+    /// Whether the link points at nothing
+    pub fn is_broken(&self) -> bool {
+        matches!(self, LinkResolution::Broken(_))
+    }
+}
+
+/// Links found in memo content, each target once, in order of appearance
+pub fn parse_links(content: &str) -> Vec<MemoLink> {
+    let mut links: Vec<MemoLink> = Vec::new();
+    let mut in_fence = false;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        // Odd segments between backticks are inline code
+        for segment in line.split('`').step_by(2) {
+            let mut rest = segment;
+            while let Some(start) = rest.find("[[") {
+                let after = &rest[start + 2..];
+                let Some(end) = after.find("]]") else {
+                    break;
+                };
+                let inner = &after[..end];
+                rest = &after[end + 2..];
+                if inner.contains('[') {
+                    continue;
+                }
+                let target = inner.split('|').next().unwrap_or_default();
+                if let Some(target) = LinkTarget::parse(target) {
+                    if !links.iter().any(|link| link.target == target) {
+                        links.push(MemoLink {
+                            target,
+                            line: index + 1,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    links
+}
+
+/// Links of a memo collection, resolved against memos, issues and files
+#[derive(Debug, Clone)]
+pub struct LinkIndex {
+    memos: Vec<(MemoId, String)>,
+    outgoing: BTreeMap<MemoId, Vec<MemoLink>>,
+    issues: HashSet<String>,
+    root: PathBuf,
+}
+
+impl LinkIndex {
+    /// Index the links of `memos`, checking issue links against `issue_names`
+    /// and file links against files under `root`
+    pub fn build(
+        memos: &[Memo],
+        issue_names: impl IntoIterator<Item = String>,
+        root: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            memos: memos
+                .iter()
+                .map(|memo| (memo.id.clone(), memo.title.clone()))
+                .collect(),
+            outgoing: memos
+                .iter()
+                .map(|memo| (memo.id.clone(), parse_links(&memo.content)))
+                .collect(),
+            issues: issue_names.into_iter().collect(),
+            root: root.into(),
+        }
+    }
+
+    /// Links in a memo's content, empty for unknown memos
+    pub fn outgoing(&self, id: &MemoId) -> &[MemoLink] {
+        self.outgoing.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Resolve a link target
+    pub fn resolve(&self, target: &LinkTarget) -> LinkResolution {
+        match target {
+            LinkTarget::Memo(name) => match self.find_memo(name) {
+                Some(id) => LinkResolution::Memo(id.clone()),
+                None => LinkResolution::Broken(format!("no memo titled '{name}'")),
+            },
+            LinkTarget::Issue(name) => {
+                if self.issues.contains(name) {
+                    LinkResolution::Found
+                } else {
+                    LinkResolution::Broken(format!("no issue named '{name}'"))
+                }
+            }
+            LinkTarget::File { path, line } => self.resolve_file(path, *line),
+        }
+    }
+
+    /// Memos linking to a memo, in index order
+    pub fn memo_backlinks(&self, id: &MemoId) -> Vec<&MemoId> {
+        self.sources(
+            |target| matches!(target, LinkTarget::Memo(name) if self.find_memo(name) == Some(id)),
+        )
+    }
+
+    /// Memos linking to the same memo, issue or file as `target`
+    ///
+    /// Line anchors are ignored, so a file backlink query matches links to any line.
+    pub fn backlinks(&self, target: &LinkTarget) -> Vec<&MemoId> {
+        match target {
+            LinkTarget::Memo(name) => match self.find_memo(name) {
+                Some(id) => self.memo_backlinks(id),
+                None => self.sources(|t| t.same_place(target)),
+            },
+            _ => self.sources(|t| t.same_place(target)),
+        }
+    }
+
+    /// Title of an indexed memo
+    pub fn title(&self, id: &MemoId) -> Option<&str> {
+        self.memos
+            .iter()
+            .find(|(memo_id, _)| memo_id == id)
+            .map(|(_, title)| title.as_str())
+    }
+
+    /// Render the links of the given memos as a Mermaid flowchart
+    ///
+    /// Every memo in `sources` is a node, linked targets become nodes as needed.
+    /// Broken links are drawn dashed to a node styled as missing.
+    pub fn to_mermaid(&self, sources: &[MemoId]) -> String {
+        let mut nodes: Vec<(String, &'static str)> = Vec::new();
+        let mut keys: Vec<String> = Vec::new();
+        let mut node = |key: String, label: String, class: &'static str| -> usize {
+            match keys.iter().position(|k| *k == key) {
+                Some(index) => index,
+                None => {
+                    keys.push(key);
+                    nodes.push((label, class));
+                    nodes.len() - 1
+                }
+            }
+        };
+
+        let mut edges = Vec::new();
+        for id in sources {
+            let title = self.title(id).unwrap_or(id.as_str()).to_string();
+            let from = node(
+                format!("memo:{id}"),
+                format!("[\"{}\"]", label(&title)),
+                "memo",
+            );
+            for link in self.outgoing(id) {
+                let resolution = self.resolve(&link.target);
+                let to = match (&link.target, &resolution) {
+                    (_, LinkResolution::Broken(_)) => node(
+                        format!("broken:{}", link.target),
+                        format!("[\"{}\"]", label(&link.target.to_string())),
+                        "broken",
+                    ),
+                    (_, LinkResolution::Memo(target_id)) => {
+                        let title = self.title(target_id).unwrap_or(target_id.as_str());
+                        node(
+                            format!("memo:{target_id}"),
+                            format!("[\"{}\"]", label(title)),
+                            "memo",
+                        )
+                    }
+                    (LinkTarget::Issue(name), _) => node(
+                        format!("issue:{name}"),
+                        format!("([\"issue: {}\"])", label(name)),
+                        "issue",
+                    ),
+                    (LinkTarget::File { path, .. }, _) => node(
+                        format!("file:{}", normalize_path(path)),
+                        format!("[/\"{}\"/]", label(path)),
+                        "file",
+                    ),
+                    (LinkTarget::Memo(_), LinkResolution::Found) => continue,
+                };
+                let arrow = if resolution.is_broken() {
+                    "-.->"
+                } else {
+                    "-->"
+                };
+                let edge_label = match &link.target {
+                    LinkTarget::File {
+                        line: Some(line), ..
+                    } => format!("|L{line}|"),
+                    _ => String::new(),
+                };
+                edges.push(format!("    n{from} {arrow}{edge_label} n{to}\n"));
+            }
+        }
+
+        let mut out = String::from("graph TD\n");
+        for (index, (shape, class)) in nodes.iter().enumerate() {
+            out.push_str(&format!("    n{index}{shape}:::{class}\n"));
+        }
+        for edge in edges {
+            out.push_str(&edge);
+        }
+        out.push_str("    classDef memo fill:#cce5ff,stroke:#004085\n");
+        out.push_str("    classDef issue fill:#fff3cd,stroke:#856404\n");
+        out.push_str("    classDef file fill:#e2e3e5,stroke:#383d41\n");
+        out.push_str("    classDef broken fill:#f8d7da,stroke:#721c24,stroke-dasharray:3\n");
+        out
+    }
+
+    fn find_memo(&self, name: &str) -> Option<&MemoId> {
+        self.memos
+            .iter()
+            .find(|(_, title)| title.eq_ignore_ascii_case(name))
+            .or_else(|| {
+                self.memos
+                    .iter()
+                    .find(|(id, _)| id.as_str().eq_ignore_ascii_case(name))
+            })
+            .map(|(id, _)| id)
+    }
+
+    fn sources(&self, links_to: impl Fn(&LinkTarget) -> bool) -> Vec<&MemoId> {
+        self.memos
+            .iter()
+            .map(|(id, _)| id)
+            .filter(|id| self.outgoing(id).iter().any(|link| links_to(&link.target)))
+            .collect()
+    }
+
+    fn resolve_file(&self, path: &str, line: Option<usize>) -> LinkResolution {
+        let full_path = self.root.join(path);
+        if !full_path.is_file() {
+            return LinkResolution::Broken(format!("no file at '{path}'"));
+        }
+        let Some(line) = line else {
+            return LinkResolution::Found;
+        };
+        match std::fs::read_to_string(&full_path) {
+            Ok(content) if content.lines().count() >= line => LinkResolution::Found,
+            Ok(content) => LinkResolution::Broken(format!(
+                "'{path}' has {} lines, not {line}",
+                content.lines().count()
+            )),
+            Err(e) => LinkResolution::Broken(format!("cannot read '{path}': {e}")),
+        }
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    s.get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| &s[prefix.len()..])
+}
+
+fn normalize_path(path: &str) -> String {
+    Path::new(path.trim_start_matches("./"))
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Mermaid node label text with quotes made safe
+fn label(text: &str) -> String {
+    text.replace('"', "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn memo(title: &str, content: &str) -> Memo {
+        let mut memo = Memo::new(title.to_string(), content.to_string());
+        memo.id = MemoId::from_filename(title);
+        memo
+    }
+
+    #[test]
+    fn test_parse_links() {
+        let content = "See [[Design|the design]] and [[issue:login]].\n\
+                       ```\n[[ignored]]\n```\n\
+                       Code `[[ignored]]`, [[file:src/a.rs#L10]], [[file:b.md#Lx]], [[ ]] [[Design]]";
+        let links = parse_links(content);
+        let targets: Vec<String> = links.iter().map(|l| l.target.to_string()).collect();
+        assert_eq!(
+            targets,
+            vec![
+                "[[Design]]",
+                "[[issue:login]]",
+                "[[file:src/a.rs#L10]]",
+                "[[file:b.md#Lx]]"
+            ]
+        );
+        assert_eq!(links[2].line, 5);
+    }
+
+    #[test]
+    fn test_link_index_resolution_and_backlinks() {
+        let root = TempDir::new().unwrap();
+        std::fs::write(root.path().join("main.rs"), "fn main() {}\n").unwrap();
+
+        let memos = vec![
+            memo("Design", "Links to [[Roadmap]] and [[issue:login]]"),
+            memo(
+                "Roadmap",
+                "Back to [[design]], [[file:main.rs#L1]], [[file:main.rs#L9]] and [[Missing]]",
+            ),
+        ];
+        let index = LinkIndex::build(&memos, vec!["login".to_string()], root.path());
+        let design = &memos[0].id;
+        let roadmap = &memos[1].id;
+
+        let resolutions: Vec<LinkResolution> = index
+            .outgoing(roadmap)
+            .iter()
+            .map(|link| index.resolve(&link.target))
+            .collect();
+        assert_eq!(resolutions[0], LinkResolution::Memo(design.clone()));
+        assert_eq!(resolutions[1], LinkResolution::Found);
+        assert_eq!(
+            resolutions[2],
+            LinkResolution::Broken("'main.rs' has 1 lines, not 9".to_string())
+        );
+        assert!(resolutions[3].is_broken());
+
+        assert_eq!(index.memo_backlinks(design), vec![roadmap]);
+        assert_eq!(
+            index.backlinks(&LinkTarget::Issue("login".to_string())),
+            vec![design]
+        );
+        assert_eq!(
+            index.backlinks(&LinkTarget::File {
+                path: "./main.rs".to_string(),
+                line: None
+            }),
+            vec![roadmap]
+        );
+
+        let mermaid = index.to_mermaid(&[design.clone(), roadmap.clone()]);
+        assert!(mermaid.starts_with("graph TD\n"));
+        assert!(mermaid.contains("n0[\"Design\"]:::memo"));
+        assert!(mermaid.contains("n2([\"issue: login\"]):::issue"));
+        assert!(mermaid.contains("n0 --> n1"));
+        assert!(mermaid.contains("n1 --> n0"));
+        assert!(mermaid.contains("n1 -->|L1| n3"));
+        assert!(mermaid.contains("n1 -.-> n5"));
+    }
+}
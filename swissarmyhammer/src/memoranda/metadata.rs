@@ -0,0 +1,280 @@
+//! Tags and folders for memos
+//!
+//! Markdown memos keep their tags and folder in a frontmatter block:
+//!
+//! ```markdown
+//! ---
+//! tags: [architecture, auth]
+//! folder: projects/login
+//! ---
+//! # Session handling
+//! ```
+//!
+//! Memos without tags or a folder are written as plain markdown, so existing
+//! memo files are unchanged until they are tagged.
+
+use super::Memo;
+use crate::frontmatter::split_frontmatter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Tags and folder of a memo
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MemoMetadata {
+    /// Free-form tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Slash-separated folder path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+}
+
+impl MemoMetadata {
+    /// Normalized metadata from raw tags and folder
+    pub fn new(tags: &[String], folder: Option<&str>) -> Self {
+        Self {
+            tags: normalize_tags(tags),
+            folder: folder.and_then(normalize_folder),
+        }
+    }
+
+    /// Whether the memo has neither tags nor a folder
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.folder.is_none()
+    }
+}
+
+impl From<&Memo> for MemoMetadata {
+    fn from(memo: &Memo) -> Self {
+        Self {
+            tags: memo.tags.clone(),
+            folder: memo.folder.clone(),
+        }
+    }
+}
+
+/// Changes to apply to memo metadata; `None` leaves a field untouched
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoMetadataUpdate {
+    /// Replacement tags
+    pub tags: Option<Vec<String>>,
+    /// New folder, empty to move the memo out of its folder
+    pub folder: Option<String>,
+}
+
+impl MemoMetadataUpdate {
+    /// Whether the update changes nothing
+    pub fn is_empty(&self) -> bool {
+        self == &MemoMetadataUpdate::default()
+    }
+
+    /// Apply the update to a memo, returning whether anything changed
+    pub fn apply(&self, memo: &mut Memo) -> bool {
+        let mut changed = false;
+        if let Some(tags) = &self.tags {
+            let tags = normalize_tags(tags);
+            changed |= memo.tags != tags;
+            memo.tags = tags;
+        }
+        if let Some(folder) = &self.folder {
+            let folder = normalize_folder(folder);
+            changed |= memo.folder != folder;
+            memo.folder = folder;
+        }
+        changed
+    }
+}
+
+/// Restricts memo listings to a tag and/or folder
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoFilter {
+    /// Only memos carrying this tag, compared case-insensitively
+    pub tag: Option<String>,
+    /// Only memos in this folder or one of its subfolders
+    pub folder: Option<String>,
+}
+
+impl MemoFilter {
+    /// Filter from optional raw tag and folder parameters
+    pub fn new(tag: Option<&str>, folder: Option<&str>) -> Self {
+        Self {
+            tag: tag.and_then(normalize_tag),
+            folder: folder.and_then(normalize_folder),
+        }
+    }
+
+    /// Whether the filter lets every memo through
+    pub fn is_empty(&self) -> bool {
+        self.tag.is_none() && self.folder.is_none()
+    }
+
+    /// Whether a memo passes the filter
+    pub fn matches(&self, memo: &Memo) -> bool {
+        let tag_matches = self.tag.as_ref().map_or(true, |tag| {
+            memo.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+        });
+        let folder_matches = self.folder.as_ref().map_or(true, |folder| {
+            memo.folder.as_ref().is_some_and(|memo_folder| {
+                memo_folder == folder
+                    || memo_folder
+                        .strip_prefix(folder.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+        });
+        tag_matches && folder_matches
+    }
+
+    /// Keep the memos that pass the filter
+    pub fn apply(&self, memos: Vec<Memo>) -> Vec<Memo> {
+        if self.is_empty() {
+            return memos;
+        }
+        memos
+            .into_iter()
+            .filter(|memo| self.matches(memo))
+            .collect()
+    }
+
+    /// Suffix for messages, such as ` tagged 'auth' in folder 'projects'`
+    ///
+    /// Empty when the filter lets every memo through.
+    pub fn describe(&self) -> String {
+        let mut description = String::new();
+        if let Some(tag) = &self.tag {
+            description.push_str(&format!(" tagged '{tag}'"));
+        }
+        if let Some(folder) = &self.folder {
+            description.push_str(&format!(" in folder '{folder}'"));
+        }
+        description
+    }
+}
+
+/// Tags used by the memos with how many memos carry each, most used first
+///
+/// Tags differing only in case are counted together under the first spelling seen.
+pub fn tag_counts(memos: &[Memo]) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    for tag in memos.iter().flat_map(|memo| &memo.tags) {
+        counts
+            .entry(tag.to_ascii_lowercase())
+            .or_insert_with(|| (tag.clone(), 0))
+            .1 += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_values().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Split memo file content into metadata and body
+///
+/// Content without a memo frontmatter block, including markdown that merely
+/// starts with a horizontal rule, yields empty metadata and the full content.
+pub fn parse_memo_content(content: &str) -> (MemoMetadata, &str) {
+    let Some((yaml, body)) = split_frontmatter(content) else {
+        return (MemoMetadata::default(), content);
+    };
+
+    match serde_yaml::from_str::<Option<MemoMetadata>>(yaml) {
+        Ok(Some(metadata)) if !metadata.is_empty() => (
+            MemoMetadata::new(&metadata.tags, metadata.folder.as_deref()),
+            body,
+        ),
+        _ => (MemoMetadata::default(), content),
+    }
+}
+
+/// Render metadata and body back into memo file content
+pub fn render_memo_content(metadata: &MemoMetadata, body: &str) -> String {
+    if metadata.is_empty() {
+        return body.to_string();
+    }
+    let yaml = serde_yaml::to_string(metadata).unwrap_or_default();
+    format!("---\n{yaml}---\n{body}")
+}
+
+fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').trim();
+    (!tag.is_empty()).then(|| tag.to_string())
+}
+
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.iter().filter_map(|t| normalize_tag(t)) {
+        if !normalized.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+fn normalize_folder(folder: &str) -> Option<String> {
+    let folder = folder
+        .split(['/', '\\'])
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect::<Vec<_>>()
+        .join("/");
+    (!folder.is_empty()).then_some(folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memo(tags: &[&str], folder: Option<&str>) -> Memo {
+        let mut memo = Memo::new("Memo".into(), "Body".into());
+        MemoMetadataUpdate {
+            tags: Some(tags.iter().map(|t| t.to_string()).collect()),
+            folder: folder.map(str::to_string),
+        }
+        .apply(&mut memo);
+        memo
+    }
+
+    #[test]
+    fn test_memo_content_round_trip() {
+        let metadata = MemoMetadata::new(
+            &["#auth".into(), " Design ".into(), "AUTH".into()],
+            Some("/projects//login/"),
+        );
+        assert_eq!(metadata.tags, vec!["auth", "Design"]);
+        assert_eq!(metadata.folder.as_deref(), Some("projects/login"));
+
+        let content = render_memo_content(&metadata, "# Notes\n");
+        assert!(content.starts_with("---\n"));
+        assert_eq!(parse_memo_content(&content), (metadata, "# Notes\n"));
+
+        assert_eq!(
+            render_memo_content(&MemoMetadata::default(), "# Plain"),
+            "# Plain"
+        );
+        let ruled = "---\nnot: memo metadata\n---\nBody";
+        assert_eq!(parse_memo_content(ruled), (MemoMetadata::default(), ruled));
+    }
+
+    #[test]
+    fn test_filter_and_tag_counts() {
+        let memos = vec![
+            memo(&["auth", "design"], Some("projects/login")),
+            memo(&["Auth"], Some("projects")),
+            memo(&[], Some("projects-old")),
+        ];
+
+        let by_folder = MemoFilter::new(None, Some("projects"));
+        assert_eq!(by_folder.apply(memos.clone()).len(), 2);
+        let by_tag = MemoFilter::new(Some("#AUTH"), Some("projects/login"));
+        assert_eq!(by_tag.apply(memos.clone()).len(), 1);
+        assert!(MemoFilter::new(Some(" "), None).is_empty());
+        assert_eq!(
+            by_tag.describe(),
+            " tagged 'AUTH' in folder 'projects/login'"
+        );
+
+        assert_eq!(
+            tag_counts(&memos),
+            vec![("auth".to_string(), 2), ("design".to_string(), 1)]
+        );
+    }
+}
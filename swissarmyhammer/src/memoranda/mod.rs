@@ -8,9 +8,13 @@
 //! ## Features
 //!
 //! - **Filename-based Identifiers**: IDs derived from sanitized memo titles for human-readable file organization
-//! - **Pure Markdown Storage**: Files stored as `.md` with content only, no metadata wrapper
+//! - **Pure Markdown Storage**: Files stored as `.md`, with frontmatter only for memos that have tags or a folder
 //! - **Automatic Timestamps**: Creation and update times derived from filesystem metadata
 //! - **Full-text Search**: Search across memo titles and content
+//! - **Semantic Search**: Embedding-based and hybrid search using the code index model
+//! - **Tags and Folders**: Optional frontmatter for grouping and filtering memos
+//! - **Revision History**: Every change keeps the replaced version for diffing and restoring
+//! - **Wiki Links**: `[[memo]]`, `[[issue:name]]` and `[[file:path#L10]]` links with backlinks
 //! - **Type-safe API**: Strong typing for memo identifiers and validation
 //!
 //! ## Basic Usage
@@ -444,6 +448,22 @@ pub use storage::{FileSystemMemoStorage, MarkdownMemoStorage, MemoState, MemoSto
 pub mod advanced_search;
 pub use advanced_search::AdvancedMemoSearchEngine;
 
+/// Memo tags, folders and filtering
+pub mod metadata;
+pub use metadata::{tag_counts, MemoFilter, MemoMetadata, MemoMetadataUpdate};
+
+/// Revision history, diffs and restores of memos
+pub mod history;
+pub use history::{unified_diff, MemoHistory, MemoRevision};
+
+/// Semantic and hybrid memo search backed by the code index embeddings
+pub mod semantic;
+pub use semantic::{reciprocal_rank_fusion, MemoEmbeddingIndex, MemoEmbeddingStats, MemoSearchMode};
+
+/// Wiki-style links between memos, issues and files
+pub mod links;
+pub use links::{parse_links, LinkIndex, LinkResolution, LinkTarget, MemoLink};
+
 /// A unique identifier for memos using ULID (Universally Unique Lexicographically Sortable Identifier)
 ///
 /// ULIDs provide both uniqueness and natural ordering, making them ideal for memo identification
@@ -661,6 +681,12 @@ pub struct Memo {
     pub created_at: DateTime<Utc>,
     /// When this memo was last modified
     pub updated_at: DateTime<Utc>,
+    /// Tags used to group and filter memos
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Optional slash-separated folder path, such as `projects/login`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
 }
 
 impl Memo {
@@ -700,6 +726,8 @@ impl Memo {
             content,
             created_at: now,
             updated_at: now,
+            tags: Vec::new(),
+            folder: None,
         }
     }
 
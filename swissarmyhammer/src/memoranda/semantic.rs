@@ -0,0 +1,267 @@
+//! Semantic and hybrid memo search
+//!
+//! Memos are embedded with the same [`EmbeddingEngine`] as the code index and
+//! stored next to it in the semantic search database. Each stored embedding
+//! carries a hash of the embedded text, so [`MemoEmbeddingIndex::refresh`] only
+//! re-embeds memos that were created or changed since the last search and drops
+//! embeddings of deleted memos.
+//!
+//! Hybrid search merges the keyword and semantic rankings with
+//! [`reciprocal_rank_fusion`].
+
+use super::{Memo, MemoId};
+use crate::error::{Result, SwissArmyHammerError};
+use crate::search::{EmbeddingEngine, FileHasher, SemanticConfig, VectorStorage};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+/// Rank constant used by reciprocal rank fusion
+///
+/// Larger values flatten the difference between top and lower ranks; 60 is the
+/// value from the original RRF paper.
+pub const RRF_K: f32 = 60.0;
+
+/// How memo search ranks results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MemoSearchMode {
+    /// Full-text keyword matching
+    #[default]
+    Keyword,
+    /// Embedding similarity
+    Semantic,
+    /// Keyword and semantic rankings fused with reciprocal rank fusion
+    Hybrid,
+}
+
+impl MemoSearchMode {
+    /// Name of the mode as accepted by [`FromStr`]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemoSearchMode::Keyword => "keyword",
+            MemoSearchMode::Semantic => "semantic",
+            MemoSearchMode::Hybrid => "hybrid",
+        }
+    }
+}
+
+impl fmt::Display for MemoSearchMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MemoSearchMode {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "keyword" => Ok(MemoSearchMode::Keyword),
+            "semantic" => Ok(MemoSearchMode::Semantic),
+            "hybrid" => Ok(MemoSearchMode::Hybrid),
+            other => Err(SwissArmyHammerError::Other(format!(
+                "Invalid search mode '{other}': expected keyword, semantic or hybrid"
+            ))),
+        }
+    }
+}
+
+/// Merge several rankings into one, best first
+///
+/// Each memo scores `1 / (RRF_K + rank)` for every ranking it appears in, with
+/// ranks starting at 1. Ties keep the order in which memos were first seen.
+pub fn reciprocal_rank_fusion(rankings: &[Vec<MemoId>]) -> Vec<(MemoId, f32)> {
+    let mut order: Vec<MemoId> = Vec::new();
+    let mut scores: HashMap<MemoId, f32> = HashMap::new();
+    for ranking in rankings {
+        for (index, id) in ranking.iter().enumerate() {
+            let score = 1.0 / (RRF_K + index as f32 + 1.0);
+            match scores.get_mut(id) {
+                Some(total) => *total += score,
+                None => {
+                    order.push(id.clone());
+                    scores.insert(id.clone(), score);
+                }
+            }
+        }
+    }
+
+    let mut fused: Vec<(MemoId, f32)> = order
+        .into_iter()
+        .map(|id| {
+            let score = scores[&id];
+            (id, score)
+        })
+        .collect();
+    fused.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    fused
+}
+
+/// Outcome of refreshing memo embeddings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoEmbeddingStats {
+    /// Memos embedded for the first time or re-embedded after a change
+    pub embedded: usize,
+    /// Memos whose stored embedding was still current
+    pub unchanged: usize,
+    /// Embeddings dropped because their memo no longer exists
+    pub removed: usize,
+}
+
+/// Memo embeddings stored alongside the semantic code index
+pub struct MemoEmbeddingIndex {
+    storage: VectorStorage,
+    engine: EmbeddingEngine,
+}
+
+impl MemoEmbeddingIndex {
+    /// Open the index in the configured semantic database and load the embedding model
+    pub async fn new(config: SemanticConfig) -> Result<Self> {
+        let engine = EmbeddingEngine::new().await?;
+        Self::with_engine(config, engine)
+    }
+
+    /// Open the index with an already loaded embedding engine
+    pub fn with_engine(config: SemanticConfig, engine: EmbeddingEngine) -> Result<Self> {
+        let storage = VectorStorage::new(config)?;
+        storage.initialize()?;
+        Ok(Self { storage, engine })
+    }
+
+    /// Bring stored embeddings in line with the given memos
+    ///
+    /// Only memos whose title, tags or content changed since they were last
+    /// embedded are sent to the model. Embeddings of memos missing from `memos`
+    /// are removed, so pass the complete memo collection.
+    pub async fn refresh(&self, memos: &[Memo]) -> Result<MemoEmbeddingStats> {
+        let model_id = self.engine.model_info().model_id;
+        let stored = self.storage.memo_embedding_hashes()?;
+        let mut stats = MemoEmbeddingStats::default();
+
+        let mut pending = Vec::new();
+        for memo in memos {
+            let text = embedding_text(memo);
+            let hash = FileHasher::hash_string(&format!("{model_id}\n{text}"));
+            if stored.get(memo.id.as_str()) == Some(&hash) {
+                stats.unchanged += 1;
+            } else {
+                pending.push((memo, text, hash));
+            }
+        }
+
+        if !pending.is_empty() {
+            let texts: Vec<&str> = pending.iter().map(|(_, text, _)| text.as_str()).collect();
+            let vectors = self.engine.embed_batch(&texts).await?;
+            for ((memo, _, hash), vector) in pending.iter().zip(vectors) {
+                self.storage
+                    .store_memo_embedding(memo.id.as_str(), hash, &vector)?;
+                stats.embedded += 1;
+            }
+        }
+
+        let current: HashSet<&str> = memos.iter().map(|memo| memo.id.as_str()).collect();
+        for memo_id in stored.keys() {
+            if !current.contains(memo_id.as_str()) && self.storage.remove_memo_embedding(memo_id)? {
+                stats.removed += 1;
+            }
+        }
+
+        tracing::debug!(
+            "Refreshed memo embeddings: {} embedded, {} unchanged, {} removed",
+            stats.embedded,
+            stats.unchanged,
+            stats.removed
+        );
+        Ok(stats)
+    }
+
+    /// Memos most similar to the query, best first, with their cosine similarity
+    ///
+    /// Only memos in `candidates` are returned, which lets callers apply tag
+    /// and folder filters before ranking.
+    pub async fn search(
+        &self,
+        query: &str,
+        candidates: &[Memo],
+        limit: usize,
+    ) -> Result<Vec<(Memo, f32)>> {
+        if query.trim().is_empty() || candidates.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let query_embedding = self.engine.embed_text(query).await?;
+        let by_id: HashMap<&str, &Memo> = candidates
+            .iter()
+            .map(|memo| (memo.id.as_str(), memo))
+            .collect();
+
+        let mut results = Vec::new();
+        for (memo_id, similarity) in self
+            .storage
+            .memo_similarity_search(&query_embedding, usize::MAX)?
+        {
+            if let Some(memo) = by_id.get(memo_id.as_str()) {
+                results.push(((*memo).clone(), similarity));
+                if results.len() == limit {
+                    break;
+                }
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Drop the stored embedding of a deleted memo
+///
+/// Does nothing when the semantic database has not been created yet, so
+/// deleting memos never loads the embedding model or creates the database.
+pub fn remove_memo_embedding(config: &SemanticConfig, memo_id: &MemoId) -> Result<bool> {
+    if !config.database_path.exists() {
+        return Ok(false);
+    }
+    let storage = VectorStorage::new(config.clone())?;
+    storage.initialize()?;
+    storage.remove_memo_embedding(memo_id.as_str())
+}
+
+/// Text sent to the embedding model for a memo
+fn embedding_text(memo: &Memo) -> String {
+    let mut text = memo.title.clone();
+    if !memo.tags.is_empty() {
+        text.push_str("\nTags: ");
+        text.push_str(&memo.tags.join(", "));
+    }
+    text.push_str("\n\n");
+    text.push_str(&memo.content);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_mode_parsing() {
+        assert_eq!(
+            "Hybrid".parse::<MemoSearchMode>().unwrap(),
+            MemoSearchMode::Hybrid
+        );
+        assert_eq!(MemoSearchMode::default().to_string(), "keyword");
+        assert!("fuzzy".parse::<MemoSearchMode>().is_err());
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion() {
+        let a = MemoId::new();
+        let b = MemoId::new();
+        let c = MemoId::new();
+
+        let fused =
+            reciprocal_rank_fusion(&[vec![a.clone(), b.clone()], vec![b.clone(), c.clone()]]);
+        let order: Vec<&MemoId> = fused.iter().map(|(id, _)| id).collect();
+        assert_eq!(order, vec![&b, &a, &c]);
+        assert!((fused[0].1 - (1.0 / 62.0 + 1.0 / 61.0)).abs() < f32::EPSILON);
+
+        assert!(reciprocal_rank_fusion(&[]).is_empty());
+    }
+}
@@ -10,7 +10,8 @@
 //!
 //! Modern markdown-based storage that stores memos as pure markdown files:
 //!
-//! - **File Format**: `{title}.md` containing pure markdown content
+//! - **File Format**: `{title}.md` containing markdown, with a frontmatter block
+//!   only when the memo has tags or a folder
 //! - **ID System**: Filename-based IDs (sanitized title without extension)
 //! - **Timestamps**: Derived from filesystem metadata
 //! - **Benefits**: Human-readable, portable, no metadata wrapper
@@ -47,7 +48,11 @@
 
 use crate::directory_utils::get_or_create_swissarmyhammer_directory;
 use crate::error::{Result, SwissArmyHammerError};
-use crate::memoranda::{AdvancedMemoSearchEngine, Memo, MemoId, SearchOptions};
+use crate::memoranda::history::{MemoHistory, MemoRevision, DEFAULT_MEMO_HISTORY_LIMIT};
+use crate::memoranda::metadata::{parse_memo_content, render_memo_content};
+use crate::memoranda::{
+    AdvancedMemoSearchEngine, Memo, MemoId, MemoMetadata, MemoMetadataUpdate, SearchOptions,
+};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use std::path::PathBuf;
@@ -133,6 +138,17 @@ pub trait MemoStorage: Send + Sync {
     /// Returns an error if the memo cannot be persisted to storage.
     async fn create_memo(&self, title: String, content: String) -> Result<Memo>;
 
+    /// Create a new memo with tags and a folder
+    ///
+    /// Behaves like [`MemoStorage::create_memo`] and stores the normalized
+    /// metadata with the memo.
+    async fn create_memo_with_metadata(
+        &self,
+        title: String,
+        content: String,
+        metadata: MemoMetadata,
+    ) -> Result<Memo>;
+
     /// Retrieve a memo by its unique identifier
     ///
     /// # Arguments
@@ -169,9 +185,41 @@ pub trait MemoStorage: Send + Sync {
     /// Returns `MemoNotFound` error if no memo exists with the given ID.
     async fn update_memo(&self, id: &MemoId, content: String) -> Result<Memo>;
 
+    /// Change the tags and/or folder of an existing memo
+    ///
+    /// The content is left untouched. The `updated_at` timestamp is refreshed
+    /// only when the metadata actually changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MemoNotFound` error if no memo exists with the given ID.
+    async fn update_memo_metadata(&self, id: &MemoId, update: &MemoMetadataUpdate) -> Result<Memo>;
+
+    /// Previous revisions of a memo, oldest first
+    ///
+    /// Every content or metadata change records the version it replaced, up to
+    /// the configured number of revisions per memo.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MemoNotFound` error if no memo exists with the given ID.
+    async fn memo_history(&self, id: &MemoId) -> Result<Vec<MemoRevision>>;
+
+    /// Bring back the content, tags and folder of a previous revision
+    ///
+    /// The version being replaced is recorded as a new revision first, so a
+    /// restore can itself be undone.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MemoNotFound` if no memo exists with the given ID and
+    /// `MemoRevisionNotFound` if the revision was never recorded or was pruned.
+    async fn restore_memo(&self, id: &MemoId, revision: u32) -> Result<Memo>;
+
     /// Delete a memo by its unique identifier
     ///
-    /// Permanently removes the memo from storage. This operation cannot be undone.
+    /// Permanently removes the memo and its revision history from storage.
+    /// This operation cannot be undone.
     ///
     /// # Arguments
     ///
@@ -336,6 +384,8 @@ pub struct FileSystemMemoStorage {
     creation_lock: Mutex<()>,
     /// Advanced search engine for full-text search capabilities
     search_engine: Option<AdvancedMemoSearchEngine>,
+    /// Previous revisions of each memo
+    history: MemoHistory,
 }
 
 /// Generate highlighted text snippets showing where search matches were found
@@ -534,7 +584,13 @@ impl FileSystemMemoStorage {
         } else {
             get_or_create_swissarmyhammer_directory()?.join("memos")
         };
-        Ok(Self::new(memos_dir))
+        Ok(Self::new(memos_dir).with_history_limit(MemoHistory::configured_limit()))
+    }
+
+    /// Keep at most `limit` previous revisions per memo, 0 to keep none
+    pub fn with_history_limit(mut self, limit: usize) -> Self {
+        self.history = MemoHistory::new(&self.state.memos_dir, limit);
+        self
     }
 
     /// Create a new filesystem storage with a specific memo directory
@@ -557,6 +613,7 @@ impl FileSystemMemoStorage {
     /// ```
     pub fn new(memos_dir: PathBuf) -> Self {
         Self {
+            history: MemoHistory::new(&memos_dir, DEFAULT_MEMO_HISTORY_LIMIT),
             state: MemoState { memos_dir },
             creation_lock: Mutex::new(()),
             search_engine: None,
@@ -578,6 +635,7 @@ impl FileSystemMemoStorage {
         let search_engine = AdvancedMemoSearchEngine::new_persistent(index_path).await?;
 
         Ok(Self {
+            history: MemoHistory::new(&memos_dir, DEFAULT_MEMO_HISTORY_LIMIT),
             state: MemoState { memos_dir },
             creation_lock: Mutex::new(()),
             search_engine: Some(search_engine),
@@ -597,7 +655,9 @@ impl FileSystemMemoStorage {
                 .join(".swissarmyhammer")
                 .join("memos")
         };
-        Self::new_with_search(memos_dir).await
+        Ok(Self::new_with_search(memos_dir)
+            .await?
+            .with_history_limit(MemoHistory::configured_limit()))
     }
 
     /// Initialize the search engine if not already present
@@ -750,9 +810,22 @@ impl FileSystemMemoStorage {
 #[async_trait]
 impl MemoStorage for FileSystemMemoStorage {
     async fn create_memo(&self, title: String, content: String) -> Result<Memo> {
+        self.create_memo_with_metadata(title, content, MemoMetadata::default())
+            .await
+    }
+
+    async fn create_memo_with_metadata(
+        &self,
+        title: String,
+        content: String,
+        metadata: MemoMetadata,
+    ) -> Result<Memo> {
         let _lock = self.creation_lock.lock().await;
 
-        let memo = Memo::new(title, content);
+        let mut memo = Memo::new(title, content);
+        let metadata = MemoMetadata::new(&metadata.tags, metadata.folder.as_deref());
+        memo.tags = metadata.tags;
+        memo.folder = metadata.folder;
         self.create_memo_file_atomically(&memo).await?;
 
         // Index the memo in the search engine if available
@@ -774,6 +847,9 @@ impl MemoStorage for FileSystemMemoStorage {
         let _lock = self.creation_lock.lock().await;
 
         let mut memo = self.get_memo(id).await?;
+        if memo.content != content {
+            self.history.record(&memo).await?;
+        }
         memo.update_content(content);
         self.save_memo_to_file(&memo).await?;
 
@@ -783,6 +859,40 @@ impl MemoStorage for FileSystemMemoStorage {
         Ok(memo)
     }
 
+    async fn update_memo_metadata(&self, id: &MemoId, update: &MemoMetadataUpdate) -> Result<Memo> {
+        let _lock = self.creation_lock.lock().await;
+
+        let mut memo = self.get_memo(id).await?;
+        let previous = memo.clone();
+        if update.apply(&mut memo) {
+            self.history.record(&previous).await?;
+            memo.updated_at = Utc::now();
+            self.save_memo_to_file(&memo).await?;
+        }
+
+        Ok(memo)
+    }
+
+    async fn memo_history(&self, id: &MemoId) -> Result<Vec<MemoRevision>> {
+        self.get_memo(id).await?;
+        self.history.revisions(id).await
+    }
+
+    async fn restore_memo(&self, id: &MemoId, revision: u32) -> Result<Memo> {
+        let _lock = self.creation_lock.lock().await;
+
+        let mut memo = self.get_memo(id).await?;
+        let revision = self.history.revision(id, revision).await?;
+        self.history.record(&memo).await?;
+        revision.apply_to(&mut memo);
+        self.save_memo_to_file(&memo).await?;
+
+        // Update the memo in the search engine if available
+        self.index_memo_if_available(&memo).await?;
+
+        Ok(memo)
+    }
+
     async fn delete_memo(&self, id: &MemoId) -> Result<()> {
         let path = self.get_memo_path(id);
         if !path.exists() {
@@ -790,6 +900,7 @@ impl MemoStorage for FileSystemMemoStorage {
         }
 
         tokio::fs::remove_file(path).await?;
+        self.history.remove(id).await?;
 
         // Remove the memo from the search engine if available
         self.remove_memo_from_index_if_available(id).await?;
@@ -934,12 +1045,14 @@ impl MemoStorage for FileSystemMemoStorage {
 
             if options.include_metadata {
                 context.push_str(&format!(
-                    "# {} ({})\nCreated: {} | Updated: {}\n\n",
+                    "# {} ({})\nCreated: {} | Updated: {}\n",
                     memo.title,
                     memo.id.as_str(),
                     memo.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
                     memo.updated_at.format("%Y-%m-%d %H:%M:%S UTC")
                 ));
+                context.push_str(&format_metadata_line(memo));
+                context.push('\n');
             }
 
             context.push_str(&memo.content);
@@ -957,6 +1070,22 @@ impl MemoStorage for FileSystemMemoStorage {
     }
 }
 
+/// Context line listing a memo's folder and tags, empty when it has neither
+fn format_metadata_line(memo: &Memo) -> String {
+    let mut parts = Vec::new();
+    if let Some(folder) = &memo.folder {
+        parts.push(format!("Folder: {folder}"));
+    }
+    if !memo.tags.is_empty() {
+        parts.push(format!("Tags: {}", memo.tags.join(", ")));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", parts.join(" | "))
+    }
+}
+
 /// Markdown-based implementation of memo storage
 ///
 /// Stores memos as pure markdown files with titles as filenames,
@@ -966,7 +1095,7 @@ impl MemoStorage for FileSystemMemoStorage {
 /// # Storage Format
 ///
 /// - Each memo is stored as `{title}.md` in the memos directory
-/// - Files contain pure markdown content without metadata
+/// - Files contain markdown content, with a frontmatter block only for tags and folder
 /// - Timestamps are read from filesystem created/modified times
 /// - ID is computed from the filename (without .md extension)
 ///
@@ -990,6 +1119,8 @@ pub struct MarkdownMemoStorage {
     creation_lock: Mutex<()>,
     /// Advanced search engine for full-text search capabilities
     search_engine: Option<AdvancedMemoSearchEngine>,
+    /// Previous revisions of each memo
+    history: MemoHistory,
 }
 
 impl MarkdownMemoStorage {
@@ -1011,7 +1142,13 @@ impl MarkdownMemoStorage {
         } else {
             get_or_create_swissarmyhammer_directory()?.join("memos")
         };
-        Ok(Self::new(memos_dir))
+        Ok(Self::new(memos_dir).with_history_limit(MemoHistory::configured_limit()))
+    }
+
+    /// Keep at most `limit` previous revisions per memo, 0 to keep none
+    pub fn with_history_limit(mut self, limit: usize) -> Self {
+        self.history = MemoHistory::new(&self.state.memos_dir, limit);
+        self
     }
 
     /// Create a new markdown storage with a specific memo directory
@@ -1025,6 +1162,7 @@ impl MarkdownMemoStorage {
     /// * `Self` - New storage instance
     pub fn new(memos_dir: PathBuf) -> Self {
         Self {
+            history: MemoHistory::new(&memos_dir, DEFAULT_MEMO_HISTORY_LIMIT),
             state: MemoState { memos_dir },
             creation_lock: Mutex::new(()),
             search_engine: None,
@@ -1090,7 +1228,7 @@ impl MarkdownMemoStorage {
     /// Load and create a memo from a markdown file
     ///
     /// Reads the file content and filesystem metadata to construct a complete Memo object.
-    /// As per issue requirements, stores pure markdown without metadata and computes title from filename.
+    /// Tags and folder come from the optional frontmatter, the title is computed from the filename.
     ///
     /// # Arguments
     ///
@@ -1100,7 +1238,8 @@ impl MarkdownMemoStorage {
     ///
     /// * `Result<Memo>` - The memo object with content and metadata
     async fn load_memo_from_markdown_file(&self, path: &PathBuf) -> Result<Memo> {
-        let content = tokio::fs::read_to_string(path).await?;
+        let file_content = tokio::fs::read_to_string(path).await?;
+        let (memo_metadata, content) = parse_memo_content(&file_content);
         let metadata = tokio::fs::metadata(path).await?;
 
         // Extract title from filename (remove .md extension)
@@ -1131,16 +1270,18 @@ impl MarkdownMemoStorage {
         Ok(Memo {
             id,
             title,
-            content,
+            content: content.to_string(),
             created_at,
             updated_at,
+            tags: memo_metadata.tags,
+            folder: memo_metadata.folder,
         })
     }
 
     /// Save a memo to a markdown file
     ///
     /// Creates the directory if it doesn't exist, then writes the memo
-    /// content as pure markdown to the appropriate file.
+    /// content as markdown, preceded by frontmatter when it has tags or a folder.
     ///
     /// # Arguments
     ///
@@ -1153,7 +1294,8 @@ impl MarkdownMemoStorage {
         self.ensure_directory_exists().await?;
 
         let path = self.get_memo_path_from_title(&memo.title);
-        tokio::fs::write(path, &memo.content).await?;
+        let content = render_memo_content(&MemoMetadata::from(memo), &memo.content);
+        tokio::fs::write(path, content).await?;
         Ok(())
     }
 
@@ -1185,6 +1327,16 @@ impl MarkdownMemoStorage {
 #[async_trait]
 impl MemoStorage for MarkdownMemoStorage {
     async fn create_memo(&self, title: String, content: String) -> Result<Memo> {
+        self.create_memo_with_metadata(title, content, MemoMetadata::default())
+            .await
+    }
+
+    async fn create_memo_with_metadata(
+        &self,
+        title: String,
+        content: String,
+        metadata: MemoMetadata,
+    ) -> Result<Memo> {
         let _lock = self.creation_lock.lock().await;
 
         // Check if a file with this title already exists
@@ -1197,6 +1349,7 @@ impl MemoStorage for MarkdownMemoStorage {
         let sanitized_title = Self::sanitize_title_for_filename(&title);
         let id = MemoId::from_filename(&sanitized_title);
         let now = Utc::now();
+        let metadata = MemoMetadata::new(&metadata.tags, metadata.folder.as_deref());
 
         let memo = Memo {
             id,
@@ -1204,6 +1357,8 @@ impl MemoStorage for MarkdownMemoStorage {
             content,
             created_at: now,
             updated_at: now,
+            tags: metadata.tags,
+            folder: metadata.folder,
         };
 
         self.save_memo_to_markdown_file(&memo).await?;
@@ -1229,6 +1384,9 @@ impl MemoStorage for MarkdownMemoStorage {
         let _lock = self.creation_lock.lock().await;
 
         let mut memo = self.get_memo(id).await?;
+        if memo.content != content {
+            self.history.record(&memo).await?;
+        }
         memo.update_content(content);
 
         // Since we're updating content only, the filename stays the same
@@ -1240,6 +1398,40 @@ impl MemoStorage for MarkdownMemoStorage {
         Ok(memo)
     }
 
+    async fn update_memo_metadata(&self, id: &MemoId, update: &MemoMetadataUpdate) -> Result<Memo> {
+        let _lock = self.creation_lock.lock().await;
+
+        let mut memo = self.get_memo(id).await?;
+        let previous = memo.clone();
+        if update.apply(&mut memo) {
+            self.history.record(&previous).await?;
+            memo.updated_at = Utc::now();
+            self.save_memo_to_markdown_file(&memo).await?;
+        }
+
+        Ok(memo)
+    }
+
+    async fn memo_history(&self, id: &MemoId) -> Result<Vec<MemoRevision>> {
+        self.get_memo(id).await?;
+        self.history.revisions(id).await
+    }
+
+    async fn restore_memo(&self, id: &MemoId, revision: u32) -> Result<Memo> {
+        let _lock = self.creation_lock.lock().await;
+
+        let mut memo = self.get_memo(id).await?;
+        let revision = self.history.revision(id, revision).await?;
+        self.history.record(&memo).await?;
+        revision.apply_to(&mut memo);
+        self.save_memo_to_markdown_file(&memo).await?;
+
+        // Update the memo in the search engine if available
+        self.index_memo_if_available(&memo).await?;
+
+        Ok(memo)
+    }
+
     async fn delete_memo(&self, id: &MemoId) -> Result<()> {
         let memo = self.get_memo(id).await?;
         let path = self.get_memo_path_from_title(&memo.title);
@@ -1249,6 +1441,7 @@ impl MemoStorage for MarkdownMemoStorage {
         }
 
         tokio::fs::remove_file(path).await?;
+        self.history.remove(id).await?;
 
         // Remove the memo from the search engine if available
         self.remove_memo_from_index_if_available(id).await?;
@@ -1393,12 +1586,14 @@ impl MemoStorage for MarkdownMemoStorage {
 
             if options.include_metadata {
                 context.push_str(&format!(
-                    "# {} ({})\nCreated: {} | Updated: {}\n\n",
+                    "# {} ({})\nCreated: {} | Updated: {}\n",
                     memo.title,
                     memo.id.as_str(),
                     memo.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
                     memo.updated_at.format("%Y-%m-%d %H:%M:%S UTC")
                 ));
+                context.push_str(&format_metadata_line(memo));
+                context.push('\n');
             }
 
             context.push_str(&memo.content);
@@ -2666,4 +2861,94 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert!(!results[0].highlights.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_markdown_history_and_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = MarkdownMemoStorage::new(temp_dir.path().join("memos")).with_history_limit(2);
+
+        let memo = storage
+            .create_memo("History".to_string(), "v1".to_string())
+            .await
+            .unwrap();
+        assert!(storage.memo_history(&memo.id).await.unwrap().is_empty());
+
+        for content in ["v2", "v3", "v4"] {
+            storage
+                .update_memo(&memo.id, content.to_string())
+                .await
+                .unwrap();
+        }
+        let history = storage.memo_history(&memo.id).await.unwrap();
+        let revisions: Vec<(u32, &str)> = history
+            .iter()
+            .map(|r| (r.revision, r.content.as_str()))
+            .collect();
+        assert_eq!(revisions, vec![(2, "v2"), (3, "v3")]);
+
+        // Restoring keeps the replaced version, so the restore can be undone
+        let restored = storage.restore_memo(&memo.id, 2).await.unwrap();
+        assert_eq!(restored.content, "v2");
+        assert_eq!(storage.get_memo(&memo.id).await.unwrap().content, "v2");
+        let history = storage.memo_history(&memo.id).await.unwrap();
+        assert_eq!(history.last().unwrap().content, "v4");
+        assert!(matches!(
+            storage.restore_memo(&memo.id, 1).await,
+            Err(SwissArmyHammerError::MemoRevisionNotFound { revision: 1, .. })
+        ));
+
+        storage.delete_memo(&memo.id).await.unwrap();
+        assert!(matches!(
+            storage.memo_history(&memo.id).await,
+            Err(SwissArmyHammerError::MemoNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_markdown_tags_and_folder_in_frontmatter() {
+        let temp_dir = TempDir::new().unwrap();
+        let memos_dir = temp_dir.path().join("memos");
+        let storage = MarkdownMemoStorage::new(memos_dir.clone());
+
+        let memo = storage
+            .create_memo_with_metadata(
+                "Tagged".to_string(),
+                "# Tagged\n\nBody".to_string(),
+                MemoMetadata::new(&["auth".to_string()], Some("projects/login")),
+            )
+            .await
+            .unwrap();
+        let raw = std::fs::read_to_string(memos_dir.join("Tagged.md")).unwrap();
+        assert!(raw.starts_with("---\n"));
+        assert!(raw.contains("folder: projects/login"));
+
+        // Content updates keep the metadata, metadata updates keep the content
+        storage
+            .update_memo(&memo.id, "# Tagged\n\nNew body".to_string())
+            .await
+            .unwrap();
+        storage
+            .update_memo_metadata(
+                &memo.id,
+                &MemoMetadataUpdate {
+                    tags: Some(vec!["auth".to_string(), "design".to_string()]),
+                    folder: Some(String::new()),
+                },
+            )
+            .await
+            .unwrap();
+
+        let loaded = storage.get_memo(&memo.id).await.unwrap();
+        assert_eq!(loaded.tags, vec!["auth", "design"]);
+        assert_eq!(loaded.folder, None);
+        assert_eq!(loaded.content, "# Tagged\n\nNew body");
+
+        // Untagged memos stay plain markdown
+        storage
+            .create_memo("Plain".to_string(), "Plain body".to_string())
+            .await
+            .unwrap();
+        let plain = std::fs::read_to_string(memos_dir.join("Plain.md")).unwrap();
+        assert_eq!(plain, "Plain body");
+    }
 }
@@ -0,0 +1,938 @@
+//! Installable workflow packages
+//!
+//! A package bundles workflows, prompts and partials together with a
+//! `package.toml` manifest describing its name, version and dependencies.
+//! Packages are installed from git repositories, `.tar.gz` archives or local
+//! directories into a versioned directory:
+//!
+//! ```text
+//! .swissarmyhammer/
+//! ├── sah.lock
+//! └── packages/
+//!     └── review-kit/
+//!         └── 1.2.0/
+//!             ├── package.toml
+//!             ├── prompts/
+//!             └── workflows/
+//! ```
+//!
+//! Every install is recorded in a `sah.lock` lockfile next to the packages
+//! directory, pinning the version, the resolved git commit and a checksum of
+//! the installed files so a team can reproduce the same set of workflows with
+//! [`PackageManager::sync`].
+//!
+//! Installed packages are picked up by the [`VirtualFileSystem`] as
+//! [`FileSource::Package`] entries, which rank above builtin files and below
+//! user and local files.
+//!
+//! [`VirtualFileSystem`]: crate::file_loader::VirtualFileSystem
+//! [`FileSource::Package`]: crate::file_loader::FileSource::Package
+
+use crate::error::SwissArmyHammerError;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// File name of the package manifest at the root of every package
+pub const PACKAGE_MANIFEST_FILE: &str = "package.toml";
+
+/// Directory, relative to a `.swissarmyhammer` root, holding installed packages
+pub const PACKAGES_DIR: &str = "packages";
+
+/// File name of the lockfile, relative to a `.swissarmyhammer` root
+pub const LOCKFILE_NAME: &str = "sah.lock";
+
+/// Current lockfile format version
+pub const LOCKFILE_VERSION: u32 = 1;
+
+/// Package manifest read from `package.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageManifest {
+    /// Package name, used as the directory name and the source label
+    pub name: String,
+    /// Package version, e.g. `1.2.0`
+    pub version: String,
+    /// Human readable description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Dependencies by package name, each value is a source specification
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
+}
+
+impl PackageManifest {
+    /// Parse and validate a manifest from TOML
+    pub fn parse(content: &str) -> Result<Self> {
+        let manifest: PackageManifest = toml::from_str(content)
+            .map_err(|e| SwissArmyHammerError::Config(format!("Invalid package manifest: {e}")))?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Load the manifest from a package directory
+    pub fn load(package_dir: &Path) -> Result<Self> {
+        let path = package_dir.join(PACKAGE_MANIFEST_FILE);
+        if !path.is_file() {
+            return Err(SwissArmyHammerError::Config(format!(
+                "No {PACKAGE_MANIFEST_FILE} found in {}",
+                package_dir.display()
+            )));
+        }
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Check that the name and version are usable as directory names
+    pub fn validate(&self) -> Result<()> {
+        if !is_valid_package_name(&self.name) {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Invalid package name '{}': use lowercase letters, digits, '-' and '_'",
+                self.name
+            )));
+        }
+        if !is_valid_package_version(&self.version) {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Invalid version '{}' for package '{}': use letters, digits, '.', '-' and '+', starting with a letter or digit",
+                self.version, self.name
+            )));
+        }
+        for dependency in self.dependencies.keys() {
+            if !is_valid_package_name(dependency) {
+                return Err(SwissArmyHammerError::Config(format!(
+                    "Invalid dependency name '{dependency}' in package '{}'",
+                    self.name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Versions name install directories, so they must not be `.`, `..` or start with a dot
+fn is_valid_package_version(version: &str) -> bool {
+    version.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+}
+
+fn is_valid_package_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_'))
+        && !name.starts_with(['-', '_'])
+}
+
+/// Where a package is installed from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackageSource {
+    /// A git repository, optionally pinned to a branch, tag or commit with `#rev`
+    Git {
+        /// Repository URL
+        url: String,
+        /// Revision to check out
+        rev: Option<String>,
+    },
+    /// A gzip compressed tarball
+    Archive(PathBuf),
+    /// A package directory on the local file system
+    Directory(PathBuf),
+}
+
+impl PackageSource {
+    /// Parse a source specification such as `https://host/repo.git#v1.0`,
+    /// `kit.tar.gz` or `./path/to/package`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Err(SwissArmyHammerError::Config(
+                "Package source cannot be empty".to_string(),
+            ));
+        }
+
+        if spec.ends_with(".tar.gz") || spec.ends_with(".tgz") {
+            return Ok(PackageSource::Archive(PathBuf::from(spec)));
+        }
+
+        let is_git = ["https://", "http://", "ssh://", "git://", "file://", "git@"]
+            .iter()
+            .any(|prefix| spec.starts_with(prefix))
+            || spec.split('#').next().is_some_and(|s| s.ends_with(".git"));
+        if is_git {
+            let (url, rev) = match spec.split_once('#') {
+                Some((url, rev)) if !rev.is_empty() => (url, Some(rev.to_string())),
+                Some((url, _)) => (url, None),
+                None => (spec, None),
+            };
+            return Ok(PackageSource::Git {
+                url: url.to_string(),
+                rev,
+            });
+        }
+
+        let path = PathBuf::from(spec);
+        if path.is_dir() {
+            return Ok(PackageSource::Directory(path));
+        }
+
+        Err(SwissArmyHammerError::Config(format!(
+            "Unrecognized package source '{spec}': expected a git URL, a .tar.gz archive or a package directory"
+        )))
+    }
+}
+
+impl fmt::Display for PackageSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackageSource::Git {
+                url,
+                rev: Some(rev),
+            } => write!(f, "{url}#{rev}"),
+            PackageSource::Git { url, rev: None } => write!(f, "{url}"),
+            PackageSource::Archive(path) | PackageSource::Directory(path) => {
+                write!(f, "{}", path.display())
+            }
+        }
+    }
+}
+
+/// A single pinned package in the lockfile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    /// Package name
+    pub name: String,
+    /// Installed version
+    pub version: String,
+    /// Source specification the package was installed from
+    pub source: String,
+    /// Resolved git commit, for git sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved: Option<String>,
+    /// SHA-256 over the installed files
+    pub checksum: String,
+    /// Names of the packages this package depends on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+}
+
+/// Lockfile pinning the installed set of packages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Lockfile format version
+    pub version: u32,
+    /// Pinned packages, sorted by name
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            packages: Vec::new(),
+        }
+    }
+}
+
+impl Lockfile {
+    /// Load a lockfile, returning an empty one when the file does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        let lockfile: Lockfile = toml::from_str(&content).map_err(|e| {
+            SwissArmyHammerError::Config(format!("Invalid lockfile {}: {e}", path.display()))
+        })?;
+        if lockfile.version > LOCKFILE_VERSION {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Lockfile {} uses format version {}, this build supports up to {LOCKFILE_VERSION}",
+                path.display(),
+                lockfile.version
+            )));
+        }
+        // Entries name install directories, so an edited lockfile must not escape them
+        if let Some(locked) = lockfile.packages.iter().find(|locked| {
+            !is_valid_package_name(&locked.name) || !is_valid_package_version(&locked.version)
+        }) {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Lockfile {} has an invalid entry '{}' version '{}'",
+                path.display(),
+                locked.name,
+                locked.version
+            )));
+        }
+        Ok(lockfile)
+    }
+
+    /// Write the lockfile with packages sorted by name
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut sorted = self.clone();
+        sorted.packages.sort_by(|a, b| a.name.cmp(&b.name));
+        let content = toml::to_string_pretty(&sorted).map_err(|e| {
+            SwissArmyHammerError::Config(format!("Failed to serialize lockfile: {e}"))
+        })?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(
+            path,
+            format!("# This file is generated by `sah package`. Do not edit by hand.\n{content}"),
+        )?;
+        Ok(())
+    }
+
+    /// Find the pinned entry for a package
+    pub fn get(&self, name: &str) -> Option<&LockedPackage> {
+        self.packages.iter().find(|p| p.name == name)
+    }
+
+    /// Insert or replace the entry for a package
+    pub fn upsert(&mut self, entry: LockedPackage) {
+        self.packages.retain(|p| p.name != entry.name);
+        self.packages.push(entry);
+    }
+
+    /// Remove the entry for a package, returning it if present
+    pub fn remove(&mut self, name: &str) -> Option<LockedPackage> {
+        let index = self.packages.iter().position(|p| p.name == name)?;
+        Some(self.packages.remove(index))
+    }
+}
+
+/// A package present in the packages directory
+#[derive(Debug, Clone)]
+pub struct InstalledPackage {
+    /// Parsed manifest
+    pub manifest: PackageManifest,
+    /// Versioned install directory
+    pub path: PathBuf,
+    /// Lockfile entry, when the package is pinned
+    pub locked: Option<LockedPackage>,
+}
+
+/// Outcome of installing a single package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallOutcome {
+    /// The package was installed
+    Installed,
+    /// An installed version was replaced
+    Upgraded {
+        /// Previously installed version
+        from: String,
+    },
+    /// The package was already installed with the same contents
+    Unchanged,
+}
+
+/// Record of one package touched by an install
+#[derive(Debug, Clone)]
+pub struct InstalledEntry {
+    /// Package name
+    pub name: String,
+    /// Installed version
+    pub version: String,
+    /// What happened
+    pub outcome: InstallOutcome,
+    /// Whether the package was pulled in as a dependency
+    pub dependency: bool,
+}
+
+/// Summary of an install or sync
+#[derive(Debug, Clone, Default)]
+pub struct InstallReport {
+    /// Packages in install order, dependencies first
+    pub entries: Vec<InstalledEntry>,
+}
+
+/// Installs, removes and lists packages under a `.swissarmyhammer` root
+#[derive(Debug, Clone)]
+pub struct PackageManager {
+    root: PathBuf,
+}
+
+impl PackageManager {
+    /// Create a manager for a `.swissarmyhammer` directory
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The `.swissarmyhammer` directory this manager works in
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory holding installed packages
+    pub fn packages_dir(&self) -> PathBuf {
+        self.root.join(PACKAGES_DIR)
+    }
+
+    /// Path of the lockfile
+    pub fn lockfile_path(&self) -> PathBuf {
+        self.root.join(LOCKFILE_NAME)
+    }
+
+    /// Load the lockfile
+    pub fn lockfile(&self) -> Result<Lockfile> {
+        Lockfile::load(&self.lockfile_path())
+    }
+
+    /// Install a package and its dependencies from a source specification
+    pub fn install(&self, spec: &str) -> Result<InstallReport> {
+        let mut lockfile = self.lockfile()?;
+        let mut report = InstallReport::default();
+        let mut visiting = HashSet::new();
+        self.install_recursive(spec, None, false, &mut lockfile, &mut visiting, &mut report)?;
+        lockfile.save(&self.lockfile_path())?;
+        Ok(report)
+    }
+
+    /// Install exactly the packages pinned in the lockfile, verifying checksums
+    pub fn sync(&self) -> Result<InstallReport> {
+        let lockfile = self.lockfile()?;
+        let mut report = InstallReport::default();
+
+        for locked in &lockfile.packages {
+            let install_dir = self.install_dir(&locked.name, &locked.version);
+            if install_dir.is_dir() && checksum_dir(&install_dir)? == locked.checksum {
+                report.entries.push(InstalledEntry {
+                    name: locked.name.clone(),
+                    version: locked.version.clone(),
+                    outcome: InstallOutcome::Unchanged,
+                    dependency: false,
+                });
+                continue;
+            }
+
+            let source = match (PackageSource::parse(&locked.source)?, &locked.resolved) {
+                (PackageSource::Git { url, .. }, Some(commit)) => PackageSource::Git {
+                    url,
+                    rev: Some(commit.clone()),
+                },
+                (source, _) => source,
+            };
+            let fetched = fetch(&source)?;
+            let manifest = PackageManifest::load(&fetched.dir)?;
+            if manifest.name != locked.name || manifest.version != locked.version {
+                return Err(SwissArmyHammerError::Config(format!(
+                    "Source {} provides {} {}, but the lockfile pins {} {}",
+                    locked.source, manifest.name, manifest.version, locked.name, locked.version
+                )));
+            }
+
+            self.place(&fetched.dir, &install_dir)?;
+            let checksum = checksum_dir(&install_dir)?;
+            if checksum != locked.checksum {
+                let _ = std::fs::remove_dir_all(&install_dir);
+                return Err(SwissArmyHammerError::Config(format!(
+                    "Checksum mismatch for {} {}: lockfile has {}, source produced {checksum}",
+                    locked.name, locked.version, locked.checksum
+                )));
+            }
+
+            report.entries.push(InstalledEntry {
+                name: locked.name.clone(),
+                version: locked.version.clone(),
+                outcome: InstallOutcome::Installed,
+                dependency: false,
+            });
+        }
+
+        Ok(report)
+    }
+
+    /// Remove an installed package and its lockfile entry
+    ///
+    /// Fails when another installed package still depends on it.
+    pub fn remove(&self, name: &str) -> Result<LockedPackage> {
+        let mut lockfile = self.lockfile()?;
+        let dependents: Vec<&str> = lockfile
+            .packages
+            .iter()
+            .filter(|p| p.dependencies.iter().any(|d| d == name))
+            .map(|p| p.name.as_str())
+            .collect();
+        if !dependents.is_empty() {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Cannot remove '{name}': required by {}",
+                dependents.join(", ")
+            )));
+        }
+
+        let removed = lockfile.remove(name).ok_or_else(|| {
+            SwissArmyHammerError::Config(format!("Package '{name}' is not installed"))
+        })?;
+        let package_dir = self.packages_dir().join(name);
+        if package_dir.exists() {
+            std::fs::remove_dir_all(&package_dir)?;
+        }
+        lockfile.save(&self.lockfile_path())?;
+        Ok(removed)
+    }
+
+    /// List the active version of every installed package, sorted by name
+    pub fn list(&self) -> Result<Vec<InstalledPackage>> {
+        let lockfile = self.lockfile()?;
+        let mut packages = Vec::new();
+
+        for (name, path) in active_package_dirs(&self.root) {
+            match PackageManifest::load(&path) {
+                Ok(manifest) => packages.push(InstalledPackage {
+                    locked: lockfile.get(&name).cloned(),
+                    manifest,
+                    path,
+                }),
+                Err(e) => tracing::warn!("Skipping package at {}: {}", path.display(), e),
+            }
+        }
+
+        Ok(packages)
+    }
+
+    fn install_dir(&self, name: &str, version: &str) -> PathBuf {
+        self.packages_dir().join(name).join(version)
+    }
+
+    fn install_recursive(
+        &self,
+        spec: &str,
+        expected_name: Option<&str>,
+        dependency: bool,
+        lockfile: &mut Lockfile,
+        visiting: &mut HashSet<String>,
+        report: &mut InstallReport,
+    ) -> Result<()> {
+        let source = PackageSource::parse(spec)?;
+        let fetched = fetch(&source)?;
+        let manifest = PackageManifest::load(&fetched.dir)?;
+
+        if let Some(expected) = expected_name {
+            if manifest.name != expected {
+                return Err(SwissArmyHammerError::Config(format!(
+                    "Dependency '{expected}' resolved to a package named '{}' ({spec})",
+                    manifest.name
+                )));
+            }
+        }
+        if !visiting.insert(manifest.name.clone()) {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Circular package dependency involving '{}'",
+                manifest.name
+            )));
+        }
+
+        for (dep_name, dep_spec) in &manifest.dependencies {
+            // A dependency already pinned by an earlier install is kept as is,
+            // so a shared lockfile is not silently moved to another version.
+            let already_installed = report.entries.iter().any(|e| &e.name == dep_name)
+                || (lockfile.get(dep_name).is_some()
+                    && self.packages_dir().join(dep_name).is_dir());
+            if already_installed {
+                continue;
+            }
+            self.install_recursive(dep_spec, Some(dep_name), true, lockfile, visiting, report)?;
+        }
+
+        let install_dir = self.install_dir(&manifest.name, &manifest.version);
+        let previous = lockfile.get(&manifest.name).cloned();
+        let checksum_before = if install_dir.is_dir() {
+            Some(checksum_dir(&install_dir)?)
+        } else {
+            None
+        };
+
+        self.place(&fetched.dir, &install_dir)?;
+        let checksum = checksum_dir(&install_dir)?;
+
+        // Only one version of a package is active at a time.
+        if let Some(previous) = &previous {
+            if previous.version != manifest.version {
+                let old_dir = self.install_dir(&manifest.name, &previous.version);
+                if old_dir.exists() {
+                    std::fs::remove_dir_all(old_dir)?;
+                }
+            }
+        }
+
+        let outcome = match &previous {
+            Some(previous) if previous.version != manifest.version => InstallOutcome::Upgraded {
+                from: previous.version.clone(),
+            },
+            Some(_) if checksum_before.as_deref() == Some(checksum.as_str()) => {
+                InstallOutcome::Unchanged
+            }
+            _ => InstallOutcome::Installed,
+        };
+
+        lockfile.upsert(LockedPackage {
+            name: manifest.name.clone(),
+            version: manifest.version.clone(),
+            source: source.to_string(),
+            resolved: fetched.commit,
+            checksum,
+            dependencies: manifest.dependencies.keys().cloned().collect(),
+        });
+        report.entries.push(InstalledEntry {
+            name: manifest.name.clone(),
+            version: manifest.version.clone(),
+            outcome,
+            dependency,
+        });
+
+        visiting.remove(&manifest.name);
+        Ok(())
+    }
+
+    /// Copy a fetched package into its install directory, replacing any previous copy
+    fn place(&self, from: &Path, install_dir: &Path) -> Result<()> {
+        if install_dir.exists() {
+            std::fs::remove_dir_all(install_dir)?;
+        }
+        std::fs::create_dir_all(install_dir)?;
+        copy_package_files(from, install_dir)
+    }
+}
+
+/// A package fetched into a temporary location
+struct FetchedPackage {
+    /// Directory containing `package.toml`
+    dir: PathBuf,
+    /// Resolved commit for git sources
+    commit: Option<String>,
+    /// Keeps the temporary checkout alive until the package is placed
+    _temp: Option<tempfile::TempDir>,
+}
+
+fn fetch(source: &PackageSource) -> Result<FetchedPackage> {
+    match source {
+        PackageSource::Directory(path) => Ok(FetchedPackage {
+            dir: path.clone(),
+            commit: None,
+            _temp: None,
+        }),
+        PackageSource::Archive(path) => {
+            let temp = tempfile::TempDir::new()?;
+            let file = std::fs::File::open(path).map_err(|e| {
+                SwissArmyHammerError::Config(format!(
+                    "Cannot open package archive {}: {e}",
+                    path.display()
+                ))
+            })?;
+            tar::Archive::new(flate2::read::GzDecoder::new(file)).unpack(temp.path())?;
+            let dir = find_package_root(temp.path())?;
+            Ok(FetchedPackage {
+                dir,
+                commit: None,
+                _temp: Some(temp),
+            })
+        }
+        PackageSource::Git { url, rev } => {
+            let temp = tempfile::TempDir::new()?;
+            let repo = git2::Repository::clone(url, temp.path()).map_err(|e| {
+                SwissArmyHammerError::git2_operation_failed(&format!("clone {url}"), e)
+            })?;
+            if let Some(rev) = rev {
+                checkout_revision(&repo, rev).map_err(|e| {
+                    SwissArmyHammerError::git2_operation_failed(&format!("checkout {rev}"), e)
+                })?;
+            }
+            let commit = repo
+                .head()
+                .ok()
+                .and_then(|head| head.peel_to_commit().ok())
+                .map(|commit| commit.id().to_string());
+            let dir = find_package_root(temp.path())?;
+            Ok(FetchedPackage {
+                dir,
+                commit,
+                _temp: Some(temp),
+            })
+        }
+    }
+}
+
+fn checkout_revision(repo: &git2::Repository, rev: &str) -> std::result::Result<(), git2::Error> {
+    let object = repo.revparse_single(rev)?;
+    let commit = object.peel_to_commit()?;
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.force();
+    repo.checkout_tree(commit.as_object(), Some(&mut checkout))?;
+    repo.set_head_detached(commit.id())
+}
+
+/// Locate the manifest at the root or in a single top-level directory,
+/// which is how most tarballs are laid out
+fn find_package_root(dir: &Path) -> Result<PathBuf> {
+    if dir.join(PACKAGE_MANIFEST_FILE).is_file() {
+        return Ok(dir.to_path_buf());
+    }
+    let subdirs: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir() && p.file_name().is_some_and(|n| n != ".git"))
+        .collect();
+    if let [only] = subdirs.as_slice() {
+        if only.join(PACKAGE_MANIFEST_FILE).is_file() {
+            return Ok(only.clone());
+        }
+    }
+    Err(SwissArmyHammerError::Config(format!(
+        "No {PACKAGE_MANIFEST_FILE} found in package source"
+    )))
+}
+
+/// Files in a package directory that are part of its contents, sorted by relative path
+fn package_files(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let relative = e.path().strip_prefix(dir).ok()?;
+            let key = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            Some((key, e.path().to_path_buf()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn copy_package_files(from: &Path, to: &Path) -> Result<()> {
+    for (relative, path) in package_files(from) {
+        let target = to.join(&relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(&path, &target)?;
+    }
+    Ok(())
+}
+
+/// SHA-256 over the relative paths and contents of every file in a package
+pub fn checksum_dir(dir: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    for (relative, path) in package_files(dir) {
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update(std::fs::read(&path)?);
+        hasher.update([0]);
+    }
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+/// Compare dotted versions numerically where possible, e.g. `1.10.0 > 1.9.2`
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut left = a.split(['.', '-', '+']);
+    let mut right = b.split(['.', '-', '+']);
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) => {
+                let ordering = match (l.parse::<u64>(), r.parse::<u64>()) {
+                    (Ok(l), Ok(r)) => l.cmp(&r),
+                    _ => l.cmp(r),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Active install directory for every package under a `.swissarmyhammer` root
+///
+/// The version pinned in the lockfile wins; packages missing from the lockfile
+/// fall back to their highest installed version. Results are sorted by name.
+pub fn active_package_dirs(root: &Path) -> Vec<(String, PathBuf)> {
+    let packages_dir = root.join(PACKAGES_DIR);
+    let Ok(entries) = std::fs::read_dir(&packages_dir) else {
+        return Vec::new();
+    };
+    let lockfile = Lockfile::load(&root.join(LOCKFILE_NAME)).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring unreadable lockfile: {}", e);
+        Lockfile::default()
+    });
+
+    let mut active = Vec::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let package_dir = entry.path();
+        let Some(name) = package_dir.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !package_dir.is_dir() {
+            continue;
+        }
+
+        let pinned = lockfile
+            .get(name)
+            .map(|locked| package_dir.join(&locked.version))
+            .filter(|dir| dir.is_dir());
+        let version_dir = pinned.or_else(|| {
+            std::fs::read_dir(&package_dir)
+                .ok()?
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.join(PACKAGE_MANIFEST_FILE).is_file())
+                .max_by(|a, b| {
+                    let a = a.file_name().unwrap_or_default().to_string_lossy();
+                    let b = b.file_name().unwrap_or_default().to_string_lossy();
+                    compare_versions(&a, &b)
+                })
+        });
+
+        if let Some(dir) = version_dir {
+            active.push((name.to_string(), dir));
+        }
+    }
+
+    active.sort_by(|a, b| a.0.cmp(&b.0));
+    active
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_package(dir: &Path, name: &str, version: &str, deps: &[(&str, &Path)]) {
+        fs::create_dir_all(dir.join("workflows")).unwrap();
+        fs::create_dir_all(dir.join("prompts")).unwrap();
+        let mut manifest = format!("name = \"{name}\"\nversion = \"{version}\"\n");
+        if !deps.is_empty() {
+            manifest.push_str("\n[dependencies]\n");
+            for (dep, path) in deps {
+                manifest.push_str(&format!("{dep} = \"{}\"\n", path.display()));
+            }
+        }
+        fs::write(dir.join(PACKAGE_MANIFEST_FILE), manifest).unwrap();
+        fs::write(
+            dir.join("workflows").join(format!("{name}-flow.md")),
+            "stateDiagram-v2\n    [*] --> Done\n    Done --> [*]\n",
+        )
+        .unwrap();
+        fs::write(dir.join("prompts").join(format!("{name}.md")), "Hello").unwrap();
+    }
+
+    #[test]
+    fn test_manifest_and_source_parsing() {
+        let manifest = PackageManifest::parse(
+            "name = \"review-kit\"\nversion = \"1.2.0\"\n\n[dependencies]\nlint-kit = \"https://example.com/lint-kit.git#v0.3.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.name, "review-kit");
+        assert_eq!(manifest.dependencies.len(), 1);
+        assert!(PackageManifest::parse("name = \"../evil\"\nversion = \"1\"\n").is_err());
+        for version in [".", "..", ".1", "-1", "", "1/2"] {
+            assert!(
+                PackageManifest::parse(&format!("name = \"kit\"\nversion = \"{version}\"\n"))
+                    .is_err(),
+                "version '{version}' should be rejected"
+            );
+        }
+        assert!(PackageManifest::parse("name = \"kit\"\nversion = \"1.0.0-rc.1+build\"\n").is_ok());
+
+        assert_eq!(
+            PackageSource::parse("https://example.com/kit.git#v1").unwrap(),
+            PackageSource::Git {
+                url: "https://example.com/kit.git".to_string(),
+                rev: Some("v1".to_string()),
+            }
+        );
+        assert_eq!(
+            PackageSource::parse("kit-1.0.tar.gz").unwrap(),
+            PackageSource::Archive(PathBuf::from("kit-1.0.tar.gz"))
+        );
+        assert!(PackageSource::parse("/definitely/not/a/package").is_err());
+
+        assert_eq!(compare_versions("1.10.0", "1.9.2"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0", "1.0.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_lockfile_rejects_entries_outside_the_package_directory() {
+        let root = TempDir::new().unwrap();
+        let path = root.path().join("sah.lock");
+        fs::write(
+            &path,
+            "version = 1\n\n[[package]]\nname = \"kit\"\nversion = \"..\"\nsource = \"kit\"\nchecksum = \"0\"\n",
+        )
+        .unwrap();
+        let error = Lockfile::load(&path).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("invalid entry 'kit' version '..'"));
+    }
+
+    #[test]
+    fn test_install_with_dependencies_writes_lockfile() {
+        let sources = TempDir::new().unwrap();
+        let root = TempDir::new().unwrap();
+        let dep_dir = sources.path().join("lint-kit");
+        let pkg_dir = sources.path().join("review-kit");
+        write_package(&dep_dir, "lint-kit", "0.3.0", &[]);
+        write_package(&pkg_dir, "review-kit", "1.2.0", &[("lint-kit", &dep_dir)]);
+
+        let manager = PackageManager::new(root.path());
+        let report = manager.install(pkg_dir.to_str().unwrap()).unwrap();
+        let names: Vec<&str> = report.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["lint-kit", "review-kit"]);
+        assert!(report.entries[0].dependency);
+
+        let lockfile = manager.lockfile().unwrap();
+        let locked = lockfile.get("review-kit").unwrap();
+        assert_eq!(locked.version, "1.2.0");
+        assert_eq!(locked.dependencies, vec!["lint-kit".to_string()]);
+        assert!(locked.checksum.starts_with("sha256:"));
+
+        let active = active_package_dirs(root.path());
+        assert_eq!(active.len(), 2);
+        assert!(active[1].1.ends_with("review-kit/1.2.0"));
+
+        // Reinstalling identical content is a no-op
+        let report = manager.install(pkg_dir.to_str().unwrap()).unwrap();
+        assert_eq!(
+            report.entries.last().unwrap().outcome,
+            InstallOutcome::Unchanged
+        );
+
+        // Dependencies cannot be removed while still required
+        assert!(manager.remove("lint-kit").is_err());
+        manager.remove("review-kit").unwrap();
+        manager.remove("lint-kit").unwrap();
+        assert!(manager.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sync_restores_and_verifies_locked_packages() {
+        let sources = TempDir::new().unwrap();
+        let root = TempDir::new().unwrap();
+        let pkg_dir = sources.path().join("review-kit");
+        write_package(&pkg_dir, "review-kit", "1.0.0", &[]);
+
+        let manager = PackageManager::new(root.path());
+        manager.install(pkg_dir.to_str().unwrap()).unwrap();
+        fs::remove_dir_all(manager.packages_dir()).unwrap();
+
+        let report = manager.sync().unwrap();
+        assert_eq!(report.entries[0].outcome, InstallOutcome::Installed);
+        assert_eq!(manager.list().unwrap().len(), 1);
+
+        // Source changed without a version bump: sync refuses the mismatch
+        fs::remove_dir_all(manager.packages_dir()).unwrap();
+        fs::write(pkg_dir.join("prompts").join("review-kit.md"), "Changed").unwrap();
+        assert!(manager.sync().is_err());
+    }
+}
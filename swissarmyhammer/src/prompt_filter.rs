@@ -73,8 +73,10 @@ impl PromptFilter {
     pub fn matches(&self, prompt: &Prompt, sources: &HashMap<String, PromptSource>) -> bool {
         // Check source filter
         if let Some(ref filter_source) = self.source {
-            let prompt_source = sources.get(&prompt.name);
-            if prompt_source != Some(filter_source) {
+            let matches_source = sources
+                .get(&prompt.name)
+                .is_some_and(|source| source.matches_filter(filter_source));
+            if !matches_source {
                 return false;
             }
         }
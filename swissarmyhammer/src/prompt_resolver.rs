@@ -1,4 +1,4 @@
-use crate::file_loader::{FileSource, VirtualFileSystem};
+use crate::file_loader::{FileOverride, FileSource, VirtualFileSystem};
 use crate::{PromptLibrary, PromptLoader, Result};
 use std::collections::HashMap;
 
@@ -28,10 +28,16 @@ impl PromptResolver {
         self.vfs.get_directories()
     }
 
+    /// Prompts and partials that replaced a same-named file from another source
+    pub fn overrides(&self) -> &[FileOverride] {
+        self.vfs.overrides()
+    }
+
     /// Load all prompts following the correct precedence:
     /// 1. Builtin prompts (least specific, embedded in binary)
-    /// 2. User prompts from ~/.swissarmyhammer/prompts
-    /// 3. Local prompts from .swissarmyhammer directories (most specific)
+    /// 2. Prompts from installed packages
+    /// 3. User prompts from ~/.swissarmyhammer/prompts
+    /// 4. Local prompts from .swissarmyhammer directories (most specific)
     ///
     /// Also loads partials into the library's storage for template rendering.
     pub fn load_all_prompts(&mut self, library: &mut PromptLibrary) -> Result<()> {
@@ -307,4 +313,27 @@ This is a user-defined debug/error prompt that should override the builtin one.
             );
         }
     }
+
+    #[test]
+    fn test_builtin_prompts_mark_sections_for_packing() {
+        let _guard = crate::test_utils::IsolatedTestEnvironment::new().unwrap();
+        let mut resolver = PromptResolver::new();
+        let mut library = PromptLibrary::new();
+        resolver.load_all_prompts(&mut library).unwrap();
+
+        let mut context = swissarmyhammer_config::TemplateContext::new();
+        context.set_var(
+            crate::template::SECTION_MARKERS_CONTEXT_KEY.to_string(),
+            serde_json::Value::Bool(true),
+        );
+        context.set_var(
+            "log_content".to_string(),
+            serde_json::Value::String("ERROR boom".to_string()),
+        );
+
+        let comments = library.render("docs/comments", &context).unwrap();
+        assert!(comments.contains("<!-- sah:section code 10 -->\n## Code to Consider"));
+        let logs = library.render("debug/logs", &context).unwrap();
+        assert!(logs.contains("<!-- sah:section logs 5 -->\n```\nERROR boom\n```"));
+    }
 }
@@ -811,10 +811,22 @@ impl PromptLibrary {
         let partial_source = crate::template::PromptPartialSource::new(Arc::new(full_library));
         let partial_compiler = liquid::partials::EagerCompiler::new(partial_source);
 
+        // Callers that pack prompts into a context window ask for section markers
+        let section_block = if enhanced_context
+            .get(crate::template::SECTION_MARKERS_CONTEXT_KEY)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        {
+            crate::template::SectionBlock::with_markers()
+        } else {
+            crate::template::SectionBlock::new()
+        };
+
         // Parse and render the template with partials support
         let liquid_template = liquid::ParserBuilder::with_stdlib()
             .partials(partial_compiler)
             .tag(crate::template::PartialTag::new())
+            .block(section_block)
             .build()
             .map_err(|e| {
                 SwissArmyHammerError::Template(format!(
@@ -1043,6 +1055,21 @@ impl PromptLoader {
                     serde_json::Value::String(title.to_string()),
                 );
             }
+            // `cache: false` opts the prompt out of the agent response cache
+            if let Some(cache) = metadata_value
+                .get("cache")
+                .and_then(serde_json::Value::as_bool)
+            {
+                prompt
+                    .metadata
+                    .insert("cache".to_string(), serde_json::Value::Bool(cache));
+            }
+            // `context` configures how the prompt is packed into the model context window
+            if let Some(context) = metadata_value.get("context").filter(|v| v.is_object()) {
+                prompt
+                    .metadata
+                    .insert("context".to_string(), context.clone());
+            }
             if let Some(desc) = metadata_value
                 .get("description")
                 .and_then(serde_json::Value::as_str)
@@ -1194,6 +1221,21 @@ impl PromptLoader {
                     serde_json::Value::String(title.to_string()),
                 );
             }
+            // `cache: false` opts the prompt out of the agent response cache
+            if let Some(cache) = metadata_value
+                .get("cache")
+                .and_then(serde_json::Value::as_bool)
+            {
+                prompt
+                    .metadata
+                    .insert("cache".to_string(), serde_json::Value::Bool(cache));
+            }
+            // `context` configures how the prompt is packed into the model context window
+            if let Some(context) = metadata_value.get("context").filter(|v| v.is_object()) {
+                prompt
+                    .metadata
+                    .insert("context".to_string(), context.clone());
+            }
             if let Some(desc) = metadata_value
                 .get("description")
                 .and_then(serde_json::Value::as_str)
@@ -1700,6 +1742,42 @@ This is another prompt.
         }
     }
 
+    #[test]
+    fn test_cache_frontmatter_is_kept_in_metadata() {
+        let loader = PromptLoader::new();
+
+        let uncached = loader
+            .load_from_string(
+                "uncached",
+                "---\ntitle: Uncached\ncache: false\n---\nHello {{name}}",
+            )
+            .unwrap();
+        assert_eq!(
+            uncached.metadata.get("cache"),
+            Some(&serde_json::Value::Bool(false))
+        );
+
+        let default = loader
+            .load_from_string("default", "---\ntitle: Default\n---\nHello")
+            .unwrap();
+        assert!(!default.metadata.contains_key("cache"));
+    }
+
+    #[test]
+    fn test_context_frontmatter_is_kept_in_metadata() {
+        let loader = PromptLoader::new();
+
+        let prompt = loader
+            .load_from_string(
+                "packed",
+                "---\ntitle: Packed\ncontext:\n  overflow: summarize\n  reserve_tokens: 2048\n---\nHello",
+            )
+            .unwrap();
+        let context = prompt.metadata.get("context").unwrap();
+        assert_eq!(context["overflow"], "summarize");
+        assert_eq!(context["reserve_tokens"], 2048);
+    }
+
     #[test]
     fn test_prompt_render_with_context() {
         use serde_json::json;
@@ -14,6 +14,7 @@ use crate::search::{
 };
 use duckdb::{Connection, ToSql};
 use serde_json;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use tracing;
@@ -73,6 +74,14 @@ impl VectorStorage {
         )
     "#;
 
+    const CREATE_MEMO_EMBEDDINGS_TABLE: &'static str = r#"
+        CREATE TABLE IF NOT EXISTS memo_embeddings (
+            memo_id TEXT PRIMARY KEY,
+            content_hash TEXT NOT NULL,
+            vector TEXT NOT NULL
+        )
+    "#;
+
     const CREATE_FILE_PATH_INDEX: &'static str =
         "CREATE INDEX IF NOT EXISTS idx_chunks_file_path ON code_chunks(file_path)";
 
@@ -132,6 +141,14 @@ impl VectorStorage {
                 SwissArmyHammerError::Storage(format!("Failed to create embeddings table: {e}"))
             })?;
 
+        // Create memo_embeddings table
+        conn.execute(Self::CREATE_MEMO_EMBEDDINGS_TABLE, [])
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!(
+                    "Failed to create memo_embeddings table: {e}"
+                ))
+            })?;
+
         // Create indexes for better performance
         conn.execute(Self::CREATE_FILE_PATH_INDEX, [])
             .map_err(|e| {
@@ -1172,6 +1189,131 @@ impl VectorStorage {
         }
     }
 
+    /// Store the embedding of a memo along with the hash of the embedded text
+    pub fn store_memo_embedding(
+        &self,
+        memo_id: &str,
+        content_hash: &ContentHash,
+        vector: &[f32],
+    ) -> Result<()> {
+        tracing::debug!("Storing embedding for memo: {}", memo_id);
+
+        let conn = self.connection.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
+        })?;
+
+        let vector_str = serde_json::to_string(vector).map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to serialize vector: {e}"))
+        })?;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO memo_embeddings (memo_id, content_hash, vector) VALUES (?, ?, ?)",
+            [&memo_id as &dyn ToSql, &content_hash.0, &vector_str],
+        )
+        .map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to store memo embedding: {e}"))
+        })?;
+
+        Ok(())
+    }
+
+    /// Content hashes of all embedded memos, keyed by memo ID
+    pub fn memo_embedding_hashes(&self) -> Result<HashMap<String, ContentHash>> {
+        let conn = self.connection.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
+        })?;
+
+        let mut stmt = conn
+            .prepare("SELECT memo_id, content_hash FROM memo_embeddings")
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to prepare memo hash query: {e}"))
+            })?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to execute memo hash query: {e}"))
+            })?;
+
+        let mut hashes = HashMap::new();
+        for row in rows {
+            let (memo_id, hash) = row.map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to read memo hash row: {e}"))
+            })?;
+            hashes.insert(memo_id, ContentHash(hash));
+        }
+        Ok(hashes)
+    }
+
+    /// Remove the embedding of a memo, returning whether one was stored
+    pub fn remove_memo_embedding(&self, memo_id: &str) -> Result<bool> {
+        tracing::debug!("Removing embedding for memo: {}", memo_id);
+
+        let conn = self.connection.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
+        })?;
+
+        let removed = conn
+            .execute(
+                "DELETE FROM memo_embeddings WHERE memo_id = ?",
+                [&memo_id as &dyn ToSql],
+            )
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to remove memo embedding: {e}"))
+            })?;
+
+        Ok(removed > 0)
+    }
+
+    /// Memo IDs most similar to the query embedding, best match first
+    pub fn memo_similarity_search(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+    ) -> Result<Vec<(String, f32)>> {
+        let conn = self.connection.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
+        })?;
+
+        let mut stmt = conn
+            .prepare("SELECT memo_id, vector FROM memo_embeddings")
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!(
+                    "Failed to prepare memo similarity query: {e}"
+                ))
+            })?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!(
+                    "Failed to execute memo similarity query: {e}"
+                ))
+            })?;
+
+        let mut scored = Vec::new();
+        for row in rows {
+            let (memo_id, vector_str) = row.map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to read memo embedding row: {e}"))
+            })?;
+            match serde_json::from_str::<Vec<f32>>(&vector_str) {
+                Ok(vector) => {
+                    let similarity = SemanticUtils::cosine_similarity(query_embedding, &vector);
+                    scored.push((memo_id, similarity));
+                }
+                Err(e) => {
+                    tracing::warn!("Skipping corrupted embedding for memo {}: {}", memo_id, e);
+                }
+            }
+        }
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+        Ok(scored)
+    }
+
     /// Explicitly close the database connection
     ///
     /// This method allows for explicit cleanup of DuckDB connections,
@@ -1455,6 +1597,30 @@ mod tests {
         assert_eq!(stats.database_size_bytes, 0);
     }
 
+    #[test]
+    fn test_memo_embeddings() {
+        let (config, _guard) = create_test_config();
+        let storage = VectorStorage::new(config).unwrap();
+        storage.initialize().unwrap();
+
+        let hash = ContentHash("hash-a".to_string());
+        storage
+            .store_memo_embedding("memo-a", &hash, &[1.0, 0.0])
+            .unwrap();
+        storage
+            .store_memo_embedding("memo-b", &ContentHash("hash-b".to_string()), &[0.0, 1.0])
+            .unwrap();
+        assert_eq!(storage.memo_embedding_hashes().unwrap()["memo-a"], hash);
+
+        let results = storage.memo_similarity_search(&[0.9, 0.1], 1).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "memo-a");
+
+        assert!(storage.remove_memo_embedding("memo-a").unwrap());
+        assert!(!storage.remove_memo_embedding("memo-a").unwrap());
+        assert_eq!(storage.memo_embedding_hashes().unwrap().len(), 1);
+    }
+
     #[test]
     fn test_get_file_chunks() {
         let (config, _guard) = create_test_config();
@@ -72,7 +72,10 @@
 
 use crate::{plugins::PluginRegistry, security, PromptLibrary, Result, SwissArmyHammerError};
 use liquid::{Object, Parser};
-use liquid_core::{Language, ParseTag, Renderable, Runtime, TagReflection, TagTokenIter};
+use liquid_core::{
+    BlockReflection, Language, ParseBlock, ParseTag, Renderable, Runtime, TagBlock, TagReflection,
+    TagTokenIter,
+};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::Write;
@@ -123,6 +126,138 @@ impl ParseTag for PartialTag {
 #[derive(Debug, Clone)]
 struct PartialRenderable;
 
+/// Prefix of the marker emitted before a `{% section %}` block's content
+pub const SECTION_START_MARKER_PREFIX: &str = "<!-- sah:section ";
+
+/// Marker emitted after a `{% section %}` block's content
+pub const SECTION_END_MARKER: &str = "<!-- sah:endsection -->";
+
+/// Template variable that makes `PromptLibrary::render` emit section markers
+pub const SECTION_MARKERS_CONTEXT_KEY: &str = "_section_markers";
+
+/// Custom block tag that names a region of a prompt for context window packing
+///
+/// ```liquid
+/// {% section memos 10 %}
+/// {{ memos }}
+/// {% endsection %}
+/// ```
+///
+/// The first argument names the section and the optional second argument is its
+/// priority; sections with lower priority are shrunk first when a rendered prompt
+/// does not fit the model context. Normally the block renders its content
+/// unchanged. Parsers built with [`SectionBlock::with_markers`] surround the
+/// content with HTML comment markers so the packer can find section boundaries;
+/// `PromptLibrary::render` does so when [`SECTION_MARKERS_CONTEXT_KEY`] is true.
+#[derive(Clone, Debug, Default)]
+pub struct SectionBlock {
+    emit_markers: bool,
+}
+
+impl SectionBlock {
+    /// Create a section block that renders its content unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a section block that wraps its content in section markers
+    pub fn with_markers() -> Self {
+        Self { emit_markers: true }
+    }
+}
+
+impl BlockReflection for SectionBlock {
+    fn start_tag(&self) -> &str {
+        "section"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endsection"
+    }
+
+    fn description(&self) -> &str {
+        "Names a prompt region and its priority for context window packing"
+    }
+}
+
+impl ParseBlock for SectionBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> liquid_core::Result<Box<dyn Renderable>> {
+        let name = arguments
+            .expect_next("Section name expected")?
+            .as_str()
+            .trim_matches(|c| c == '"' || c == '\'')
+            .to_string();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(liquid_core::Error::with_msg(format!(
+                "Invalid section name '{name}': use letters, digits, '-' and '_'"
+            )));
+        }
+
+        let priority = match arguments.next() {
+            Some(token) => token.as_str().parse::<i64>().map_err(|_| {
+                liquid_core::Error::with_msg(format!(
+                    "Section priority must be an integer, got '{}'",
+                    token.as_str()
+                ))
+            })?,
+            None => 0,
+        };
+        arguments.expect_nothing()?;
+
+        let template = liquid_core::Template::new(tokens.parse_all(options)?);
+        tokens.assert_empty();
+
+        Ok(Box::new(SectionRenderable {
+            name,
+            priority,
+            emit_markers: self.emit_markers,
+            template,
+        }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+/// Renderable for the section block
+#[derive(Debug)]
+struct SectionRenderable {
+    name: String,
+    priority: i64,
+    emit_markers: bool,
+    template: liquid_core::Template,
+}
+
+impl Renderable for SectionRenderable {
+    fn render_to(&self, output: &mut dyn Write, runtime: &dyn Runtime) -> liquid_core::Result<()> {
+        let write_error = |e: std::io::Error| liquid_core::Error::with_msg(e.to_string());
+
+        if self.emit_markers {
+            write!(
+                output,
+                "{}{} {} -->",
+                SECTION_START_MARKER_PREFIX, self.name, self.priority
+            )
+            .map_err(write_error)?;
+        }
+        self.template.render_to(output, runtime)?;
+        if self.emit_markers {
+            write!(output, "{SECTION_END_MARKER}").map_err(write_error)?;
+        }
+        Ok(())
+    }
+}
+
 /// Convert string to URL-friendly slug
 fn slugify_string(input: &str) -> String {
     input
@@ -834,6 +969,7 @@ impl TemplateEngine {
     pub fn default_parser() -> liquid::Parser {
         liquid::ParserBuilder::with_stdlib()
             .tag(PartialTag::new())
+            .block(SectionBlock::new())
             .build()
             .expect("Failed to build Liquid parser")
     }
@@ -844,6 +980,7 @@ impl TemplateEngine {
         liquid::ParserBuilder::with_stdlib()
             .partials(partial_compiler)
             .tag(PartialTag::new())
+            .block(SectionBlock::new())
             .build()
             .expect("Failed to build Liquid parser with partials")
     }
@@ -1010,6 +1147,38 @@ mod tests {
         assert_eq!(result, "Hello World!");
     }
 
+    #[test]
+    fn test_section_block_renders_content_and_optional_markers() {
+        let source = "A {% section memos 5 %}{{ name }}{% endsection %} B";
+        let globals = liquid::object!({ "name": "memo" });
+
+        let plain = liquid::ParserBuilder::with_stdlib()
+            .block(SectionBlock::new())
+            .build()
+            .unwrap()
+            .parse(source)
+            .unwrap();
+        assert_eq!(plain.render(&globals).unwrap(), "A memo B");
+
+        let marked = liquid::ParserBuilder::with_stdlib()
+            .block(SectionBlock::with_markers())
+            .build()
+            .unwrap()
+            .parse(source)
+            .unwrap();
+        assert_eq!(
+            marked.render(&globals).unwrap(),
+            "A <!-- sah:section memos 5 -->memo<!-- sah:endsection --> B"
+        );
+
+        let invalid = liquid::ParserBuilder::with_stdlib()
+            .block(SectionBlock::new())
+            .build()
+            .unwrap()
+            .parse("{% section memos high %}x{% endsection %}");
+        assert!(invalid.is_err());
+    }
+
     #[test]
     fn test_empty_template() {
         let engine = TemplateEngine::new();
@@ -1,8 +1,8 @@
 //! Action parsing utilities for workflow state descriptions
 
 use crate::workflow::actions::{
-    AbortAction, ActionError, ActionResult, LogAction, LogLevel, PromptAction, SetVariableAction,
-    ShellAction, SubWorkflowAction, WaitAction,
+    AbortAction, ActionError, ActionResult, ApprovalAction, LogAction, LogLevel, PromptAction,
+    SetVariableAction, ShellAction, SubWorkflowAction, WaitAction,
 };
 use chumsky::prelude::*;
 use regex::Regex;
@@ -320,6 +320,27 @@ impl ActionParser {
         }
     }
 
+    /// Parse an approval gate action from description
+    /// Format: Await approval "merge to main?"
+    pub fn parse_approval_action(&self, description: &str) -> ActionResult<Option<ApprovalAction>> {
+        let parser = Self::case_insensitive("await")
+            .then_ignore(Self::whitespace())
+            .then_ignore(Self::case_insensitive("approval"))
+            .ignore_then(
+                Self::whitespace()
+                    .ignore_then(Self::quoted_string())
+                    .or_not(),
+            )
+            .then_ignore(end());
+
+        match parser.parse(description.trim()).into_result() {
+            Ok(message) => Ok(Some(ApprovalAction::new(
+                message.unwrap_or_else(|| "Approve to continue?".to_string()),
+            ))),
+            Err(_) => Ok(None),
+        }
+    }
+
     /// Parse a sub-workflow action from description
     /// Format: Run workflow "workflow-name" with input1="value1" input2="value2"
     /// Format: Delegate to "workflow-name" with input="${data}"
@@ -751,6 +772,28 @@ mod tests {
         assert!(matches!(action.level, LogLevel::Warning));
     }
 
+    #[test]
+    fn test_parse_approval_action() {
+        let parser = ActionParser::new().unwrap();
+
+        let action = parser
+            .parse_approval_action("await approval \"merge to main?\"")
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.message, "merge to main?");
+
+        let action = parser
+            .parse_approval_action("Await approval")
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.message, "Approve to continue?");
+
+        assert!(parser
+            .parse_approval_action("await approvals")
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn test_parse_set_variable_action() {
         let parser = ActionParser::new().unwrap();
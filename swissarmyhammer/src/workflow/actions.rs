@@ -8,6 +8,9 @@ use crate::shell_security::{
 };
 
 use crate::workflow::action_parser::ActionParser;
+use crate::workflow::approval::{
+    ApprovalDecision, PendingApproval, APPROVAL_RESULT_KEY, PENDING_APPROVAL_KEY,
+};
 use crate::workflow::mcp_integration::{response_processing, WorkflowShellContext};
 use crate::workflow::{
     WorkflowExecutor, WorkflowName, WorkflowRunStatus, WorkflowStorage, WorkflowTemplateContext,
@@ -28,7 +31,12 @@ use crate::{PromptLibrary, PromptResolver};
 use async_trait::async_trait;
 use swissarmyhammer_config::agent::{AgentConfig, AgentExecutorType, LlamaAgentConfig};
 
-use super::agents::LlamaAgentExecutor;
+use super::agents::context_window::{record_prompt_token_usage, strip_section_markers};
+use super::agents::response_cache::{
+    is_cache_hit, with_response_cache, AGENT_CACHE_CONTEXT_KEY, CACHE_HITS_CONTEXT_KEY,
+    CACHE_MISSES_CONTEXT_KEY,
+};
+use super::agents::{AgentResponseCache, LlamaAgentExecutor, PromptPacker};
 
 thread_local! {
     /// Thread-local test storage registry for tests
@@ -103,6 +111,12 @@ pub enum ActionError {
     /// Shell security validation error
     #[error("Shell security error: {0}")]
     ShellSecurityError(#[from] ShellSecurityError),
+    /// Rendered prompt does not fit the model context window
+    #[error("Context window exceeded: {0}")]
+    ContextOverflow(String),
+    /// An approval gate was rejected
+    #[error("Approval rejected: {0}")]
+    ApprovalRejected(String),
 }
 
 /// Result type for action operations
@@ -680,6 +694,18 @@ pub trait VariableSubstitution {
     }
 }
 
+/// User and system prompts rendered for a prompt action
+struct RenderedPrompts {
+    /// Rendered user prompt, with section markers
+    user_prompt: String,
+    /// Rendered system prompt, if any
+    system_prompt: Option<String>,
+    /// Whether responses may be served from the agent response cache
+    cacheable: bool,
+    /// The prompt's `context` frontmatter
+    context_settings: Option<Value>,
+}
+
 /// Action that executes a prompt using Claude
 #[derive(Debug, Clone)]
 pub struct PromptAction {
@@ -768,10 +794,13 @@ impl Action for PromptAction {
 
 impl PromptAction {
     /// Render both user prompt and system prompt using the same library instance
+    ///
+    /// Also reports whether the prompt allows its response to be cached; prompts opt
+    /// out with `cache: false` in their frontmatter.
     fn render_prompts_directly(
         &self,
         context: &WorkflowTemplateContext,
-    ) -> ActionResult<(String, Option<String>)> {
+    ) -> ActionResult<RenderedPrompts> {
         tracing::debug!(
             "Starting render_prompts_directly for prompt: {}",
             self.prompt_name
@@ -827,6 +856,12 @@ impl PromptAction {
             template_context.set_var(key.clone(), serde_json::Value::String(value.clone()));
         }
 
+        // Mark `{% section %}` blocks so the prompt can be packed into the context window
+        template_context.set_var(
+            crate::template::SECTION_MARKERS_CONTEXT_KEY.to_string(),
+            Value::Bool(true),
+        );
+
         tracing::debug!("Created template context successfully");
 
         // Convert library to Arc for partials support
@@ -859,7 +894,7 @@ impl PromptAction {
 
         // Render system prompt using the same library instance (optional)
         let system_prompt = match library_arc.render(".system", &template_context) {
-            Ok(prompt) => Some(prompt),
+            Ok(prompt) => Some(strip_section_markers(&prompt)),
             Err(e) => {
                 tracing::warn!(
                     "Failed to render system prompt: {}. Proceeding without system prompt.",
@@ -878,7 +913,22 @@ impl PromptAction {
             tracing::debug!("No system prompt will be used");
         }
 
-        Ok((rendered, system_prompt))
+        let metadata = library_arc
+            .get(&self.prompt_name)
+            .ok()
+            .map(|prompt| prompt.metadata)
+            .unwrap_or_default();
+        let cacheable = metadata
+            .get("cache")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        Ok(RenderedPrompts {
+            user_prompt: rendered,
+            system_prompt,
+            cacheable,
+            context_settings: metadata.get("context").cloned(),
+        })
     }
 
     /// Execute the command once without retry logic
@@ -903,13 +953,32 @@ impl PromptAction {
         );
 
         // Render both user and system prompts using the same library instance
-        let (user_prompt, system_prompt) = match self.render_prompts_directly(context) {
+        let rendered = match self.render_prompts_directly(context) {
             Ok(prompts) => prompts,
             Err(e) => {
                 tracing::error!("Failed to render prompts: {:?}", e);
                 return Err(e);
             }
         };
+        let RenderedPrompts {
+            user_prompt,
+            system_prompt,
+            cacheable,
+            context_settings,
+        } = rendered;
+
+        // Fit the prompt into the model context window and record its token counts
+        let packer =
+            PromptPacker::for_agent(&context.get_agent_config(), context_settings.as_ref());
+        let packed = match packer.pack(&self.prompt_name, system_prompt.as_deref(), &user_prompt) {
+            Ok(packed) => packed,
+            Err(e) => {
+                record_prompt_token_usage(context, &e.usage);
+                return Err(ActionError::ContextOverflow(e.to_string()));
+            }
+        };
+        record_prompt_token_usage(context, &packed.usage);
+        let user_prompt = packed.text;
 
         // Log the actual prompt being sent to Claude
         tracing::debug!("Piping prompt:\n{}", user_prompt);
@@ -921,13 +990,20 @@ impl PromptAction {
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
 
+        // `--cache` or `--no-cache` override the cache configuration for the run
+        let cache_requested = context
+            .get(AGENT_CACHE_CONTEXT_KEY)
+            .and_then(|v| v.as_bool());
+
         // Execute the rendered prompt using the AgentExecutor trait
 
         // Create execution context
         let execution_context = AgentExecutionContext::new(context);
 
         // Get executor based on configuration
-        let executor = self.get_executor(&execution_context).await?;
+        let (executor, cached) = self
+            .get_executor(&execution_context, cacheable, cache_requested)
+            .await?;
 
         // Execute prompt through trait
         let response = executor
@@ -939,6 +1015,20 @@ impl PromptAction {
             )
             .await?;
 
+        // Count cache lookups so the executor can report them in run metrics
+        if cached {
+            let counter_key = if is_cache_hit(&response) {
+                CACHE_HITS_CONTEXT_KEY
+            } else {
+                CACHE_MISSES_CONTEXT_KEY
+            };
+            let count = context
+                .get(counter_key)
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            context.insert(counter_key.to_string(), Value::from(count + 1));
+        }
+
         // Extract response text for logging
         let response_text = response.content.clone();
 
@@ -985,12 +1075,29 @@ impl PromptAction {
         Ok(response_value)
     }
 
-    /// Get executor based on execution context
+    /// Get executor based on execution context, wrapped in the response cache when enabled
+    ///
+    /// The cache is used when the prompt is `cacheable` and the run's `--cache` or
+    /// `--no-cache` choice, or else the cache configuration, enables it. Also reports
+    /// whether the executor was wrapped, so cache lookups are only counted when the
+    /// cache actually ran.
     async fn get_executor(
         &self,
         context: &AgentExecutionContext<'_>,
-    ) -> ActionResult<Box<dyn AgentExecutor>> {
-        AgentExecutorFactory::create_executor(context).await
+        cacheable: bool,
+        cache_requested: Option<bool>,
+    ) -> ActionResult<(Box<dyn AgentExecutor>, bool)> {
+        let executor = AgentExecutorFactory::create_executor(context).await?;
+
+        if cacheable {
+            return Ok(with_response_cache(
+                executor,
+                AgentResponseCache::default(),
+                cache_requested,
+            ));
+        }
+
+        Ok((executor, false))
     }
 }
 
@@ -1278,6 +1385,64 @@ impl Action for AbortAction {
     impl_as_any!();
 }
 
+/// Action that pauses the run until a human approves or rejects it out of band
+///
+/// The first execution records a pending approval and the executor pauses the
+/// run. After `sah flow approve` resumes the run, the action executes again and
+/// consumes the recorded decision.
+#[derive(Debug, Clone)]
+pub struct ApprovalAction {
+    /// Question shown to the approver
+    pub message: String,
+}
+
+impl ApprovalAction {
+    /// Create a new approval action
+    pub fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl VariableSubstitution for ApprovalAction {}
+
+#[async_trait::async_trait]
+impl Action for ApprovalAction {
+    async fn execute(&self, context: &mut WorkflowTemplateContext) -> ActionResult<Value> {
+        let message = self.substitute_string(&self.message, context);
+
+        let Some(decision) = ApprovalDecision::take_from_context(context) else {
+            tracing::info!("Waiting for approval: {}", message);
+            PendingApproval::new(message).store(context);
+            return Ok(Value::Null);
+        };
+
+        context.remove(PENDING_APPROVAL_KEY);
+        let result = decision.to_result(&message);
+        context.insert(APPROVAL_RESULT_KEY.to_string(), result.clone());
+
+        if decision.approved {
+            tracing::info!("Approved: {}", message);
+            context.insert(LAST_ACTION_RESULT_KEY.to_string(), Value::Bool(true));
+            Ok(result)
+        } else {
+            Err(ActionError::ApprovalRejected(format!(
+                "{message} ({})",
+                decision.reason.as_deref().unwrap_or("no reason given")
+            )))
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("Await approval: {}", self.message)
+    }
+
+    fn action_type(&self) -> &'static str {
+        "approval"
+    }
+
+    impl_as_any!();
+}
+
 /// Validate that an argument key is safe for command-line use
 fn is_valid_argument_key(key: &str) -> bool {
     !key.is_empty()
@@ -2252,6 +2417,10 @@ pub fn parse_action_from_description(description: &str) -> ActionResult<Option<B
         return Ok(Some(Box::new(abort_action)));
     }
 
+    if let Some(approval_action) = parser.parse_approval_action(description)? {
+        return Ok(Some(Box::new(approval_action)));
+    }
+
     if let Some(shell_action) = parser.parse_shell_action(description)? {
         return Ok(Some(Box::new(shell_action)));
     }
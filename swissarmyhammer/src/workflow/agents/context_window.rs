@@ -0,0 +1,1085 @@
+//! Context window aware prompt packing
+//!
+//! Rendered prompts that pull in issue content, memos or code outlines can
+//! exceed the context window of the model that runs them, which is easy to do
+//! with local LlamaAgent models. This module estimates tokens per model family,
+//! reports the estimate of each `{% section %}` block in the rendered prompt,
+//! and applies the overflow strategy declared by the prompt: shrink the lowest
+//! priority sections, summarize them, or fail.
+//!
+//! Token counts are estimates, not the output of the model's tokenizer, and they
+//! err high on purpose. Letters are counted at fewer letters per token than the
+//! family's tokenizer averages, while digits, punctuation, symbols and line
+//! breaks, which make up much of code and which tokenizers split finely, cost a
+//! token each. For prose and source code the estimate should be at or above the
+//! real count, by up to about twice for punctuation heavy code. It undercounts
+//! only letter runs the tokenizer splits into pieces shorter than the family's
+//! letters per token, such as random identifiers or base64, and then by at most
+//! that ratio for those runs. `reserve_tokens` covers what is left.
+//!
+//! A prompt that still does not fit is only refused when the window is known,
+//! from the model metadata or a configured override, and the prompt opted into
+//! packing with `{% section %}` blocks or `context` settings. Otherwise the
+//! overflow is logged and the prompt is sent as is.
+//!
+//! Prompts configure packing in their frontmatter:
+//!
+//! ```yaml
+//! context:
+//!   overflow: summarize   # truncate (default), summarize or fail
+//!   reserve_tokens: 4096  # tokens kept free for the response
+//!   window: 32768         # override the model context window
+//! ```
+
+use crate::template::{SECTION_END_MARKER, SECTION_START_MARKER_PREFIX};
+use crate::workflow::agents::model_catalog::ModelCatalog;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use swissarmyhammer_config::agent::{AgentConfig, AgentExecutorConfig};
+use swissarmyhammer_config::ModelSource;
+
+/// Context key holding the token usage of each prompt executed in a run
+pub const PROMPT_TOKEN_USAGE_CONTEXT_KEY: &str = "_prompt_token_usage";
+
+/// Tokens kept free for the model response when a prompt does not say otherwise
+pub const DEFAULT_RESERVE_TOKENS: usize = 4096;
+
+/// Model families with distinct token estimates and default context windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelFamily {
+    /// Anthropic Claude models
+    Claude,
+    /// Meta Llama models
+    Llama,
+    /// Alibaba Qwen models
+    Qwen,
+    /// Mistral and Mixtral models
+    Mistral,
+    /// Microsoft Phi models
+    Phi,
+    /// Google Gemma models
+    Gemma,
+    /// Any other model
+    Generic,
+}
+
+impl ModelFamily {
+    /// Detect the family from a model name, repository or GGUF architecture
+    pub fn from_model_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        if name.contains("claude") {
+            ModelFamily::Claude
+        } else if name.contains("qwen") {
+            ModelFamily::Qwen
+        } else if name.contains("llama") {
+            ModelFamily::Llama
+        } else if name.contains("mistral") || name.contains("mixtral") {
+            ModelFamily::Mistral
+        } else if name.contains("phi") {
+            ModelFamily::Phi
+        } else if name.contains("gemma") {
+            ModelFamily::Gemma
+        } else {
+            ModelFamily::Generic
+        }
+    }
+
+    /// Letters counted per token, below what the family's tokenizer averages for
+    /// English prose and code, so estimates err high
+    pub fn letters_per_token(&self) -> f64 {
+        match self {
+            ModelFamily::Claude => 3.0,
+            ModelFamily::Llama => 3.2,
+            ModelFamily::Qwen => 3.2,
+            ModelFamily::Mistral => 3.0,
+            ModelFamily::Phi => 3.2,
+            ModelFamily::Gemma => 3.5,
+            ModelFamily::Generic => 3.0,
+        }
+    }
+
+    /// Context window assumed when the model does not report one
+    pub fn default_context_window(&self) -> usize {
+        match self {
+            ModelFamily::Claude => 200_000,
+            ModelFamily::Qwen | ModelFamily::Mistral => 32_768,
+            ModelFamily::Phi => 16_384,
+            ModelFamily::Llama | ModelFamily::Gemma | ModelFamily::Generic => 8_192,
+        }
+    }
+
+    /// Estimator used to approximate token counts for this family
+    pub fn token_estimator(&self) -> Box<dyn TokenEstimator> {
+        Box::new(HeuristicTokenEstimator::new(self.letters_per_token()))
+    }
+}
+
+/// Estimates and truncates text in model tokens
+pub trait TokenEstimator: Send + Sync {
+    /// Estimated number of tokens in `text`
+    fn estimate_tokens(&self, text: &str) -> usize;
+
+    /// The longest prefix of `text` estimated to fit in `max_tokens`
+    fn truncate_to_tokens<'a>(&self, text: &'a str, max_tokens: usize) -> &'a str;
+}
+
+/// Token estimate that errs high
+///
+/// Each run of ASCII letters costs one token per `letters_per_token` letters,
+/// rounded up. Digits, punctuation and symbols cost a token each, and any other
+/// character one token per UTF-8 byte, the most a byte level tokenizer spends.
+/// A single space between words is free, every line break costs a token and
+/// further whitespace, such as indentation, one token per four characters.
+/// This needs no vocabulary files; see the module documentation for its bounds.
+#[derive(Debug, Clone)]
+pub struct HeuristicTokenEstimator {
+    letters_per_token: f64,
+}
+
+impl HeuristicTokenEstimator {
+    /// Create an estimator counting `letters_per_token` letters per token
+    pub fn new(letters_per_token: f64) -> Self {
+        Self {
+            letters_per_token: letters_per_token.max(1.0),
+        }
+    }
+
+    fn letter_tokens(&self, letters: usize) -> usize {
+        (letters as f64 / self.letters_per_token).ceil() as usize
+    }
+
+    fn word_tokens(&self, word: &str) -> usize {
+        let mut tokens = 0;
+        let mut letters = 0;
+        for c in word.chars() {
+            if c.is_ascii_alphabetic() {
+                letters += 1;
+                continue;
+            }
+            tokens += self.letter_tokens(letters);
+            letters = 0;
+            tokens += c.len_utf8();
+        }
+        tokens + self.letter_tokens(letters)
+    }
+
+    fn whitespace_tokens(whitespace: &str) -> usize {
+        let line_breaks = whitespace.matches('\n').count();
+        let other = whitespace.chars().count() - line_breaks;
+        let free = usize::from(line_breaks == 0);
+        line_breaks + (other.saturating_sub(free) + 3) / 4
+    }
+}
+
+impl TokenEstimator for HeuristicTokenEstimator {
+    fn estimate_tokens(&self, text: &str) -> usize {
+        text_runs(text)
+            .map(|(_, run, is_word)| {
+                if is_word {
+                    self.word_tokens(run)
+                } else {
+                    Self::whitespace_tokens(run)
+                }
+            })
+            .sum()
+    }
+
+    fn truncate_to_tokens<'a>(&self, text: &'a str, max_tokens: usize) -> &'a str {
+        let mut used = 0;
+        let mut end = 0;
+        for (start, run, is_word) in text_runs(text) {
+            let tokens = if is_word {
+                self.word_tokens(run)
+            } else {
+                Self::whitespace_tokens(run)
+            };
+            if used + tokens > max_tokens {
+                break;
+            }
+            used += tokens;
+            if is_word {
+                end = start + run.len();
+            }
+        }
+        &text[..end]
+    }
+}
+
+/// Byte offset and text of every run of words or whitespace, and whether it is a word
+fn text_runs(text: &str) -> impl Iterator<Item = (usize, &str, bool)> {
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        let rest = &text[start..];
+        let first = rest.chars().next()?;
+        let is_word = !first.is_whitespace();
+        let len = rest
+            .find(|c: char| c.is_whitespace() == is_word)
+            .unwrap_or(rest.len());
+        let run = (start, &rest[..len], is_word);
+        start += len;
+        Some(run)
+    })
+}
+
+/// What to do when a rendered prompt does not fit the context window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverflowStrategy {
+    /// Truncate or drop the lowest priority sections
+    #[default]
+    Truncate,
+    /// Summarize the lowest priority sections, then truncate if still too large
+    Summarize,
+    /// Fail with an error describing the section sizes
+    Fail,
+}
+
+impl FromStr for OverflowStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "truncate" => Ok(OverflowStrategy::Truncate),
+            "summarize" | "summarise" => Ok(OverflowStrategy::Summarize),
+            "fail" => Ok(OverflowStrategy::Fail),
+            other => Err(format!(
+                "Unknown overflow strategy '{other}', expected truncate, summarize or fail"
+            )),
+        }
+    }
+}
+
+/// Token budget for a single prompt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextBudget {
+    /// Model context window in tokens
+    pub context_window: usize,
+    /// The window is a family default rather than reported by the model or configured
+    #[serde(default)]
+    pub window_estimated: bool,
+    /// Tokens kept free for the response
+    pub reserve_tokens: usize,
+    /// Strategy applied when the prompt does not fit
+    pub strategy: OverflowStrategy,
+}
+
+impl ContextBudget {
+    /// Build a budget from a model profile and the prompt's `context` frontmatter
+    ///
+    /// Frontmatter takes precedence over the `SWISSARMYHAMMER_CONTEXT_WINDOW`,
+    /// `SWISSARMYHAMMER_CONTEXT_RESERVE` and `SWISSARMYHAMMER_CONTEXT_OVERFLOW`
+    /// environment variables, which take precedence over the model defaults.
+    pub fn from_settings(profile: &ModelProfile, settings: Option<&serde_json::Value>) -> Self {
+        let setting = |key: &str| settings.and_then(|s| s.get(key));
+        let env = |key: &str| std::env::var(key).ok();
+
+        let configured_window = setting("window")
+            .and_then(serde_json::Value::as_u64)
+            .map(|v| v as usize)
+            .or_else(|| env("SWISSARMYHAMMER_CONTEXT_WINDOW").and_then(|v| v.parse().ok()));
+        let window_estimated = configured_window.is_none() && profile.window_estimated;
+        let context_window = configured_window.unwrap_or(profile.context_window);
+        let reserve_tokens = setting("reserve_tokens")
+            .and_then(serde_json::Value::as_u64)
+            .map(|v| v as usize)
+            .or_else(|| env("SWISSARMYHAMMER_CONTEXT_RESERVE").and_then(|v| v.parse().ok()))
+            .unwrap_or(DEFAULT_RESERVE_TOKENS);
+        let strategy = setting("overflow")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+            .or_else(|| env("SWISSARMYHAMMER_CONTEXT_OVERFLOW"))
+            .and_then(|v| match v.parse() {
+                Ok(strategy) => Some(strategy),
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            context_window,
+            window_estimated,
+            reserve_tokens,
+            strategy,
+        }
+    }
+
+    /// Tokens available to the system and user prompts
+    pub fn available_tokens(&self) -> usize {
+        self.context_window.saturating_sub(self.reserve_tokens)
+    }
+}
+
+/// How a section was changed to fit the context window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionAction {
+    /// Sent unchanged
+    Kept,
+    /// Replaced by an extractive summary
+    Summarized,
+    /// Shortened from the end
+    Truncated,
+    /// Removed entirely
+    Dropped,
+}
+
+/// Estimated token count of one `{% section %}` in a rendered prompt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionTokenCount {
+    /// Section name
+    pub name: String,
+    /// Section priority; lower priorities are shrunk first
+    pub priority: i64,
+    /// Tokens in the rendered section
+    pub original_tokens: usize,
+    /// Tokens sent to the model
+    pub tokens: usize,
+    /// How the section was changed
+    pub action: SectionAction,
+}
+
+/// Estimated token usage of a prompt, recorded in run metrics
+///
+/// All counts come from the model family's [`TokenEstimator`], not the model's tokenizer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptTokenUsage {
+    /// Name of the prompt
+    pub prompt: String,
+    /// Model family whose characters per token ratio estimated the tokens
+    pub model_family: ModelFamily,
+    /// Budget the prompt was packed into
+    pub budget: ContextBudget,
+    /// Estimated tokens in the system prompt
+    pub system_tokens: usize,
+    /// Estimated tokens in the rendered user prompt before packing
+    pub original_tokens: usize,
+    /// Estimated tokens in the system and user prompts sent to the model
+    pub total_tokens: usize,
+    /// Per-section token estimates
+    pub sections: Vec<SectionTokenCount>,
+}
+
+/// A user prompt packed into the context window
+#[derive(Debug, Clone)]
+pub struct PackedPrompt {
+    /// Prompt text to send, with section markers removed
+    pub text: String,
+    /// Token accounting for the prompt
+    pub usage: PromptTokenUsage,
+}
+
+/// Error raised when a prompt cannot be made to fit the context window
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(
+    "Prompt '{prompt}' needs {required_tokens} tokens but only {available_tokens} fit in the \
+     {context_window} token context window ({reserve_tokens} reserved for the response). {detail}"
+)]
+pub struct ContextOverflowError {
+    /// Name of the prompt
+    pub prompt: String,
+    /// Tokens the prompt needs after packing
+    pub required_tokens: usize,
+    /// Tokens available for prompts
+    pub available_tokens: usize,
+    /// Model context window
+    pub context_window: usize,
+    /// Tokens reserved for the response
+    pub reserve_tokens: usize,
+    /// Section breakdown and remediation hint
+    pub detail: String,
+    /// Token accounting at the point of failure
+    pub usage: PromptTokenUsage,
+}
+
+/// A run of prompt text attributed to its innermost section
+#[derive(Debug, Clone)]
+struct Segment {
+    section: Option<usize>,
+    text: String,
+}
+
+#[derive(Debug, Clone)]
+struct Section {
+    name: String,
+    priority: i64,
+    original_tokens: usize,
+    action: SectionAction,
+}
+
+/// Packs rendered prompts into a model context window
+pub struct PromptPacker {
+    family: ModelFamily,
+    estimator: Box<dyn TokenEstimator>,
+    budget: ContextBudget,
+    context_declared: bool,
+}
+
+impl PromptPacker {
+    /// Create a packer for a model family and budget
+    pub fn new(family: ModelFamily, budget: ContextBudget) -> Self {
+        Self {
+            family,
+            estimator: family.token_estimator(),
+            budget,
+            context_declared: true,
+        }
+    }
+
+    /// Create a packer for the configured agent and the prompt's `context` settings
+    pub fn for_agent(agent_config: &AgentConfig, settings: Option<&serde_json::Value>) -> Self {
+        let profile = model_profile(agent_config);
+        Self::new(
+            profile.family,
+            ContextBudget::from_settings(&profile, settings),
+        )
+        .with_context_declared(settings.is_some())
+    }
+
+    /// Whether the prompt declared `context` settings
+    ///
+    /// Prompts without settings or sections are sent even when they overflow.
+    pub fn with_context_declared(mut self, declared: bool) -> Self {
+        self.context_declared = declared;
+        self
+    }
+
+    /// The budget prompts are packed into
+    pub fn budget(&self) -> &ContextBudget {
+        &self.budget
+    }
+
+    /// Estimate tokens with this packer's estimator
+    pub fn estimate_tokens(&self, text: &str) -> usize {
+        self.estimator.estimate_tokens(text)
+    }
+
+    /// Fit a rendered user prompt, with section markers, into the budget
+    pub fn pack(
+        &self,
+        prompt_name: &str,
+        system_prompt: Option<&str>,
+        rendered: &str,
+    ) -> Result<PackedPrompt, ContextOverflowError> {
+        let system_tokens = system_prompt
+            .map(|s| self.estimate_tokens(&strip_section_markers(s)))
+            .unwrap_or(0);
+        let (mut segments, mut sections) = parse_sections(rendered);
+        for (index, section) in sections.iter_mut().enumerate() {
+            section.original_tokens = self.section_tokens(&segments, index);
+        }
+        let original_tokens = self.user_tokens(&segments);
+        let available = self.budget.available_tokens();
+
+        let fits = |segments: &[Segment]| system_tokens + self.user_tokens(segments) <= available;
+
+        if !fits(&segments) && self.budget.strategy != OverflowStrategy::Fail {
+            // Lowest priority first; among equals, the largest section first
+            let mut order: Vec<usize> = (0..sections.len()).collect();
+            order.sort_by_key(|&i| {
+                (
+                    sections[i].priority,
+                    std::cmp::Reverse(sections[i].original_tokens),
+                )
+            });
+
+            if self.budget.strategy == OverflowStrategy::Summarize {
+                for &index in &order {
+                    if fits(&segments) {
+                        break;
+                    }
+                    if self.summarize_section(&mut segments, index) {
+                        sections[index].action = SectionAction::Summarized;
+                    }
+                }
+            }
+
+            for &index in &order {
+                let total = system_tokens + self.user_tokens(&segments);
+                if total <= available {
+                    break;
+                }
+                let excess = total - available;
+                let current = self.section_tokens(&segments, index);
+                if current == 0 {
+                    continue;
+                }
+                let name = &sections[index].name;
+                let truncated_note =
+                    format!("\n[... section '{name}' truncated to fit the context window]\n");
+                let note_tokens = self.estimate_tokens(&truncated_note);
+                if excess + note_tokens >= current {
+                    replace_section(
+                        &mut segments,
+                        index,
+                        &format!("\n[section '{name}' omitted to fit the context window]\n"),
+                    );
+                    sections[index].action = SectionAction::Dropped;
+                } else {
+                    let target = current - excess - note_tokens;
+                    self.truncate_section(&mut segments, index, target, &truncated_note);
+                    sections[index].action = SectionAction::Truncated;
+                }
+            }
+        }
+
+        let total_tokens = system_tokens + self.user_tokens(&segments);
+        let usage = PromptTokenUsage {
+            prompt: prompt_name.to_string(),
+            model_family: self.family,
+            budget: self.budget.clone(),
+            system_tokens,
+            original_tokens,
+            total_tokens,
+            sections: sections
+                .iter()
+                .enumerate()
+                .map(|(index, section)| SectionTokenCount {
+                    name: section.name.clone(),
+                    priority: section.priority,
+                    original_tokens: section.original_tokens,
+                    tokens: self.section_tokens(&segments, index),
+                    action: section.action,
+                })
+                .collect(),
+        };
+
+        if total_tokens > available {
+            let error = self.overflow_error(usage);
+            // Only refuse prompts when the window is known and the prompt opted into packing
+            if self.budget.window_estimated || (!self.context_declared && sections.is_empty()) {
+                let reason = if self.budget.window_estimated {
+                    "the context window is an estimate for the model family"
+                } else {
+                    "the prompt declares no sections or context settings"
+                };
+                tracing::warn!("{} Sending it anyway because {}.", error, reason);
+                return Ok(PackedPrompt {
+                    text: segments.into_iter().map(|s| s.text).collect(),
+                    usage: error.usage,
+                });
+            }
+            return Err(error);
+        }
+
+        Ok(PackedPrompt {
+            text: segments.into_iter().map(|s| s.text).collect(),
+            usage,
+        })
+    }
+
+    fn overflow_error(&self, usage: PromptTokenUsage) -> ContextOverflowError {
+        let mut sections: Vec<&SectionTokenCount> = usage.sections.iter().collect();
+        sections.sort_by_key(|s| std::cmp::Reverse(s.original_tokens));
+        let breakdown: Vec<String> = sections
+            .iter()
+            .map(|s| {
+                format!(
+                    "{} (priority {}): {} tokens",
+                    s.name, s.priority, s.original_tokens
+                )
+            })
+            .collect();
+
+        let detail = if breakdown.is_empty() {
+            "The prompt has no {% section %} blocks that could be shrunk; wrap large \
+             content in sections or use a model with a larger context window."
+                .to_string()
+        } else if self.budget.strategy == OverflowStrategy::Fail {
+            format!(
+                "Sections: {}. Set `context.overflow` to truncate or summarize to shrink them.",
+                breakdown.join(", ")
+            )
+        } else {
+            format!(
+                "Sections: {}. Content outside sections is too large to fit.",
+                breakdown.join(", ")
+            )
+        };
+
+        ContextOverflowError {
+            prompt: usage.prompt.clone(),
+            required_tokens: usage.total_tokens,
+            available_tokens: self.budget.available_tokens(),
+            context_window: self.budget.context_window,
+            reserve_tokens: self.budget.reserve_tokens,
+            detail,
+            usage,
+        }
+    }
+
+    fn user_tokens(&self, segments: &[Segment]) -> usize {
+        segments.iter().map(|s| self.estimate_tokens(&s.text)).sum()
+    }
+
+    fn section_tokens(&self, segments: &[Segment], index: usize) -> usize {
+        segments
+            .iter()
+            .filter(|s| s.section == Some(index))
+            .map(|s| self.estimate_tokens(&s.text))
+            .sum()
+    }
+
+    /// Replace each segment of a section with its summary; true when it got smaller
+    fn summarize_section(&self, segments: &mut [Segment], index: usize) -> bool {
+        let before = self.section_tokens(segments, index);
+        let summaries: Vec<(usize, String)> = segments
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.section == Some(index))
+            .map(|(i, s)| (i, summarize_markdown(&s.text)))
+            .collect();
+        let after: usize = summaries.iter().map(|(_, s)| self.estimate_tokens(s)).sum();
+        if after >= before {
+            return false;
+        }
+        for (i, summary) in summaries {
+            segments[i].text = summary;
+        }
+        true
+    }
+
+    /// Keep the first `target` tokens of a section and append the truncation note
+    fn truncate_section(&self, segments: &mut [Segment], index: usize, target: usize, note: &str) {
+        let mut remaining = target;
+        let mut last_kept = None;
+        for (i, segment) in segments.iter_mut().enumerate() {
+            if segment.section != Some(index) {
+                continue;
+            }
+            let tokens = self.estimator.estimate_tokens(&segment.text);
+            if tokens <= remaining {
+                remaining -= tokens;
+            } else {
+                segment.text = self
+                    .estimator
+                    .truncate_to_tokens(&segment.text, remaining)
+                    .to_string();
+                remaining = 0;
+            }
+            last_kept = Some(i);
+        }
+        if let Some(i) = last_kept {
+            segments[i].text.push_str(note);
+        }
+    }
+}
+
+fn replace_section(segments: &mut [Segment], index: usize, replacement: &str) {
+    let mut replaced = false;
+    for segment in segments.iter_mut().filter(|s| s.section == Some(index)) {
+        if replaced {
+            segment.text.clear();
+        } else {
+            segment.text = replacement.to_string();
+            replaced = true;
+        }
+    }
+}
+
+/// Split rendered text at section markers
+///
+/// Text is attributed to the innermost enclosing section. Repeated sections
+/// with the same name, e.g. from a loop, are accounted as one section.
+fn parse_sections(rendered: &str) -> (Vec<Segment>, Vec<Section>) {
+    let mut segments = Vec::new();
+    let mut sections: Vec<Section> = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+    let mut rest = rendered;
+
+    let push = |segments: &mut Vec<Segment>, section: Option<usize>, text: &str| {
+        if !text.is_empty() {
+            segments.push(Segment {
+                section,
+                text: text.to_string(),
+            });
+        }
+    };
+
+    loop {
+        let start = rest.find(SECTION_START_MARKER_PREFIX);
+        let end = rest.find(SECTION_END_MARKER);
+        match (start, end) {
+            (Some(s), e) if e.map_or(true, |e| s < e) => {
+                let header_start = s + SECTION_START_MARKER_PREFIX.len();
+                let Some(header_len) = rest[header_start..].find("-->") else {
+                    break;
+                };
+                push(&mut segments, stack.last().copied(), &rest[..s]);
+
+                let header = rest[header_start..header_start + header_len].trim();
+                let mut parts = header.split_whitespace();
+                let name = parts.next().unwrap_or("section").to_string();
+                let priority = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+
+                let index = match sections.iter().position(|sec| sec.name == name) {
+                    Some(index) => index,
+                    None => {
+                        sections.push(Section {
+                            name,
+                            priority,
+                            original_tokens: 0,
+                            action: SectionAction::Kept,
+                        });
+                        sections.len() - 1
+                    }
+                };
+                stack.push(index);
+                rest = &rest[header_start + header_len + "-->".len()..];
+            }
+            (_, Some(e)) => {
+                push(&mut segments, stack.last().copied(), &rest[..e]);
+                stack.pop();
+                rest = &rest[e + SECTION_END_MARKER.len()..];
+            }
+            _ => break,
+        }
+    }
+    push(&mut segments, stack.last().copied(), rest);
+
+    (segments, sections)
+}
+
+/// Remove section markers from rendered text
+pub fn strip_section_markers(rendered: &str) -> String {
+    parse_sections(rendered)
+        .0
+        .into_iter()
+        .map(|s| s.text)
+        .collect()
+}
+
+/// Extractive summary of markdown: headings and the first line of each paragraph
+///
+/// Fenced code blocks are replaced by a placeholder. The summary is deterministic
+/// so summarized prompts still hit the agent response cache.
+pub fn summarize_markdown(text: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    let mut paragraph_start = true;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            if !in_code {
+                lines.push("[code omitted]");
+            }
+            in_code = !in_code;
+            paragraph_start = true;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        if trimmed.is_empty() {
+            paragraph_start = true;
+        } else if trimmed.starts_with('#') {
+            lines.push(line);
+            paragraph_start = true;
+        } else if paragraph_start {
+            lines.push(line);
+            paragraph_start = false;
+        }
+    }
+
+    let mut summary = lines.join("\n");
+    if text.starts_with('\n') {
+        summary.insert(0, '\n');
+    }
+    if text.ends_with('\n') {
+        summary.push('\n');
+    }
+    summary
+}
+
+/// Model family and context window of the configured agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelProfile {
+    /// Model family
+    pub family: ModelFamily,
+    /// Context window in tokens
+    pub context_window: usize,
+    /// The window is the family default because the model did not report one
+    pub window_estimated: bool,
+}
+
+/// Model family and context window for the configured agent
+///
+/// For LlamaAgent the window comes from the GGUF header of the configured model
+/// when it has been downloaded, and is estimated from the family before that.
+pub fn model_profile(agent_config: &AgentConfig) -> ModelProfile {
+    match &agent_config.executor {
+        AgentExecutorConfig::ClaudeCode(_) => ModelProfile {
+            family: ModelFamily::Claude,
+            context_window: ModelFamily::Claude.default_context_window(),
+            window_estimated: false,
+        },
+        AgentExecutorConfig::LlamaAgent(config) => {
+            let source = &config.model.source;
+            let source_name = match source {
+                ModelSource::HuggingFace { repo, filename, .. } => {
+                    format!("{}/{}", repo, filename.as_deref().unwrap_or_default())
+                }
+                ModelSource::Local { filename, .. } => filename.display().to_string(),
+            };
+            let info = ModelCatalog::new()
+                .with_model_source(source)
+                .resolve(source)
+                .and_then(|model| model.info);
+
+            let family = info
+                .as_ref()
+                .and_then(|info| info.architecture.as_deref())
+                .map(ModelFamily::from_model_name)
+                .filter(|family| *family != ModelFamily::Generic)
+                .unwrap_or_else(|| ModelFamily::from_model_name(&source_name));
+            let window = info
+                .and_then(|info| info.context_length)
+                .map(|len| len as usize);
+            ModelProfile {
+                family,
+                context_window: window.unwrap_or_else(|| family.default_context_window()),
+                window_estimated: window.is_none(),
+            }
+        }
+    }
+}
+
+/// Append a prompt's token usage to the run context for the executor to record
+pub fn record_prompt_token_usage(
+    context: &mut crate::workflow::template_context::WorkflowTemplateContext,
+    usage: &PromptTokenUsage,
+) {
+    let mut entries = context
+        .get(PROMPT_TOKEN_USAGE_CONTEXT_KEY)
+        .and_then(|v| v.as_array().cloned())
+        .unwrap_or_default();
+    if let Ok(value) = serde_json::to_value(usage) {
+        entries.push(value);
+    }
+    context.insert(
+        PROMPT_TOKEN_USAGE_CONTEXT_KEY.to_string(),
+        serde_json::Value::Array(entries),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(name: &str, priority: i64, body: &str) -> String {
+        format!("{SECTION_START_MARKER_PREFIX}{name} {priority} -->{body}{SECTION_END_MARKER}")
+    }
+
+    /// `count` words of one token each in every family's estimate
+    fn words(count: usize) -> String {
+        vec!["cat"; count].join(" ")
+    }
+
+    fn budget(window: usize, strategy: OverflowStrategy) -> ContextBudget {
+        ContextBudget {
+            context_window: window,
+            window_estimated: false,
+            reserve_tokens: 0,
+            strategy,
+        }
+    }
+
+    #[test]
+    fn test_model_family_detection() {
+        assert_eq!(ModelFamily::from_model_name("claude"), ModelFamily::Claude);
+        assert_eq!(
+            ModelFamily::from_model_name("unsloth/Qwen3-Coder-30B-A3B-Instruct-GGUF"),
+            ModelFamily::Qwen
+        );
+        assert_eq!(ModelFamily::from_model_name("qwen3moe"), ModelFamily::Qwen);
+        assert_eq!(
+            ModelFamily::from_model_name("Phi-4-mini-instruct-Q4_K_M.gguf"),
+            ModelFamily::Phi
+        );
+        assert_eq!(ModelFamily::from_model_name("gpt2"), ModelFamily::Generic);
+    }
+
+    #[test]
+    fn test_heuristic_estimator_counts_and_truncates() {
+        let estimator = HeuristicTokenEstimator::new(4.0);
+        assert_eq!(estimator.estimate_tokens(""), 0);
+        assert_eq!(estimator.estimate_tokens("a bb ccc"), 3);
+        assert_eq!(estimator.estimate_tokens("abcdefghij"), 3);
+
+        let text = "one two three four";
+        assert_eq!(estimator.truncate_to_tokens(text, 2), "one two");
+        assert_eq!(estimator.truncate_to_tokens(text, 0), "");
+        assert_eq!(estimator.truncate_to_tokens(text, 10), text);
+    }
+
+    #[test]
+    fn test_heuristic_estimator_errs_high_on_code() {
+        let estimator = ModelFamily::Claude.token_estimator();
+
+        // Punctuation and digits cost a token each, letter runs one per 3 letters
+        assert_eq!(estimator.estimate_tokens("let x = foo(bar, 42);"), 11);
+        assert_eq!(estimator.estimate_tokens("get_or_try_init"), 8);
+        // Line breaks and indentation are counted, a single space is not
+        assert_eq!(estimator.estimate_tokens("if a {\n        b\n}"), 9);
+        // Other characters cost up to a token per byte
+        assert_eq!(estimator.estimate_tokens("é 🚀"), 6);
+
+        let code = "fn main() {\n    println!(\"{}\", 1 + 2);\n}\n";
+        assert!(estimator.estimate_tokens(code) <= code.len());
+        assert_eq!(estimator.truncate_to_tokens(code, 6), "fn main() {");
+    }
+
+    #[test]
+    fn test_fitting_prompt_is_unchanged_apart_from_markers() {
+        let rendered = format!("Intro\n{}\nOutro", section("memos", 1, "memo text"));
+        let packer =
+            PromptPacker::new(ModelFamily::Gemma, budget(1000, OverflowStrategy::Truncate));
+
+        let packed = packer.pack("test", None, &rendered).unwrap();
+        assert_eq!(packed.text, "Intro\nmemo text\nOutro");
+        assert_eq!(packed.usage.sections.len(), 1);
+        assert_eq!(packed.usage.sections[0].name, "memos");
+        assert_eq!(packed.usage.sections[0].tokens, 4);
+        assert_eq!(packed.usage.sections[0].action, SectionAction::Kept);
+        assert_eq!(packed.usage.total_tokens, packed.usage.original_tokens);
+    }
+
+    #[test]
+    fn test_lowest_priority_section_is_shrunk_first() {
+        let rendered = format!(
+            "Task\n{}\n{}",
+            section("issue", 10, &words(40)),
+            section("memos", 1, &words(200)),
+        );
+        let packer = PromptPacker::new(ModelFamily::Gemma, budget(120, OverflowStrategy::Truncate));
+
+        let packed = packer.pack("test", None, &rendered).unwrap();
+        assert!(packed.usage.total_tokens <= 120);
+
+        let issue = &packed.usage.sections[0];
+        assert_eq!(issue.action, SectionAction::Kept);
+        assert_eq!(issue.tokens, 40);
+
+        let memos = &packed.usage.sections[1];
+        assert_eq!(memos.action, SectionAction::Truncated);
+        assert!(memos.tokens < memos.original_tokens);
+        assert!(packed.text.contains("section 'memos' truncated"));
+        assert!(!packed.text.contains(SECTION_START_MARKER_PREFIX));
+    }
+
+    #[test]
+    fn test_section_is_dropped_when_truncation_is_not_enough() {
+        let rendered = format!(
+            "{}{}",
+            section("outline", 0, &words(30)),
+            section("issue", 5, &words(100)),
+        );
+        let packer = PromptPacker::new(ModelFamily::Gemma, budget(80, OverflowStrategy::Truncate));
+
+        let packed = packer.pack("test", None, &rendered).unwrap();
+        assert_eq!(packed.usage.sections[0].action, SectionAction::Dropped);
+        assert_eq!(packed.usage.sections[1].action, SectionAction::Truncated);
+        assert!(packed.text.contains("section 'outline' omitted"));
+    }
+
+    #[test]
+    fn test_summarize_strategy_summarizes_before_truncating() {
+        let memo = format!(
+            "# Memo\n{}\n{}\n\n```\n{}\n```\n",
+            words(5),
+            words(60),
+            words(60)
+        );
+        let rendered = format!("Task\n{}", section("memos", 0, &memo));
+        let packer = PromptPacker::new(ModelFamily::Gemma, budget(40, OverflowStrategy::Summarize));
+
+        let packed = packer.pack("test", None, &rendered).unwrap();
+        assert_eq!(packed.usage.sections[0].action, SectionAction::Summarized);
+        assert!(packed.text.contains("# Memo"));
+        assert!(packed.text.contains("[code omitted]"));
+    }
+
+    #[test]
+    fn test_fail_strategy_reports_sections() {
+        let rendered = section("memos", 0, &words(100));
+        let packer = PromptPacker::new(ModelFamily::Gemma, budget(50, OverflowStrategy::Fail));
+
+        let err = packer.pack("code", None, &rendered).unwrap_err();
+        assert_eq!(err.required_tokens, 100);
+        let message = err.to_string();
+        assert!(message.contains("Prompt 'code' needs 100 tokens"));
+        assert!(message.contains("memos (priority 0): 100 tokens"));
+    }
+
+    #[test]
+    fn test_content_outside_sections_can_still_overflow() {
+        let packer = PromptPacker::new(ModelFamily::Gemma, budget(10, OverflowStrategy::Truncate));
+        let err = packer
+            .pack("test", Some("system words"), &words(20))
+            .unwrap_err();
+        assert_eq!(err.usage.system_tokens, 4);
+        assert!(err.to_string().contains("no {% section %} blocks"));
+    }
+
+    #[test]
+    fn test_repeated_and_nested_sections() {
+        let rendered = format!(
+            "{}{}{}",
+            section("memo", 1, "a b"),
+            section("memo", 1, "c"),
+            section("outer", 2, &format!("x {} y", section("inner", 0, "z"))),
+        );
+        let (segments, sections) = parse_sections(&rendered);
+        let names: Vec<&str> = sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["memo", "outer", "inner"]);
+        assert_eq!(strip_section_markers(&rendered), "a bcx z y");
+        assert_eq!(segments.iter().filter(|s| s.section == Some(0)).count(), 2);
+    }
+
+    #[test]
+    fn test_budget_settings_from_frontmatter() {
+        let settings = serde_json::json!({
+            "overflow": "summarize",
+            "reserve_tokens": 100,
+            "window": 2048,
+        });
+        let profile = ModelProfile {
+            family: ModelFamily::Llama,
+            context_window: 8192,
+            window_estimated: true,
+        };
+        let budget = ContextBudget::from_settings(&profile, Some(&settings));
+        assert_eq!(budget.context_window, 2048);
+        assert!(!budget.window_estimated);
+        assert_eq!(budget.reserve_tokens, 100);
+        assert_eq!(budget.strategy, OverflowStrategy::Summarize);
+        assert_eq!(budget.available_tokens(), 1948);
+    }
+
+    #[test]
+    fn test_overflow_is_only_refused_for_known_windows_and_declared_packing() {
+        let prompt = words(20);
+
+        // No sections and no context settings: sent as is
+        let packer = PromptPacker::new(ModelFamily::Gemma, budget(10, OverflowStrategy::Fail))
+            .with_context_declared(false);
+        let packed = packer.pack("test", None, &prompt).unwrap();
+        assert_eq!(packed.text, prompt);
+        assert_eq!(packed.usage.total_tokens, 20);
+
+        // Estimated window: sent after packing as far as possible
+        let mut estimated = budget(10, OverflowStrategy::Fail);
+        estimated.window_estimated = true;
+        let rendered = section("memos", 0, &prompt);
+        let packer = PromptPacker::new(ModelFamily::Gemma, estimated);
+        assert!(packer.pack("test", None, &rendered).is_ok());
+
+        // Known window with sections: refused
+        let packer = PromptPacker::new(ModelFamily::Gemma, budget(10, OverflowStrategy::Fail))
+            .with_context_declared(false);
+        assert!(packer.pack("test", None, &rendered).is_err());
+    }
+}
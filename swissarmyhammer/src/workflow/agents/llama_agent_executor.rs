@@ -321,7 +321,8 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
                 "type": "object",
                 "properties": {
                     "content": {"type": "string", "description": "Markdown content of the issue"},
-                    "name": {"type": "string", "description": "Name of the issue (optional for nameless issues)"}
+                    "name": {"type": "string", "description": "Name of the issue (optional for nameless issues)"},
+                    "parent": {"type": "string", "description": "Name of the epic the issue belongs to"}
                 },
                 "required": ["content"]
             }
@@ -334,7 +335,8 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
                 "properties": {
                     "show_completed": {"type": "boolean", "description": "Include completed issues in the list (default: false)"},
                     "show_active": {"type": "boolean", "description": "Include active issues in the list (default: true)"},
-                    "format": {"type": "string", "description": "Output format - table, json, or markdown (default: table)"}
+                    "format": {"type": "string", "description": "Output format - table, tree, json, or markdown (default: table)"},
+                    "epic": {"type": "string", "description": "Only list this epic and its sub-issues"}
                 },
                 "required": []
             }
@@ -346,7 +348,10 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
                 "type": "object",
                 "properties": {
                     "name": {"type": "string", "description": "Name of the issue to show. Use 'current' to show the issue for the current git branch. Use 'next' to show the next pending issue."},
-                    "raw": {"type": "boolean", "description": "Show raw content only without formatting (default: false)"}
+                    "raw": {"type": "boolean", "description": "Show raw content only without formatting (default: false)"},
+                    "activity_kind": {"type": "string", "description": "Only show activity entries of this kind (comment, status, branch, merge, archive)"},
+                    "activity_limit": {"type": "integer", "description": "Only show this many of the latest activity entries"},
+                    "activity_only": {"type": "boolean", "description": "Show the activity log without the issue details (default: false)"}
                 },
                 "required": ["name"]
             }
@@ -357,7 +362,8 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
             "inputSchema": {
                 "type": "object",
                 "properties": {
-                    "name": {"type": "string", "description": "Issue name to work on"}
+                    "name": {"type": "string", "description": "Issue name to work on"},
+                    "owner": {"type": "string", "description": "Who claims the issue (default: SAH_AGENT_ID, or host and process)"}
                 },
                 "required": ["name"]
             }
@@ -368,7 +374,8 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
             "inputSchema": {
                 "type": "object",
                 "properties": {
-                    "name": {"type": "string", "description": "Issue name to mark as complete. Use 'current' to mark the current issue complete."}
+                    "name": {"type": "string", "description": "Issue name to mark as complete. Use 'current' to mark the current issue complete."},
+                    "force": {"type": "boolean", "description": "Complete even when acceptance criteria or sub-issues are still open (default: false)"}
                 },
                 "required": ["name"]
             }
@@ -391,7 +398,9 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
             "description": "Check if all issues are completed",
             "inputSchema": {
                 "type": "object",
-                "properties": {},
+                "properties": {
+                    "epic": {"type": "string", "description": "Only check the sub-issues of this epic"}
+                },
                 "required": []
             }
         }),
@@ -407,6 +416,148 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
                 "required": ["name"]
             }
         }),
+        json!({
+            "name": "issue_graph",
+            "description": "Render the dependency graph between issues as Mermaid or DOT",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "format": {"type": "string", "description": "Output format - mermaid or dot (default: mermaid)"},
+                    "include_completed": {"type": "boolean", "description": "Include completed issues in the graph (default: false)"}
+                },
+                "required": []
+            }
+        }),
+        json!({
+            "name": "issue_reopen",
+            "description": "Move a completed or archived issue back to the active issues as todo",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "description": "Name of the completed or archived issue to reopen"}
+                },
+                "required": ["name"]
+            }
+        }),
+        json!({
+            "name": "issue_archive",
+            "description": "Move an issue to the archive, excluding it from listings and completion checks",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "description": "Name of the issue to archive"}
+                },
+                "required": ["name"]
+            }
+        }),
+        json!({
+            "name": "issue_delete",
+            "description": "Permanently delete an issue, refusing while its branch is checked out or unmerged",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "description": "Name of the issue to delete"},
+                    "force": {"type": "boolean", "description": "Delete even when the issue branch has unmerged work (default: false)"},
+                    "delete_branch": {"type": "boolean", "description": "Also delete the issue branch (default: false)"}
+                },
+                "required": ["name"]
+            }
+        }),
+        json!({
+            "name": "issue_search",
+            "description": "Search active and completed issues by keyword or semantic similarity",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": {"type": "string", "description": "Search query"},
+                    "mode": {"type": "string", "description": "keyword or semantic (default: keyword)"},
+                    "status": {"type": "string", "description": "Only return issues in this status"},
+                    "labels": {"type": "array", "items": {"type": "string"}, "description": "Only return issues carrying all of these labels"},
+                    "limit": {"type": "integer", "description": "Maximum number of results (default: 10)"}
+                },
+                "required": ["query"]
+            }
+        }),
+        json!({
+            "name": "issue_check",
+            "description": "Tick or untick an acceptance criterion checklist item of an issue",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "description": "Issue name"},
+                    "index": {"type": "integer", "description": "Position of the criterion, starting at 1"},
+                    "text": {"type": "string", "description": "Text contained in exactly one criterion"},
+                    "checked": {"type": "boolean", "description": "Tick the criterion, or untick it when false (default: true)"}
+                },
+                "required": ["name"]
+            }
+        }),
+        json!({
+            "name": "issue_comment",
+            "description": "Add a comment to an issue's activity log",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "description": "Issue name, or 'current' for the current branch"},
+                    "message": {"type": "string", "description": "Comment text"},
+                    "author": {"type": "string", "description": "Who wrote the comment"}
+                },
+                "required": ["name", "message"]
+            }
+        }),
+        json!({
+            "name": "issue_export",
+            "description": "Export issues as GitHub-compatible JSON, CSV or a markdown report",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "format": {"type": "string", "description": "github-json (default), csv or markdown"},
+                    "output": {"type": "string", "description": "File to write the export to, returned when omitted"},
+                    "include_completed": {"type": "boolean", "description": "Include completed issues (default: true)"}
+                },
+                "required": []
+            }
+        }),
+        json!({
+            "name": "issue_import",
+            "description": "Create and update issues from a GitHub-compatible JSON or CSV file",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "file": {"type": "string", "description": "JSON or CSV file to import"},
+                    "format": {"type": "string", "description": "github-json or csv, guessed from the extension when omitted"},
+                    "dry_run": {"type": "boolean", "description": "Only report what would change (default: false)"}
+                },
+                "required": ["file"]
+            }
+        }),
+        json!({
+            "name": "issue_claim",
+            "description": "Claim or release the lease that keeps parallel agents off an issue",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "description": "Issue name, 'next' or 'current'"},
+                    "owner": {"type": "string", "description": "Who takes the lease (default: SAH_AGENT_ID, or host and process)"},
+                    "ttl_seconds": {"type": "integer", "description": "Lease duration after each claim (default: 1800)"},
+                    "release": {"type": "boolean", "description": "Give up the lease instead of taking it (default: false)"},
+                    "force": {"type": "boolean", "description": "Release a lease held by another owner (default: false)"}
+                },
+                "required": ["name"]
+            }
+        }),
+        json!({
+            "name": "issue_migrate",
+            "description": "Move all issues between the markdown and embedded database storage backends",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "to": {"type": "string", "description": "Backend to move the issues to: fs or duckdb"},
+                    "keep_source": {"type": "boolean", "description": "Leave the issues in the old backend as well (default: false)"}
+                },
+                "required": ["to"]
+            }
+        }),
         // Memo tools
         json!({
             "name": "memo_create",
@@ -415,7 +566,9 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
                 "type": "object",
                 "properties": {
                     "title": {"type": "string", "description": "Title of the memo"},
-                    "content": {"type": "string", "description": "Markdown content of the memo"}
+                    "content": {"type": "string", "description": "Markdown content of the memo"},
+                    "tags": {"type": "array", "items": {"type": "string"}, "description": "Tags for grouping and filtering the memo"},
+                    "folder": {"type": "string", "description": "Optional slash-separated folder path"}
                 },
                 "required": ["title", "content"]
             }
@@ -425,7 +578,10 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
             "description": "List all available memos with their titles, IDs, and content previews",
             "inputSchema": {
                 "type": "object",
-                "properties": {},
+                "properties": {
+                    "tag": {"type": "string", "description": "Only include memos with this tag"},
+                    "folder": {"type": "string", "description": "Only include memos in this folder or its subfolders"}
+                },
                 "required": []
             }
         }),
@@ -442,14 +598,16 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
         }),
         json!({
             "name": "memo_update",
-            "description": "Update a memo's content by its ID",
+            "description": "Update a memo's content, tags or folder by its ID",
             "inputSchema": {
                 "type": "object",
                 "properties": {
                     "id": {"type": "string", "description": "ULID identifier of the memo to update"},
-                    "content": {"type": "string", "description": "New markdown content for the memo"}
+                    "content": {"type": "string", "description": "New markdown content for the memo"},
+                    "tags": {"type": "array", "items": {"type": "string"}, "description": "Replacement tags for the memo"},
+                    "folder": {"type": "string", "description": "New folder path for the memo, empty to move it out of its folder"}
                 },
-                "required": ["id", "content"]
+                "required": ["id"]
             }
         }),
         json!({
@@ -469,7 +627,11 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
             "inputSchema": {
                 "type": "object",
                 "properties": {
-                    "query": {"type": "string", "description": "Search query string to match against memo titles and content"}
+                    "query": {"type": "string", "description": "Search query string to match against memo titles and content"},
+                    "tag": {"type": "string", "description": "Only search memos with this tag"},
+                    "folder": {"type": "string", "description": "Only search memos in this folder or its subfolders"},
+                    "mode": {"type": "string", "enum": ["keyword", "semantic", "hybrid"], "description": "Ranking mode: keyword matching (default), embedding similarity, or both fused"},
+                    "limit": {"type": "integer", "description": "Maximum number of results for semantic and hybrid search"}
                 },
                 "required": ["query"]
             }
@@ -479,7 +641,69 @@ fn get_complete_sah_tools() -> Vec<serde_json::Value> {
             "description": "Get all memo content formatted for AI context consumption",
             "inputSchema": {
                 "type": "object",
-                "properties": {},
+                "properties": {
+                    "tag": {"type": "string", "description": "Only include memos with this tag"},
+                    "folder": {"type": "string", "description": "Only include memos in this folder or its subfolders"}
+                },
+                "required": []
+            }
+        }),
+        json!({
+            "name": "memo_tags",
+            "description": "List the tags used across memos with their counts",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "folder": {"type": "string", "description": "Only count tags of memos in this folder or its subfolders"}
+                },
+                "required": []
+            }
+        }),
+        json!({
+            "name": "memo_history",
+            "description": "List the previous revisions of a memo",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "description": "Identifier of the memo"}
+                },
+                "required": ["id"]
+            }
+        }),
+        json!({
+            "name": "memo_diff",
+            "description": "Compare two versions of a memo as a unified diff",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "description": "Identifier of the memo"},
+                    "from": {"type": "integer", "description": "Older revision, the most recent revision when omitted"},
+                    "to": {"type": "integer", "description": "Newer revision, the current content when omitted"}
+                },
+                "required": ["id"]
+            }
+        }),
+        json!({
+            "name": "memo_restore",
+            "description": "Bring back a previous revision of a memo",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "description": "Identifier of the memo"},
+                    "revision": {"type": "integer", "description": "Revision to restore, as listed by memo_history"}
+                },
+                "required": ["id", "revision"]
+            }
+        }),
+        json!({
+            "name": "memo_graph",
+            "description": "Export the links between memos, issues and files as a Mermaid graph",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "tag": {"type": "string", "description": "Only graph the links of memos with this tag"},
+                    "folder": {"type": "string", "description": "Only graph the links of memos in this folder or its subfolders"}
+                },
                 "required": []
             }
         }),
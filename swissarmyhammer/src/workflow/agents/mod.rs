@@ -3,6 +3,20 @@
 //! This module contains implementations of different agent executors that can
 //! be used to execute prompts and interact with AI systems.
 
+pub mod context_window;
 pub mod llama_agent_executor;
+pub mod model_catalog;
+pub mod response_cache;
 
+pub use context_window::{
+    ContextBudget, ContextOverflowError, ModelFamily, ModelProfile, OverflowStrategy, PackedPrompt,
+    PromptPacker, PromptTokenUsage, SectionAction, SectionTokenCount,
+};
 pub use llama_agent_executor::LlamaAgentExecutor;
+pub use model_catalog::{
+    ChecksumStatus, GgufModelInfo, LocalModel, ModelCatalog, ModelVerification,
+};
+pub use response_cache::{
+    AgentResponseCache, AgentResponseCacheConfig, AgentResponseCacheStats, CachedAgentResponse,
+    CachingAgentExecutor,
+};
@@ -0,0 +1,1173 @@
+//! Local GGUF model discovery and inspection for the LlamaAgent executor
+//!
+//! LlamaAgent runs models in-process, so the models it uses live on the local
+//! machine: either in the HuggingFace hub cache (populated by earlier downloads)
+//! or in folders referenced by `ModelSource::Local`. This module finds those
+//! files, reads their GGUF headers to report architecture, context length and
+//! quantization, verifies them against the checksums recorded by the hub cache,
+//! and writes a model selection back into `sah.toml`.
+
+use crate::{Result, SwissArmyHammerError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use swissarmyhammer_config::ModelSource;
+use walkdir::WalkDir;
+
+/// Magic bytes at the start of every GGUF file
+pub const GGUF_MAGIC: [u8; 4] = *b"GGUF";
+
+/// File extension used by GGUF model files
+pub const GGUF_EXTENSION: &str = "gguf";
+
+/// Upper bound on a single metadata string, to reject corrupt headers early
+const MAX_GGUF_STRING_LEN: u64 = 64 * 1024 * 1024;
+
+/// Upper bound on the number of metadata entries, to reject corrupt headers early
+const MAX_GGUF_METADATA_ENTRIES: u64 = 1_000_000;
+
+/// Fraction of available memory a model may use before a warning is raised
+const MEMORY_WARNING_RATIO: f64 = 0.9;
+
+/// Metadata extracted from the header of a GGUF model file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GgufModelInfo {
+    /// GGUF format version
+    pub version: u32,
+    /// Number of tensors stored in the file
+    pub tensor_count: u64,
+    /// Model architecture (`general.architecture`), e.g. `llama` or `qwen3moe`
+    pub architecture: Option<String>,
+    /// Human readable model name (`general.name`)
+    pub name: Option<String>,
+    /// Training context length (`<architecture>.context_length`)
+    pub context_length: Option<u64>,
+    /// Raw `general.file_type` value
+    pub file_type: Option<u32>,
+    /// Quantization label derived from the file type or the file name
+    pub quantization: Option<String>,
+    /// All scalar metadata values rendered as strings
+    pub metadata: HashMap<String, String>,
+}
+
+/// A GGUF model file found on the local machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalModel {
+    /// Path to the model file
+    pub path: PathBuf,
+    /// HuggingFace repository the file was downloaded from, if it lives in the hub cache
+    pub repo: Option<String>,
+    /// File name relative to the repository snapshot (or the bare file name for local files)
+    pub filename: String,
+    /// Size of the model file in bytes
+    pub size_bytes: u64,
+    /// Parsed header information, if the header could be read
+    pub info: Option<GgufModelInfo>,
+    /// Error encountered while reading the header
+    pub header_error: Option<String>,
+}
+
+impl LocalModel {
+    /// Display name used in listings: `repo/filename` for hub models, the path otherwise
+    pub fn display_name(&self) -> String {
+        match &self.repo {
+            Some(repo) => format!("{}/{}", repo, self.filename),
+            None => self.path.display().to_string(),
+        }
+    }
+
+    /// Quantization label, taken from the header or guessed from the file name
+    pub fn quantization(&self) -> Option<String> {
+        self.info
+            .as_ref()
+            .and_then(|info| info.quantization.clone())
+            .or_else(|| quantization_from_filename(&self.filename))
+    }
+
+    /// The `ModelSource` that selects this model in configuration
+    pub fn to_model_source(&self) -> ModelSource {
+        match &self.repo {
+            Some(repo) => ModelSource::HuggingFace {
+                repo: repo.clone(),
+                filename: Some(self.filename.clone()),
+                folder: None,
+            },
+            None => ModelSource::Local {
+                filename: self.path.clone(),
+                folder: None,
+            },
+        }
+    }
+
+    /// Whether this model is the one selected by `source`
+    pub fn matches_source(&self, source: &ModelSource) -> bool {
+        match (source, &self.repo) {
+            (
+                ModelSource::HuggingFace {
+                    repo,
+                    filename,
+                    folder,
+                },
+                Some(own_repo),
+            ) => {
+                if repo != own_repo {
+                    return false;
+                }
+                match (filename, folder) {
+                    (Some(filename), _) => {
+                        self.filename == *filename
+                            || self.filename.ends_with(&format!("/{filename}"))
+                    }
+                    (None, Some(folder)) => self.filename.starts_with(&format!("{folder}/")),
+                    (None, None) => true,
+                }
+            }
+            (ModelSource::Local { filename, folder }, None) => {
+                let configured = match folder {
+                    Some(folder) if filename.is_relative() => folder.join(filename),
+                    _ => filename.clone(),
+                };
+                same_file(&configured, &self.path)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Outcome of a checksum comparison during verification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum ChecksumStatus {
+    /// The file content matches the checksum recorded by the hub cache
+    Match { sha256: String },
+    /// The file content differs from the recorded checksum
+    Mismatch { expected: String, actual: String },
+    /// No reference checksum is available for this file
+    Unavailable,
+}
+
+/// Result of verifying a model file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelVerification {
+    /// Path of the verified file
+    pub path: PathBuf,
+    /// Size of the file in bytes
+    pub size_bytes: u64,
+    /// Parsed header, when the header is valid
+    pub info: Option<GgufModelInfo>,
+    /// Header parsing error, when the header is invalid
+    pub header_error: Option<String>,
+    /// Checksum comparison result
+    pub checksum: ChecksumStatus,
+    /// Warning raised when the model is unlikely to fit in memory
+    pub memory_warning: Option<String>,
+}
+
+impl ModelVerification {
+    /// Whether the model passed every check that could be performed
+    pub fn is_valid(&self) -> bool {
+        self.header_error.is_none() && !matches!(self.checksum, ChecksumStatus::Mismatch { .. })
+    }
+}
+
+/// Catalog of GGUF models available on this machine
+#[derive(Debug, Clone)]
+pub struct ModelCatalog {
+    hf_cache_dir: Option<PathBuf>,
+    local_dirs: Vec<PathBuf>,
+}
+
+impl Default for ModelCatalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModelCatalog {
+    /// Create a catalog over the default HuggingFace hub cache
+    pub fn new() -> Self {
+        Self {
+            hf_cache_dir: default_hf_cache_dir(),
+            local_dirs: Vec::new(),
+        }
+    }
+
+    /// Use a specific HuggingFace hub cache directory
+    pub fn with_hf_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.hf_cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Also scan a local directory for GGUF files
+    pub fn with_local_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        if !self.local_dirs.contains(&dir) {
+            self.local_dirs.push(dir);
+        }
+        self
+    }
+
+    /// Also scan the folder referenced by a local model source
+    pub fn with_model_source(self, source: &ModelSource) -> Self {
+        match source {
+            ModelSource::Local { filename, folder } => {
+                let dir = folder
+                    .clone()
+                    .or_else(|| filename.parent().map(Path::to_path_buf))
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .unwrap_or_else(|| PathBuf::from("."));
+                self.with_local_dir(dir)
+            }
+            ModelSource::HuggingFace { .. } => self,
+        }
+    }
+
+    /// The HuggingFace hub cache directory this catalog scans
+    pub fn hf_cache_dir(&self) -> Option<&Path> {
+        self.hf_cache_dir.as_deref()
+    }
+
+    /// List every GGUF model found in the hub cache and local directories
+    pub fn list(&self) -> Vec<LocalModel> {
+        let mut models = Vec::new();
+
+        if let Some(cache_dir) = &self.hf_cache_dir {
+            models.extend(scan_hf_cache(cache_dir));
+        }
+
+        for dir in &self.local_dirs {
+            for path in gguf_files_in(dir, 1) {
+                if !models.iter().any(|m| same_file(&m.path, &path)) {
+                    models.push(local_model_at(path));
+                }
+            }
+        }
+
+        models.sort_by_key(|m| m.display_name());
+        models
+    }
+
+    /// Find models whose path, repository or file name matches `query`
+    ///
+    /// A path to an existing GGUF file always resolves to that file. Otherwise
+    /// an exact match on the display name, repository file name or path wins
+    /// over partial matches.
+    pub fn find(&self, query: &str) -> Vec<LocalModel> {
+        let path = Path::new(query);
+        if path.is_file() && is_gguf_path(path) {
+            return vec![local_model_at(path.to_path_buf())];
+        }
+
+        let models = self.list();
+
+        let exact: Vec<LocalModel> = models
+            .iter()
+            .filter(|m| {
+                m.display_name() == query
+                    || m.filename == query
+                    || m.path == Path::new(query)
+                    || m.path.file_name().is_some_and(|name| name == query)
+            })
+            .cloned()
+            .collect();
+        if !exact.is_empty() {
+            return exact;
+        }
+
+        let needle = query.to_lowercase();
+        models
+            .into_iter()
+            .filter(|m| m.display_name().to_lowercase().contains(&needle))
+            .collect()
+    }
+
+    /// Find the catalog entry selected by a configured model source
+    pub fn resolve(&self, source: &ModelSource) -> Option<LocalModel> {
+        if let ModelSource::Local { filename, folder } = source {
+            let path = match folder {
+                Some(folder) if filename.is_relative() => folder.join(filename),
+                _ => filename.clone(),
+            };
+            return path.is_file().then(|| local_model_at(path));
+        }
+
+        self.list().into_iter().find(|m| m.matches_source(source))
+    }
+}
+
+/// Default HuggingFace hub cache, honouring `HF_HUB_CACHE` and `HF_HOME`
+pub fn default_hf_cache_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("HF_HUB_CACHE") {
+        if !dir.is_empty() {
+            return Some(PathBuf::from(dir));
+        }
+    }
+    if let Ok(home) = std::env::var("HF_HOME") {
+        if !home.is_empty() {
+            return Some(PathBuf::from(home).join("hub"));
+        }
+    }
+    dirs::home_dir().map(|home| home.join(".cache").join("huggingface").join("hub"))
+}
+
+/// Scan a HuggingFace hub cache for GGUF files in model snapshots
+fn scan_hf_cache(cache_dir: &Path) -> Vec<LocalModel> {
+    let mut models = Vec::new();
+    let Ok(entries) = std::fs::read_dir(cache_dir) else {
+        return models;
+    };
+
+    for entry in entries.flatten() {
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+        let Some(repo) = repo_from_cache_dir_name(&dir_name) else {
+            continue;
+        };
+
+        let snapshots = entry.path().join("snapshots");
+        let Ok(revisions) = std::fs::read_dir(&snapshots) else {
+            continue;
+        };
+
+        // Several revisions may contain the same file; keep the newest one
+        let mut newest: HashMap<String, (std::time::SystemTime, PathBuf)> = HashMap::new();
+        for revision in revisions.flatten() {
+            let revision_dir = revision.path();
+            let modified = revision
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::UNIX_EPOCH);
+            for path in gguf_files_in(&revision_dir, usize::MAX) {
+                let Ok(relative) = path.strip_prefix(&revision_dir) else {
+                    continue;
+                };
+                let filename = relative.to_string_lossy().replace('\\', "/");
+                let is_newer = newest
+                    .get(&filename)
+                    .map_or(true, |(existing, _)| *existing < modified);
+                if is_newer {
+                    newest.insert(filename, (modified, path));
+                }
+            }
+        }
+
+        for (filename, (_, path)) in newest {
+            models.push(inspect_model(path, Some(repo.clone()), filename));
+        }
+    }
+
+    models
+}
+
+/// Convert a hub cache directory name (`models--org--name`) into a repository id
+fn repo_from_cache_dir_name(dir_name: &str) -> Option<String> {
+    let rest = dir_name.strip_prefix("models--")?;
+    if rest.is_empty() {
+        return None;
+    }
+    Some(rest.replace("--", "/"))
+}
+
+/// All `.gguf` files below `dir`, following symlinks, up to `max_depth` levels deep
+fn gguf_files_in(dir: &Path, max_depth: usize) -> Vec<PathBuf> {
+    WalkDir::new(dir)
+        .follow_links(true)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| is_gguf_path(path))
+        .collect()
+}
+
+fn is_gguf_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(GGUF_EXTENSION))
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Inspect a GGUF file outside the hub cache
+fn local_model_at(path: PathBuf) -> LocalModel {
+    let filename = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    inspect_model(path, None, filename)
+}
+
+fn inspect_model(path: PathBuf, repo: Option<String>, filename: String) -> LocalModel {
+    let size_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    let (info, header_error) = match read_gguf_info(&path) {
+        Ok(mut info) => {
+            if info.quantization.is_none() {
+                info.quantization = quantization_from_filename(&filename);
+            }
+            (Some(info), None)
+        }
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    LocalModel {
+        path,
+        repo,
+        filename,
+        size_bytes,
+        info,
+        header_error,
+    }
+}
+
+/// Read and parse the GGUF header of a model file
+///
+/// Only the header and metadata section are read; tensor data is never loaded.
+pub fn read_gguf_info(path: &Path) -> Result<GgufModelInfo> {
+    let file = File::open(path)?;
+    let mut reader = GgufReader {
+        inner: BufReader::new(file),
+    };
+    reader.read_info().map_err(|e| match e {
+        SwissArmyHammerError::Other(msg) => SwissArmyHammerError::Other(format!(
+            "Invalid GGUF header in {}: {}",
+            path.display(),
+            msg
+        )),
+        other => other,
+    })
+}
+
+/// A scalar or array value from the GGUF metadata section
+#[derive(Debug, Clone, PartialEq)]
+enum GgufValue {
+    Unsigned(u64),
+    Signed(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Array(u64),
+}
+
+impl GgufValue {
+    fn as_u64(&self) -> Option<u64> {
+        match self {
+            GgufValue::Unsigned(v) => Some(*v),
+            GgufValue::Signed(v) if *v >= 0 => Some(*v as u64),
+            _ => None,
+        }
+    }
+
+    fn render(&self) -> String {
+        match self {
+            GgufValue::Unsigned(v) => v.to_string(),
+            GgufValue::Signed(v) => v.to_string(),
+            GgufValue::Float(v) => v.to_string(),
+            GgufValue::Bool(v) => v.to_string(),
+            GgufValue::String(v) => v.clone(),
+            GgufValue::Array(len) => format!("[{} items]", len),
+        }
+    }
+}
+
+struct GgufReader<R> {
+    inner: R,
+}
+
+impl<R: Read + Seek> GgufReader<R> {
+    fn read_info(&mut self) -> Result<GgufModelInfo> {
+        let mut magic = [0u8; 4];
+        self.inner.read_exact(&mut magic).map_err(truncated)?;
+        if magic != GGUF_MAGIC {
+            return Err(SwissArmyHammerError::Other(
+                "missing GGUF magic bytes".to_string(),
+            ));
+        }
+
+        let version = self.read_u32()?;
+        if !(2..=3).contains(&version) {
+            return Err(SwissArmyHammerError::Other(format!(
+                "unsupported GGUF version {}",
+                version
+            )));
+        }
+
+        let tensor_count = self.read_u64()?;
+        let kv_count = self.read_u64()?;
+        if kv_count > MAX_GGUF_METADATA_ENTRIES {
+            return Err(SwissArmyHammerError::Other(format!(
+                "implausible metadata entry count {}",
+                kv_count
+            )));
+        }
+
+        let mut values = HashMap::new();
+        for _ in 0..kv_count {
+            let key = self.read_string()?;
+            let value_type = self.read_u32()?;
+            let value = self.read_value(value_type)?;
+            values.insert(key, value);
+        }
+
+        let architecture = match values.get("general.architecture") {
+            Some(GgufValue::String(arch)) => Some(arch.clone()),
+            _ => None,
+        };
+        let name = match values.get("general.name") {
+            Some(GgufValue::String(name)) => Some(name.clone()),
+            _ => None,
+        };
+        let context_length = architecture
+            .as_ref()
+            .and_then(|arch| values.get(&format!("{arch}.context_length")))
+            .and_then(GgufValue::as_u64);
+        let file_type = values
+            .get("general.file_type")
+            .and_then(GgufValue::as_u64)
+            .map(|v| v as u32);
+        let quantization = file_type.and_then(quantization_name).map(str::to_string);
+
+        let metadata = values
+            .iter()
+            .filter(|(_, value)| !matches!(value, GgufValue::Array(_)))
+            .map(|(key, value)| (key.clone(), value.render()))
+            .collect();
+
+        Ok(GgufModelInfo {
+            version,
+            tensor_count,
+            architecture,
+            name,
+            context_length,
+            file_type,
+            quantization,
+            metadata,
+        })
+    }
+
+    fn read_value(&mut self, value_type: u32) -> Result<GgufValue> {
+        Ok(match value_type {
+            0 => GgufValue::Unsigned(self.read_bytes::<1>()?[0] as u64),
+            1 => GgufValue::Signed(self.read_bytes::<1>()?[0] as i8 as i64),
+            2 => GgufValue::Unsigned(u16::from_le_bytes(self.read_bytes()?) as u64),
+            3 => GgufValue::Signed(i16::from_le_bytes(self.read_bytes()?) as i64),
+            4 => GgufValue::Unsigned(self.read_u32()? as u64),
+            5 => GgufValue::Signed(i32::from_le_bytes(self.read_bytes()?) as i64),
+            6 => GgufValue::Float(f32::from_le_bytes(self.read_bytes()?) as f64),
+            7 => GgufValue::Bool(self.read_bytes::<1>()?[0] != 0),
+            8 => GgufValue::String(self.read_string()?),
+            9 => {
+                let element_type = self.read_u32()?;
+                let len = self.read_u64()?;
+                self.skip_array(element_type, len)?;
+                GgufValue::Array(len)
+            }
+            10 => GgufValue::Unsigned(self.read_u64()?),
+            11 => GgufValue::Signed(i64::from_le_bytes(self.read_bytes()?)),
+            12 => GgufValue::Float(f64::from_le_bytes(self.read_bytes()?)),
+            other => {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "unknown metadata value type {}",
+                    other
+                )))
+            }
+        })
+    }
+
+    fn skip_array(&mut self, element_type: u32, len: u64) -> Result<()> {
+        let element_size: Option<u64> = match element_type {
+            0 | 1 | 7 => Some(1),
+            2 | 3 => Some(2),
+            4..=6 => Some(4),
+            10..=12 => Some(8),
+            _ => None,
+        };
+
+        match element_size {
+            Some(size) => {
+                let bytes = size.checked_mul(len).ok_or_else(|| {
+                    SwissArmyHammerError::Other(format!("implausible array length {}", len))
+                })?;
+                let offset = i64::try_from(bytes).map_err(|_| {
+                    SwissArmyHammerError::Other(format!("implausible array length {}", len))
+                })?;
+                self.inner.seek(SeekFrom::Current(offset))?;
+            }
+            None => {
+                for _ in 0..len {
+                    self.read_value(element_type)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        let len = self.read_u64()?;
+        if len > MAX_GGUF_STRING_LEN {
+            return Err(SwissArmyHammerError::Other(format!(
+                "implausible string length {}",
+                len
+            )));
+        }
+        let mut buf = vec![0u8; len as usize];
+        self.inner.read_exact(&mut buf).map_err(truncated)?;
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.read_bytes()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.read_bytes()?))
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.inner.read_exact(&mut buf).map_err(truncated)?;
+        Ok(buf)
+    }
+}
+
+fn truncated(e: std::io::Error) -> SwissArmyHammerError {
+    if e.kind() == std::io::ErrorKind::UnexpectedEof {
+        SwissArmyHammerError::Other("header is truncated".to_string())
+    } else {
+        SwissArmyHammerError::Io(e)
+    }
+}
+
+/// Map a llama.cpp `general.file_type` value to its quantization label
+pub fn quantization_name(file_type: u32) -> Option<&'static str> {
+    Some(match file_type {
+        0 => "F32",
+        1 => "F16",
+        2 => "Q4_0",
+        3 => "Q4_1",
+        7 => "Q8_0",
+        8 => "Q5_0",
+        9 => "Q5_1",
+        10 => "Q2_K",
+        11 => "Q3_K_S",
+        12 => "Q3_K_M",
+        13 => "Q3_K_L",
+        14 => "Q4_K_S",
+        15 => "Q4_K_M",
+        16 => "Q5_K_S",
+        17 => "Q5_K_M",
+        18 => "Q6_K",
+        19 => "IQ2_XXS",
+        20 => "IQ2_XS",
+        21 => "Q2_K_S",
+        22 => "IQ3_XS",
+        23 => "IQ3_XXS",
+        24 => "IQ1_S",
+        25 => "IQ4_NL",
+        26 => "IQ3_S",
+        27 => "IQ3_M",
+        28 => "IQ2_S",
+        29 => "IQ2_M",
+        30 => "IQ4_XS",
+        31 => "IQ1_M",
+        32 => "BF16",
+        _ => return None,
+    })
+}
+
+/// Guess the quantization label from a file name such as `model-Q4_K_M.gguf`
+pub fn quantization_from_filename(filename: &str) -> Option<String> {
+    let stem = Path::new(filename)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_uppercase())?;
+
+    stem.split(['-', '.'])
+        .rev()
+        .find(|part| is_quantization_tag(part))
+        .map(str::to_string)
+}
+
+fn is_quantization_tag(part: &str) -> bool {
+    if matches!(part, "F16" | "F32" | "BF16") {
+        return true;
+    }
+    part.strip_prefix("IQ")
+        .or_else(|| part.strip_prefix('Q'))
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_digit())
+}
+
+/// Verify a model file: parse its header and compare its checksum with the hub cache
+///
+/// Files in the HuggingFace hub cache are symlinks to `blobs/<sha256>` for
+/// LFS-tracked files, so the blob name is used as the reference checksum.
+/// Computing the checksum reads the entire file.
+pub fn verify_model(path: &Path) -> Result<ModelVerification> {
+    let metadata = std::fs::metadata(path).map_err(|_| SwissArmyHammerError::FileNotFound {
+        path: path.display().to_string(),
+        suggestion: "Check the model path or run 'sah model list'".to_string(),
+    })?;
+
+    let (info, header_error) = match read_gguf_info(path) {
+        Ok(info) => (Some(info), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    let checksum = match expected_sha256(path) {
+        Some(expected) => {
+            let actual = sha256_file(path)?;
+            if actual == expected {
+                ChecksumStatus::Match { sha256: actual }
+            } else {
+                ChecksumStatus::Mismatch { expected, actual }
+            }
+        }
+        None => ChecksumStatus::Unavailable,
+    };
+
+    Ok(ModelVerification {
+        path: path.to_path_buf(),
+        size_bytes: metadata.len(),
+        info,
+        header_error,
+        checksum,
+        memory_warning: memory_warning(metadata.len()),
+    })
+}
+
+/// Reference sha256 for a hub cache file, taken from the blob it links to
+fn expected_sha256(path: &Path) -> Option<String> {
+    let target = std::fs::read_link(path).ok()?;
+    let in_blobs = target
+        .parent()
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == "blobs");
+    if !in_blobs {
+        return None;
+    }
+    let name = target.file_name()?.to_string_lossy().to_string();
+    (name.len() == 64 && name.chars().all(|c| c.is_ascii_hexdigit())).then_some(name)
+}
+
+/// Compute the hex encoded sha256 digest of a file
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let read = reader.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Memory currently available to load a model, in bytes
+pub fn available_memory_bytes() -> Option<u64> {
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    let available = system.available_memory();
+    (available > 0).then_some(available)
+}
+
+/// Warning message when a model of `size_bytes` is unlikely to fit in memory
+pub fn memory_warning(size_bytes: u64) -> Option<String> {
+    memory_warning_for(size_bytes, available_memory_bytes()?)
+}
+
+fn memory_warning_for(size_bytes: u64, available_bytes: u64) -> Option<String> {
+    if (size_bytes as f64) <= available_bytes as f64 * MEMORY_WARNING_RATIO {
+        return None;
+    }
+    Some(format!(
+        "Model needs about {} but only {} of memory is available",
+        format_size(size_bytes),
+        format_size(available_bytes)
+    ))
+}
+
+/// Format a byte count using binary units, e.g. `4.7 GiB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Write a LlamaAgent model selection into a `sah.toml` file
+///
+/// Switches `agent.executor` to `llama-agent` and replaces its model source.
+/// Other LlamaAgent settings (MCP server, batch size, ...) are kept when the
+/// file already selects LlamaAgent; everything outside `agent.executor` is left
+/// untouched. Comments in the file are not preserved.
+pub fn write_model_selection(config_file: &Path, source: &ModelSource) -> Result<()> {
+    let mut document: toml::Table = if config_file.exists() {
+        let content = std::fs::read_to_string(config_file)?;
+        toml::from_str(&content).map_err(|e| {
+            SwissArmyHammerError::Config(format!(
+                "Failed to parse {}: {}",
+                config_file.display(),
+                e
+            ))
+        })?
+    } else {
+        toml::Table::new()
+    };
+
+    apply_model_selection(&mut document, source)?;
+
+    let content = toml::to_string_pretty(&document).map_err(|e| {
+        SwissArmyHammerError::Config(format!("Failed to serialize configuration: {}", e))
+    })?;
+    if let Some(parent) = config_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(config_file, content)?;
+    Ok(())
+}
+
+fn apply_model_selection(document: &mut toml::Table, source: &ModelSource) -> Result<()> {
+    let agent = child_table(document, "agent")?;
+    let executor = child_table(agent, "executor")?;
+
+    let was_llama = executor
+        .get("type")
+        .and_then(toml::Value::as_str)
+        .is_some_and(|t| t == "llama-agent");
+    executor.insert(
+        "type".to_string(),
+        toml::Value::String("llama-agent".to_string()),
+    );
+    if !was_llama {
+        executor.remove("config");
+    }
+
+    let config = child_table(executor, "config")?;
+    let model = child_table(config, "model")?;
+    model.insert(
+        "source".to_string(),
+        toml::Value::Table(model_source_table(source)),
+    );
+    Ok(())
+}
+
+fn child_table<'a>(table: &'a mut toml::Table, key: &str) -> Result<&'a mut toml::Table> {
+    table
+        .entry(key.to_string())
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        .as_table_mut()
+        .ok_or_else(|| {
+            SwissArmyHammerError::Config(format!("Configuration key '{}' is not a table", key))
+        })
+}
+
+fn model_source_table(source: &ModelSource) -> toml::Table {
+    let mut fields = toml::Table::new();
+    let variant = match source {
+        ModelSource::HuggingFace {
+            repo,
+            filename,
+            folder,
+        } => {
+            fields.insert("repo".to_string(), toml::Value::String(repo.clone()));
+            if let Some(filename) = filename {
+                fields.insert(
+                    "filename".to_string(),
+                    toml::Value::String(filename.clone()),
+                );
+            }
+            if let Some(folder) = folder {
+                fields.insert("folder".to_string(), toml::Value::String(folder.clone()));
+            }
+            "HuggingFace"
+        }
+        ModelSource::Local { filename, folder } => {
+            fields.insert(
+                "filename".to_string(),
+                toml::Value::String(filename.display().to_string()),
+            );
+            if let Some(folder) = folder {
+                fields.insert(
+                    "folder".to_string(),
+                    toml::Value::String(folder.display().to_string()),
+                );
+            }
+            "Local"
+        }
+    };
+
+    let mut source_table = toml::Table::new();
+    source_table.insert(variant.to_string(), toml::Value::Table(fields));
+    source_table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn push_string(buf: &mut Vec<u8>, s: &str) {
+        buf.extend_from_slice(&(s.len() as u64).to_le_bytes());
+        buf.extend_from_slice(s.as_bytes());
+    }
+
+    /// Build a minimal GGUF v3 header with a few metadata entries
+    fn synthetic_gguf(file_type: u32) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&GGUF_MAGIC);
+        buf.extend_from_slice(&3u32.to_le_bytes());
+        buf.extend_from_slice(&42u64.to_le_bytes());
+        buf.extend_from_slice(&5u64.to_le_bytes());
+
+        push_string(&mut buf, "general.architecture");
+        buf.extend_from_slice(&8u32.to_le_bytes());
+        push_string(&mut buf, "llama");
+
+        push_string(&mut buf, "general.name");
+        buf.extend_from_slice(&8u32.to_le_bytes());
+        push_string(&mut buf, "Tiny Test");
+
+        push_string(&mut buf, "llama.context_length");
+        buf.extend_from_slice(&4u32.to_le_bytes());
+        buf.extend_from_slice(&4096u32.to_le_bytes());
+
+        push_string(&mut buf, "tokenizer.ggml.tokens");
+        buf.extend_from_slice(&9u32.to_le_bytes());
+        buf.extend_from_slice(&8u32.to_le_bytes());
+        buf.extend_from_slice(&2u64.to_le_bytes());
+        push_string(&mut buf, "<s>");
+        push_string(&mut buf, "</s>");
+
+        push_string(&mut buf, "general.file_type");
+        buf.extend_from_slice(&4u32.to_le_bytes());
+        buf.extend_from_slice(&file_type.to_le_bytes());
+
+        buf
+    }
+
+    fn write_file(path: &Path, bytes: &[u8]) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut file = File::create(path).unwrap();
+        file.write_all(bytes).unwrap();
+    }
+
+    #[test]
+    fn test_read_gguf_info_parses_header() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("tiny.gguf");
+        write_file(&path, &synthetic_gguf(15));
+
+        let info = read_gguf_info(&path).unwrap();
+        assert_eq!(info.version, 3);
+        assert_eq!(info.tensor_count, 42);
+        assert_eq!(info.architecture.as_deref(), Some("llama"));
+        assert_eq!(info.name.as_deref(), Some("Tiny Test"));
+        assert_eq!(info.context_length, Some(4096));
+        assert_eq!(info.quantization.as_deref(), Some("Q4_K_M"));
+        assert!(!info.metadata.contains_key("tokenizer.ggml.tokens"));
+    }
+
+    #[test]
+    fn test_read_gguf_info_rejects_bad_files() {
+        let temp = TempDir::new().unwrap();
+
+        let not_gguf = temp.path().join("bad.gguf");
+        write_file(&not_gguf, b"definitely not a model");
+        assert!(read_gguf_info(&not_gguf).is_err());
+
+        let truncated = temp.path().join("truncated.gguf");
+        write_file(&truncated, &synthetic_gguf(15)[..40]);
+        let err = read_gguf_info(&truncated).unwrap_err().to_string();
+        assert!(err.contains("truncated"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_quantization_from_filename() {
+        assert_eq!(
+            quantization_from_filename("Phi-4-mini-instruct-Q4_K_M.gguf").as_deref(),
+            Some("Q4_K_M")
+        );
+        assert_eq!(
+            quantization_from_filename("model.IQ3_XS.gguf").as_deref(),
+            Some("IQ3_XS")
+        );
+        assert_eq!(
+            quantization_from_filename("model-f16.gguf").as_deref(),
+            Some("F16")
+        );
+        assert_eq!(quantization_from_filename("model.gguf"), None);
+    }
+
+    #[test]
+    fn test_catalog_lists_hf_cache_models() {
+        let temp = TempDir::new().unwrap();
+        let snapshot = temp
+            .path()
+            .join("models--unsloth--Tiny-GGUF")
+            .join("snapshots")
+            .join("abc123");
+        write_file(&snapshot.join("Tiny-Q8_0.gguf"), &synthetic_gguf(7));
+        write_file(&snapshot.join("README.md"), b"readme");
+
+        let catalog = ModelCatalog::new().with_hf_cache_dir(temp.path());
+        let models = catalog.list();
+        assert_eq!(models.len(), 1);
+
+        let model = &models[0];
+        assert_eq!(model.repo.as_deref(), Some("unsloth/Tiny-GGUF"));
+        assert_eq!(model.filename, "Tiny-Q8_0.gguf");
+        assert_eq!(model.quantization().as_deref(), Some("Q8_0"));
+        assert_eq!(model.display_name(), "unsloth/Tiny-GGUF/Tiny-Q8_0.gguf");
+
+        let source = model.to_model_source();
+        assert!(model.matches_source(&source));
+        assert!(catalog.resolve(&source).is_some());
+        assert_eq!(catalog.find("tiny").len(), 1);
+    }
+
+    #[test]
+    fn test_catalog_lists_local_dir_models() {
+        let temp = TempDir::new().unwrap();
+        let hf_cache = temp.path().join("hub");
+        let models_dir = temp.path().join("models");
+        write_file(&models_dir.join("local-Q5_K_M.gguf"), &synthetic_gguf(17));
+
+        let catalog = ModelCatalog::new()
+            .with_hf_cache_dir(&hf_cache)
+            .with_local_dir(&models_dir);
+        let models = catalog.list();
+        assert_eq!(models.len(), 1);
+        assert!(models[0].repo.is_none());
+        assert_eq!(models[0].quantization().as_deref(), Some("Q5_K_M"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_verify_model_compares_blob_checksum() {
+        let temp = TempDir::new().unwrap();
+        let bytes = synthetic_gguf(15);
+        let digest = format!("{:x}", Sha256::digest(&bytes));
+
+        let repo_dir = temp.path().join("models--org--Model-GGUF");
+        let blob = repo_dir.join("blobs").join(&digest);
+        write_file(&blob, &bytes);
+        let snapshot = repo_dir.join("snapshots").join("rev");
+        std::fs::create_dir_all(&snapshot).unwrap();
+        let link = snapshot.join("model.gguf");
+        std::os::unix::fs::symlink(&blob, &link).unwrap();
+
+        let verification = verify_model(&link).unwrap();
+        assert!(verification.is_valid());
+        assert_eq!(
+            verification.checksum,
+            ChecksumStatus::Match { sha256: digest }
+        );
+
+        // Corrupt the blob so the content no longer matches its name
+        write_file(&blob, b"GGUF corrupted");
+        let verification = verify_model(&link).unwrap();
+        assert!(!verification.is_valid());
+        assert!(matches!(
+            verification.checksum,
+            ChecksumStatus::Mismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_memory_warning_threshold() {
+        let gib = 1024 * 1024 * 1024;
+        assert!(memory_warning_for(4 * gib, 16 * gib).is_none());
+        let warning = memory_warning_for(20 * gib, 16 * gib).unwrap();
+        assert!(warning.contains("20.0 GiB"));
+    }
+
+    #[test]
+    fn test_write_model_selection_preserves_other_settings() {
+        let temp = TempDir::new().unwrap();
+        let config_file = temp.path().join("sah.toml");
+        std::fs::write(
+            &config_file,
+            r#"project_name = "demo"
+
+[agent.executor]
+type = "llama-agent"
+
+[agent.executor.config.mcp_server]
+port = 0
+timeout_seconds = 30
+
+[agent.executor.config.model.source.Local]
+filename = "/tmp/old.gguf"
+"#,
+        )
+        .unwrap();
+
+        let source = ModelSource::HuggingFace {
+            repo: "unsloth/Phi-4-mini-instruct-GGUF".to_string(),
+            filename: Some("Phi-4-mini-instruct-Q4_K_M.gguf".to_string()),
+            folder: None,
+        };
+        write_model_selection(&config_file, &source).unwrap();
+
+        let document: toml::Table =
+            toml::from_str(&std::fs::read_to_string(&config_file).unwrap()).unwrap();
+        assert_eq!(document["project_name"].as_str(), Some("demo"));
+
+        let executor = &document["agent"]["executor"];
+        assert_eq!(executor["type"].as_str(), Some("llama-agent"));
+        assert_eq!(
+            executor["config"]["mcp_server"]["timeout_seconds"].as_integer(),
+            Some(30)
+        );
+        let source = &executor["config"]["model"]["source"];
+        assert!(source.get("Local").is_none());
+        assert_eq!(
+            source["HuggingFace"]["repo"].as_str(),
+            Some("unsloth/Phi-4-mini-instruct-GGUF")
+        );
+    }
+
+    #[test]
+    fn test_write_model_selection_replaces_claude_config() {
+        let temp = TempDir::new().unwrap();
+        let config_file = temp.path().join("nested").join("sah.toml");
+        std::fs::create_dir_all(config_file.parent().unwrap()).unwrap();
+        std::fs::write(
+            &config_file,
+            "[agent.executor]\ntype = \"claude-code\"\n\n[agent.executor.config]\nargs = []\n",
+        )
+        .unwrap();
+
+        let source = ModelSource::Local {
+            filename: PathBuf::from("/models/tiny.gguf"),
+            folder: None,
+        };
+        write_model_selection(&config_file, &source).unwrap();
+
+        let document: toml::Table =
+            toml::from_str(&std::fs::read_to_string(&config_file).unwrap()).unwrap();
+        let config = &document["agent"]["executor"]["config"];
+        assert!(config.get("args").is_none());
+        assert_eq!(
+            config["model"]["source"]["Local"]["filename"].as_str(),
+            Some("/models/tiny.gguf")
+        );
+    }
+}
@@ -0,0 +1,646 @@
+//! Content-addressed cache of agent responses
+//!
+//! Re-running a workflow after a late-state failure would otherwise pay again for
+//! identical early prompts. This module provides a disk-backed cache keyed by a
+//! SHA-256 hash of the executor type, model, system prompt and rendered prompt,
+//! and a [`CachingAgentExecutor`] decorator that adds caching to any
+//! [`AgentExecutor`] implementation.
+//!
+//! Entries live under `.swissarmyhammer/cache/agent` and are bounded by a TTL,
+//! a maximum entry count and a maximum total size on disk.
+//!
+//! The cache is opt-in, with `sah flow run --cache` or `SWISSARMYHAMMER_AGENT_CACHE=1`;
+//! `--no-cache` bypasses it for one run even when the environment enables it.
+//! A hit replays the recorded response without running the agent, so side effects
+//! of the original run such as file edits, tool calls and commits are not repeated.
+
+use crate::workflow::actions::{ActionResult, AgentExecutionContext, AgentExecutor, AgentResponse};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use swissarmyhammer_config::agent::AgentExecutorType;
+
+/// Default directory for cached agent responses, relative to the working directory
+pub const DEFAULT_AGENT_CACHE_DIR: &str = ".swissarmyhammer/cache/agent";
+
+/// Metadata key set on responses served from the cache
+pub const CACHE_HIT_METADATA_KEY: &str = "cache_hit";
+
+/// Context key overriding the cache configuration for a run
+///
+/// `true` is set by `--cache` and `false` by `--no-cache`; without it the
+/// configuration decides.
+pub const AGENT_CACHE_CONTEXT_KEY: &str = "_agent_cache";
+
+/// Context key holding the number of agent cache hits in the current run
+pub const CACHE_HITS_CONTEXT_KEY: &str = "_agent_cache_hits";
+
+/// Context key holding the number of agent cache misses in the current run
+pub const CACHE_MISSES_CONTEXT_KEY: &str = "_agent_cache_misses";
+
+/// Configuration for the agent response cache
+#[derive(Debug, Clone)]
+pub struct AgentResponseCacheConfig {
+    /// Whether the cache is enabled for every run, not only runs started with `--cache`
+    pub enabled: bool,
+    /// Directory where cache entries are stored
+    pub directory: PathBuf,
+    /// How long an entry stays valid after it was written
+    pub ttl: Duration,
+    /// Maximum number of entries kept on disk
+    pub max_entries: usize,
+    /// Maximum total size of all entries in bytes
+    pub max_size_bytes: u64,
+}
+
+impl Default for AgentResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: std::env::var("SWISSARMYHAMMER_AGENT_CACHE")
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+            directory: std::env::var("SWISSARMYHAMMER_AGENT_CACHE_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from(DEFAULT_AGENT_CACHE_DIR)),
+            ttl: Duration::from_secs(
+                std::env::var("SWISSARMYHAMMER_AGENT_CACHE_TTL")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(7 * 24 * 3600),
+            ), // 7 days default
+            max_entries: std::env::var("SWISSARMYHAMMER_AGENT_CACHE_MAX_ENTRIES")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1000),
+            max_size_bytes: std::env::var("SWISSARMYHAMMER_AGENT_CACHE_MAX_BYTES")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(100 * 1024 * 1024), // 100 MB default
+        }
+    }
+}
+
+/// A single cached response as stored on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedAgentResponse {
+    /// Cache key this entry was stored under
+    pub key: String,
+    /// Executor type that produced the response
+    pub executor_type: AgentExecutorType,
+    /// Model identifier that produced the response
+    pub model: String,
+    /// When the entry was written
+    pub created_at: DateTime<Utc>,
+    /// The cached response
+    pub response: AgentResponse,
+}
+
+/// Statistics about the on-disk cache
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentResponseCacheStats {
+    /// Number of entries on disk
+    pub entries: usize,
+    /// Total size of all entries in bytes
+    pub total_bytes: u64,
+}
+
+/// Disk-backed, content-addressed cache of agent responses
+#[derive(Debug, Clone)]
+pub struct AgentResponseCache {
+    config: AgentResponseCacheConfig,
+}
+
+impl AgentResponseCache {
+    /// Create a cache with the given configuration
+    pub fn new(config: AgentResponseCacheConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the cache configuration
+    pub fn config(&self) -> &AgentResponseCacheConfig {
+        &self.config
+    }
+
+    /// Compute the cache key for a prompt execution
+    ///
+    /// Fields are length-prefixed before hashing so that moving text between the
+    /// system prompt and the rendered prompt always produces a different key.
+    pub fn cache_key(
+        executor_type: AgentExecutorType,
+        model: &str,
+        system_prompt: &str,
+        rendered_prompt: &str,
+    ) -> String {
+        let mut hasher = Sha256::new();
+        for field in [
+            format!("{executor_type:?}").as_str(),
+            model,
+            system_prompt,
+            rendered_prompt,
+        ] {
+            hasher.update((field.len() as u64).to_le_bytes());
+            hasher.update(field.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Path of the entry file for a key
+    fn entry_path(&self, key: &str) -> PathBuf {
+        let shard = key.get(..2).unwrap_or("00");
+        self.config
+            .directory
+            .join(shard)
+            .join(format!("{key}.json"))
+    }
+
+    /// Look up a cached response, removing the entry if it has expired
+    pub fn get(&self, key: &str) -> Option<CachedAgentResponse> {
+        let path = self.entry_path(key);
+        let content = std::fs::read_to_string(&path).ok()?;
+
+        let entry: CachedAgentResponse = match serde_json::from_str(&content) {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!("Discarding corrupt agent cache entry {}: {}", key, e);
+                let _ = std::fs::remove_file(&path);
+                return None;
+            }
+        };
+
+        if self.is_expired(&entry) {
+            tracing::debug!("Agent cache entry {} expired", key);
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+
+        Some(entry)
+    }
+
+    /// Store a response and enforce the configured size limits
+    pub fn put(
+        &self,
+        key: &str,
+        executor_type: AgentExecutorType,
+        model: &str,
+        response: &AgentResponse,
+    ) -> std::io::Result<()> {
+        let entry = CachedAgentResponse {
+            key: key.to_string(),
+            executor_type,
+            model: model.to_string(),
+            created_at: Utc::now(),
+            response: response.clone(),
+        };
+
+        let path = self.entry_path(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string(&entry)?;
+        // Write to a temporary file first so readers never see a partial entry
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, content)?;
+        std::fs::rename(&tmp_path, &path)?;
+
+        self.prune()?;
+        Ok(())
+    }
+
+    /// Remove expired entries and evict the oldest entries until the cache fits its limits
+    pub fn prune(&self) -> std::io::Result<AgentResponseCacheStats> {
+        let mut entries = self.list_entry_files();
+        let now = std::time::SystemTime::now();
+
+        entries.retain(|(path, modified, _)| {
+            let expired = now
+                .duration_since(*modified)
+                .map(|age| age > self.config.ttl)
+                .unwrap_or(false);
+            if expired {
+                let _ = std::fs::remove_file(path);
+            }
+            !expired
+        });
+
+        // Oldest first so eviction removes the least recently written entries
+        entries.sort_by_key(|(_, modified, _)| *modified);
+
+        let mut total_bytes: u64 = entries.iter().map(|(_, _, size)| size).sum();
+        let mut remaining = entries.len();
+
+        for (path, _, size) in &entries {
+            if remaining <= self.config.max_entries && total_bytes <= self.config.max_size_bytes {
+                break;
+            }
+            std::fs::remove_file(path)?;
+            total_bytes = total_bytes.saturating_sub(*size);
+            remaining -= 1;
+        }
+
+        Ok(AgentResponseCacheStats {
+            entries: remaining,
+            total_bytes,
+        })
+    }
+
+    /// Get statistics about the entries currently on disk
+    pub fn stats(&self) -> AgentResponseCacheStats {
+        let entries = self.list_entry_files();
+        AgentResponseCacheStats {
+            entries: entries.len(),
+            total_bytes: entries.iter().map(|(_, _, size)| size).sum(),
+        }
+    }
+
+    /// Remove every entry from the cache
+    pub fn clear(&self) -> std::io::Result<()> {
+        if self.config.directory.exists() {
+            std::fs::remove_dir_all(&self.config.directory)?;
+        }
+        Ok(())
+    }
+
+    fn is_expired(&self, entry: &CachedAgentResponse) -> bool {
+        let age = Utc::now().signed_duration_since(entry.created_at);
+        age.to_std()
+            .map(|age| age > self.config.ttl)
+            .unwrap_or(false)
+    }
+
+    fn list_entry_files(&self) -> Vec<(PathBuf, std::time::SystemTime, u64)> {
+        list_json_files(&self.config.directory)
+    }
+}
+
+impl Default for AgentResponseCache {
+    fn default() -> Self {
+        Self::new(AgentResponseCacheConfig::default())
+    }
+}
+
+/// Collect `(path, modified, size)` for all `.json` files below a directory
+fn list_json_files(dir: &Path) -> Vec<(PathBuf, std::time::SystemTime, u64)> {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .filter_map(|e| {
+            let metadata = e.metadata().ok()?;
+            let modified = metadata.modified().ok()?;
+            Some((e.path().to_path_buf(), modified, metadata.len()))
+        })
+        .collect()
+}
+
+/// Agent executor decorator that serves repeated prompts from an [`AgentResponseCache`]
+///
+/// Only successful responses are cached. Responses served from the cache carry
+/// `"cache_hit": true` in their metadata.
+pub struct CachingAgentExecutor {
+    inner: Box<dyn AgentExecutor>,
+    cache: AgentResponseCache,
+}
+
+impl CachingAgentExecutor {
+    /// Wrap an executor with a response cache
+    pub fn new(inner: Box<dyn AgentExecutor>, cache: AgentResponseCache) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Mark a response as served from the cache
+    fn mark_cache_hit(mut response: AgentResponse) -> AgentResponse {
+        let mut metadata = match response.metadata.take() {
+            Some(serde_json::Value::Object(map)) => map,
+            Some(other) => {
+                let mut map = serde_json::Map::new();
+                map.insert("original".to_string(), other);
+                map
+            }
+            None => serde_json::Map::new(),
+        };
+        metadata.insert(
+            CACHE_HIT_METADATA_KEY.to_string(),
+            serde_json::Value::Bool(true),
+        );
+        response.metadata = Some(serde_json::Value::Object(metadata));
+        response
+    }
+}
+
+/// Wrap an executor in the response cache when the run uses it
+///
+/// `requested` is the run's override, `Some(true)` for `--cache` and `Some(false)`
+/// for `--no-cache`; without one the cache configuration decides. Also reports
+/// whether the executor was wrapped.
+pub fn with_response_cache(
+    executor: Box<dyn AgentExecutor>,
+    cache: AgentResponseCache,
+    requested: Option<bool>,
+) -> (Box<dyn AgentExecutor>, bool) {
+    if requested.unwrap_or(cache.config().enabled) {
+        (Box::new(CachingAgentExecutor::new(executor, cache)), true)
+    } else {
+        (executor, false)
+    }
+}
+
+/// Check whether a response was served from the agent response cache
+pub fn is_cache_hit(response: &AgentResponse) -> bool {
+    response
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get(CACHE_HIT_METADATA_KEY))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+#[async_trait]
+impl AgentExecutor for CachingAgentExecutor {
+    async fn execute_prompt(
+        &self,
+        system_prompt: String,
+        rendered_prompt: String,
+        context: &AgentExecutionContext<'_>,
+        timeout: Duration,
+    ) -> ActionResult<AgentResponse> {
+        let executor_type = self.inner.executor_type();
+        let model = context.workflow_context.get_model_name();
+        let key =
+            AgentResponseCache::cache_key(executor_type, &model, &system_prompt, &rendered_prompt);
+
+        if let Some(entry) = self.cache.get(&key) {
+            tracing::info!("Agent response cache hit ({})", &key[..12]);
+            return Ok(Self::mark_cache_hit(entry.response));
+        }
+
+        let response = self
+            .inner
+            .execute_prompt(system_prompt, rendered_prompt, context, timeout)
+            .await?;
+
+        if response.is_success() {
+            if let Err(e) = self.cache.put(&key, executor_type, &model, &response) {
+                tracing::warn!("Failed to write agent response cache entry: {}", e);
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn executor_type(&self) -> AgentExecutorType {
+        self.inner.executor_type()
+    }
+
+    async fn initialize(&mut self) -> ActionResult<()> {
+        self.inner.initialize().await
+    }
+
+    async fn shutdown(&mut self) -> ActionResult<()> {
+        self.inner.shutdown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::WorkflowTemplateContext;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    struct CountingExecutor {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl AgentExecutor for CountingExecutor {
+        async fn execute_prompt(
+            &self,
+            _system_prompt: String,
+            rendered_prompt: String,
+            _context: &AgentExecutionContext<'_>,
+            _timeout: Duration,
+        ) -> ActionResult<AgentResponse> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(AgentResponse::success(format!("echo: {rendered_prompt}")))
+        }
+
+        fn executor_type(&self) -> AgentExecutorType {
+            AgentExecutorType::ClaudeCode
+        }
+
+        async fn initialize(&mut self) -> ActionResult<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> ActionResult<()> {
+            Ok(())
+        }
+    }
+
+    fn test_config(dir: &Path) -> AgentResponseCacheConfig {
+        AgentResponseCacheConfig {
+            enabled: true,
+            directory: dir.to_path_buf(),
+            ttl: Duration::from_secs(3600),
+            max_entries: 100,
+            max_size_bytes: 10 * 1024 * 1024,
+        }
+    }
+
+    #[test]
+    fn test_cache_key_is_stable_and_field_sensitive() {
+        let a = AgentResponseCache::cache_key(AgentExecutorType::ClaudeCode, "m", "sys", "p");
+        let b = AgentResponseCache::cache_key(AgentExecutorType::ClaudeCode, "m", "sys", "p");
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 64);
+
+        let moved = AgentResponseCache::cache_key(AgentExecutorType::ClaudeCode, "m", "sy", "sp");
+        assert_ne!(a, moved);
+
+        let other_executor =
+            AgentResponseCache::cache_key(AgentExecutorType::LlamaAgent, "m", "sys", "p");
+        assert_ne!(a, other_executor);
+    }
+
+    #[test]
+    fn test_put_and_get_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let cache = AgentResponseCache::new(test_config(temp.path()));
+        let key = AgentResponseCache::cache_key(AgentExecutorType::ClaudeCode, "m", "", "hello");
+
+        assert!(cache.get(&key).is_none());
+        cache
+            .put(
+                &key,
+                AgentExecutorType::ClaudeCode,
+                "m",
+                &AgentResponse::success("world".to_string()),
+            )
+            .unwrap();
+
+        let entry = cache.get(&key).unwrap();
+        assert_eq!(entry.response.content, "world");
+        assert_eq!(entry.model, "m");
+        assert_eq!(cache.stats().entries, 1);
+    }
+
+    #[test]
+    fn test_expired_entries_are_not_returned() {
+        let temp = TempDir::new().unwrap();
+        let mut config = test_config(temp.path());
+        config.ttl = Duration::from_secs(0);
+        let cache = AgentResponseCache::new(config);
+
+        cache
+            .put(
+                "abcdef",
+                AgentExecutorType::ClaudeCode,
+                "m",
+                &AgentResponse::success("stale".to_string()),
+            )
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+
+        assert!(cache.get("abcdef").is_none());
+    }
+
+    #[test]
+    fn test_prune_enforces_max_entries() {
+        let temp = TempDir::new().unwrap();
+        let mut config = test_config(temp.path());
+        config.max_entries = 2;
+        let cache = AgentResponseCache::new(config);
+
+        for i in 0..4 {
+            let key = AgentResponseCache::cache_key(
+                AgentExecutorType::ClaudeCode,
+                "m",
+                "",
+                &format!("prompt {i}"),
+            );
+            cache
+                .put(
+                    &key,
+                    AgentExecutorType::ClaudeCode,
+                    "m",
+                    &AgentResponse::success(format!("response {i}")),
+                )
+                .unwrap();
+        }
+
+        assert_eq!(cache.stats().entries, 2);
+    }
+
+    #[tokio::test]
+    async fn test_caching_executor_serves_repeated_prompts() {
+        let temp = TempDir::new().unwrap();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let executor = CachingAgentExecutor::new(
+            Box::new(CountingExecutor {
+                calls: calls.clone(),
+            }),
+            AgentResponseCache::new(test_config(temp.path())),
+        );
+
+        let workflow_context = WorkflowTemplateContext::with_vars_for_test(HashMap::new());
+        let context = AgentExecutionContext::new(&workflow_context);
+
+        let first = executor
+            .execute_prompt(
+                String::new(),
+                "hi".to_string(),
+                &context,
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert!(!is_cache_hit(&first));
+
+        let second = executor
+            .execute_prompt(
+                String::new(),
+                "hi".to_string(),
+                &context,
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert!(is_cache_hit(&second));
+        assert_eq!(second.content, "echo: hi");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_cache_skips_reads_and_writes_when_enabled() {
+        let temp = TempDir::new().unwrap();
+        let workflow_context = WorkflowTemplateContext::with_vars_for_test(HashMap::new());
+        let context = AgentExecutionContext::new(&workflow_context);
+        let model = workflow_context.get_model_name();
+        let cache = AgentResponseCache::new(test_config(temp.path()));
+        let key = AgentResponseCache::cache_key(AgentExecutorType::ClaudeCode, &model, "", "hi");
+        cache
+            .put(
+                &key,
+                AgentExecutorType::ClaudeCode,
+                &model,
+                &AgentResponse::success("cached".to_string()),
+            )
+            .unwrap();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counting = |calls: &Arc<AtomicUsize>| -> Box<dyn AgentExecutor> {
+            Box::new(CountingExecutor {
+                calls: calls.clone(),
+            })
+        };
+
+        // The configuration enables the cache, `--no-cache` still bypasses it
+        let (executor, cached) = with_response_cache(
+            counting(&calls),
+            AgentResponseCache::new(test_config(temp.path())),
+            Some(false),
+        );
+        assert!(!cached);
+        for prompt in ["hi", "fresh"] {
+            let response = executor
+                .execute_prompt(
+                    String::new(),
+                    prompt.to_string(),
+                    &context,
+                    Duration::from_secs(5),
+                )
+                .await
+                .unwrap();
+            assert!(!is_cache_hit(&response));
+            assert_eq!(response.content, format!("echo: {prompt}"));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.stats().entries, 1);
+
+        // Without the override the same entry is served
+        let (executor, cached) = with_response_cache(
+            counting(&calls),
+            AgentResponseCache::new(test_config(temp.path())),
+            None,
+        );
+        assert!(cached);
+        let response = executor
+            .execute_prompt(
+                String::new(),
+                "hi".to_string(),
+                &context,
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content, "cached");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,209 @@
+//! Human approval gates for workflow runs
+//!
+//! A state whose action is `await approval "Merge to main?"` records a pending
+//! approval in the run context and the executor pauses the run. Nothing blocks
+//! on stdin, so gates work for headless runs and under the MCP server. The run
+//! is persisted and continued later by `sah flow approve <run_id>` or the
+//! `flow_approve` MCP tool, which record an [`ApprovalDecision`] and resume the
+//! run. When the gate state executes again it consumes the decision: approval
+//! succeeds and follows the state's success transition, rejection fails the
+//! action so the run follows its failure transition.
+
+use crate::workflow::{WorkflowRun, WorkflowRunStatus, WorkflowTemplateContext};
+use crate::{Result, SwissArmyHammerError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Context key holding the approval a paused run is waiting for
+pub const PENDING_APPROVAL_KEY: &str = "_pending_approval";
+
+/// Context key holding the decision recorded for a pending approval
+pub const APPROVAL_DECISION_KEY: &str = "_approval_decision";
+
+/// Workflow variable describing the outcome of the last approval gate
+pub const APPROVAL_RESULT_KEY: &str = "approval";
+
+/// An approval gate waiting for a decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingApproval {
+    /// State that requested the approval
+    #[serde(default)]
+    pub state: String,
+    /// Question shown to the approver
+    pub message: String,
+    /// When the approval was requested
+    pub requested_at: DateTime<Utc>,
+}
+
+impl PendingApproval {
+    /// Create a pending approval requested now
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            state: String::new(),
+            message: message.into(),
+            requested_at: Utc::now(),
+        }
+    }
+
+    /// The pending approval recorded in a run context, if any
+    pub fn from_context(context: &WorkflowTemplateContext) -> Option<Self> {
+        context
+            .get(PENDING_APPROVAL_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    /// Record this pending approval in a run context
+    pub fn store(&self, context: &mut WorkflowTemplateContext) {
+        if let Ok(value) = serde_json::to_value(self) {
+            context.insert(PENDING_APPROVAL_KEY.to_string(), value);
+        }
+    }
+}
+
+/// Decision taken on a pending approval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalDecision {
+    /// Whether the gate was approved
+    pub approved: bool,
+    /// Reason given by the approver
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Who took the decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_by: Option<String>,
+    /// When the decision was taken
+    pub decided_at: DateTime<Utc>,
+}
+
+impl ApprovalDecision {
+    /// Approve the gate
+    pub fn approve(reason: Option<String>) -> Self {
+        Self::new(true, reason)
+    }
+
+    /// Reject the gate
+    pub fn reject(reason: Option<String>) -> Self {
+        Self::new(false, reason)
+    }
+
+    fn new(approved: bool, reason: Option<String>) -> Self {
+        Self {
+            approved,
+            reason,
+            decided_by: None,
+            decided_at: Utc::now(),
+        }
+    }
+
+    /// Record who took the decision
+    pub fn with_decided_by(mut self, decided_by: impl Into<String>) -> Self {
+        self.decided_by = Some(decided_by.into());
+        self
+    }
+
+    /// Take the decision recorded in a run context, if any
+    pub fn take_from_context(context: &mut WorkflowTemplateContext) -> Option<Self> {
+        context
+            .remove(APPROVAL_DECISION_KEY)
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+
+    /// The `approval` workflow variable describing this decision
+    pub fn to_result(&self, message: &str) -> Value {
+        serde_json::json!({
+            "approved": self.approved,
+            "message": message,
+            "reason": self.reason,
+            "decided_by": self.decided_by,
+            "decided_at": self.decided_at,
+        })
+    }
+}
+
+/// Record a decision on the approval a paused run is waiting for
+///
+/// The run is marked as running again so the caller can resume it; the gate
+/// state consumes the decision when it executes. Fails when the run is not
+/// paused on an approval gate.
+pub fn record_approval_decision(
+    run: &mut WorkflowRun,
+    decision: ApprovalDecision,
+) -> Result<PendingApproval> {
+    let pending = match PendingApproval::from_context(&run.context) {
+        Some(pending) if run.status == WorkflowRunStatus::Paused => pending,
+        _ => {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Workflow run {} is not waiting for approval (status: {:?})",
+                run.id, run.status
+            )))
+        }
+    };
+
+    run.context.insert(
+        APPROVAL_DECISION_KEY.to_string(),
+        serde_json::to_value(&decision)?,
+    );
+    run.status = WorkflowRunStatus::Running;
+    run.metadata.insert(
+        "approval".to_string(),
+        if decision.approved {
+            "approved".to_string()
+        } else {
+            "rejected".to_string()
+        },
+    );
+
+    Ok(pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::test_helpers::*;
+
+    fn paused_run() -> WorkflowRun {
+        let mut workflow = create_workflow("release", "Release workflow", "gate");
+        workflow.add_state(create_state("gate", "Wait for approval", false));
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let abort_path = temp_dir.path().join(".abort");
+        let mut run = WorkflowRun::new_with_abort_path(workflow, abort_path.to_str().unwrap());
+        let mut pending = PendingApproval::new("Merge to main?");
+        pending.state = "gate".to_string();
+        pending.store(&mut run.context);
+        run.status = WorkflowRunStatus::Paused;
+        run
+    }
+
+    #[test]
+    fn test_record_decision_marks_run_ready_to_resume() {
+        let mut run = paused_run();
+
+        let pending = record_approval_decision(
+            &mut run,
+            ApprovalDecision::reject(Some("tests are red".to_string())).with_decided_by("alice"),
+        )
+        .unwrap();
+
+        assert_eq!(pending.message, "Merge to main?");
+        assert_eq!(pending.state, "gate");
+        assert_eq!(run.status, WorkflowRunStatus::Running);
+
+        let decision = ApprovalDecision::take_from_context(&mut run.context).unwrap();
+        assert!(!decision.approved);
+        assert_eq!(decision.reason.as_deref(), Some("tests are red"));
+        assert_eq!(decision.decided_by.as_deref(), Some("alice"));
+        assert!(ApprovalDecision::take_from_context(&mut run.context).is_none());
+    }
+
+    #[test]
+    fn test_record_decision_requires_pending_approval() {
+        let mut run = paused_run();
+        run.context.remove(PENDING_APPROVAL_KEY);
+        assert!(record_approval_decision(&mut run, ApprovalDecision::approve(None)).is_err());
+
+        let mut run = paused_run();
+        run.status = WorkflowRunStatus::Completed;
+        assert!(record_approval_decision(&mut run, ApprovalDecision::approve(None)).is_err());
+    }
+}
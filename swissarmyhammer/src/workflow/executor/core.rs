@@ -1,9 +1,13 @@
 //! Core workflow execution logic
 
 use super::{
-    ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult, DEFAULT_MAX_HISTORY_SIZE,
-    LAST_ACTION_RESULT_KEY, MAX_TRANSITIONS,
+    ActionInvocation, ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult,
+    StateOutcome, WorkflowObserver, DEFAULT_MAX_HISTORY_SIZE, LAST_ACTION_RESULT_KEY,
+    MAX_TRANSITIONS,
 };
+use crate::workflow::agents::context_window::{PromptTokenUsage, PROMPT_TOKEN_USAGE_CONTEXT_KEY};
+use crate::workflow::agents::response_cache::{CACHE_HITS_CONTEXT_KEY, CACHE_MISSES_CONTEXT_KEY};
+use crate::workflow::approval::PendingApproval;
 use crate::workflow::{
     metrics::{MemoryMetrics, WorkflowMetrics},
     parse_action_from_description_with_context, ActionError, CompensationKey, ErrorContext,
@@ -12,6 +16,7 @@ use crate::workflow::{
 };
 use cel_interpreter::Program;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -29,6 +34,8 @@ pub struct WorkflowExecutor {
     test_storage: Option<Arc<crate::workflow::storage::WorkflowStorage>>,
     /// Working directory for file operations (including abort file)
     working_dir: std::path::PathBuf,
+    /// Observers notified as runs progress
+    observers: Vec<Box<dyn WorkflowObserver>>,
 }
 
 impl WorkflowExecutor {
@@ -41,6 +48,7 @@ impl WorkflowExecutor {
             cache_manager: WorkflowCacheManager::new(),
             test_storage: None,
             working_dir: std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")),
+            observers: Vec::new(),
         }
     }
 
@@ -53,6 +61,7 @@ impl WorkflowExecutor {
             cache_manager: WorkflowCacheManager::new(),
             test_storage: None,
             working_dir: working_dir.as_ref().to_path_buf(),
+            observers: Vec::new(),
         }
     }
 
@@ -65,9 +74,15 @@ impl WorkflowExecutor {
             cache_manager: WorkflowCacheManager::new(),
             test_storage: Some(storage),
             working_dir: std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")),
+            observers: Vec::new(),
         }
     }
 
+    /// Register an observer to be notified as runs progress
+    pub fn add_observer<O: WorkflowObserver + 'static>(&mut self, observer: O) {
+        self.observers.push(Box::new(observer));
+    }
+
     /// Get the workflow storage (test storage if available, otherwise create file system storage)
     pub fn get_storage(&self) -> crate::Result<Arc<crate::workflow::storage::WorkflowStorage>> {
         if let Some(storage) = &self.test_storage {
@@ -246,6 +261,26 @@ impl WorkflowExecutor {
         &mut self,
         run: &mut WorkflowRun,
         remaining_transitions: usize,
+    ) -> ExecutorResult<()> {
+        for observer in &mut self.observers {
+            observer.on_run_start(run);
+        }
+
+        let result = self
+            .execute_transitions_with_limit(run, remaining_transitions)
+            .await;
+
+        for observer in &mut self.observers {
+            observer.on_run_end(run, result.as_ref().err());
+        }
+        result
+    }
+
+    /// Run the execution loop until the run finishes, pauses or exhausts its transitions
+    async fn execute_transitions_with_limit(
+        &mut self,
+        run: &mut WorkflowRun,
+        remaining_transitions: usize,
     ) -> ExecutorResult<()> {
         // Abort file checking happens at the flow command level before execution begins
 
@@ -329,6 +364,55 @@ impl WorkflowExecutor {
             return Ok(());
         }
 
+        if self.observers.is_empty() {
+            return self.execute_state_body(run, current_state_id).await;
+        }
+
+        let variables_before = run.context.workflow_vars();
+        for observer in &mut self.observers {
+            observer.on_state_enter(run, &current_state_id);
+        }
+
+        let state_start_time = Instant::now();
+        let result = self.execute_state_body(run, current_state_id.clone()).await;
+        let outcome = StateOutcome::from_result(state_start_time.elapsed(), &result);
+
+        self.notify_variable_changes(run, &variables_before);
+        for observer in &mut self.observers {
+            observer.on_state_exit(run, &current_state_id, &outcome);
+        }
+        result
+    }
+
+    /// Notify observers of workflow variables the last state set or changed
+    fn notify_variable_changes(
+        &mut self,
+        run: &WorkflowRun,
+        variables_before: &HashMap<String, Value>,
+    ) {
+        // Underscore-prefixed keys are executor bookkeeping rather than workflow variables
+        let mut changed: Vec<(&String, &Value)> = run
+            .context
+            .iter()
+            .filter(|(name, value)| {
+                !name.starts_with('_') && variables_before.get(*name) != Some(*value)
+            })
+            .collect();
+        changed.sort_by_key(|(name, _)| *name);
+
+        for (name, value) in changed {
+            for observer in &mut self.observers {
+                observer.on_variable_set(run, name, value);
+            }
+        }
+    }
+
+    /// Execute a non-terminal state's action, or its fork, join or choice semantics
+    async fn execute_state_body(
+        &mut self,
+        run: &mut WorkflowRun,
+        current_state_id: StateId,
+    ) -> ExecutorResult<()> {
         // Check if this is a fork state
         if self.is_fork_state(run, &current_state_id) {
             return self.execute_fork_state(run).await;
@@ -384,6 +468,25 @@ impl WorkflowExecutor {
         let state_duration = state_start_time.elapsed();
         self.metrics
             .record_state_execution(&run.id, current_state_id.clone(), state_duration);
+        self.sync_agent_metrics(run);
+
+        // Pause the run when an approval gate is waiting for a decision
+        if let Some(mut pending) = PendingApproval::from_context(&run.context) {
+            pending.state = current_state_id.to_string();
+            pending.store(&mut run.context);
+            run.status = WorkflowRunStatus::Paused;
+            self.log_event(
+                ExecutionEventType::StateExecution,
+                format!(
+                    "State {current_state_id} is waiting for approval: {}",
+                    pending.message
+                ),
+            );
+            return Err(ExecutorError::ManualInterventionRequired(format!(
+                "State {current_state_id} is waiting for approval: {}",
+                pending.message
+            )));
+        }
 
         // Check if this state requires manual intervention
         if self.requires_manual_intervention(run) {
@@ -467,6 +570,10 @@ impl WorkflowExecutor {
         // Record transition in metrics
         self.metrics.record_transition(&run.id);
 
+        for observer in &mut self.observers {
+            observer.on_transition(run, &run.current_state, &next_state);
+        }
+
         // Update the run
         run.transition_to(next_state);
 
@@ -531,14 +638,29 @@ impl WorkflowExecutor {
         if let Some(action) =
             parse_action_from_description_with_context(&action_text, &context_hashmap)?
         {
+            let action_description = action.description();
+            let action_type = action.action_type();
             self.log_event(
                 ExecutionEventType::StateExecution,
-                format!("Executing action: {}", action.description()),
+                format!("Executing action: {action_description}"),
             );
 
             // Execute the action and handle result
+            let action_start_time = Instant::now();
             let result = self.execute_action_direct(run, action).await;
 
+            if !self.observers.is_empty() {
+                let invocation = ActionInvocation {
+                    action_type: action_type.to_string(),
+                    description: action_description,
+                    duration: action_start_time.elapsed(),
+                };
+                let current_state = run.current_state.clone();
+                for observer in &mut self.observers {
+                    observer.on_action_complete(run, &current_state, &invocation, &result);
+                }
+            }
+
             // Handle the result and optionally store it in the Store As variable
             self.handle_action_result_with_store_as(run, result, store_as_var)
                 .await?;
@@ -750,6 +872,8 @@ impl WorkflowExecutor {
             ActionError::ShellSecurityError(security_error) => {
                 format!("Shell security violation: {security_error}")
             }
+            ActionError::ContextOverflow(msg) => format!("Context window exceeded: {msg}"),
+            ActionError::ApprovalRejected(msg) => format!("Approval rejected: {msg}"),
         }
     }
 
@@ -875,6 +999,26 @@ impl WorkflowExecutor {
         &mut self.metrics
     }
 
+    /// Copy the cache counters and prompt token usage maintained by prompt actions into run metrics
+    fn sync_agent_metrics(&mut self, run: &WorkflowRun) {
+        let counter =
+            |key: &str| run.context.get(key).and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+        let hits = counter(CACHE_HITS_CONTEXT_KEY);
+        let misses = counter(CACHE_MISSES_CONTEXT_KEY);
+        if hits > 0 || misses > 0 {
+            self.metrics
+                .update_agent_cache_metrics(&run.id, hits, misses);
+        }
+
+        if let Some(usage) = run
+            .context
+            .get(PROMPT_TOKEN_USAGE_CONTEXT_KEY)
+            .and_then(|v| serde_json::from_value::<Vec<PromptTokenUsage>>(v.clone()).ok())
+        {
+            self.metrics.update_prompt_token_usage(&run.id, usage);
+        }
+    }
+
     /// Update memory metrics for a specific run
     pub fn update_memory_metrics(
         &mut self,
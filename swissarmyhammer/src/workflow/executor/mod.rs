@@ -2,6 +2,7 @@
 
 pub mod core;
 pub mod fork_join;
+pub mod observer;
 #[cfg(test)]
 mod tests;
 pub mod validation;
@@ -104,3 +105,7 @@ impl std::fmt::Display for ExecutionEventType {
 
 // Re-export main types
 pub use core::WorkflowExecutor;
+pub use observer::{
+    read_run_events, ActionInvocation, EventLevel, JsonlEventObserver, MetricsObserver,
+    StateOutcome, WorkflowEvent, WorkflowEventReader, WorkflowEventRecord, WorkflowObserver,
+};
@@ -0,0 +1,803 @@
+//! Observer hooks for workflow execution
+//!
+//! Observers registered on a [`WorkflowExecutor`](super::WorkflowExecutor) are
+//! notified as a run progresses: when the executor starts driving a run, when a
+//! state is entered and exited, on every transition, when an action changes a
+//! workflow variable and when the executor stops driving the run. Callbacks run
+//! synchronously on the executor's task and cannot fail the run, so observers
+//! should log rather than propagate their own errors.
+//!
+//! Two observers are provided: [`JsonlEventObserver`] appends every event to a
+//! JSONL file next to the persisted run, and [`MetricsObserver`] feeds a shared
+//! [`WorkflowMetrics`] collector. [`WorkflowEventReader`] reads an event file
+//! back incrementally, which is how `sah flow logs --follow` tails a run that is
+//! still executing in another process.
+
+use super::ExecutorError;
+use crate::workflow::agents::context_window::PROMPT_TOKEN_USAGE_CONTEXT_KEY;
+use crate::workflow::{
+    ActionError, FileSystemWorkflowRunStorage, PromptTokenUsage, StateId, WorkflowMetrics,
+    WorkflowRun, WorkflowRunId, WorkflowRunStatus,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Maximum number of characters of action output kept in an event
+pub const ACTION_OUTPUT_EXCERPT_CHARS: usize = 500;
+
+/// How a state's execution ended
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateOutcome {
+    /// Time spent executing the state
+    pub duration: Duration,
+    /// Whether the state executed without error
+    pub success: bool,
+    /// Error that ended the state, if any
+    pub error: Option<String>,
+}
+
+impl StateOutcome {
+    /// Build the outcome of a state execution from its result
+    pub fn from_result(duration: Duration, result: &Result<(), ExecutorError>) -> Self {
+        Self {
+            duration,
+            success: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        }
+    }
+}
+
+/// An action executed by a state
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionInvocation {
+    /// Kind of action, such as `prompt` or `shell`
+    pub action_type: String,
+    /// Human-readable description of the action
+    pub description: String,
+    /// Time spent executing the action
+    pub duration: Duration,
+}
+
+/// Callbacks invoked by the executor while it drives a workflow run
+///
+/// Every method has an empty default implementation so observers only
+/// implement the events they care about.
+pub trait WorkflowObserver: Send + Sync {
+    /// The executor started (or resumed) driving the run from its current state
+    fn on_run_start(&mut self, _run: &WorkflowRun) {}
+
+    /// A state is about to execute
+    fn on_state_enter(&mut self, _run: &WorkflowRun, _state: &StateId) {}
+
+    /// A state finished executing
+    fn on_state_exit(&mut self, _run: &WorkflowRun, _state: &StateId, _outcome: &StateOutcome) {}
+
+    /// The run is moving from one state to another
+    fn on_transition(&mut self, _run: &WorkflowRun, _from: &StateId, _to: &StateId) {}
+
+    /// A state's action finished, successfully or not
+    fn on_action_complete(
+        &mut self,
+        _run: &WorkflowRun,
+        _state: &StateId,
+        _action: &ActionInvocation,
+        _result: &Result<Value, ActionError>,
+    ) {
+    }
+
+    /// A state's action set or changed a workflow variable
+    fn on_variable_set(&mut self, _run: &WorkflowRun, _name: &str, _value: &Value) {}
+
+    /// The executor stopped driving the run because it finished, paused or failed
+    fn on_run_end(&mut self, _run: &WorkflowRun, _error: Option<&ExecutorError>) {}
+}
+
+/// Status a run ends with from an observer's point of view
+///
+/// Callers mark a run as failed only after the executor returns, so a run that
+/// ended with an error is reported as failed even if its status is still running.
+pub fn final_run_status(run: &WorkflowRun, error: Option<&ExecutorError>) -> WorkflowRunStatus {
+    match (run.status, error) {
+        (WorkflowRunStatus::Running, Some(_)) => WorkflowRunStatus::Failed,
+        (status, _) => status,
+    }
+}
+
+/// Severity of a workflow event, used to filter event logs
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum EventLevel {
+    /// Variable changes and other detail
+    Debug,
+    /// Normal progress of a run
+    #[default]
+    Info,
+    /// Runs that stopped without finishing, such as paused or cancelled runs
+    Warn,
+    /// Failed states, actions and runs
+    Error,
+}
+
+impl std::fmt::Display for EventLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            EventLevel::Debug => "debug",
+            EventLevel::Info => "info",
+            EventLevel::Warn => "warn",
+            EventLevel::Error => "error",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl FromStr for EventLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "debug" | "trace" => Ok(EventLevel::Debug),
+            "info" => Ok(EventLevel::Info),
+            "warn" | "warning" => Ok(EventLevel::Warn),
+            "error" => Ok(EventLevel::Error),
+            other => Err(format!(
+                "Invalid event level '{other}'. Expected debug, info, warn or error"
+            )),
+        }
+    }
+}
+
+/// A single observed workflow event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WorkflowEvent {
+    /// The executor started driving the run
+    RunStart {
+        /// State the run starts or resumes from
+        state: String,
+    },
+    /// A state is about to execute
+    StateEnter {
+        /// State being entered
+        state: String,
+    },
+    /// A state finished executing
+    StateExit {
+        /// State being exited
+        state: String,
+        /// Execution time in milliseconds
+        duration_ms: u64,
+        /// Whether the state executed without error
+        success: bool,
+        /// Error that ended the state, if any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// A state's action finished
+    ActionOutput {
+        /// State whose action ran
+        state: String,
+        /// Description of the action
+        action: String,
+        /// Kind of action, such as `prompt` or `shell`
+        #[serde(default)]
+        action_type: String,
+        /// Execution time in milliseconds
+        #[serde(default)]
+        duration_ms: u64,
+        /// Exit code of a shell action
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        exit_code: Option<i64>,
+        /// Prompt sent by a prompt action
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prompt: Option<String>,
+        /// Tokens sent to the model by a prompt action
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tokens: Option<usize>,
+        /// Whether the action succeeded
+        success: bool,
+        /// Beginning of the action's output
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output: Option<String>,
+        /// Error returned by the action, if any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// The run moved between states
+    Transition {
+        /// Source state
+        from: String,
+        /// Target state
+        to: String,
+    },
+    /// A workflow variable was set
+    VariableSet {
+        /// Variable name
+        name: String,
+        /// New value
+        value: Value,
+    },
+    /// The executor stopped driving the run
+    RunEnd {
+        /// Status of the run at that point
+        status: WorkflowRunStatus,
+        /// Error that stopped the run, if any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+impl WorkflowEvent {
+    /// Severity of the event
+    pub fn level(&self) -> EventLevel {
+        match self {
+            WorkflowEvent::VariableSet { .. } => EventLevel::Debug,
+            WorkflowEvent::StateExit { success: false, .. }
+            | WorkflowEvent::ActionOutput { success: false, .. } => EventLevel::Error,
+            WorkflowEvent::RunEnd { error: Some(_), .. }
+            | WorkflowEvent::RunEnd {
+                status: WorkflowRunStatus::Failed,
+                ..
+            } => EventLevel::Error,
+            WorkflowEvent::RunEnd {
+                status: WorkflowRunStatus::Paused | WorkflowRunStatus::Cancelled,
+                ..
+            } => EventLevel::Warn,
+            _ => EventLevel::Info,
+        }
+    }
+
+    /// Whether the event concerns the given state
+    pub fn involves_state(&self, state: &str) -> bool {
+        match self {
+            WorkflowEvent::RunStart { state: s }
+            | WorkflowEvent::StateEnter { state: s }
+            | WorkflowEvent::StateExit { state: s, .. }
+            | WorkflowEvent::ActionOutput { state: s, .. } => s == state,
+            WorkflowEvent::Transition { from, to } => from == state || to == state,
+            WorkflowEvent::VariableSet { .. } | WorkflowEvent::RunEnd { .. } => false,
+        }
+    }
+}
+
+impl std::fmt::Display for WorkflowEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkflowEvent::RunStart { state } => write!(f, "Run started at state {state}"),
+            WorkflowEvent::StateEnter { state } => write!(f, "Entered state {state}"),
+            WorkflowEvent::StateExit {
+                state,
+                duration_ms,
+                error,
+                ..
+            } => match error {
+                Some(error) => write!(f, "State {state} failed after {duration_ms}ms: {error}"),
+                None => write!(f, "Exited state {state} after {duration_ms}ms"),
+            },
+            WorkflowEvent::ActionOutput {
+                state,
+                action,
+                output,
+                error,
+                ..
+            } => match (error, output) {
+                (Some(error), _) => write!(f, "[{state}] {action} failed: {error}"),
+                (None, Some(output)) => write!(f, "[{state}] {action}: {output}"),
+                (None, None) => write!(f, "[{state}] {action} completed"),
+            },
+            WorkflowEvent::Transition { from, to } => write!(f, "Transition {from} → {to}"),
+            WorkflowEvent::VariableSet { name, value } => write!(f, "Set {name} = {value}"),
+            WorkflowEvent::RunEnd { status, error } => match error {
+                Some(error) => write!(f, "Run ended ({status:?}): {error}"),
+                None => write!(f, "Run ended ({status:?})"),
+            },
+        }
+    }
+}
+
+/// A workflow event as written to a run's event file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowEventRecord {
+    /// When the event occurred
+    pub timestamp: DateTime<Utc>,
+    /// Severity of the event
+    #[serde(default)]
+    pub level: EventLevel,
+    /// Run the event belongs to
+    pub run_id: String,
+    /// Name of the workflow being run
+    pub workflow: String,
+    /// The event itself
+    #[serde(flatten)]
+    pub event: WorkflowEvent,
+}
+
+impl WorkflowEventRecord {
+    /// Record an event for a run, timestamped now
+    pub fn new(run: &WorkflowRun, event: WorkflowEvent) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            level: event.level(),
+            run_id: run.id.to_string(),
+            workflow: run.workflow.name.to_string(),
+            event,
+        }
+    }
+}
+
+/// Observer appending every event to `runs/<run>/events.jsonl` under a run storage directory
+///
+/// The file is opened in append mode for each event, so a resumed run keeps
+/// extending the same file and readers can follow it while the run executes.
+pub struct JsonlEventObserver {
+    run_storage_dir: PathBuf,
+}
+
+impl JsonlEventObserver {
+    /// Write event files under the given run storage directory
+    pub fn new(run_storage_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            run_storage_dir: run_storage_dir.into(),
+        }
+    }
+
+    /// Path of the event file for a run
+    pub fn events_path(&self, run: &WorkflowRun) -> PathBuf {
+        FileSystemWorkflowRunStorage::run_events_path(&self.run_storage_dir, &run.id)
+    }
+
+    fn write(&self, run: &WorkflowRun, event: WorkflowEvent) {
+        let path = self.events_path(run);
+        let record = WorkflowEventRecord::new(run, event);
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let line = serde_json::to_string(&record)?;
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)?;
+            writeln!(file, "{line}")
+        })();
+
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to write workflow event to {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+impl WorkflowObserver for JsonlEventObserver {
+    fn on_run_start(&mut self, run: &WorkflowRun) {
+        self.write(
+            run,
+            WorkflowEvent::RunStart {
+                state: run.current_state.to_string(),
+            },
+        );
+    }
+
+    fn on_state_enter(&mut self, run: &WorkflowRun, state: &StateId) {
+        self.write(
+            run,
+            WorkflowEvent::StateEnter {
+                state: state.to_string(),
+            },
+        );
+    }
+
+    fn on_state_exit(&mut self, run: &WorkflowRun, state: &StateId, outcome: &StateOutcome) {
+        self.write(
+            run,
+            WorkflowEvent::StateExit {
+                state: state.to_string(),
+                duration_ms: outcome.duration.as_millis() as u64,
+                success: outcome.success,
+                error: outcome.error.clone(),
+            },
+        );
+    }
+
+    fn on_transition(&mut self, run: &WorkflowRun, from: &StateId, to: &StateId) {
+        self.write(
+            run,
+            WorkflowEvent::Transition {
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+        );
+    }
+
+    fn on_action_complete(
+        &mut self,
+        run: &WorkflowRun,
+        state: &StateId,
+        action: &ActionInvocation,
+        result: &Result<Value, ActionError>,
+    ) {
+        let (output, error) = match result {
+            Ok(Value::Null) => (None, None),
+            Ok(Value::String(output)) => (Some(excerpt(output)), None),
+            Ok(value) => (Some(excerpt(&value.to_string())), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        let exit_code = match action.action_type.as_str() {
+            "shell" => run.context.get("exit_code").and_then(|v| v.as_i64()),
+            _ => None,
+        };
+        let usage = match action.action_type.as_str() {
+            "prompt" => last_prompt_token_usage(run, &action.description),
+            _ => None,
+        };
+        self.write(
+            run,
+            WorkflowEvent::ActionOutput {
+                state: state.to_string(),
+                action: action.description.clone(),
+                action_type: action.action_type.clone(),
+                duration_ms: action.duration.as_millis() as u64,
+                exit_code,
+                prompt: usage.as_ref().map(|u| u.prompt.clone()),
+                tokens: usage.map(|u| u.total_tokens),
+                success: result.is_ok(),
+                output,
+                error,
+            },
+        );
+    }
+
+    fn on_variable_set(&mut self, run: &WorkflowRun, name: &str, value: &Value) {
+        self.write(
+            run,
+            WorkflowEvent::VariableSet {
+                name: name.to_string(),
+                value: value.clone(),
+            },
+        );
+    }
+
+    fn on_run_end(&mut self, run: &WorkflowRun, error: Option<&ExecutorError>) {
+        self.write(
+            run,
+            WorkflowEvent::RunEnd {
+                status: final_run_status(run, error),
+                error: error.map(|e| e.to_string()),
+            },
+        );
+    }
+}
+
+/// Token usage of the prompt an action just sent, if it was recorded
+///
+/// Prompt actions append their usage to the run context before calling the
+/// agent; responses served from the cache record nothing, so the last entry is
+/// only used when it names the prompt in the action's description.
+fn last_prompt_token_usage(run: &WorkflowRun, description: &str) -> Option<PromptTokenUsage> {
+    let entries = run
+        .context
+        .get(PROMPT_TOKEN_USAGE_CONTEXT_KEY)?
+        .as_array()?;
+    let usage: PromptTokenUsage = serde_json::from_value(entries.last()?.clone()).ok()?;
+    description
+        .contains(&format!("'{}'", usage.prompt))
+        .then_some(usage)
+}
+
+/// Shorten action output to [`ACTION_OUTPUT_EXCERPT_CHARS`] characters
+fn excerpt(output: &str) -> String {
+    match output.char_indices().nth(ACTION_OUTPUT_EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}…", &output[..end]),
+        None => output.to_string(),
+    }
+}
+
+/// Incremental reader for a run's JSONL event file
+///
+/// Each call to [`read_new`](Self::read_new) returns the records appended
+/// since the previous call. A trailing line that is still being written is
+/// left for the next call, and lines that fail to parse are skipped.
+pub struct WorkflowEventReader {
+    path: PathBuf,
+    offset: u64,
+}
+
+impl WorkflowEventReader {
+    /// Read the event file at `path` from the beginning
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            offset: 0,
+        }
+    }
+
+    /// Path of the event file being read
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records appended since the last call; empty while the file does not exist
+    pub fn read_new(&mut self) -> std::io::Result<Vec<WorkflowEventRecord>> {
+        let mut file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buffer = String::new();
+        file.read_to_string(&mut buffer)?;
+
+        let complete = match buffer.rfind('\n') {
+            Some(index) => &buffer[..=index],
+            None => return Ok(Vec::new()),
+        };
+        self.offset += complete.len() as u64;
+
+        Ok(parse_event_lines(complete))
+    }
+}
+
+/// Read every event recorded for a run stored under `base_path`
+///
+/// Falls back to the compressed event file of an archived run, and returns no
+/// events when the run has no event log.
+pub fn read_run_events(
+    base_path: &Path,
+    id: &WorkflowRunId,
+) -> crate::Result<Vec<WorkflowEventRecord>> {
+    let path = FileSystemWorkflowRunStorage::run_events_path(base_path, id);
+    if path.exists() {
+        return Ok(WorkflowEventReader::new(path).read_new()?);
+    }
+
+    let archive_path = FileSystemWorkflowRunStorage::run_archived_events_path(base_path, id);
+    if !archive_path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = FileSystemWorkflowRunStorage::read_archived_file(&archive_path)?;
+    Ok(parse_event_lines(&String::from_utf8_lossy(&data)))
+}
+
+/// Parse JSONL event records, skipping malformed lines
+fn parse_event_lines(text: &str) -> Vec<WorkflowEventRecord> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                tracing::debug!("Skipping malformed workflow event: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Observer feeding a [`WorkflowMetrics`] collector that can be shared with other components
+#[derive(Default)]
+pub struct MetricsObserver {
+    metrics: Arc<Mutex<WorkflowMetrics>>,
+}
+
+impl MetricsObserver {
+    /// Feed a new, empty metrics collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed an existing metrics collector
+    pub fn with_metrics(metrics: Arc<Mutex<WorkflowMetrics>>) -> Self {
+        Self { metrics }
+    }
+
+    /// The metrics collector this observer feeds
+    pub fn metrics(&self) -> Arc<Mutex<WorkflowMetrics>> {
+        self.metrics.clone()
+    }
+
+    fn update(&self, f: impl FnOnce(&mut WorkflowMetrics)) {
+        match self.metrics.lock() {
+            Ok(mut metrics) => f(&mut metrics),
+            Err(e) => tracing::warn!("Workflow metrics lock poisoned: {}", e),
+        }
+    }
+}
+
+impl WorkflowObserver for MetricsObserver {
+    fn on_run_start(&mut self, run: &WorkflowRun) {
+        self.update(|metrics| metrics.start_run(run.id, run.workflow.name.clone()));
+    }
+
+    fn on_state_exit(&mut self, run: &WorkflowRun, state: &StateId, outcome: &StateOutcome) {
+        self.update(|metrics| {
+            metrics.record_state_execution(&run.id, state.clone(), outcome.duration)
+        });
+    }
+
+    fn on_transition(&mut self, run: &WorkflowRun, _from: &StateId, _to: &StateId) {
+        self.update(|metrics| metrics.record_transition(&run.id));
+    }
+
+    fn on_run_end(&mut self, run: &WorkflowRun, error: Option<&ExecutorError>) {
+        let status = final_run_status(run, error);
+        self.update(|metrics| metrics.complete_run(&run.id, status, error.map(|e| e.to_string())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::test_helpers::*;
+    use tempfile::TempDir;
+
+    fn test_run(temp_dir: &TempDir) -> WorkflowRun {
+        let mut workflow = create_workflow("observed", "Observed workflow", "start");
+        workflow.add_state(create_state("start", "Start state", false));
+        workflow.add_state(create_state("end", "End state", true));
+        let abort_path = temp_dir.path().join(".abort");
+        WorkflowRun::new_with_abort_path(workflow, abort_path.to_str().unwrap())
+    }
+
+    #[test]
+    fn test_jsonl_observer_appends_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let run = test_run(&temp_dir);
+        let mut observer = JsonlEventObserver::new(temp_dir.path().join("workflow-runs"));
+
+        observer.on_run_start(&run);
+        observer.on_state_exit(
+            &run,
+            &StateId::new("start"),
+            &StateOutcome {
+                duration: Duration::from_millis(42),
+                success: true,
+                error: None,
+            },
+        );
+        observer.on_variable_set(&run, "greeting", &Value::String("hi".to_string()));
+        observer.on_run_end(
+            &run,
+            Some(&ExecutorError::ExecutionFailed("boom".to_string())),
+        );
+
+        let content = std::fs::read_to_string(observer.events_path(&run)).unwrap();
+        let records: Vec<WorkflowEventRecord> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(records.len(), 4);
+        assert!(records.iter().all(|r| r.run_id == run.id.to_string()));
+        assert_eq!(
+            records[0].event,
+            WorkflowEvent::RunStart {
+                state: "start".to_string()
+            }
+        );
+        assert_eq!(
+            records[1].event,
+            WorkflowEvent::StateExit {
+                state: "start".to_string(),
+                duration_ms: 42,
+                success: true,
+                error: None,
+            }
+        );
+        assert!(content
+            .lines()
+            .nth(2)
+            .unwrap()
+            .contains("\"event\":\"variable_set\""));
+        assert_eq!(
+            records[3].event,
+            WorkflowEvent::RunEnd {
+                status: WorkflowRunStatus::Failed,
+                error: Some("Execution failed: boom".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_event_reader_returns_only_new_complete_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let run = test_run(&temp_dir);
+        let mut observer = JsonlEventObserver::new(temp_dir.path());
+        let mut reader = WorkflowEventReader::new(observer.events_path(&run));
+
+        assert!(reader.read_new().unwrap().is_empty());
+
+        observer.on_run_start(&run);
+        observer.on_state_enter(&run, &StateId::new("start"));
+        assert_eq!(reader.read_new().unwrap().len(), 2);
+        assert!(reader.read_new().unwrap().is_empty());
+
+        // A partially written line is left for the next read
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(reader.path())
+            .unwrap();
+        write!(file, "{{\"timestamp\":").unwrap();
+        assert!(reader.read_new().unwrap().is_empty());
+        writeln!(file).unwrap();
+        observer.on_action_complete(
+            &run,
+            &StateId::new("start"),
+            &ActionInvocation {
+                action_type: "shell".to_string(),
+                description: "Execute shell command: false".to_string(),
+                duration: Duration::from_millis(3),
+            },
+            &Err(ActionError::ExecutionError("exit code 1".to_string())),
+        );
+
+        let records = reader.read_new().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].level, EventLevel::Error);
+        assert!(records[0].event.involves_state("start"));
+        assert!(!records[0].event.involves_state("end"));
+    }
+
+    #[test]
+    fn test_event_levels() {
+        assert_eq!("WARNING".parse::<EventLevel>().unwrap(), EventLevel::Warn);
+        assert!("loud".parse::<EventLevel>().is_err());
+        assert!(EventLevel::Error > EventLevel::Info);
+
+        let set = WorkflowEvent::VariableSet {
+            name: "x".to_string(),
+            value: Value::Bool(true),
+        };
+        assert_eq!(set.level(), EventLevel::Debug);
+        let paused = WorkflowEvent::RunEnd {
+            status: WorkflowRunStatus::Paused,
+            error: None,
+        };
+        assert_eq!(paused.level(), EventLevel::Warn);
+        assert_eq!(
+            excerpt(&"x".repeat(ACTION_OUTPUT_EXCERPT_CHARS + 10))
+                .chars()
+                .count(),
+            ACTION_OUTPUT_EXCERPT_CHARS + 1
+        );
+    }
+
+    #[test]
+    fn test_metrics_observer_feeds_shared_metrics() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut run = test_run(&temp_dir);
+        let mut observer = MetricsObserver::new();
+        let metrics = observer.metrics();
+
+        observer.on_run_start(&run);
+        observer.on_state_exit(
+            &run,
+            &StateId::new("start"),
+            &StateOutcome {
+                duration: Duration::from_millis(5),
+                success: true,
+                error: None,
+            },
+        );
+        observer.on_transition(&run, &StateId::new("start"), &StateId::new("end"));
+        run.complete();
+        observer.on_run_end(&run, None);
+
+        let metrics = metrics.lock().unwrap();
+        let run_metrics = metrics.get_run_metrics(&run.id).unwrap();
+        assert_eq!(run_metrics.status, WorkflowRunStatus::Completed);
+        assert_eq!(run_metrics.transition_count, 1);
+        assert_eq!(
+            run_metrics.state_durations.get(&StateId::new("start")),
+            Some(&Duration::from_millis(5))
+        );
+    }
+}
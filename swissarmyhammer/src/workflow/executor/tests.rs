@@ -1896,3 +1896,156 @@ async fn test_abort_file_not_present_normal_execution() {
     assert_eq!(run.current_state.as_str(), "end");
     assert_eq!(run.status, WorkflowRunStatus::Completed);
 }
+
+fn create_approval_workflow() -> Workflow {
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Approval Gate Test"),
+        "Test approval gates".to_string(),
+        StateId::new("gate"),
+    );
+
+    workflow.add_state(create_state("gate", "Await approval \"Ship it?\"", false));
+    workflow.add_state(create_state("shipped", "Shipped", true));
+    workflow.add_state(create_state("rejected", "Rejected", true));
+
+    workflow.add_transition(create_transition(
+        "gate",
+        "shipped",
+        ConditionType::OnSuccess,
+    ));
+    workflow.add_transition(create_transition(
+        "gate",
+        "rejected",
+        ConditionType::OnFailure,
+    ));
+
+    workflow
+}
+
+#[tokio::test]
+async fn test_approval_gate_pauses_and_resumes_on_approval() {
+    use crate::workflow::approval::{record_approval_decision, ApprovalDecision};
+
+    let _test_env = IsolatedTestEnvironment::new().expect("Failed to create test environment");
+    let mut executor = WorkflowExecutor::new();
+
+    let run = executor
+        .start_and_execute_workflow(create_approval_workflow())
+        .await
+        .unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Paused);
+    assert_eq!(run.current_state.as_str(), "gate");
+
+    let mut run = run;
+    let pending = record_approval_decision(
+        &mut run,
+        ApprovalDecision::approve(None).with_decided_by("bob"),
+    )
+    .unwrap();
+    assert_eq!(pending.state, "gate");
+    assert_eq!(pending.message, "Ship it?");
+
+    let run = WorkflowExecutor::new().resume_workflow(run).await.unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+    assert_eq!(run.current_state.as_str(), "shipped");
+    assert_eq!(run.context.get("approval").unwrap()["decided_by"], "bob");
+}
+
+#[tokio::test]
+async fn test_approval_gate_rejection_follows_failure_transition() {
+    use crate::workflow::approval::{record_approval_decision, ApprovalDecision};
+
+    let _test_env = IsolatedTestEnvironment::new().expect("Failed to create test environment");
+    let mut executor = WorkflowExecutor::new();
+
+    let mut run = executor
+        .start_and_execute_workflow(create_approval_workflow())
+        .await
+        .unwrap();
+    record_approval_decision(
+        &mut run,
+        ApprovalDecision::reject(Some("not today".to_string())),
+    )
+    .unwrap();
+
+    let run = WorkflowExecutor::new().resume_workflow(run).await.unwrap();
+    assert_eq!(run.current_state.as_str(), "rejected");
+    assert_eq!(run.context.get("approval").unwrap()["approved"], false);
+}
+
+/// Observer recording a one-line summary of every callback
+struct RecordingObserver {
+    events: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+impl WorkflowObserver for RecordingObserver {
+    fn on_run_start(&mut self, run: &WorkflowRun) {
+        self.push(format!("run_start {}", run.current_state));
+    }
+
+    fn on_state_enter(&mut self, _run: &WorkflowRun, state: &StateId) {
+        self.push(format!("enter {state}"));
+    }
+
+    fn on_state_exit(&mut self, _run: &WorkflowRun, state: &StateId, outcome: &StateOutcome) {
+        self.push(format!("exit {state} {}", outcome.success));
+    }
+
+    fn on_transition(&mut self, _run: &WorkflowRun, from: &StateId, to: &StateId) {
+        self.push(format!("transition {from} -> {to}"));
+    }
+
+    fn on_variable_set(&mut self, _run: &WorkflowRun, name: &str, value: &Value) {
+        if name == "greeting" {
+            self.push(format!("set {name}={value}"));
+        }
+    }
+
+    fn on_run_end(&mut self, run: &WorkflowRun, error: Option<&ExecutorError>) {
+        self.push(format!("run_end {:?} {}", run.status, error.is_some()));
+    }
+}
+
+impl RecordingObserver {
+    fn push(&self, event: String) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[tokio::test]
+async fn test_observers_receive_run_lifecycle() {
+    let _test_env = IsolatedTestEnvironment::new().expect("Failed to create test environment");
+
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Observed Workflow"),
+        "Test workflow observers".to_string(),
+        StateId::new("start"),
+    );
+    workflow.add_state(create_state("start", "Set greeting=\"hello\"", false));
+    workflow.add_state(create_state("end", "End state", true));
+    workflow.add_transition(create_transition("start", "end", ConditionType::Always));
+
+    let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut executor = WorkflowExecutor::new();
+    executor.add_observer(RecordingObserver {
+        events: events.clone(),
+    });
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+
+    let events = events.lock().unwrap();
+    assert_eq!(
+        *events,
+        vec![
+            "run_start start",
+            "enter start",
+            "set greeting=\"hello\"",
+            "exit start true",
+            "transition start -> end",
+            "enter end",
+            "exit end true",
+            "run_end Completed false",
+        ]
+    );
+}
@@ -3,7 +3,7 @@
 //! This module provides comprehensive metrics tracking for workflow execution,
 //! including timing, success/failure rates, and resource usage statistics.
 
-use crate::workflow::{StateId, WorkflowName, WorkflowRunId, WorkflowRunStatus};
+use crate::workflow::{PromptTokenUsage, StateId, WorkflowName, WorkflowRunId, WorkflowRunStatus};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -61,6 +61,15 @@ pub struct RunMetrics {
     pub memory_metrics: MemoryMetrics,
     /// Error details if run failed
     pub error_details: Option<String>,
+    /// Number of agent prompts served from the response cache
+    #[serde(default)]
+    pub agent_cache_hits: usize,
+    /// Number of agent prompts that missed the response cache
+    #[serde(default)]
+    pub agent_cache_misses: usize,
+    /// Token counts of each prompt sent to an agent
+    #[serde(default)]
+    pub prompt_token_usage: Vec<PromptTokenUsage>,
 }
 
 /// Memory usage metrics for a workflow run
@@ -175,6 +184,9 @@ impl WorkflowMetrics {
             transition_count: 0,
             memory_metrics: MemoryMetrics::new(),
             error_details: None,
+            agent_cache_hits: 0,
+            agent_cache_misses: 0,
+            prompt_token_usage: Vec::new(),
         };
 
         self.run_metrics.insert(run_id, run_metrics);
@@ -244,6 +256,30 @@ impl WorkflowMetrics {
         self.update_global_metrics();
     }
 
+    /// Update agent response cache counters for a run
+    pub fn update_agent_cache_metrics(
+        &mut self,
+        run_id: &WorkflowRunId,
+        hits: usize,
+        misses: usize,
+    ) {
+        if let Some(run_metrics) = self.run_metrics.get_mut(run_id) {
+            run_metrics.agent_cache_hits = hits;
+            run_metrics.agent_cache_misses = misses;
+        }
+    }
+
+    /// Update the token counts of the prompts executed in a run
+    pub fn update_prompt_token_usage(
+        &mut self,
+        run_id: &WorkflowRunId,
+        usage: Vec<PromptTokenUsage>,
+    ) {
+        if let Some(run_metrics) = self.run_metrics.get_mut(run_id) {
+            run_metrics.prompt_token_usage = usage;
+        }
+    }
+
     /// Update memory metrics for a run
     pub fn update_memory_metrics(&mut self, run_id: &WorkflowRunId, memory_metrics: MemoryMetrics) {
         if let Some(run_metrics) = self.run_metrics.get_mut(run_id) {
@@ -608,7 +644,9 @@ impl Default for ResourceTrends {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::workflow::{StateId, WorkflowName, WorkflowRunId, WorkflowRunStatus};
+    use crate::workflow::{
+        PromptTokenUsage, StateId, WorkflowName, WorkflowRunId, WorkflowRunStatus,
+    };
     use std::time::Duration;
 
     #[test]
@@ -661,6 +699,56 @@ mod tests {
         assert_eq!(run_metrics.state_durations.get(&state_id), Some(&duration));
     }
 
+    #[test]
+    fn test_update_agent_cache_metrics() {
+        let mut metrics = WorkflowMetrics::new();
+        let run_id = WorkflowRunId::new();
+
+        metrics.start_run(run_id, WorkflowName::new("test_workflow"));
+        metrics.update_agent_cache_metrics(&run_id, 3, 1);
+
+        let run_metrics = metrics
+            .run_metrics
+            .get(&run_id)
+            .expect("Run metrics should exist after start_run");
+        assert_eq!(run_metrics.agent_cache_hits, 3);
+        assert_eq!(run_metrics.agent_cache_misses, 1);
+    }
+
+    #[test]
+    fn test_update_prompt_token_usage() {
+        use crate::workflow::{ContextBudget, ModelFamily, OverflowStrategy};
+
+        let mut metrics = WorkflowMetrics::new();
+        let run_id = WorkflowRunId::new();
+
+        metrics.start_run(run_id, WorkflowName::new("test_workflow"));
+        metrics.update_prompt_token_usage(
+            &run_id,
+            vec![PromptTokenUsage {
+                prompt: "code".to_string(),
+                model_family: ModelFamily::Qwen,
+                budget: ContextBudget {
+                    context_window: 32_768,
+                    window_estimated: false,
+                    reserve_tokens: 4096,
+                    strategy: OverflowStrategy::Truncate,
+                },
+                system_tokens: 100,
+                original_tokens: 900,
+                total_tokens: 1000,
+                sections: Vec::new(),
+            }],
+        );
+
+        let run_metrics = metrics
+            .run_metrics
+            .get(&run_id)
+            .expect("Run metrics should exist after start_run");
+        assert_eq!(run_metrics.prompt_token_usage.len(), 1);
+        assert_eq!(run_metrics.prompt_token_usage[0].total_tokens, 1000);
+    }
+
     #[test]
     fn test_memory_metrics() {
         let mut memory_metrics = MemoryMetrics::new();
@@ -8,6 +8,7 @@ pub mod actions;
 #[cfg(test)]
 mod actions_tests;
 mod agents;
+pub mod approval;
 mod cache;
 mod definition;
 mod error_utils;
@@ -21,8 +22,11 @@ mod graph;
 mod graph_tests;
 mod mcp_integration;
 mod metrics;
+mod otlp;
 mod parser;
+mod retention;
 mod run;
+mod run_diff;
 mod state;
 mod storage;
 pub mod template_context;
@@ -43,10 +47,18 @@ pub use actions::{
     parse_action_from_description_with_context, validate_command,
     validate_environment_variables_security, validate_working_directory_security, Action,
     ActionError, ActionResult, AgentExecutionContext, AgentExecutor, AgentExecutorFactory,
-    LogAction, LogLevel, PromptAction, SetVariableAction, ShellAction, SubWorkflowAction,
-    WaitAction,
+    ApprovalAction, LogAction, LogLevel, PromptAction, SetVariableAction, ShellAction,
+    SubWorkflowAction, WaitAction,
 };
-pub use agents::LlamaAgentExecutor;
+pub use agents::{context_window, model_catalog};
+pub use agents::{
+    AgentResponseCache, AgentResponseCacheConfig, AgentResponseCacheStats, CachedAgentResponse,
+    CachingAgentExecutor, ChecksumStatus, ContextBudget, ContextOverflowError, GgufModelInfo,
+    LlamaAgentExecutor, LocalModel, ModelCatalog, ModelFamily, ModelProfile, ModelVerification,
+    OverflowStrategy, PackedPrompt, PromptPacker, PromptTokenUsage, SectionAction,
+    SectionTokenCount,
+};
+pub use approval::{record_approval_decision, ApprovalDecision, PendingApproval};
 pub use cache::{
     CacheStats, CelProgramCache, TransitionCache, TransitionPath, WorkflowCache,
     WorkflowCacheManager,
@@ -57,7 +69,9 @@ pub use error_utils::{
     handle_command_error, handle_command_error_with_mapper,
 };
 pub use executor::{
-    ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult, WorkflowExecutor,
+    read_run_events, ActionInvocation, EventLevel, ExecutionEvent, ExecutionEventType,
+    ExecutorError, ExecutorResult, JsonlEventObserver, MetricsObserver, StateOutcome,
+    WorkflowEvent, WorkflowEventReader, WorkflowEventRecord, WorkflowExecutor, WorkflowObserver,
 };
 pub use graph::{GraphError, GraphResult, WorkflowGraphAnalyzer};
 pub use mcp_integration::{response_processing, WorkflowShellContext};
@@ -65,15 +79,26 @@ pub use metrics::{
     GlobalMetrics, MemoryMetrics, ResourceTrends, RunMetrics, StateExecutionCount, WorkflowMetrics,
     WorkflowSummaryMetrics,
 };
+pub use otlp::{
+    otlp_traces_url, OtlpAttribute, OtlpSpan, OtlpStatus, OtlpTrace, OtlpValue,
+    DEFAULT_OTLP_SERVICE_NAME, OTLP_SCOPE_NAME,
+};
 pub use parser::{MermaidParser, ParseError, ParseResult};
+pub use retention::{
+    prune_run_storage, run_storage_size, scan_run_storage, PruneAction, PruneDecision, PruneReport,
+    RunRetentionPolicy, StoredRunInfo, RUN_RETENTION_CONFIG_KEY,
+};
 pub use run::{WorkflowRun, WorkflowRunId, WorkflowRunStatus};
+pub use run_diff::{
+    OutputComparison, PathDivergence, RunDiff, RunSnapshot, RunSummary, StateDelta, VariableChange,
+};
 pub use state::{
     CompensationKey, ErrorContext, State, StateError, StateId, StateResult, StateType,
 };
 pub use storage::{
     CompressedWorkflowStorage, FileSystemWorkflowRunStorage, FileSystemWorkflowStorage,
     MemoryWorkflowRunStorage, MemoryWorkflowStorage, WorkflowResolver, WorkflowRunStorageBackend,
-    WorkflowStorage, WorkflowStorageBackend,
+    WorkflowStorage, WorkflowStorageBackend, LOCAL_RUN_STORAGE_DIR,
 };
 pub use template_context::WorkflowTemplateContext;
 pub use transition::{ConditionType, Transition, TransitionCondition};
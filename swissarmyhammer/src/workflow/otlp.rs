@@ -0,0 +1,563 @@
+//! OpenTelemetry trace export for workflow runs
+//!
+//! Converts an [`ExecutionTrace`] into OTLP spans: one span for the run, a child
+//! span per executed state and, when the run's event log is available, a child
+//! span per agent prompt or tool call with the prompt name, token count and exit
+//! code as attributes. The result is encoded as an OTLP/JSON
+//! `ExportTraceServiceRequest`, which can be written to a file for offline
+//! analysis or posted to any OTLP/HTTP collector such as Jaeger or Tempo.
+//!
+//! Trace and span IDs are derived from the run ID, so exporting the same run
+//! twice produces the same trace.
+
+use crate::workflow::{ExecutionTrace, WorkflowEvent, WorkflowEventRecord, WorkflowRunStatus};
+use crate::{Result, SwissArmyHammerError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+/// Service name reported when none is configured
+pub const DEFAULT_OTLP_SERVICE_NAME: &str = "swissarmyhammer";
+
+/// Instrumentation scope of the exported spans
+pub const OTLP_SCOPE_NAME: &str = "swissarmyhammer.workflow";
+
+/// Path OTLP/HTTP collectors accept traces on
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+
+/// Span kind `SPAN_KIND_INTERNAL`
+const SPAN_KIND_INTERNAL: u8 = 1;
+
+/// Span kind `SPAN_KIND_CLIENT`, used for calls out to agents and tools
+const SPAN_KIND_CLIENT: u8 = 3;
+
+/// Value of a span attribute in OTLP/JSON encoding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OtlpValue {
+    /// String attribute
+    StringValue(String),
+    /// Integer attribute, encoded as a decimal string like other 64-bit values
+    IntValue(String),
+    /// Boolean attribute
+    BoolValue(bool),
+}
+
+/// Span attribute
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OtlpAttribute {
+    /// Attribute name
+    pub key: String,
+    /// Attribute value
+    pub value: OtlpValue,
+}
+
+impl OtlpAttribute {
+    /// String attribute
+    pub fn string(key: &str, value: impl Into<String>) -> Self {
+        Self {
+            key: key.to_string(),
+            value: OtlpValue::StringValue(value.into()),
+        }
+    }
+
+    /// Integer attribute
+    pub fn int(key: &str, value: i64) -> Self {
+        Self {
+            key: key.to_string(),
+            value: OtlpValue::IntValue(value.to_string()),
+        }
+    }
+
+    /// Boolean attribute
+    pub fn bool(key: &str, value: bool) -> Self {
+        Self {
+            key: key.to_string(),
+            value: OtlpValue::BoolValue(value),
+        }
+    }
+}
+
+/// Span status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OtlpStatus {
+    /// `1` for OK, `2` for error
+    pub code: u8,
+    /// Error description
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub message: String,
+}
+
+impl OtlpStatus {
+    fn ok() -> Self {
+        Self {
+            code: 1,
+            message: String::new(),
+        }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            code: 2,
+            message: message.into(),
+        }
+    }
+}
+
+/// A span in OTLP/JSON encoding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtlpSpan {
+    /// 32 hex character trace ID
+    pub trace_id: String,
+    /// 16 hex character span ID
+    pub span_id: String,
+    /// Span ID of the parent, empty for the root span
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub parent_span_id: String,
+    /// Span name
+    pub name: String,
+    /// Span kind
+    pub kind: u8,
+    /// Start time in nanoseconds since the Unix epoch
+    pub start_time_unix_nano: String,
+    /// End time in nanoseconds since the Unix epoch
+    pub end_time_unix_nano: String,
+    /// Span attributes
+    pub attributes: Vec<OtlpAttribute>,
+    /// Span status
+    pub status: OtlpStatus,
+}
+
+/// The spans of one workflow run
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtlpTrace {
+    /// Service name reported in the resource attributes
+    pub service_name: String,
+    /// Run span followed by its descendants
+    pub spans: Vec<OtlpSpan>,
+}
+
+impl OtlpTrace {
+    /// Build the spans for a run from its trace and, if available, its recorded events
+    ///
+    /// Events add agent and tool call spans; without them only the run and
+    /// state spans are produced.
+    pub fn from_trace(trace: &ExecutionTrace, events: &[WorkflowEventRecord]) -> Self {
+        let trace_id = trace_id_for_run(&trace.run_id);
+        let run_span_id = span_id_for(&trace.run_id, "run");
+
+        let run_end = trace
+            .completed_at
+            .or_else(|| {
+                trace
+                    .execution_path
+                    .last()
+                    .map(|step| step.timestamp + to_chrono(step.duration.unwrap_or(Duration::ZERO)))
+            })
+            .unwrap_or(trace.started_at);
+
+        let mut run_attributes = vec![
+            OtlpAttribute::string("workflow.name", &trace.workflow_name),
+            OtlpAttribute::string("workflow.run_id", &trace.run_id),
+            OtlpAttribute::string("workflow.status", format!("{:?}", trace.status)),
+            OtlpAttribute::int("workflow.state_count", trace.execution_path.len() as i64),
+        ];
+        if let Some(error) = &trace.error_details {
+            run_attributes.push(OtlpAttribute::string("error.message", error));
+        }
+
+        let mut spans = vec![OtlpSpan {
+            trace_id: trace_id.clone(),
+            span_id: run_span_id.clone(),
+            parent_span_id: String::new(),
+            name: format!("workflow {}", trace.workflow_name),
+            kind: SPAN_KIND_INTERNAL,
+            start_time_unix_nano: unix_nanos(trace.started_at),
+            end_time_unix_nano: unix_nanos(run_end),
+            attributes: run_attributes,
+            status: match (trace.status, &trace.error_details) {
+                (WorkflowRunStatus::Failed, error) => {
+                    OtlpStatus::error(error.clone().unwrap_or_else(|| "Workflow failed".into()))
+                }
+                (_, Some(error)) => OtlpStatus::error(error.clone()),
+                _ => OtlpStatus::ok(),
+            },
+        }];
+
+        // State spans, remembered with their windows so action spans can find their parent
+        let mut state_windows = Vec::new();
+        for (index, step) in trace.execution_path.iter().enumerate() {
+            let start = step.timestamp;
+            let end = match step.duration {
+                Some(duration) => start + to_chrono(duration),
+                None => trace
+                    .execution_path
+                    .get(index + 1)
+                    .map(|next| next.timestamp)
+                    .unwrap_or(run_end),
+            };
+            let span_id = span_id_for(&trace.run_id, &format!("state:{index}"));
+
+            let mut attributes = vec![
+                OtlpAttribute::string("workflow.state", step.state_id.as_str()),
+                OtlpAttribute::int("workflow.step", index as i64),
+            ];
+            if !step.state_description.is_empty() {
+                attributes.push(OtlpAttribute::string(
+                    "workflow.state.description",
+                    &step.state_description,
+                ));
+            }
+            if let Some(next) = &step.transition_taken {
+                attributes.push(OtlpAttribute::string("workflow.transition", next.as_str()));
+            }
+
+            spans.push(OtlpSpan {
+                trace_id: trace_id.clone(),
+                span_id: span_id.clone(),
+                parent_span_id: run_span_id.clone(),
+                name: format!("state {}", step.state_id),
+                kind: SPAN_KIND_INTERNAL,
+                start_time_unix_nano: unix_nanos(start),
+                end_time_unix_nano: unix_nanos(end),
+                attributes,
+                status: match (&step.error, step.success) {
+                    (Some(error), _) => OtlpStatus::error(error.clone()),
+                    (None, false) => OtlpStatus::error("State failed"),
+                    (None, true) => OtlpStatus::ok(),
+                },
+            });
+            state_windows.push((step.state_id.as_str().to_string(), start, span_id));
+        }
+
+        let action_events = events
+            .iter()
+            .filter(|record| record.run_id == trace.run_id)
+            .enumerate();
+        for (index, record) in action_events {
+            let WorkflowEvent::ActionOutput {
+                state,
+                action,
+                action_type,
+                duration_ms,
+                exit_code,
+                prompt,
+                tokens,
+                success,
+                error,
+                ..
+            } = &record.event
+            else {
+                continue;
+            };
+
+            let end = record.timestamp;
+            let start = end - to_chrono(Duration::from_millis(*duration_ms));
+            let parent = state_windows
+                .iter()
+                .rev()
+                .find(|(id, state_start, _)| id == state && *state_start <= end)
+                .map(|(_, _, span_id)| span_id.clone())
+                .unwrap_or_else(|| run_span_id.clone());
+
+            let (name, kind) = match (action_type.as_str(), prompt) {
+                ("prompt", Some(prompt)) => (format!("agent {prompt}"), SPAN_KIND_CLIENT),
+                ("prompt", None) => ("agent prompt".to_string(), SPAN_KIND_CLIENT),
+                ("shell", _) => ("tool shell".to_string(), SPAN_KIND_CLIENT),
+                ("", _) => ("action".to_string(), SPAN_KIND_INTERNAL),
+                (other, _) => (format!("action {other}"), SPAN_KIND_INTERNAL),
+            };
+
+            let mut attributes = vec![
+                OtlpAttribute::string("workflow.state", state),
+                OtlpAttribute::string("workflow.action.type", action_type),
+                OtlpAttribute::string("workflow.action.description", action),
+                OtlpAttribute::bool("workflow.action.success", *success),
+            ];
+            if let Some(prompt) = prompt {
+                attributes.push(OtlpAttribute::string("gen_ai.prompt.name", prompt));
+            }
+            if let Some(tokens) = tokens {
+                attributes.push(OtlpAttribute::int(
+                    "gen_ai.usage.input_tokens",
+                    *tokens as i64,
+                ));
+            }
+            if let Some(exit_code) = exit_code {
+                attributes.push(OtlpAttribute::int("process.exit.code", *exit_code));
+            }
+
+            spans.push(OtlpSpan {
+                trace_id: trace_id.clone(),
+                span_id: span_id_for(&trace.run_id, &format!("action:{index}")),
+                parent_span_id: parent,
+                name,
+                kind,
+                start_time_unix_nano: unix_nanos(start),
+                end_time_unix_nano: unix_nanos(end),
+                attributes,
+                status: match error {
+                    Some(error) => OtlpStatus::error(error.clone()),
+                    None => OtlpStatus::ok(),
+                },
+            });
+        }
+
+        Self {
+            service_name: DEFAULT_OTLP_SERVICE_NAME.to_string(),
+            spans,
+        }
+    }
+
+    /// Report the spans under a different service name
+    pub fn with_service_name(mut self, service_name: impl Into<String>) -> Self {
+        self.service_name = service_name.into();
+        self
+    }
+
+    /// Encode as an OTLP/JSON `ExportTraceServiceRequest`
+    pub fn to_request_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [OtlpAttribute::string("service.name", &self.service_name)]
+                },
+                "scopeSpans": [{
+                    "scope": {
+                        "name": OTLP_SCOPE_NAME,
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                    "spans": self.spans,
+                }]
+            }]
+        })
+    }
+
+    /// Post the trace to an OTLP/HTTP collector
+    ///
+    /// `endpoint` is the collector's base URL, such as `http://localhost:4318`;
+    /// the standard `/v1/traces` path is appended unless already present.
+    pub async fn export_http(&self, endpoint: &str) -> Result<()> {
+        let url = otlp_traces_url(endpoint);
+        let response = reqwest::Client::new()
+            .post(&url)
+            .json(&self.to_request_json())
+            .send()
+            .await
+            .map_err(|e| {
+                SwissArmyHammerError::Other(format!("Failed to send trace to {url}: {e}"))
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(SwissArmyHammerError::Other(format!(
+                "OTLP collector at {url} rejected trace ({status}): {body}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Full URL traces are posted to for a collector endpoint
+pub fn otlp_traces_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with(OTLP_TRACES_PATH) {
+        endpoint.to_string()
+    } else {
+        format!("{endpoint}{OTLP_TRACES_PATH}")
+    }
+}
+
+/// Trace ID for a run; run IDs are ULIDs, which are already 128 bits
+fn trace_id_for_run(run_id: &str) -> String {
+    match ulid::Ulid::from_string(run_id) {
+        Ok(ulid) => format!("{:032x}", ulid.0),
+        Err(_) => hex_digest(run_id, 16),
+    }
+}
+
+/// Span ID for a named part of a run
+fn span_id_for(run_id: &str, part: &str) -> String {
+    hex_digest(&format!("{run_id}/{part}"), 8)
+}
+
+fn hex_digest(input: &str, bytes: usize) -> String {
+    Sha256::digest(input.as_bytes())[..bytes]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn unix_nanos(time: DateTime<Utc>) -> String {
+    time.timestamp_nanos_opt().unwrap_or_default().to_string()
+}
+
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::{ExecutionStep, StateId, WorkflowRunId};
+
+    fn sample_trace(run_id: &str, started_at: DateTime<Utc>) -> ExecutionTrace {
+        ExecutionTrace {
+            run_id: run_id.to_string(),
+            workflow_name: "implement".to_string(),
+            execution_path: vec![
+                ExecutionStep {
+                    state_id: StateId::new("plan"),
+                    state_description: String::new(),
+                    duration: Some(Duration::from_millis(1500)),
+                    timestamp: started_at,
+                    success: true,
+                    error: None,
+                    transition_taken: Some(StateId::new("test")),
+                },
+                ExecutionStep {
+                    state_id: StateId::new("test"),
+                    state_description: String::new(),
+                    duration: Some(Duration::from_millis(500)),
+                    timestamp: started_at + chrono::Duration::seconds(2),
+                    success: false,
+                    error: Some("exit code 1".to_string()),
+                    transition_taken: None,
+                },
+            ],
+            status: WorkflowRunStatus::Failed,
+            total_duration: Some(Duration::from_secs(3)),
+            started_at,
+            completed_at: Some(started_at + chrono::Duration::seconds(3)),
+            error_details: Some("exit code 1".to_string()),
+        }
+    }
+
+    fn action_event(
+        run_id: &str,
+        timestamp: DateTime<Utc>,
+        event: WorkflowEvent,
+    ) -> WorkflowEventRecord {
+        WorkflowEventRecord {
+            timestamp,
+            level: event.level(),
+            run_id: run_id.to_string(),
+            workflow: "implement".to_string(),
+            event,
+        }
+    }
+
+    #[test]
+    fn test_spans_form_run_state_action_hierarchy() {
+        let run_id = WorkflowRunId::new().to_string();
+        let started_at = Utc::now();
+        let trace = sample_trace(&run_id, started_at);
+        let events = vec![
+            action_event(
+                &run_id,
+                started_at + chrono::Duration::milliseconds(1400),
+                WorkflowEvent::ActionOutput {
+                    state: "plan".to_string(),
+                    action: "Execute prompt 'plan'".to_string(),
+                    action_type: "prompt".to_string(),
+                    duration_ms: 1200,
+                    exit_code: None,
+                    prompt: Some("plan".to_string()),
+                    tokens: Some(812),
+                    success: true,
+                    output: None,
+                    error: None,
+                },
+            ),
+            action_event(
+                &run_id,
+                started_at + chrono::Duration::milliseconds(2400),
+                WorkflowEvent::ActionOutput {
+                    state: "test".to_string(),
+                    action: "Execute shell command: cargo test".to_string(),
+                    action_type: "shell".to_string(),
+                    duration_ms: 350,
+                    exit_code: Some(1),
+                    prompt: None,
+                    tokens: None,
+                    success: false,
+                    output: None,
+                    error: Some("exit code 1".to_string()),
+                },
+            ),
+        ];
+
+        let otlp = OtlpTrace::from_trace(&trace, &events);
+        assert_eq!(otlp.spans.len(), 5);
+
+        let run_span = &otlp.spans[0];
+        assert_eq!(run_span.trace_id.len(), 32);
+        assert_eq!(run_span.span_id.len(), 16);
+        assert!(run_span.parent_span_id.is_empty());
+        assert_eq!(run_span.status.code, 2);
+        assert!(otlp.spans.iter().all(|s| s.trace_id == run_span.trace_id));
+
+        let plan_span = &otlp.spans[1];
+        assert_eq!(plan_span.parent_span_id, run_span.span_id);
+        assert_eq!(plan_span.name, "state plan");
+
+        let agent_span = &otlp.spans[3];
+        assert_eq!(agent_span.name, "agent plan");
+        assert_eq!(agent_span.parent_span_id, plan_span.span_id);
+        assert!(agent_span
+            .attributes
+            .contains(&OtlpAttribute::int("gen_ai.usage.input_tokens", 812)));
+
+        let tool_span = &otlp.spans[4];
+        assert_eq!(tool_span.name, "tool shell");
+        assert_eq!(tool_span.parent_span_id, otlp.spans[2].span_id);
+        assert!(tool_span
+            .attributes
+            .contains(&OtlpAttribute::int("process.exit.code", 1)));
+        assert_eq!(tool_span.status.code, 2);
+        let tool_start: i64 = tool_span.start_time_unix_nano.parse().unwrap();
+        let tool_end: i64 = tool_span.end_time_unix_nano.parse().unwrap();
+        assert_eq!(tool_end - tool_start, 350_000_000);
+
+        // Exporting the same run again yields the same IDs
+        assert_eq!(OtlpTrace::from_trace(&trace, &events), otlp);
+    }
+
+    #[test]
+    fn test_request_json_encoding() {
+        let run_id = WorkflowRunId::new().to_string();
+        let trace = sample_trace(&run_id, Utc::now());
+        let json = OtlpTrace::from_trace(&trace, &[])
+            .with_service_name("ci")
+            .to_request_json();
+
+        let resource = &json["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "ci"
+        );
+        let spans = resource["scopeSpans"][0]["spans"].as_array().unwrap();
+        assert_eq!(spans.len(), 3);
+        assert!(spans[0]["startTimeUnixNano"].is_string());
+        assert!(spans[1]["parentSpanId"].is_string());
+        assert_eq!(
+            spans[0]["attributes"][0],
+            serde_json::json!({"key": "workflow.name", "value": {"stringValue": "implement"}})
+        );
+    }
+
+    #[test]
+    fn test_otlp_traces_url() {
+        assert_eq!(
+            otlp_traces_url("http://localhost:4318"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            otlp_traces_url("http://localhost:4318/v1/traces/"),
+            "http://localhost:4318/v1/traces"
+        );
+    }
+}
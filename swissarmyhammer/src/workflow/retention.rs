@@ -0,0 +1,430 @@
+//! Retention of stored workflow runs
+//!
+//! Every `sah flow run` leaves a directory in run storage with its event log,
+//! plus the run itself when it failed or paused. A [`RunRetentionPolicy`],
+//! configured under `[run_retention]` in `sah.toml`, decides which of those
+//! directories are removed and which are compressed with zstd:
+//!
+//! - the newest `keep_last` runs of each workflow are always kept
+//! - older completed runs are removed after `max_age_days`
+//! - failed, cancelled and interrupted runs are removed after `failed_max_age_days`
+//! - paused runs are never removed, since they may still be approved or resumed
+//! - kept runs older than `compress_after_days` are archived
+
+use crate::workflow::{
+    read_run_events, FileSystemWorkflowRunStorage, WorkflowEvent, WorkflowRunId, WorkflowRunStatus,
+    WorkflowRunStorageBackend,
+};
+use crate::{Result, SwissArmyHammerError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Config key of the retention policy in `sah.toml`
+pub const RUN_RETENTION_CONFIG_KEY: &str = "run_retention";
+
+/// How long stored workflow runs are kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunRetentionPolicy {
+    /// Newest runs of each workflow that are always kept
+    pub keep_last: usize,
+    /// Days after which other completed runs are removed
+    pub max_age_days: u32,
+    /// Days after which other failed, cancelled and interrupted runs are removed
+    pub failed_max_age_days: u32,
+    /// Days after which kept runs are compressed, 0 to never compress
+    pub compress_after_days: u32,
+    /// Size of run storage in megabytes above which `sah doctor` warns
+    pub size_warning_mb: u64,
+}
+
+impl Default for RunRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            keep_last: 20,
+            max_age_days: 7,
+            failed_max_age_days: 30,
+            compress_after_days: 3,
+            size_warning_mb: 500,
+        }
+    }
+}
+
+impl RunRetentionPolicy {
+    /// Read a policy from the `[run_retention]` config table, defaulting missing keys
+    pub fn from_config_value(value: &serde_json::Value) -> Result<Self> {
+        serde_json::from_value(value.clone()).map_err(|e| {
+            SwissArmyHammerError::Config(format!("Invalid {RUN_RETENTION_CONFIG_KEY} config: {e}"))
+        })
+    }
+
+    /// Decide what happens to each stored run
+    ///
+    /// Runs that are simply kept are left out of the result.
+    pub fn plan(&self, runs: &[StoredRunInfo], now: DateTime<Utc>) -> Vec<PruneDecision> {
+        let mut by_workflow: HashMap<&str, Vec<&StoredRunInfo>> = HashMap::new();
+        for run in runs {
+            by_workflow.entry(&run.workflow).or_default().push(run);
+        }
+
+        let mut decisions = Vec::new();
+        for workflow_runs in by_workflow.values_mut() {
+            workflow_runs.sort_by_key(|run| std::cmp::Reverse(run.started_at));
+
+            for (index, run) in workflow_runs.iter().enumerate() {
+                let age_days = (now - run.started_at).num_days().max(0) as u32;
+                let max_age_days = match run.status {
+                    WorkflowRunStatus::Paused => None,
+                    WorkflowRunStatus::Completed => Some(self.max_age_days),
+                    WorkflowRunStatus::Failed
+                    | WorkflowRunStatus::Cancelled
+                    | WorkflowRunStatus::Running => Some(self.failed_max_age_days),
+                };
+
+                if let Some(max_age_days) = max_age_days {
+                    if index >= self.keep_last && age_days > max_age_days {
+                        decisions.push(PruneDecision {
+                            run: (*run).clone(),
+                            action: PruneAction::Remove,
+                            reason: format!(
+                                "{:?} {age_days} days ago, beyond the newest {} runs of {}",
+                                run.status, self.keep_last, run.workflow
+                            ),
+                        });
+                        continue;
+                    }
+                }
+
+                let finished = !matches!(
+                    run.status,
+                    WorkflowRunStatus::Paused | WorkflowRunStatus::Running
+                );
+                if self.compress_after_days > 0
+                    && finished
+                    && !run.archived
+                    && age_days >= self.compress_after_days
+                {
+                    decisions.push(PruneDecision {
+                        run: (*run).clone(),
+                        action: PruneAction::Archive,
+                        reason: format!("older than {} days", self.compress_after_days),
+                    });
+                }
+            }
+        }
+
+        decisions.sort_by_key(|decision| decision.run.started_at);
+        decisions
+    }
+}
+
+/// A run directory found in run storage
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredRunInfo {
+    /// Run ID
+    pub id: WorkflowRunId,
+    /// Directory holding the run's files
+    pub dir: PathBuf,
+    /// Workflow name
+    pub workflow: String,
+    /// Final status; runs whose event log never recorded an end are `Running`
+    pub status: WorkflowRunStatus,
+    /// When the run started
+    pub started_at: DateTime<Utc>,
+    /// Whether the run's files are already compressed
+    pub archived: bool,
+    /// Size of the run's files in bytes
+    pub size_bytes: u64,
+}
+
+/// What happens to a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PruneAction {
+    /// Delete the run directory
+    Remove,
+    /// Compress the run's files
+    Archive,
+}
+
+/// A run that retention removes or archives, and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct PruneDecision {
+    /// The affected run
+    pub run: StoredRunInfo,
+    /// What happens to it
+    pub action: PruneAction,
+    /// Human-readable reason
+    pub reason: String,
+}
+
+/// Outcome of applying a retention policy
+#[derive(Debug, Clone, PartialEq)]
+pub struct PruneReport {
+    /// Runs found in storage
+    pub total_runs: usize,
+    /// Runs removed or archived, or that would be in a dry run
+    pub decisions: Vec<PruneDecision>,
+    /// Bytes freed; estimated as the full size of removed runs in a dry run
+    pub bytes_freed: u64,
+}
+
+/// List the runs in run storage under `base_path`
+///
+/// Stored runs provide their own status; runs that only left an event log,
+/// such as successful `flow run`s, are described from their events.
+pub fn scan_run_storage(base_path: &Path) -> Result<Vec<StoredRunInfo>> {
+    let runs_dir = base_path.join("runs");
+    if !runs_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let storage = FileSystemWorkflowRunStorage::new(base_path)?;
+
+    let mut runs = Vec::new();
+    for entry in std::fs::read_dir(&runs_dir)?.filter_map(|e| e.ok()) {
+        let dir = entry.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        let Some(id) = parse_run_dir_name(&entry.file_name().to_string_lossy()) else {
+            continue;
+        };
+
+        let described = match storage.get_run(&id) {
+            Ok(run) => Some((run.workflow.name.to_string(), run.status, run.started_at)),
+            Err(_) => describe_from_events(base_path, &id)?,
+        };
+        let Some((workflow, status, started_at)) = described else {
+            tracing::debug!("Skipping run directory without run or events: {:?}", dir);
+            continue;
+        };
+
+        runs.push(StoredRunInfo {
+            id,
+            archived: FileSystemWorkflowRunStorage::is_archived_run_dir(&dir),
+            size_bytes: directory_size(&dir),
+            dir,
+            workflow,
+            status,
+            started_at,
+        });
+    }
+
+    Ok(runs)
+}
+
+/// Apply a retention policy to run storage under `base_path`
+///
+/// With `dry_run` nothing is changed and the report lists what would be.
+pub fn prune_run_storage(
+    base_path: &Path,
+    policy: &RunRetentionPolicy,
+    dry_run: bool,
+) -> Result<PruneReport> {
+    let runs = scan_run_storage(base_path)?;
+    let decisions = policy.plan(&runs, Utc::now());
+
+    let mut bytes_freed = 0;
+    for decision in &decisions {
+        match decision.action {
+            PruneAction::Remove => {
+                if !dry_run {
+                    std::fs::remove_dir_all(&decision.run.dir)?;
+                }
+                bytes_freed += decision.run.size_bytes;
+            }
+            PruneAction::Archive => {
+                if !dry_run {
+                    FileSystemWorkflowRunStorage::archive_run_dir(&decision.run.dir)?;
+                    bytes_freed += decision
+                        .run
+                        .size_bytes
+                        .saturating_sub(directory_size(&decision.run.dir));
+                }
+            }
+        }
+    }
+
+    Ok(PruneReport {
+        total_runs: runs.len(),
+        decisions,
+        bytes_freed,
+    })
+}
+
+/// Total size in bytes of the runs in run storage under `base_path`
+pub fn run_storage_size(base_path: &Path) -> u64 {
+    directory_size(&base_path.join("runs"))
+}
+
+/// Run directories are named after the `Debug` form of the run ID
+fn parse_run_dir_name(name: &str) -> Option<WorkflowRunId> {
+    let ulid = name.strip_prefix("WorkflowRunId(")?.strip_suffix(')')?;
+    WorkflowRunId::parse(ulid).ok()
+}
+
+fn describe_from_events(
+    base_path: &Path,
+    id: &WorkflowRunId,
+) -> Result<Option<(String, WorkflowRunStatus, DateTime<Utc>)>> {
+    let events = read_run_events(base_path, id)?;
+    let Some(first) = events.first() else {
+        return Ok(None);
+    };
+    let status = events
+        .iter()
+        .rev()
+        .find_map(|record| match &record.event {
+            WorkflowEvent::RunEnd { status, .. } => Some(*status),
+            _ => None,
+        })
+        .unwrap_or(WorkflowRunStatus::Running);
+    Ok(Some((first.workflow.clone(), status, first.timestamp)))
+}
+
+fn directory_size(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::test_helpers::{create_state, create_workflow};
+    use crate::workflow::{JsonlEventObserver, Workflow, WorkflowObserver, WorkflowRun};
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    fn info(workflow: &str, status: WorkflowRunStatus, days_ago: i64) -> StoredRunInfo {
+        StoredRunInfo {
+            id: WorkflowRunId::new(),
+            dir: PathBuf::new(),
+            workflow: workflow.to_string(),
+            status,
+            started_at: Utc::now() - Duration::days(days_ago),
+            archived: false,
+            size_bytes: 100,
+        }
+    }
+
+    fn test_workflow() -> Workflow {
+        let mut workflow = create_workflow("retention", "Retention test", "start");
+        workflow.add_state(create_state("start", "Start", true));
+        workflow
+    }
+
+    #[test]
+    fn test_plan_keeps_newest_runs_and_failed_runs_longer() {
+        let policy = RunRetentionPolicy {
+            keep_last: 1,
+            max_age_days: 7,
+            failed_max_age_days: 30,
+            compress_after_days: 3,
+            size_warning_mb: 500,
+        };
+        let runs = vec![
+            info("implement", WorkflowRunStatus::Completed, 20),
+            info("implement", WorkflowRunStatus::Completed, 10),
+            info("implement", WorkflowRunStatus::Failed, 10),
+            info("implement", WorkflowRunStatus::Failed, 40),
+            info("implement", WorkflowRunStatus::Paused, 90),
+            info("implement", WorkflowRunStatus::Completed, 1),
+            info("review", WorkflowRunStatus::Completed, 50),
+        ];
+
+        let decisions = policy.plan(&runs, Utc::now());
+        let action_for = |run: &StoredRunInfo| {
+            decisions
+                .iter()
+                .find(|d| d.run.id == run.id)
+                .map(|d| d.action)
+        };
+
+        assert_eq!(action_for(&runs[0]), Some(PruneAction::Remove));
+        assert_eq!(action_for(&runs[1]), Some(PruneAction::Remove));
+        assert_eq!(action_for(&runs[2]), Some(PruneAction::Archive));
+        assert_eq!(action_for(&runs[3]), Some(PruneAction::Remove));
+        assert_eq!(action_for(&runs[4]), None);
+        assert_eq!(action_for(&runs[5]), None);
+        // The only run of a workflow is always kept
+        assert_eq!(action_for(&runs[6]), Some(PruneAction::Archive));
+    }
+
+    #[test]
+    fn test_policy_from_config_defaults_missing_keys() {
+        let policy =
+            RunRetentionPolicy::from_config_value(&serde_json::json!({"keep_last": 5})).unwrap();
+        assert_eq!(policy.keep_last, 5);
+        assert_eq!(
+            policy.failed_max_age_days,
+            RunRetentionPolicy::default().failed_max_age_days
+        );
+
+        assert!(
+            RunRetentionPolicy::from_config_value(&serde_json::json!({"keep_last": "all"}))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_prune_archives_and_removes_run_directories() {
+        let temp = TempDir::new().unwrap();
+        let base = temp.path();
+
+        // A failed run stored with its events
+        let mut failed = WorkflowRun::new(test_workflow());
+        failed.started_at = Utc::now() - Duration::days(10);
+        failed.fail();
+        let mut storage = FileSystemWorkflowRunStorage::new(base).unwrap();
+        storage.store_run(&failed).unwrap();
+        let mut observer = JsonlEventObserver::new(base);
+        observer.on_run_start(&failed);
+
+        // A successful run that only left events behind
+        let completed = WorkflowRun::new(test_workflow());
+        observer.on_run_start(&completed);
+        observer.on_run_end(&completed, None);
+
+        let runs = scan_run_storage(base).unwrap();
+        assert_eq!(runs.len(), 2);
+        let completed_info = runs.iter().find(|r| r.id == completed.id).unwrap();
+        assert_eq!(completed_info.status, WorkflowRunStatus::Completed);
+        assert_eq!(completed_info.workflow, "retention");
+
+        let policy = RunRetentionPolicy {
+            keep_last: 0,
+            max_age_days: 0,
+            failed_max_age_days: 30,
+            compress_after_days: 3,
+            size_warning_mb: 500,
+        };
+
+        let dry_run = prune_run_storage(base, &policy, true).unwrap();
+        assert_eq!(dry_run.decisions.len(), 1);
+        assert_eq!(dry_run.decisions[0].action, PruneAction::Archive);
+        assert!(!FileSystemWorkflowRunStorage::is_archived_run_dir(
+            &dry_run.decisions[0].run.dir
+        ));
+
+        let report = prune_run_storage(base, &policy, false).unwrap();
+        assert_eq!(report.decisions.len(), 1);
+        let failed_dir = &report.decisions[0].run.dir;
+        assert!(FileSystemWorkflowRunStorage::is_archived_run_dir(
+            failed_dir
+        ));
+
+        // Archived runs and their events stay readable
+        let reloaded = FileSystemWorkflowRunStorage::new(base).unwrap();
+        assert_eq!(
+            reloaded.get_run(&failed.id).unwrap().status,
+            WorkflowRunStatus::Failed
+        );
+        assert_eq!(read_run_events(base, &failed.id).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,661 @@
+//! Comparison of two workflow runs
+//!
+//! Builds a [`RunDiff`] from the [`ExecutionTrace`]s of two runs and, when
+//! available, their recorded events: where the state paths diverge, how time and
+//! tokens spent in each state changed, which variables ended up with different
+//! values, and the agent outputs of the first prompt whose output differs. The
+//! diff serializes to JSON for CI and renders as text for people.
+
+use crate::workflow::{ExecutionTrace, WorkflowEvent, WorkflowEventRecord, WorkflowRunStatus};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Width of each column in the side-by-side output comparison
+const SIDE_BY_SIDE_COLUMN_WIDTH: usize = 48;
+
+/// Everything known about one run that a diff compares
+#[derive(Debug, Clone)]
+pub struct RunSnapshot {
+    /// States the run executed
+    pub trace: ExecutionTrace,
+    /// Recorded events, empty for runs without an event log
+    pub events: Vec<WorkflowEventRecord>,
+    /// Final workflow variable values
+    pub variables: BTreeMap<String, Value>,
+}
+
+impl RunSnapshot {
+    /// Create a snapshot, taking variable values from the `VariableSet` events
+    pub fn new(trace: ExecutionTrace, events: Vec<WorkflowEventRecord>) -> Self {
+        let mut variables = BTreeMap::new();
+        for record in &events {
+            if let WorkflowEvent::VariableSet { name, value } = &record.event {
+                variables.insert(name.clone(), value.clone());
+            }
+        }
+        Self {
+            trace,
+            events,
+            variables,
+        }
+    }
+
+    /// Add final variable values, such as those of a stored run
+    ///
+    /// Underscore-prefixed keys are executor bookkeeping and are skipped.
+    pub fn with_variables(mut self, variables: impl IntoIterator<Item = (String, Value)>) -> Self {
+        self.variables.extend(
+            variables
+                .into_iter()
+                .filter(|(name, _)| !name.starts_with('_')),
+        );
+        self
+    }
+
+    fn path(&self) -> Vec<String> {
+        self.trace
+            .execution_path
+            .iter()
+            .map(|step| step.state_id.to_string())
+            .collect()
+    }
+
+    /// Tokens sent by prompt actions, per state
+    fn tokens_by_state(&self) -> BTreeMap<&str, usize> {
+        let mut tokens = BTreeMap::new();
+        for record in &self.events {
+            if let WorkflowEvent::ActionOutput {
+                state,
+                tokens: Some(count),
+                ..
+            } = &record.event
+            {
+                *tokens.entry(state.as_str()).or_insert(0) += count;
+            }
+        }
+        tokens
+    }
+
+    /// Outputs of prompt actions in execution order
+    fn agent_outputs(&self) -> Vec<AgentOutput<'_>> {
+        self.events
+            .iter()
+            .filter_map(|record| match &record.event {
+                WorkflowEvent::ActionOutput {
+                    state,
+                    action,
+                    action_type,
+                    output,
+                    error,
+                    ..
+                } if action_type == "prompt" => Some(AgentOutput {
+                    state,
+                    action,
+                    text: match (error, output) {
+                        (Some(error), _) => format!("error: {error}"),
+                        (None, Some(output)) => output.clone(),
+                        (None, None) => String::new(),
+                    },
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+struct AgentOutput<'a> {
+    state: &'a str,
+    action: &'a str,
+    text: String,
+}
+
+/// Overview of one side of a diff
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunSummary {
+    /// Run ID
+    pub run_id: String,
+    /// Workflow name
+    pub workflow: String,
+    /// Final status
+    pub status: WorkflowRunStatus,
+    /// States in execution order
+    pub path: Vec<String>,
+    /// Total run time in milliseconds
+    pub duration_ms: Option<u64>,
+    /// Tokens sent by all prompt actions
+    pub tokens: Option<usize>,
+}
+
+/// First step at which the state paths differ
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PathDivergence {
+    /// Zero-based step index
+    pub step: usize,
+    /// State run A executed at that step, if it got that far
+    pub state_a: Option<String>,
+    /// State run B executed at that step, if it got that far
+    pub state_b: Option<String>,
+}
+
+/// Time and tokens spent in one state by each run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StateDelta {
+    /// State ID
+    pub state: String,
+    /// Times run A entered the state
+    pub visits_a: usize,
+    /// Times run B entered the state
+    pub visits_b: usize,
+    /// Milliseconds run A spent in the state
+    pub duration_ms_a: Option<u64>,
+    /// Milliseconds run B spent in the state
+    pub duration_ms_b: Option<u64>,
+    /// Change in milliseconds from A to B
+    pub duration_delta_ms: Option<i64>,
+    /// Tokens run A's prompts sent from the state
+    pub tokens_a: Option<usize>,
+    /// Tokens run B's prompts sent from the state
+    pub tokens_b: Option<usize>,
+    /// Change in tokens from A to B
+    pub tokens_delta: Option<i64>,
+}
+
+/// Variable whose final value differs between the runs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VariableChange {
+    /// Variable name
+    pub name: String,
+    /// Value at the end of run A, if set
+    pub value_a: Option<Value>,
+    /// Value at the end of run B, if set
+    pub value_b: Option<Value>,
+}
+
+/// Agent outputs of the first prompt whose output differs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OutputComparison {
+    /// State the prompt ran in
+    pub state: String,
+    /// Run A's prompt action, if it ran one at this point
+    pub action_a: Option<String>,
+    /// Run B's prompt action, if it ran one at this point
+    pub action_b: Option<String>,
+    /// Beginning of run A's output
+    pub output_a: Option<String>,
+    /// Beginning of run B's output
+    pub output_b: Option<String>,
+}
+
+/// Differences between two workflow runs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunDiff {
+    /// The baseline run
+    pub run_a: RunSummary,
+    /// The run compared against the baseline
+    pub run_b: RunSummary,
+    /// Where the state paths diverge, `None` when they are identical
+    pub divergence: Option<PathDivergence>,
+    /// Per-state timing and token deltas, in order of first execution
+    pub states: Vec<StateDelta>,
+    /// Variables with different final values
+    pub variables: Vec<VariableChange>,
+    /// First differing agent output, `None` when all agent outputs match
+    pub first_output_difference: Option<OutputComparison>,
+}
+
+impl RunDiff {
+    /// Compare run `b` against the baseline run `a`
+    pub fn between(a: &RunSnapshot, b: &RunSnapshot) -> Self {
+        let path_a = a.path();
+        let path_b = b.path();
+
+        let divergence = (0..path_a.len().max(path_b.len()))
+            .find(|&i| path_a.get(i) != path_b.get(i))
+            .map(|step| PathDivergence {
+                step,
+                state_a: path_a.get(step).cloned(),
+                state_b: path_b.get(step).cloned(),
+            });
+
+        let tokens_a = a.tokens_by_state();
+        let tokens_b = b.tokens_by_state();
+        let mut state_order: Vec<&String> = Vec::new();
+        for state in path_a.iter().chain(&path_b) {
+            if !state_order.contains(&state) {
+                state_order.push(state);
+            }
+        }
+        let states = state_order
+            .into_iter()
+            .map(|state| {
+                let duration_ms_a = state_duration_ms(&a.trace, state);
+                let duration_ms_b = state_duration_ms(&b.trace, state);
+                let state_tokens_a = tokens_a.get(state.as_str()).copied();
+                let state_tokens_b = tokens_b.get(state.as_str()).copied();
+                StateDelta {
+                    state: state.clone(),
+                    visits_a: path_a.iter().filter(|s| *s == state).count(),
+                    visits_b: path_b.iter().filter(|s| *s == state).count(),
+                    duration_ms_a,
+                    duration_ms_b,
+                    duration_delta_ms: delta(duration_ms_a, duration_ms_b),
+                    tokens_a: state_tokens_a,
+                    tokens_b: state_tokens_b,
+                    tokens_delta: delta(
+                        state_tokens_a.map(|t| t as u64),
+                        state_tokens_b.map(|t| t as u64),
+                    ),
+                }
+            })
+            .collect();
+
+        let mut names: Vec<&String> = a.variables.keys().chain(b.variables.keys()).collect();
+        names.sort();
+        names.dedup();
+        let variables = names
+            .into_iter()
+            .filter(|name| a.variables.get(*name) != b.variables.get(*name))
+            .map(|name| VariableChange {
+                name: name.clone(),
+                value_a: a.variables.get(name).cloned(),
+                value_b: b.variables.get(name).cloned(),
+            })
+            .collect();
+
+        let outputs_a = a.agent_outputs();
+        let outputs_b = b.agent_outputs();
+        let first_output_difference = (0..outputs_a.len().max(outputs_b.len()))
+            .find(|&i| match (outputs_a.get(i), outputs_b.get(i)) {
+                (Some(x), Some(y)) => x.state != y.state || x.text != y.text,
+                _ => true,
+            })
+            .map(|i| {
+                let (output_a, output_b) = (outputs_a.get(i), outputs_b.get(i));
+                OutputComparison {
+                    state: output_a
+                        .or(output_b)
+                        .map(|o| o.state.to_string())
+                        .unwrap_or_default(),
+                    action_a: output_a.map(|o| o.action.to_string()),
+                    action_b: output_b.map(|o| o.action.to_string()),
+                    output_a: output_a.map(|o| o.text.clone()),
+                    output_b: output_b.map(|o| o.text.clone()),
+                }
+            });
+
+        Self {
+            run_a: summarize(a, path_a, &tokens_a),
+            run_b: summarize(b, path_b, &tokens_b),
+            divergence,
+            states,
+            variables,
+            first_output_difference,
+        }
+    }
+
+    /// Whether both runs took the same path, set the same variables and got the same agent outputs
+    ///
+    /// Timing and token counts are not considered.
+    pub fn is_equivalent(&self) -> bool {
+        self.divergence.is_none()
+            && self.variables.is_empty()
+            && self.first_output_difference.is_none()
+    }
+
+    /// Render the diff for a terminal
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Comparing {} runs {} (A) and {} (B)",
+            self.run_a.workflow, self.run_a.run_id, self.run_b.run_id
+        );
+        for (label, run) in [("A", &self.run_a), ("B", &self.run_b)] {
+            let _ = writeln!(
+                out,
+                "  {label}: {:?}, {} states, {}, {} tokens",
+                run.status,
+                run.path.len(),
+                run.duration_ms
+                    .map(format_ms)
+                    .unwrap_or_else(|| "unknown duration".to_string()),
+                run.tokens
+                    .map(|t| t.to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+            );
+        }
+
+        out.push_str("\nPath:\n");
+        match &self.divergence {
+            None => {
+                let _ = writeln!(out, "  identical: {}", self.run_a.path.join(" → "));
+            }
+            Some(divergence) => {
+                let _ = writeln!(
+                    out,
+                    "  diverged at step {}: A {} / B {}",
+                    divergence.step + 1,
+                    divergence.state_a.as_deref().unwrap_or("(ended)"),
+                    divergence.state_b.as_deref().unwrap_or("(ended)"),
+                );
+                let _ = writeln!(out, "  A: {}", self.run_a.path.join(" → "));
+                let _ = writeln!(out, "  B: {}", self.run_b.path.join(" → "));
+            }
+        }
+
+        if !self.states.is_empty() {
+            let width = self
+                .states
+                .iter()
+                .map(|s| s.state.chars().count())
+                .max()
+                .unwrap_or(0)
+                .max("State".len());
+            out.push_str("\nStates:\n");
+            let _ = writeln!(
+                out,
+                "  {:<width$}  {:<9}  {:<28}  Tokens",
+                "State", "Visits", "Time"
+            );
+            for state in &self.states {
+                let visits = format!("{} → {}", state.visits_a, state.visits_b);
+                let time = format_change(
+                    state.duration_ms_a.map(format_ms),
+                    state.duration_ms_b.map(format_ms),
+                    state.duration_delta_ms.map(|d| format_signed(d, "ms")),
+                );
+                let tokens = format_change(
+                    state.tokens_a.map(|t| t.to_string()),
+                    state.tokens_b.map(|t| t.to_string()),
+                    state.tokens_delta.map(|d| format_signed(d, "")),
+                );
+                let _ = writeln!(
+                    out,
+                    "  {:<width$}  {:<9}  {:<28}  {}",
+                    state.state, visits, time, tokens
+                );
+            }
+        }
+
+        out.push_str("\nVariables:\n");
+        if self.variables.is_empty() {
+            out.push_str("  no changes\n");
+        }
+        for change in &self.variables {
+            let _ = writeln!(
+                out,
+                "  {}: {} → {}",
+                change.name,
+                format_value(change.value_a.as_ref()),
+                format_value(change.value_b.as_ref()),
+            );
+        }
+
+        out.push_str("\nAgent outputs:\n");
+        match &self.first_output_difference {
+            None => out.push_str("  no differences\n"),
+            Some(comparison) => {
+                let _ = writeln!(out, "  first difference in state {}", comparison.state);
+                let left = comparison.output_a.as_deref().unwrap_or("(no output)");
+                let right = comparison.output_b.as_deref().unwrap_or("(no output)");
+                out.push_str(&side_by_side(left, right));
+            }
+        }
+
+        out
+    }
+}
+
+fn summarize(
+    snapshot: &RunSnapshot,
+    path: Vec<String>,
+    tokens: &BTreeMap<&str, usize>,
+) -> RunSummary {
+    RunSummary {
+        run_id: snapshot.trace.run_id.clone(),
+        workflow: snapshot.trace.workflow_name.clone(),
+        status: snapshot.trace.status,
+        path,
+        duration_ms: snapshot.trace.total_duration.map(|d| d.as_millis() as u64),
+        tokens: (!tokens.is_empty()).then(|| tokens.values().sum()),
+    }
+}
+
+/// Total time spent in a state, `None` when no visit recorded a duration
+fn state_duration_ms(trace: &ExecutionTrace, state: &str) -> Option<u64> {
+    trace
+        .execution_path
+        .iter()
+        .filter(|step| step.state_id.as_str() == state)
+        .filter_map(|step| step.duration)
+        .map(|d| d.as_millis() as u64)
+        .reduce(|total, ms| total + ms)
+}
+
+fn delta(a: Option<u64>, b: Option<u64>) -> Option<i64> {
+    Some(b? as i64 - a? as i64)
+}
+
+fn format_ms(ms: u64) -> String {
+    if ms >= 1000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{ms}ms")
+    }
+}
+
+fn format_signed(value: i64, unit: &str) -> String {
+    format!("{value:+}{unit}")
+}
+
+fn format_change(a: Option<String>, b: Option<String>, delta: Option<String>) -> String {
+    let a = a.unwrap_or_else(|| "-".to_string());
+    let b = b.unwrap_or_else(|| "-".to_string());
+    match delta {
+        Some(delta) => format!("{a} → {b} ({delta})"),
+        None => format!("{a} → {b}"),
+    }
+}
+
+fn format_value(value: Option<&Value>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "(unset)".to_string(),
+    }
+}
+
+/// Lay two texts out in columns, wrapping long lines
+fn side_by_side(left: &str, right: &str) -> String {
+    let left = wrap(left, SIDE_BY_SIDE_COLUMN_WIDTH);
+    let right = wrap(right, SIDE_BY_SIDE_COLUMN_WIDTH);
+    let width = SIDE_BY_SIDE_COLUMN_WIDTH;
+
+    let mut out = format!("  {:<width$} | B\n", "A");
+    let _ = writeln!(out, "  {}-+-{}", "-".repeat(width), "-".repeat(width));
+    for i in 0..left.len().max(right.len()) {
+        let l = left.get(i).map(String::as_str).unwrap_or("");
+        let r = right.get(i).map(String::as_str).unwrap_or("");
+        let _ = writeln!(out, "  {l:<width$} | {r}");
+    }
+    out
+}
+
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let chars: Vec<char> = line.chars().collect();
+        if chars.is_empty() {
+            lines.push(String::new());
+        }
+        for chunk in chars.chunks(width) {
+            lines.push(chunk.iter().collect());
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::{ExecutionStep, StateId};
+    use chrono::Utc;
+    use std::time::Duration;
+
+    fn trace(run_id: &str, states: &[(&str, u64)]) -> ExecutionTrace {
+        let started_at = Utc::now();
+        ExecutionTrace {
+            run_id: run_id.to_string(),
+            workflow_name: "implement".to_string(),
+            execution_path: states
+                .iter()
+                .map(|(state, ms)| ExecutionStep {
+                    state_id: StateId::new(*state),
+                    state_description: String::new(),
+                    duration: Some(Duration::from_millis(*ms)),
+                    timestamp: started_at,
+                    success: true,
+                    error: None,
+                    transition_taken: None,
+                })
+                .collect(),
+            status: WorkflowRunStatus::Completed,
+            total_duration: Some(Duration::from_millis(states.iter().map(|(_, ms)| ms).sum())),
+            started_at,
+            completed_at: None,
+            error_details: None,
+        }
+    }
+
+    fn record(run_id: &str, event: WorkflowEvent) -> WorkflowEventRecord {
+        WorkflowEventRecord {
+            timestamp: Utc::now(),
+            level: event.level(),
+            run_id: run_id.to_string(),
+            workflow: "implement".to_string(),
+            event,
+        }
+    }
+
+    fn prompt_output(
+        run_id: &str,
+        state: &str,
+        tokens: usize,
+        output: &str,
+    ) -> WorkflowEventRecord {
+        record(
+            run_id,
+            WorkflowEvent::ActionOutput {
+                state: state.to_string(),
+                action: format!("Execute prompt '{state}'"),
+                action_type: "prompt".to_string(),
+                duration_ms: 10,
+                exit_code: None,
+                prompt: Some(state.to_string()),
+                tokens: Some(tokens),
+                success: true,
+                output: Some(output.to_string()),
+                error: None,
+            },
+        )
+    }
+
+    fn variable(run_id: &str, name: &str, value: Value) -> WorkflowEventRecord {
+        record(
+            run_id,
+            WorkflowEvent::VariableSet {
+                name: name.to_string(),
+                value,
+            },
+        )
+    }
+
+    #[test]
+    fn test_diff_reports_divergence_deltas_variables_and_outputs() {
+        let a = RunSnapshot::new(
+            trace("A", &[("plan", 1000), ("implement", 4000), ("done", 5)]),
+            vec![
+                prompt_output("A", "plan", 800, "Plan: add a flag"),
+                prompt_output("A", "implement", 2000, "Added --verbose"),
+                variable("A", "result", serde_json::json!("ok")),
+            ],
+        );
+        let b = RunSnapshot::new(
+            trace("B", &[("plan", 1500), ("implement", 3000), ("review", 200)]),
+            vec![
+                prompt_output("B", "plan", 780, "Plan: add a flag"),
+                prompt_output("B", "implement", 2500, "Added --quiet"),
+                variable("B", "result", serde_json::json!("needs review")),
+            ],
+        )
+        .with_variables([
+            ("attempts".to_string(), serde_json::json!(2)),
+            ("_internal".to_string(), serde_json::json!(true)),
+        ]);
+
+        let diff = RunDiff::between(&a, &b);
+
+        assert_eq!(
+            diff.divergence,
+            Some(PathDivergence {
+                step: 2,
+                state_a: Some("done".to_string()),
+                state_b: Some("review".to_string()),
+            })
+        );
+
+        let states: Vec<&str> = diff.states.iter().map(|s| s.state.as_str()).collect();
+        assert_eq!(states, ["plan", "implement", "done", "review"]);
+        assert_eq!(diff.states[0].duration_delta_ms, Some(500));
+        assert_eq!(diff.states[0].tokens_delta, Some(-20));
+        assert_eq!(diff.states[1].tokens_delta, Some(500));
+        assert_eq!(diff.states[3].visits_a, 0);
+        assert_eq!(diff.states[3].duration_delta_ms, None);
+        assert_eq!(diff.run_b.tokens, Some(3280));
+
+        let names: Vec<&str> = diff.variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["attempts", "result"]);
+        assert_eq!(diff.variables[0].value_a, None);
+
+        let comparison = diff.first_output_difference.as_ref().unwrap();
+        assert_eq!(comparison.state, "implement");
+        assert_eq!(comparison.output_a.as_deref(), Some("Added --verbose"));
+        assert_eq!(comparison.output_b.as_deref(), Some("Added --quiet"));
+        assert!(!diff.is_equivalent());
+
+        let text = diff.render_text();
+        assert!(text.contains("diverged at step 3: A done / B review"));
+        assert!(text.contains("result: \"ok\" → \"needs review\""));
+        assert!(text.contains("Added --verbose"));
+        assert!(text.contains("| Added --quiet"));
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["divergence"]["step"], 2);
+        assert_eq!(json["states"][1]["duration_delta_ms"], -1000);
+    }
+
+    #[test]
+    fn test_identical_runs_are_equivalent() {
+        let a = RunSnapshot::new(
+            trace("A", &[("start", 10), ("end", 10)]),
+            vec![prompt_output("A", "start", 100, "hello")],
+        );
+        let b = RunSnapshot::new(
+            trace("B", &[("start", 30), ("end", 10)]),
+            vec![prompt_output("B", "start", 100, "hello")],
+        );
+
+        let diff = RunDiff::between(&a, &b);
+        assert!(diff.is_equivalent());
+        assert_eq!(diff.states[0].duration_delta_ms, Some(20));
+        assert!(diff.render_text().contains("identical: start → end"));
+    }
+
+    #[test]
+    fn test_wrap_splits_long_lines() {
+        let wrapped = wrap(&"x".repeat(100), 48);
+        assert_eq!(wrapped.len(), 3);
+        assert_eq!(wrapped[2].len(), 4);
+    }
+}
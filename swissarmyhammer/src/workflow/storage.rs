@@ -1,6 +1,6 @@
 //! Storage abstractions and implementations for workflows and workflow runs
 
-use crate::file_loader::{FileSource, VirtualFileSystem};
+use crate::file_loader::{FileOverride, FileSource, VirtualFileSystem};
 use crate::workflow::{MermaidParser, Workflow, WorkflowName, WorkflowRun, WorkflowRunId};
 use crate::{Result, SwissArmyHammerError};
 use base64::{engine::general_purpose, Engine as _};
@@ -34,10 +34,16 @@ impl WorkflowResolver {
         self.vfs.get_directories()
     }
 
+    /// Workflows that replaced a same-named file from another source
+    pub fn overrides(&self) -> &[FileOverride] {
+        self.vfs.overrides()
+    }
+
     /// Load all workflows following the correct precedence:
     /// 1. Builtin workflows (least specific, embedded in binary or resource directories)
-    /// 2. User workflows from ~/.swissarmyhammer/workflows
-    /// 3. Local workflows from .swissarmyhammer directories (most specific)
+    /// 2. Workflows from installed packages
+    /// 3. User workflows from ~/.swissarmyhammer/workflows
+    /// 4. Local workflows from .swissarmyhammer directories (most specific)
     pub fn load_all_workflows(&mut self, storage: &mut dyn WorkflowStorageBackend) -> Result<()> {
         // Load builtin workflows first (least precedence)
         self.load_builtin_workflows()?;
@@ -513,6 +519,24 @@ impl WorkflowStorageBackend for FileSystemWorkflowStorage {
     }
 }
 
+/// Directory holding runs persisted by `sah flow run`, relative to the working directory
+///
+/// Runs paused at an approval gate are kept here for `sah flow approve`, the
+/// `flow_approve` MCP tool and `sah flow resume`.
+pub const LOCAL_RUN_STORAGE_DIR: &str = ".swissarmyhammer/workflow-runs";
+
+/// Name of the file holding a stored run
+const RUN_FILE: &str = "run.json";
+
+/// Name of the file holding a run's events
+const EVENTS_FILE: &str = "events.jsonl";
+
+/// Name of the compressed copy of [`RUN_FILE`] in an archived run
+const ARCHIVED_RUN_FILE: &str = "run.json.zst";
+
+/// Name of the compressed copy of [`EVENTS_FILE`] in an archived run
+const ARCHIVED_EVENTS_FILE: &str = "events.jsonl.zst";
+
 /// File system workflow run storage implementation
 pub struct FileSystemWorkflowRunStorage {
     base_path: PathBuf,
@@ -559,18 +583,99 @@ impl FileSystemWorkflowRunStorage {
             },
         )?;
 
+        // Archived runs only have a compressed copy
+        for entry in std::fs::read_dir(&runs_dir)?.filter_map(|e| e.ok()) {
+            let archive_path = entry.path().join(ARCHIVED_RUN_FILE);
+            if archive_path.exists() && !entry.path().join(RUN_FILE).exists() {
+                match Self::read_archived_run(&archive_path) {
+                    Ok(run) => {
+                        self.cache.insert(run.id, run);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Skipping unreadable archived run {:?}: {}", archive_path, e)
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
 
     fn run_path(&self, id: &WorkflowRunId) -> PathBuf {
-        self.base_path
-            .join("runs")
-            .join(format!("{id:?}"))
-            .join("run.json")
+        self.run_dir(id).join(RUN_FILE)
+    }
+
+    fn archived_run_path(&self, id: &WorkflowRunId) -> PathBuf {
+        self.run_dir(id).join(ARCHIVED_RUN_FILE)
+    }
+
+    fn read_archived_run(path: &Path) -> Result<WorkflowRun> {
+        let json_data = Self::read_archived_file(path)?;
+        Ok(serde_json::from_slice(&json_data)?)
     }
 
     fn run_dir(&self, id: &WorkflowRunId) -> PathBuf {
-        self.base_path.join("runs").join(format!("{id:?}"))
+        Self::run_dir_in(&self.base_path, id)
+    }
+
+    fn run_dir_in(base_path: &Path, id: &WorkflowRunId) -> PathBuf {
+        base_path.join("runs").join(format!("{id:?}"))
+    }
+
+    /// Path of the JSONL event file written alongside a run stored under `base_path`
+    pub fn run_events_path(base_path: &Path, id: &WorkflowRunId) -> PathBuf {
+        Self::run_dir_in(base_path, id).join(EVENTS_FILE)
+    }
+
+    /// Path of the JSONL event file for a run in this storage
+    pub fn events_path(&self, id: &WorkflowRunId) -> PathBuf {
+        Self::run_events_path(&self.base_path, id)
+    }
+
+    /// Path of the compressed event file of an archived run stored under `base_path`
+    pub fn run_archived_events_path(base_path: &Path, id: &WorkflowRunId) -> PathBuf {
+        Self::run_dir_in(base_path, id).join(ARCHIVED_EVENTS_FILE)
+    }
+
+    /// Base directory of this storage
+    pub fn base_path(&self) -> &Path {
+        &self.base_path
+    }
+
+    /// Compress the run and event files in a run directory with zstd
+    ///
+    /// Archived runs stay readable through this storage; the originals are
+    /// removed once their compressed copies are written. Returns whether
+    /// anything was compressed.
+    pub fn archive_run_dir(run_dir: &Path) -> Result<bool> {
+        let mut archived = false;
+        for (file, archive) in [
+            (RUN_FILE, ARCHIVED_RUN_FILE),
+            (EVENTS_FILE, ARCHIVED_EVENTS_FILE),
+        ] {
+            let path = run_dir.join(file);
+            if !path.exists() {
+                continue;
+            }
+            let compressed = zstd_compress(&std::fs::read(&path)?, DEFAULT_COMPRESSION_LEVEL)?;
+            std::fs::write(run_dir.join(archive), compressed)?;
+            std::fs::remove_file(&path)?;
+            archived = true;
+        }
+        Ok(archived)
+    }
+
+    /// Whether a run directory has been archived
+    pub fn is_archived_run_dir(run_dir: &Path) -> bool {
+        !run_dir.join(RUN_FILE).exists()
+            && !run_dir.join(EVENTS_FILE).exists()
+            && (run_dir.join(ARCHIVED_RUN_FILE).exists()
+                || run_dir.join(ARCHIVED_EVENTS_FILE).exists())
+    }
+
+    /// Read and decompress a file written by [`Self::archive_run_dir`]
+    pub fn read_archived_file(path: &Path) -> Result<Vec<u8>> {
+        zstd_decompress(&std::fs::read(path)?)
     }
 }
 
@@ -585,6 +690,12 @@ impl WorkflowRunStorageBackend for FileSystemWorkflowRunStorage {
         let content = serde_json::to_string_pretty(run)?;
         std::fs::write(&path, content)?;
 
+        // A stored run supersedes any archived copy
+        let archive_path = self.archived_run_path(&run.id);
+        if archive_path.exists() {
+            std::fs::remove_file(&archive_path)?;
+        }
+
         self.cache.insert(run.id, run.clone());
         Ok(())
     }
@@ -595,12 +706,14 @@ impl WorkflowRunStorageBackend for FileSystemWorkflowRunStorage {
         }
 
         let path = self.run_path(id);
-        if !path.exists() {
+        let archive_path = self.archived_run_path(id);
+        let run: WorkflowRun = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&path)?)?
+        } else if archive_path.exists() {
+            Self::read_archived_run(&archive_path)?
+        } else {
             return Err(SwissArmyHammerError::WorkflowRunNotFound(format!("{id:?}")));
-        }
-
-        let content = std::fs::read_to_string(&path)?;
-        let run: WorkflowRun = serde_json::from_str(&content)?;
+        };
         self.cache.insert(*id, run.clone());
 
         Ok(run)
@@ -807,22 +920,35 @@ impl CompressedWorkflowStorage {
 
     /// Create with default compression level (3)
     pub fn with_default_compression(inner: Box<dyn WorkflowStorageBackend>) -> Self {
-        Self::new(inner, 3)
+        Self::new(inner, DEFAULT_COMPRESSION_LEVEL)
     }
 
     /// Compress data using zstd
     fn compress_data(&self, data: &[u8]) -> Result<Vec<u8>> {
-        zstd::encode_all(data, self.compression_level)
-            .map_err(|e| SwissArmyHammerError::Storage(format!("Compression failed: {e}")))
+        zstd_compress(data, self.compression_level)
     }
 
     /// Decompress data using zstd
     fn decompress_data(&self, data: &[u8]) -> Result<Vec<u8>> {
-        zstd::decode_all(data)
-            .map_err(|e| SwissArmyHammerError::Storage(format!("Decompression failed: {e}")))
+        zstd_decompress(data)
     }
 }
 
+/// Default zstd compression level for stored workflows and archived runs
+const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
+/// Compress data using zstd
+fn zstd_compress(data: &[u8], level: i32) -> Result<Vec<u8>> {
+    zstd::encode_all(data, level)
+        .map_err(|e| SwissArmyHammerError::Storage(format!("Compression failed: {e}")))
+}
+
+/// Decompress data using zstd
+fn zstd_decompress(data: &[u8]) -> Result<Vec<u8>> {
+    zstd::decode_all(data)
+        .map_err(|e| SwissArmyHammerError::Storage(format!("Decompression failed: {e}")))
+}
+
 impl WorkflowStorageBackend for CompressedWorkflowStorage {
     fn store_workflow(&mut self, workflow: Workflow) -> Result<()> {
         // Serialize workflow to JSON
@@ -3,7 +3,10 @@
 //! This module provides functionality to visualize workflow execution using Mermaid diagrams
 //! with execution overlays showing actual paths taken, timing information, and execution status.
 
-use crate::workflow::{RunMetrics, StateId, Workflow, WorkflowRun, WorkflowRunStatus};
+use crate::workflow::{
+    RunMetrics, StateId, Workflow, WorkflowEvent, WorkflowEventRecord, WorkflowRun,
+    WorkflowRunStatus,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -188,6 +191,92 @@ impl ExecutionVisualizer {
         }
     }
 
+    /// Generate execution trace from a run's recorded events
+    ///
+    /// Event logs are written for every run, including successful runs that are
+    /// not kept in run storage, and carry real per-state timing and errors.
+    /// Returns `None` when the events contain no run start.
+    pub fn generate_trace_from_events(
+        &self,
+        events: &[WorkflowEventRecord],
+    ) -> Option<ExecutionTrace> {
+        let first = events
+            .iter()
+            .find(|r| matches!(r.event, WorkflowEvent::RunStart { .. }))?;
+
+        let mut trace = ExecutionTrace {
+            run_id: first.run_id.clone(),
+            workflow_name: first.workflow.clone(),
+            execution_path: Vec::new(),
+            status: WorkflowRunStatus::Running,
+            total_duration: None,
+            started_at: first.timestamp,
+            completed_at: None,
+            error_details: None,
+        };
+
+        for record in events {
+            match &record.event {
+                WorkflowEvent::StateEnter { state } => {
+                    if trace.execution_path.len() >= MAX_EXECUTION_STEPS {
+                        break;
+                    }
+                    trace.execution_path.push(ExecutionStep {
+                        state_id: StateId::new(state),
+                        state_description: String::new(),
+                        duration: None,
+                        timestamp: record.timestamp,
+                        success: true,
+                        error: None,
+                        transition_taken: None,
+                    });
+                }
+                WorkflowEvent::StateExit {
+                    state,
+                    duration_ms,
+                    success,
+                    error,
+                } => {
+                    if let Some(step) = trace
+                        .execution_path
+                        .iter_mut()
+                        .rev()
+                        .find(|step| step.state_id.as_str() == state)
+                    {
+                        step.duration = Some(Duration::from_millis(*duration_ms));
+                        step.success = *success;
+                        step.error = error.clone();
+                    }
+                }
+                WorkflowEvent::Transition { from, to } => {
+                    if let Some(step) = trace
+                        .execution_path
+                        .iter_mut()
+                        .rev()
+                        .find(|step| step.state_id.as_str() == from)
+                    {
+                        step.transition_taken = Some(StateId::new(to));
+                    }
+                }
+                WorkflowEvent::RunEnd { status, error } => {
+                    trace.status = *status;
+                    trace.error_details = error.clone();
+                    trace.completed_at = Some(record.timestamp);
+                    trace.total_duration = record
+                        .timestamp
+                        .signed_duration_since(trace.started_at)
+                        .to_std()
+                        .ok();
+                }
+                WorkflowEvent::RunStart { .. }
+                | WorkflowEvent::ActionOutput { .. }
+                | WorkflowEvent::VariableSet { .. } => {}
+            }
+        }
+
+        Some(trace)
+    }
+
     /// Generate execution trace with metrics
     pub fn generate_trace_with_metrics(
         &self,
@@ -59,6 +59,9 @@ fn create_sample_run_metrics() -> RunMetrics {
             history_size: 3,
         },
         error_details: None,
+        agent_cache_hits: 0,
+        agent_cache_misses: 0,
+        prompt_token_usage: Vec::new(),
     }
 }
 